@@ -0,0 +1,87 @@
+use cosmwasm_std::{Addr, Api, CanonicalAddr, StdError};
+use ethers_core::types::H256;
+
+/// Canonical address lengths that fit in an `H256`. 20 byte addresses are
+/// left-padded with zeros
+const SHORT_ADDR_BYTES: usize = 20;
+const LONG_ADDR_BYTES: usize = 32;
+
+/// Error types for address encoding
+#[derive(Debug, thiserror::Error)]
+pub enum AddressError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Canonical address must be 20 or 32 bytes. Got {length} bytes")]
+    InvalidLength { length: usize },
+}
+
+/// Encode `address` as its canonical bytes in an H256
+pub fn addr_to_h256(api: &dyn Api, address: &Addr) -> Result<H256, AddressError> {
+    let canonical = api.addr_canonicalize(address.as_str())?;
+    canonical_to_h256(&canonical)
+}
+
+/// Decode H256 holding canonical address bytes into a human address. Leading
+/// 12 zero bytes are read as a 20 byte address
+pub fn h256_to_addr(api: &dyn Api, h256: H256) -> Result<Addr, AddressError> {
+    let bytes = h256.as_bytes();
+    let padding = LONG_ADDR_BYTES - SHORT_ADDR_BYTES;
+    let canonical = if bytes[..padding].iter().all(|byte| *byte == 0) {
+        CanonicalAddr::from(&bytes[padding..])
+    } else {
+        CanonicalAddr::from(bytes)
+    };
+
+    Ok(api.addr_humanize(&canonical)?)
+}
+
+/// Left-pad 20 or 32 byte canonical address into an H256
+pub fn canonical_to_h256(canonical: &CanonicalAddr) -> Result<H256, AddressError> {
+    let length = canonical.len();
+    if length != SHORT_ADDR_BYTES && length != LONG_ADDR_BYTES {
+        return Err(AddressError::InvalidLength { length });
+    }
+
+    let mut buf = [0u8; 32];
+    buf[LONG_ADDR_BYTES - length..].copy_from_slice(canonical.as_slice());
+    Ok(H256::from(buf))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::testing::MockApi;
+
+    #[test]
+    fn pads_short_canonical_addresses() {
+        let canonical = CanonicalAddr::from(vec![7u8; 20]);
+        let h256 = canonical_to_h256(&canonical).unwrap();
+
+        assert_eq!(&[0u8; 12], &h256.as_bytes()[..12]);
+        assert_eq!(canonical.as_slice(), &h256.as_bytes()[12..]);
+    }
+
+    #[test]
+    fn keeps_long_canonical_addresses() {
+        let canonical = CanonicalAddr::from(vec![7u8; 32]);
+        let h256 = canonical_to_h256(&canonical).unwrap();
+
+        assert_eq!(H256::repeat_byte(7), h256);
+    }
+
+    #[test]
+    fn rejects_other_lengths() {
+        for length in [0, 19, 21, 33, 54] {
+            let canonical = CanonicalAddr::from(vec![7u8; length]);
+            match canonical_to_h256(&canonical) {
+                Err(AddressError::InvalidLength { length: got }) => assert_eq!(length, got),
+                res => panic!("Unexpected result {:?}", res),
+            }
+        }
+
+        // Mock api canonical addresses are neither 20 nor 32 bytes
+        let api = MockApi::default();
+        assert!(addr_to_h256(&api, &Addr::unchecked("contract")).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+use cosmwasm_std::Uint128;
+use ethers_core::types::H256;
+use serde::{Deserialize, Serialize};
+
+use crate::traits::{Decode, Encode};
+use crate::NomadError;
+
+const TRANSFER_MESSAGE_LEN: usize = 100;
+
+/// Canonical identifier for a bridged token
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct TokenId {
+    /// 4   Domain the token originates from
+    pub domain: u32,
+    /// 32  Token identifier in origin domain convention
+    pub id: H256,
+}
+
+/// Token transfer carried in `NomadMessage.body` between bridge routers
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TransferMessage {
+    /// 36  Canonical token being transferred
+    pub token: TokenId,
+    /// 32  Recipient in destination convention
+    pub recipient: H256,
+    /// 32  Amount (big-endian uint256, upper 16 bytes must be zero)
+    pub amount: Uint128,
+}
+
+impl Encode for TokenId {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: std::io::Write,
+    {
+        writer.write_all(&self.domain.to_be_bytes())?;
+        writer.write_all(self.id.as_ref())?;
+        Ok(36)
+    }
+}
+
+impl Decode for TokenId {
+    fn read_from<R>(reader: &mut R) -> Result<Self, NomadError>
+    where
+        R: std::io::Read,
+    {
+        let mut domain = [0u8; 4];
+        reader.read_exact(&mut domain)?;
+
+        let mut id = [0u8; 32];
+        reader.read_exact(id.as_mut())?;
+
+        Ok(Self {
+            domain: u32::from_be_bytes(domain),
+            id: H256::from(id),
+        })
+    }
+}
+
+impl Encode for TransferMessage {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: std::io::Write,
+    {
+        self.token.write_to(writer)?;
+        writer.write_all(self.recipient.as_ref())?;
+        writer.write_all(&[0u8; 16])?;
+        writer.write_all(&self.amount.u128().to_be_bytes())?;
+        Ok(TRANSFER_MESSAGE_LEN)
+    }
+}
+
+impl Decode for TransferMessage {
+    fn read_from<R>(reader: &mut R) -> Result<Self, NomadError>
+    where
+        R: std::io::Read,
+    {
+        let token = TokenId::read_from(reader)?;
+
+        let mut recipient = [0u8; 32];
+        reader.read_exact(recipient.as_mut())?;
+
+        let mut amount_high = [0u8; 16];
+        reader.read_exact(&mut amount_high)?;
+        if amount_high != [0u8; 16] {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "transfer amount overflows u128",
+            )
+            .into());
+        }
+
+        let mut amount = [0u8; 16];
+        reader.read_exact(&mut amount)?;
+
+        Ok(Self {
+            token,
+            recipient: H256::from(recipient),
+            amount: Uint128::new(u128::from_be_bytes(amount)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encodes_and_decodes_transfer_message() {
+        let message = TransferMessage {
+            token: TokenId {
+                domain: 1000,
+                id: H256::repeat_byte(1),
+            },
+            recipient: H256::repeat_byte(2),
+            amount: Uint128::new(u128::MAX),
+        };
+
+        let encoded = message.to_vec();
+        assert_eq!(TRANSFER_MESSAGE_LEN, encoded.len());
+
+        let decoded = TransferMessage::read_from(&mut encoded.as_slice()).unwrap();
+        assert_eq!(message, decoded);
+    }
+
+    #[test]
+    fn rejects_amount_overflow() {
+        let mut encoded = TransferMessage {
+            token: TokenId {
+                domain: 1000,
+                id: H256::zero(),
+            },
+            recipient: H256::zero(),
+            amount: Uint128::new(1),
+        }
+        .to_vec();
+        encoded[68] = 1;
+
+        assert!(TransferMessage::read_from(&mut encoded.as_slice()).is_err());
+    }
+}
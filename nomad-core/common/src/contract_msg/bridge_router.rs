@@ -0,0 +1,117 @@
+use cosmwasm_std::Uint128;
+use cw20::Cw20ReceiveMsg;
+use ethers_core::types::H256;
+use serde::{Deserialize, Serialize};
+
+use crate::TokenId;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InstantiateMsg {
+    pub local_domain: u32,
+    pub home: String,
+    pub connection_manager: String,
+    /// Code id of cw20 contract used to deploy representation tokens
+    pub token_code_id: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Escrow native denom sent in funds and transfer to `recipient` on
+    /// `destination`
+    SendNative {
+        destination: u32,
+        recipient: H256,
+    },
+    /// Escrow (or burn if representation) cw20 tokens. `msg` is a
+    /// `ReceiveMsg`
+    Receive(Cw20ReceiveMsg),
+    /// Handle transfer message delivered by a replica
+    HandleMsg {
+        origin: u32,
+        nonce: u32,
+        sender: H256,
+        message: Vec<u8>,
+    },
+    /// Enroll remote bridge router for `domain` (owner only)
+    SetRouter {
+        domain: u32,
+        router: H256,
+    },
+    /// Register an existing local cw20 (minter must be bridge router) as
+    /// representation for remote token (owner only)
+    EnrollRepresentation {
+        token: TokenId,
+        representation: String,
+    },
+    RenounceOwnership {},
+    TransferOwnership {
+        new_owner: String,
+    },
+}
+
+/// Message embedded in `Cw20ReceiveMsg.msg`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    Send { destination: u32, recipient: H256 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Router {
+        domain: u32,
+    },
+    /// Local representation of remote token
+    Representation {
+        token: TokenId,
+    },
+    /// Canonical token id of local representation
+    CanonicalToken {
+        representation: String,
+    },
+    /// Local token for canonical id originating on this domain
+    LocalToken {
+        id: H256,
+    },
+    Escrowed {
+        id: H256,
+    },
+    LocalDomain {},
+    Owner {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalToken {
+    Native { denom: String },
+    Cw20 { address: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RouterResponse {
+    /// Zero if no router enrolled
+    pub router: H256,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RepresentationResponse {
+    /// Representation address or "0x0" if not yet deployed
+    pub representation: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CanonicalTokenResponse {
+    pub token: Option<TokenId>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LocalTokenResponse {
+    pub token: Option<LocalToken>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct EscrowedResponse {
+    pub amount: Uint128,
+}
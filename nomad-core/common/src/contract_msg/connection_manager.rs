@@ -1,18 +1,25 @@
-use ethers_core::types::{H160, H256};
+use ethers_core::types::H256;
 use serde::{Deserialize, Serialize};
 
+use crate::SignerId;
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
-pub struct InstantiateMsg {
-    pub chain_addr_length_bytes: usize,
-}
+pub struct InstantiateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MigrateMsg {}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
+    /// Unenroll replica for `domain` given a watcher-signed failure
+    /// notification of `updater`. The ETH address watcher is recovered from
+    /// the signature if `watcher` is None
     UnenrollReplica {
         domain: u32,
         updater: H256,
         signature: Vec<u8>,
+        watcher: Option<SignerId>,
     },
     OwnerEnrollReplica {
         domain: u32,
@@ -21,8 +28,13 @@ pub enum ExecuteMsg {
     OwnerUnenrollReplica {
         replica: String,
     },
+    /// Enroll replica hub for one of the remote domains it serves
+    OwnerEnrollHub {
+        domain: u32,
+        hub: String,
+    },
     SetWatcherPermission {
-        watcher: H160,
+        watcher: SignerId,
         domain: u32,
         access: bool,
     },
@@ -41,12 +53,10 @@ pub enum QueryMsg {
     Home {},
     DomainToReplica { domain: u32 },
     ReplicaToDomain { replica: String },
-    WatcherPermission { watcher: H160, domain: u32 },
+    WatcherPermission { watcher: SignerId, domain: u32 },
     IsReplica { replica: String },
     LocalDomain {},
     Owner {},
-
-    ChainAddrLengthBytes {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
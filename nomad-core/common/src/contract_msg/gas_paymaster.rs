@@ -0,0 +1,77 @@
+use cosmwasm_std::Uint128;
+use ethers_core::types::H256;
+use serde::{Deserialize, Serialize};
+
+use crate::SignerId;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InstantiateMsg {
+    /// Native denom payments are made in
+    pub denom: String,
+    /// Signs attestations that a processor delivered a message
+    pub attester: SignerId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Pay for processing message `leaf` on `destination`. Must send at
+    /// least the destination's quote. Payments for the same leaf add up.
+    /// Fails once the leaf's payment was claimed
+    PayForMessage {
+        leaf: H256,
+        destination: u32,
+    },
+    /// Claim payment for `leaf` with attester's signature over
+    /// `delivery_digest` for the caller
+    Claim {
+        leaf: H256,
+        signature: Vec<u8>,
+    },
+    /// Set minimum payment per message to `destination` (owner only)
+    SetQuote {
+        destination: u32,
+        amount: Uint128,
+    },
+    /// Set delivery attester (owner only)
+    SetAttester {
+        attester: SignerId,
+    },
+    RenounceOwnership {},
+    TransferOwnership {
+        new_owner: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Quote { destination: u32 },
+    Payment { leaf: H256 },
+    Attester {},
+    Owner {},
+}
+
+/// Unclaimed payment for a message
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Payment {
+    pub destination: u32,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct QuoteResponse {
+    /// None if payments to destination are not accepted
+    pub amount: Option<Uint128>,
+    pub denom: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PaymentResponse {
+    pub payment: Option<Payment>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AttesterResponse {
+    pub attester: SignerId,
+}
@@ -0,0 +1,71 @@
+use cosmwasm_std::WasmMsg;
+use ethers_core::types::H256;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InstantiateMsg {
+    pub local_domain: u32,
+    pub home: String,
+    pub connection_manager: String,
+    pub governor_domain: u32,
+    /// Local governor. Must be set iff governor domain is the local domain
+    pub governor: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Execute calls on the local chain (governor only)
+    ExecuteCalls { calls: Vec<WasmMsg> },
+    /// Dispatch calls to the governance router on `domain` (governor only)
+    DispatchCalls { domain: u32, calls: Vec<WasmMsg> },
+    /// Transfer governorship to `governor` on `domain`. Broadcast to all
+    /// enrolled routers (governor only)
+    TransferGovernor {
+        domain: u32,
+        governor: Option<String>,
+    },
+    /// Enroll remote governance router for `domain` (governor only)
+    SetRouter { domain: u32, router: H256 },
+    /// Handle governance message delivered by a replica
+    HandleMsg {
+        origin: u32,
+        nonce: u32,
+        sender: H256,
+        message: Vec<u8>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Governor {},
+    Router { domain: u32 },
+    LocalDomain {},
+}
+
+/// Governance message carried in `NomadMessage.body`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum GovernanceMessage {
+    Batch {
+        calls: Vec<WasmMsg>,
+    },
+    TransferGovernor {
+        domain: u32,
+        governor: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GovernorResponse {
+    pub domain: u32,
+    /// Local governor or "0x0" if governor is remote
+    pub governor: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RouterResponse {
+    /// Zero if no router enrolled
+    pub router: H256,
+}
@@ -1,11 +1,14 @@
 use super::{merkle, nomad_base, ownable, queue};
-use ethers_core::types::{H160, H256};
+use ethers_core::types::H256;
 use serde::{Deserialize, Serialize};
 
+use crate::{MessageStatus, SignerId};
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct InstantiateMsg {
     pub local_domain: u32,
-    pub updater: H160,
+    pub updaters: Vec<SignerId>,
+    pub threshold: u32,
 }
 
 impl From<InstantiateMsg> for ownable::InstantiateMsg {
@@ -30,41 +33,83 @@ impl From<InstantiateMsg> for nomad_base::InstantiateMsg {
     fn from(msg: InstantiateMsg) -> Self {
         nomad_base::InstantiateMsg {
             local_domain: msg.local_domain,
-            updater: msg.updater,
+            updaters: msg.updaters,
+            threshold: msg.threshold,
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
+    /// Dispatch message to `recipient` on `destination`. Recipient is not
+    /// validated locally, as it lives on the destination chain.
     Dispatch {
         destination: u32,
-        recipient: String,
+        recipient: H256,
         message_body: Vec<u8>,
     },
+    /// Update signed by at least threshold updaters
     Update {
         committed_root: H256,
         new_root: H256,
-        signature: Vec<u8>,
+        signatures: Vec<Vec<u8>>,
     },
+    /// Two conflicting updates each signed by at least threshold updaters
     DoubleUpdate {
         old_root: H256,
         new_roots: [H256; 2],
-        signature: Vec<u8>,
-        signature_2: Vec<u8>,
+        signatures: Vec<Vec<u8>>,
+        signatures_2: Vec<Vec<u8>>,
     },
     ImproperUpdate {
         old_root: H256,
         new_root: H256,
-        signature: Vec<u8>,
+        signatures: Vec<Vec<u8>>,
     },
-    SetUpdater {
-        updater: H160,
+    /// Replace updater set (updater manager only)
+    SetUpdaters {
+        updaters: Vec<SignerId>,
+        threshold: u32,
     },
     SetUpdaterManager {
         updater_manager: String,
     },
+    /// Toggle storing dispatched messages for on-chain lookup (owner only)
+    SetMessageIndexing {
+        enabled: bool,
+    },
+    /// Toggle storing full merkle tree to serve proofs (owner only)
+    SetFullTreeStorage {
+        enabled: bool,
+    },
+    /// Allow or disallow replicas on `port_id` over `connection_id` to open
+    /// a channel to home (owner only). Each allowed counterparty holds at most
+    /// one channel.
+    SetIbcCounterparty {
+        connection_id: String,
+        port_id: String,
+        allowed: bool,
+    },
+    /// Send message with proof against committed root to replica over IBC
+    /// channel `channel_id`
+    RelayMessage {
+        channel_id: String,
+        message: Vec<u8>,
+        proof: [H256; 32],
+        index: u64,
+    },
+    /// Set gas limit of loopback recipient handle call (owner only)
+    SetProcessGas {
+        process_gas: u64,
+    },
+    /// Redeliver failed loopback message (dispatched to local domain)
+    RetryLoopback {
+        message: Vec<u8>,
+    },
     RenounceOwnership {},
     TransferOwnership {
         new_owner: String,
@@ -78,19 +123,53 @@ pub enum QueryMsg {
     Count {},
     HomeDomainHash {},
     LocalDomain {},
-    Nonces { domain: u32 },
+    Nonces {
+        domain: u32,
+    },
     Owner {},
-    QueueContains { item: H256 },
+    QueueContains {
+        item: H256,
+    },
     QueueEnd {},
     QueueLength {},
     Root {},
     State {},
     SuggestUpdate {},
-    // Tree {},
-    Updater {},
+    Updaters {},
     UpdaterManager {},
 
     MaxMessageBodyBytes {},
+
+    MessageIndexing {},
+    /// Indexed messages to `destination` in nonce order
+    MessagesByDestination {
+        destination: u32,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
+    MessageByLeafIndex {
+        leaf_index: u64,
+    },
+    LeafIndex {
+        leaf: H256,
+    },
+
+    FullTreeStorage {},
+    /// Proof of leaf at `leaf_index` against current root, usable in replica
+    /// `Prove`
+    Proof {
+        leaf_index: u64,
+    },
+
+    /// Connected IBC channels updates are sent over
+    IbcChannels {},
+
+    /// Delivery status of message dispatched to local domain
+    LoopbackStatus {
+        leaf: H256,
+    },
+    /// Gas limit of loopback recipient handle call
+    ProcessGas {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -108,3 +187,48 @@ pub struct SuggestUpdateResponse {
 pub struct UpdaterManagerResponse {
     pub updater_manager: String,
 }
+
+/// Dispatched message stored when message indexing is enabled
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DispatchedMessage {
+    pub leaf: H256,
+    pub leaf_index: u64,
+    /// Encoded `NomadMessage`
+    pub message: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MessageIndexingResponse {
+    pub enabled: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MessagesResponse {
+    pub messages: Vec<DispatchedMessage>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MessageResponse {
+    /// None if leaf index unknown or dispatched while indexing was disabled
+    pub message: Option<DispatchedMessage>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LeafIndexResponse {
+    pub leaf_index: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct IbcChannelsResponse {
+    pub channels: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LoopbackStatusResponse {
+    pub status: MessageStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ProcessGasResponse {
+    pub process_gas: u64,
+}
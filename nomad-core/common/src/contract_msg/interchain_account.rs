@@ -0,0 +1,30 @@
+use cosmwasm_std::CosmosMsg;
+use ethers_core::types::H256;
+use serde::{Deserialize, Serialize};
+
+/// Proxy account is owned by the router that instantiates it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InstantiateMsg {
+    pub origin: u32,
+    pub sender: H256,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Execute calls as the account (router only)
+    ExecuteCalls { calls: Vec<CosmosMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Account {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AccountResponse {
+    pub router: String,
+    pub origin: u32,
+    pub sender: H256,
+}
@@ -0,0 +1,62 @@
+use cosmwasm_std::CosmosMsg;
+use ethers_core::types::H256;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InstantiateMsg {
+    pub local_domain: u32,
+    pub home: String,
+    pub connection_manager: String,
+    /// Code id of interchain account proxy contract
+    pub account_code_id: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Execute calls through the account of message origin and sender.
+    /// Account is deployed on first use
+    HandleMsg {
+        origin: u32,
+        nonce: u32,
+        sender: H256,
+        message: Vec<u8>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Account { origin: u32, sender: H256 },
+    AccountCodeId {},
+    LocalDomain {},
+}
+
+/// Calls carried in `NomadMessage.body`. If `reply` is set, the outcome is
+/// dispatched back to the sender as a `CallsResult`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InterchainCalls {
+    pub calls: Vec<CosmosMsg>,
+    pub reply: bool,
+}
+
+/// Outcome of `InterchainCalls` with nonce of the message that carried them.
+/// Failed calls are reverted and leave the account untouched
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CallsResult {
+    pub nonce: u32,
+    pub account: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AccountResponse {
+    /// Account address or "0x0" if not yet deployed
+    pub account: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AccountCodeIdResponse {
+    pub account_code_id: u64,
+}
@@ -7,7 +7,14 @@ pub struct InstantiateMsg {}
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    Insert { element: H256 },
+    Insert {
+        element: H256,
+    },
+    /// Toggle storing every tree node so proofs can be served on-chain.
+    /// Only leaves inserted after enabling are provable
+    SetFullTreeStorage {
+        enabled: bool,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -15,6 +22,12 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     Root {},
     Count {},
+    FullTreeStorage {},
+    /// Proof of leaf at `leaf_index` against current root. Requires full tree
+    /// storage
+    Proof {
+        leaf_index: u64,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -26,3 +39,9 @@ pub struct RootResponse {
 pub struct CountResponse {
     pub count: usize,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FullTreeStorageResponse {
+    /// First provable leaf index or `None` if full tree storage disabled
+    pub start_index: Option<u64>,
+}
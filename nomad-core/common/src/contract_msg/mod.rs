@@ -1,9 +1,16 @@
+pub mod bridge_router;
 pub mod connection_manager;
+pub mod gas_paymaster;
+pub mod governance_router;
 pub mod home;
+pub mod interchain_account;
+pub mod interchain_account_router;
 pub mod merkle;
 pub mod nomad_base;
 pub mod ownable;
 pub mod queue;
+pub mod reference_recipient;
 pub mod replica;
+pub mod replica_hub;
 pub mod test;
 pub mod updater_manager;
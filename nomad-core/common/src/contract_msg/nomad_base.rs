@@ -1,13 +1,15 @@
 use crate::ownable;
-use ethers_core::types::{H160, H256};
+use ethers_core::types::H256;
 use serde::{Deserialize, Serialize};
 
-use crate::States;
+use crate::{SignerId, States};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct InstantiateMsg {
     pub local_domain: u32,
-    pub updater: H160,
+    pub updaters: Vec<SignerId>,
+    /// Number of updater signatures an update needs
+    pub threshold: u32,
 }
 
 impl From<InstantiateMsg> for ownable::InstantiateMsg {
@@ -36,8 +38,8 @@ pub enum QueryMsg {
     LocalDomain {},
     /// Return contract's current state
     State {},
-    /// Return updater address
-    Updater {},
+    /// Return updater set and signature threshold
+    Updaters {},
     /// Owner of contract (inherited from ownable)
     Owner {},
 }
@@ -67,7 +69,9 @@ pub struct StateResponse {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
-pub struct UpdaterResponse {
-    /// Updater address
-    pub updater: H160,
+pub struct UpdatersResponse {
+    /// Updater identities
+    pub updaters: Vec<SignerId>,
+    /// Number of updater signatures an update needs
+    pub threshold: u32,
 }
@@ -7,10 +7,20 @@ pub struct InstantiateMsg {}
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    Enqueue { item: H256 },
+    Enqueue {
+        item: H256,
+    },
     Dequeue {},
-    EnqueueBatch { items: Vec<H256> },
-    DequeueBatch { number: u64 },
+    EnqueueBatch {
+        items: Vec<H256>,
+    },
+    DequeueBatch {
+        number: u64,
+    },
+    /// Dequeue all items up to and including `item`
+    DequeueThrough {
+        item: H256,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -1,41 +1,84 @@
+use crate::light_client::{ConsensusState, SignedHeader, Validator};
 use crate::nomad_base;
-use ethers_core::types::{H160, H256};
+use cosmwasm_std::Binary;
+use ethers_core::types::H256;
 use serde::{Deserialize, Serialize};
 
-use crate::MessageStatus;
+use crate::{MessageStatus, SignerId};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct InstantiateMsg {
-    pub chain_addr_length_bytes: usize,
     pub local_domain: u32,
     pub remote_domain: u32,
-    pub updater: H160,
+    pub updaters: Vec<SignerId>,
+    pub threshold: u32,
     pub committed_root: H256,
     pub optimistic_seconds: u64,
+    /// Defaults to optimistic verification if None
+    pub root_verifier: Option<RootVerifier>,
+}
+
+/// How replica accepts roots submitted by update
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RootVerifier {
+    /// Update signed by updater threshold. Root is acceptable once
+    /// `optimistic_seconds` have passed
+    Optimistic {},
+    /// Update signed by at least `threshold` of `validators`. Root is
+    /// acceptable immediately
+    Attestation {
+        validators: Vec<SignerId>,
+        threshold: u32,
+    },
+    /// Root proven by ICS-23 proof of home's incremental merkle against an
+    /// origin chain header verified by Tendermint light client. Root is
+    /// acceptable immediately
+    LightClient {
+        /// Origin chain id
+        chain_id: String,
+        /// Canonical address of home on origin chain
+        home: Binary,
+        /// Seconds a verified header's validator set is trusted for
+        trusting_period: u64,
+    },
+}
+
+impl Default for RootVerifier {
+    fn default() -> Self {
+        RootVerifier::Optimistic {}
+    }
 }
 
 impl From<InstantiateMsg> for nomad_base::InstantiateMsg {
     fn from(msg: InstantiateMsg) -> Self {
         Self {
             local_domain: msg.local_domain,
-            updater: msg.updater,
+            updaters: msg.updaters,
+            threshold: msg.threshold,
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
+    /// Update verified by replica's root verifier: signed by at least
+    /// threshold updaters (optimistic) or validators (attestation)
     Update {
         committed_root: H256,
         new_root: H256,
-        signature: Vec<u8>,
+        signatures: Vec<Vec<u8>>,
     },
+    /// Two conflicting updates each signed by at least threshold updaters
     DoubleUpdate {
         old_root: H256,
         new_roots: [H256; 2],
-        signature: Vec<u8>,
-        signature_2: Vec<u8>,
+        signatures: Vec<Vec<u8>>,
+        signatures_2: Vec<Vec<u8>>,
     },
     Prove {
         leaf: H256,
@@ -50,6 +93,18 @@ pub enum ExecuteMsg {
         proof: [H256; 32],
         index: u64,
     },
+    /// Prove and process many messages. Items succeed or fail on their own
+    /// and results are returned in order as `BatchProveAndProcessResponse`.
+    /// Results only cover proving and dispatch: recipient calls run after the
+    /// batch and their outcome is reported by `ProcessSucceeded` and
+    /// `ProcessFailed` events and `MessageStatus`
+    BatchProveAndProcess {
+        items: Vec<BatchItem>,
+    },
+    /// Redeliver message whose previous delivery failed
+    RetryProcess {
+        message: Vec<u8>,
+    },
     SetConfirmation {
         root: H256,
         confirm_at: u64,
@@ -57,8 +112,47 @@ pub enum ExecuteMsg {
     SetOptimisticTimeout {
         optimistic_seconds: u64,
     },
-    SetUpdater {
-        updater: H160,
+    /// Replace updater set (owner only)
+    SetUpdaters {
+        updaters: Vec<SignerId>,
+        threshold: u32,
+    },
+    /// Switch between optimistic, attestation and light client root
+    /// verification (owner only)
+    SetRootVerifier {
+        root_verifier: RootVerifier,
+    },
+    /// Trust origin chain consensus state without verification (owner only).
+    /// Light client verifies headers from here
+    SetTrustedConsensusState {
+        height: u64,
+        consensus_state: ConsensusState,
+    },
+    /// Verify origin chain header from the consensus state at
+    /// `trusted_height`. `validators` is the header's validator set and
+    /// `trusted_validators` the trusted header's next validator set
+    UpdateLightClient {
+        signed_header: SignedHeader,
+        validators: Vec<Validator>,
+        trusted_height: u64,
+        trusted_validators: Vec<Validator>,
+    },
+    /// Commit root of home's incremental merkle, proven against app hash of
+    /// consensus state at `height` (ABCI query height + 1). `proofs` are the
+    /// wasm store and multistore commitment proofs of the raw merkle value
+    ProveCommittedRoot {
+        height: u64,
+        incremental_merkle: Binary,
+        proofs: Vec<Binary>,
+    },
+    /// Set gas limit of recipient handle call (owner only)
+    SetProcessGas {
+        process_gas: u64,
+    },
+    /// Only process messages in nonce order starting from `next_nonce`, or
+    /// in any order if None (owner only)
+    SetOrderedDelivery {
+        next_nonce: Option<u32>,
     },
     RenounceOwnership {},
     TransferOwnership {
@@ -73,12 +167,17 @@ pub enum QueryMsg {
     ConfirmAt { root: H256 },
     MessageStatus { leaf: H256 },
     OptimisticSeconds {},
+    ProcessGas {},
+    NextExpectedNonce {},
     RemoteDomain {},
+    RootVerifier {},
+    ConsensusState { height: u64 },
+    LatestConsensusHeight {},
     CommittedRoot {},
     HomeDomainHash {},
     LocalDomain {},
     State {},
-    Updater {},
+    Updaters {},
     Owner {},
 }
 
@@ -92,6 +191,34 @@ pub struct ConfirmAtResponse {
     pub confirm_at: u64,
 }
 
+/// Message with proof of its leaf at `index`. `proof` holds the lowest
+/// siblings of the leaf's path; omitted ones are taken from paths of earlier
+/// items in the batch.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BatchItem {
+    pub message: Vec<u8>,
+    pub proof: Vec<H256>,
+    pub index: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BatchItemResult {
+    pub leaf: H256,
+    /// Item was proven and its recipient call was dispatched. Does NOT mean
+    /// the message was delivered: the recipient call runs after the batch
+    /// returns and may still fail, leaving the message `Failed` and
+    /// retryable. Check `ProcessSucceeded` / `ProcessFailed` events or
+    /// query `MessageStatus` for the delivery outcome.
+    pub dispatched: bool,
+    /// Error that failed the item
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BatchProveAndProcessResponse {
+    pub results: Vec<BatchItemResult>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct MessageStatusResponse {
     pub status: MessageStatus,
@@ -102,7 +229,33 @@ pub struct OptimisticSecondsResponse {
     pub optimistic_seconds: u64,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ProcessGasResponse {
+    pub process_gas: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NextExpectedNonceResponse {
+    /// None if messages may be processed in any order
+    pub next_nonce: Option<u32>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct RemoteDomainResponse {
     pub remote_domain: u32,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RootVerifierResponse {
+    pub root_verifier: RootVerifier,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ConsensusStateResponse {
+    pub consensus_state: Option<ConsensusState>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LatestConsensusHeightResponse {
+    pub height: u64,
+}
@@ -0,0 +1,113 @@
+use ethers_core::types::H256;
+use serde::{Deserialize, Serialize};
+
+use crate::SignerId;
+
+/// Remote home served by the replica hub
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RemoteHome {
+    pub domain: u32,
+    pub updaters: Vec<SignerId>,
+    pub threshold: u32,
+    pub committed_root: H256,
+    pub optimistic_seconds: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InstantiateMsg {
+    pub local_domain: u32,
+    pub remotes: Vec<RemoteHome>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Submit update signed by at least threshold of `domain`'s updaters
+    Update {
+        domain: u32,
+        committed_root: H256,
+        new_root: H256,
+        signatures: Vec<Vec<u8>>,
+    },
+    /// Fail `domain` given two conflicting updates each signed by at least
+    /// threshold of its updaters
+    DoubleUpdate {
+        domain: u32,
+        old_root: H256,
+        new_roots: [H256; 2],
+        signatures: Vec<Vec<u8>>,
+        signatures_2: Vec<Vec<u8>>,
+    },
+    /// Prove message against a root of the message's origin domain
+    Prove {
+        message: Vec<u8>,
+        proof: [H256; 32],
+        index: u64,
+    },
+    Process {
+        message: Vec<u8>,
+    },
+    ProveAndProcess {
+        message: Vec<u8>,
+        proof: [H256; 32],
+        index: u64,
+    },
+    /// Redeliver message whose previous delivery failed
+    RetryProcess {
+        message: Vec<u8>,
+    },
+    /// Start serving a new remote home (owner only)
+    EnrollRemote {
+        remote: RemoteHome,
+    },
+    SetConfirmation {
+        domain: u32,
+        root: H256,
+        confirm_at: u64,
+    },
+    SetOptimisticTimeout {
+        domain: u32,
+        optimistic_seconds: u64,
+    },
+    /// Replace `domain`'s updater set (owner only)
+    SetUpdaters {
+        domain: u32,
+        updaters: Vec<SignerId>,
+        threshold: u32,
+    },
+    /// Set gas limit of recipient handle call (owner only)
+    SetProcessGas {
+        process_gas: u64,
+    },
+    RenounceOwnership {},
+    TransferOwnership {
+        new_owner: String,
+    },
+}
+
+/// Per-domain queries return the same responses as the single-domain
+/// replica and nomad base
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    AcceptableRoot { domain: u32, root: H256 },
+    ConfirmAt { domain: u32, root: H256 },
+    MessageStatus { leaf: H256 },
+    OptimisticSeconds { domain: u32 },
+    CommittedRoot { domain: u32 },
+    HomeDomainHash { domain: u32 },
+    State { domain: u32 },
+    Updaters { domain: u32 },
+    ProcessGas {},
+    RemoteDomains {},
+    LocalDomain {},
+    Owner {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RemoteDomainsResponse {
+    pub domains: Vec<u32>,
+}
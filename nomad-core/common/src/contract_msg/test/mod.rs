@@ -1,2 +1,3 @@
+pub mod bad_recipient;
 pub mod test_recipient;
 pub mod test_replica;
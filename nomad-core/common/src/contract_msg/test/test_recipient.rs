@@ -2,7 +2,10 @@ use ethers_core::types::H256;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
-pub struct InstantiateMsg {}
+pub struct InstantiateMsg {
+    pub home: String,
+    pub connection_manager: String,
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -13,8 +16,26 @@ pub enum ExecuteMsg {
         sender: H256,
         message: Vec<u8>,
     },
+    /// Enroll test recipient on `domain` as remote router (owner only)
+    EnrollRemoteRouter { domain: u32, router: H256 },
+    /// Send `message` to remote router on `destination`
+    SendMessage { destination: u32, message: Vec<u8> },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
-pub enum QueryMsg {}
+pub enum QueryMsg {
+    RemoteRouter { domain: u32 },
+    MessageCount {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RemoteRouterResponse {
+    /// Zero if no router enrolled
+    pub router: H256,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MessageCountResponse {
+    pub count: u64,
+}
@@ -1,29 +1,131 @@
-use ethers_core::types::H160;
+use cosmwasm_std::Uint128;
 use serde::{Deserialize, Serialize};
 
+use crate::SignerId;
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct InstantiateMsg {
-    pub updater: H160,
+    pub updaters: Vec<SignerId>,
+    /// Number of updater signatures an update needs
+    pub threshold: u32,
+    /// Native denom updaters must bond in
+    pub bond_denom: String,
+    /// Minimum bond each updater must post when added
+    pub bond_amount: Uint128,
+    /// Share of a slashed bond paid to the reporter, in basis points
+    pub reporter_share_bps: u64,
+    /// Recipient of the non-reporter share of a slashed bond. Burned if None
+    pub treasury: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MigrateMsg {
+    /// Required when migrating from v0.1.x (before updater bonding)
+    pub slashing_params: Option<SlashingParams>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SlashingParams {
+    pub bond_denom: String,
+    pub bond_amount: Uint128,
+    pub reporter_share_bps: u64,
+    pub treasury: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    SetHome { home: String },
-    SetUpdater { updater: H160 },
-    SlashUpdater { reporter: String },
+    SetHome {
+        home: String,
+    },
+    /// Add bonded updater to set (owner only). Bond is sent in funds
+    AddUpdater {
+        updater: SignerId,
+    },
+    /// Remove updater from set and refund its bond (owner only)
+    RemoveUpdater {
+        updater: SignerId,
+    },
+    /// Set number of updater signatures an update needs (owner only)
+    SetThreshold {
+        threshold: u32,
+    },
+    SetSlashingConfig {
+        reporter_share_bps: u64,
+        treasury: Option<String>,
+    },
+    /// Slash bonds of `updaters` that signed a fraudulent update (home only)
+    SlashUpdaters {
+        reporter: String,
+        updaters: Vec<SignerId>,
+    },
     RenounceOwnership {},
-    TransferOwnership { new_owner: String },
+    TransferOwnership {
+        new_owner: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    Updater {},
+    Updaters {},
+    Bond {
+        updater: SignerId,
+    },
+    SlashingConfig {},
+    SlashingHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
     Owner {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
-pub struct UpdaterResponse {
-    pub updater: H160,
+pub struct UpdatersResponse {
+    pub updaters: Vec<SignerId>,
+    pub threshold: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BondResponse {
+    /// Bonded updater
+    pub updater: SignerId,
+    /// Address that posted the bond and is refunded when the updater is removed
+    pub depositor: String,
+    /// Bonded amount remaining
+    pub amount: Uint128,
+    /// Bond denom
+    pub denom: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SlashingConfigResponse {
+    pub bond_denom: String,
+    pub bond_amount: Uint128,
+    pub reporter_share_bps: u64,
+    pub treasury: Option<String>,
+}
+
+/// A single executed slash
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SlashRecord {
+    /// Sequential slash id
+    pub id: u64,
+    /// Slashed updater
+    pub updater: SignerId,
+    /// Reporter that received the reporter share
+    pub reporter: String,
+    /// Amount paid to reporter
+    pub reporter_amount: Uint128,
+    /// Amount sent to treasury (or burned if no treasury set)
+    pub remainder_amount: Uint128,
+    /// Treasury that received the remainder. None if burned
+    pub treasury: Option<String>,
+    /// Block height of slash
+    pub height: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SlashingHistoryResponse {
+    pub slashes: Vec<SlashRecord>,
 }
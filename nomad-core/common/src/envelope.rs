@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+use crate::traits::{Decode, Encode};
+use crate::NomadError;
+
+/// Prefix marking a versioned envelope at the start of a message body
+pub const ENVELOPE_MAGIC: [u8; 4] = *b"NMEV";
+
+/// Current envelope version
+pub const ENVELOPE_VERSION: u8 = 1;
+
+const ENVELOPE_HEADER_LEN: usize = 13;
+
+/// Optional header carried in `NomadMessage.body`. Bodies without the magic
+/// prefix are delivered as is and never expire.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Envelope {
+    /// 8   Last block time (unix seconds) message may be processed at
+    pub expires_at: u64,
+    /// 0+  Body handed to the recipient
+    pub body: Vec<u8>,
+}
+
+impl Envelope {
+    /// Wrap a body with an expiry
+    pub fn new(expires_at: u64, body: Vec<u8>) -> Self {
+        Self { expires_at, body }
+    }
+
+    /// Read envelope from a message body. Returns `None` for bodies without
+    /// the envelope prefix.
+    pub fn parse(body: &[u8]) -> Result<Option<Self>, NomadError> {
+        if !body.starts_with(&ENVELOPE_MAGIC) {
+            return Ok(None);
+        }
+        Self::read_from(&mut &body[..]).map(Some)
+    }
+
+    /// Strip the envelope from a message body, if it carries one
+    pub fn unwrap_body(body: Vec<u8>) -> Result<Vec<u8>, NomadError> {
+        Ok(match Self::parse(&body)? {
+            Some(envelope) => envelope.body,
+            None => body,
+        })
+    }
+
+    /// True if block time `now` is past the expiry
+    pub fn is_expired(&self, now: u64) -> bool {
+        now > self.expires_at
+    }
+}
+
+impl Encode for Envelope {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: std::io::Write,
+    {
+        writer.write_all(&ENVELOPE_MAGIC)?;
+        writer.write_all(&[ENVELOPE_VERSION])?;
+        writer.write_all(&self.expires_at.to_be_bytes())?;
+        writer.write_all(&self.body)?;
+        Ok(ENVELOPE_HEADER_LEN + self.body.len())
+    }
+}
+
+impl Decode for Envelope {
+    fn read_from<R>(reader: &mut R) -> Result<Self, NomadError>
+    where
+        R: std::io::Read,
+    {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != ENVELOPE_MAGIC {
+            return Err(NomadError::NotEnvelope);
+        }
+
+        let mut version = [0u8; 1];
+        reader.read_exact(&mut version)?;
+        if version[0] != ENVELOPE_VERSION {
+            return Err(NomadError::UnsupportedEnvelopeVersion(version[0]));
+        }
+
+        let mut expires_at = [0u8; 8];
+        reader.read_exact(&mut expires_at)?;
+
+        let mut body = vec![];
+        reader.read_to_end(&mut body)?;
+
+        Ok(Self {
+            expires_at: u64::from_be_bytes(expires_at),
+            body,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_passes_through_plain_bodies() {
+        let envelope = Envelope::new(100, b"hello".to_vec());
+        let encoded = envelope.to_vec();
+        assert_eq!(encoded.len(), ENVELOPE_HEADER_LEN + 5);
+
+        assert_eq!(Some(envelope.clone()), Envelope::parse(&encoded).unwrap());
+        assert_eq!(b"hello".to_vec(), Envelope::unwrap_body(encoded).unwrap());
+
+        assert_eq!(None, Envelope::parse(b"hello").unwrap());
+        assert_eq!(
+            b"hello".to_vec(),
+            Envelope::unwrap_body(b"hello".to_vec()).unwrap()
+        );
+
+        assert!(!envelope.is_expired(100));
+        assert!(envelope.is_expired(101));
+
+        let mut unknown = envelope.to_vec();
+        unknown[4] = 2;
+        assert!(matches!(
+            Envelope::parse(&unknown),
+            Err(NomadError::UnsupportedEnvelopeVersion(2))
+        ));
+    }
+}
@@ -0,0 +1,118 @@
+use cosmwasm_std::{to_binary, Binary, IbcChannel, IbcOrder, StdResult};
+use ethers_core::types::H256;
+use serde::{Deserialize, Serialize};
+
+/// Channel version of home and replica IBC channels
+pub const NOMAD_IBC_VERSION: &str = "nomad-1";
+/// Channels are ordered so updates reach replica in the order home accepted
+/// them
+pub const NOMAD_IBC_ORDER: IbcOrder = IbcOrder::Ordered;
+
+/// Error types for IBC channel handshakes
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum IbcChannelError {
+    #[error("Nomad channels must be ordered")]
+    InvalidOrder {},
+
+    #[error("Invalid channel version {version}, expected {expected}")]
+    InvalidVersion { version: String, expected: String },
+}
+
+/// Check channel (and counterparty, during handshake) uses Nomad order and
+/// version
+pub fn check_nomad_channel(
+    channel: &IbcChannel,
+    counterparty_version: Option<&str>,
+) -> Result<(), IbcChannelError> {
+    if channel.order != NOMAD_IBC_ORDER {
+        return Err(IbcChannelError::InvalidOrder {});
+    }
+
+    for version in std::iter::once(channel.version.as_str()).chain(counterparty_version) {
+        if version != NOMAD_IBC_VERSION {
+            return Err(IbcChannelError::InvalidVersion {
+                version: version.to_owned(),
+                expected: NOMAD_IBC_VERSION.to_owned(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Packet sent by home to replica
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NomadPacket {
+    /// Update accepted by home, signed by updater threshold
+    Update {
+        committed_root: H256,
+        new_root: H256,
+        signatures: Vec<Vec<u8>>,
+    },
+    /// Message with proof against a root home committed
+    Message {
+        message: Vec<u8>,
+        proof: [H256; 32],
+        index: u64,
+    },
+}
+
+/// Acknowledgement of `NomadPacket`, in the ICS-20 result/error format
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NomadAck {
+    Result(Binary),
+    Error(String),
+}
+
+impl NomadAck {
+    pub fn success() -> StdResult<Binary> {
+        to_binary(&NomadAck::Result(Binary::from(vec![1])))
+    }
+
+    pub fn error(error: impl ToString) -> StdResult<Binary> {
+        to_binary(&NomadAck::Error(error.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::testing::mock_ibc_channel;
+
+    #[test]
+    fn checks_channel_order_and_version() {
+        let channel = mock_ibc_channel("channel-0", IbcOrder::Ordered, NOMAD_IBC_VERSION);
+        assert_eq!(Ok(()), check_nomad_channel(&channel, None));
+        assert_eq!(
+            Ok(()),
+            check_nomad_channel(&channel, Some(NOMAD_IBC_VERSION))
+        );
+
+        assert_eq!(
+            Err(IbcChannelError::InvalidVersion {
+                version: "ics20-1".to_owned(),
+                expected: NOMAD_IBC_VERSION.to_owned(),
+            }),
+            check_nomad_channel(&channel, Some("ics20-1"))
+        );
+
+        let channel = mock_ibc_channel("channel-0", IbcOrder::Unordered, NOMAD_IBC_VERSION);
+        assert_eq!(
+            Err(IbcChannelError::InvalidOrder {}),
+            check_nomad_channel(&channel, None)
+        );
+    }
+
+    #[test]
+    fn encodes_acks_as_result_or_error() {
+        assert_eq!(
+            Binary::from(br#"{"result":"AQ=="}"#.to_vec()),
+            NomadAck::success().unwrap()
+        );
+        assert_eq!(
+            Binary::from(br#"{"error":"bad root"}"#.to_vec()),
+            NomadAck::error("bad root").unwrap()
+        );
+    }
+}
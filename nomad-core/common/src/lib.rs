@@ -1,20 +1,39 @@
-use ethers_core::types::SignatureError;
 use serde::{Deserialize, Serialize};
 
+mod address;
+pub use address::*;
+
 mod message;
 pub use message::*;
 
+mod envelope;
+pub use envelope::*;
+
+mod bridge_message;
+pub use bridge_message::*;
+
 mod contract_msg;
 pub use contract_msg::*;
 
 pub mod merkle_tree;
 
+pub mod light_client;
+
 mod utils;
 pub use utils::*;
 
 mod traits;
 pub use traits::*;
 
+mod signature;
+pub use signature::*;
+
+mod updater_set;
+pub use updater_set::*;
+
+mod ibc;
+pub use ibc::*;
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub enum States {
     UnInitialized,
@@ -27,6 +46,10 @@ pub enum MessageStatus {
     None,
     Pending,
     Processed,
+    /// Recipient errored or ran out of gas. Holds the error. Can be retried
+    Failed(String),
+    /// Envelope expiry passed before processing. Never delivered
+    Expired,
 }
 
 impl Default for MessageStatus {
@@ -40,9 +63,17 @@ impl Default for MessageStatus {
 pub enum NomadError {
     /// Signature Error pasthrough
     #[error(transparent)]
-    SignatureError(#[from] SignatureError),
+    SignatureError(#[from] ethers_core::types::SignatureError),
 
     /// IO error from Read/Write usage
     #[error(transparent)]
     IoError(#[from] std::io::Error),
+
+    /// Body does not start with the envelope prefix
+    #[error("Body is not an envelope")]
+    NotEnvelope,
+
+    /// Envelope prefix with an unknown version
+    #[error("Unsupported envelope version: {0}")]
+    UnsupportedEnvelopeVersion(u8),
 }
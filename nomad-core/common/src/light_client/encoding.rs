@@ -0,0 +1,228 @@
+use sha2::{Digest, Sha256};
+
+use super::{BlockId, Commit, CommitSig, Header, PartSetHeader, Timestamp, Validator, Version};
+
+/// Precommit vote type
+const SIGNED_MSG_TYPE_PRECOMMIT: u64 = 2;
+/// Validator addresses are the first 20 bytes of sha256(pub_key)
+const ADDRESS_BYTES: usize = 20;
+
+const WIRE_VARINT: u32 = 0;
+const WIRE_FIXED64: u32 = 1;
+const WIRE_BYTES: u32 = 2;
+
+fn encode_varint(mut value: u64, buf: &mut Vec<u8>) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn encode_key(field: u32, wire_type: u32, buf: &mut Vec<u8>) {
+    encode_varint(((field << 3) | wire_type) as u64, buf);
+}
+
+/// Varint field, omitted if zero (proto3 default)
+fn encode_varint_field(field: u32, value: u64, buf: &mut Vec<u8>) {
+    if value != 0 {
+        encode_key(field, WIRE_VARINT, buf);
+        encode_varint(value, buf);
+    }
+}
+
+/// Sfixed64 field, omitted if zero
+fn encode_sfixed64_field(field: u32, value: i64, buf: &mut Vec<u8>) {
+    if value != 0 {
+        encode_key(field, WIRE_FIXED64, buf);
+        buf.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+/// Bytes or string field, omitted if empty
+fn encode_bytes_field(field: u32, value: &[u8], buf: &mut Vec<u8>) {
+    if !value.is_empty() {
+        encode_message_field(field, value, buf);
+    }
+}
+
+/// Embedded message field. Always encoded (gogoproto non-nullable)
+fn encode_message_field(field: u32, value: &[u8], buf: &mut Vec<u8>) {
+    encode_key(field, WIRE_BYTES, buf);
+    encode_varint(value.len() as u64, buf);
+    buf.extend_from_slice(value);
+}
+
+fn encode_timestamp(timestamp: &Timestamp) -> Vec<u8> {
+    let mut buf = vec![];
+    encode_varint_field(1, timestamp.seconds as u64, &mut buf);
+    encode_varint_field(2, timestamp.nanos as i64 as u64, &mut buf);
+    buf
+}
+
+fn encode_part_set_header(part_set_header: &PartSetHeader) -> Vec<u8> {
+    let mut buf = vec![];
+    encode_varint_field(1, part_set_header.total as u64, &mut buf);
+    encode_bytes_field(2, &part_set_header.hash, &mut buf);
+    buf
+}
+
+/// Encoding of both `BlockID` and `CanonicalBlockID`
+fn encode_block_id(block_id: &BlockId) -> Vec<u8> {
+    let mut buf = vec![];
+    encode_bytes_field(1, &block_id.hash, &mut buf);
+    encode_message_field(
+        2,
+        &encode_part_set_header(&block_id.part_set_header),
+        &mut buf,
+    );
+    buf
+}
+
+fn encode_version(version: &Version) -> Vec<u8> {
+    let mut buf = vec![];
+    encode_varint_field(1, version.block, &mut buf);
+    encode_varint_field(2, version.app, &mut buf);
+    buf
+}
+
+/// Header fields are hashed as protobuf wrapper types (`StringValue`,
+/// `Int64Value`, `BytesValue`), which all hold their value in field 1
+fn encode_wrapped_bytes(value: &[u8]) -> Vec<u8> {
+    let mut buf = vec![];
+    encode_bytes_field(1, value, &mut buf);
+    buf
+}
+
+fn encode_wrapped_varint(value: u64) -> Vec<u8> {
+    let mut buf = vec![];
+    encode_varint_field(1, value, &mut buf);
+    buf
+}
+
+fn leaf_hash(leaf: &[u8]) -> [u8; 32] {
+    Sha256::new().chain([0u8]).chain(leaf).finalize().into()
+}
+
+fn inner_hash(left: &[u8], right: &[u8]) -> [u8; 32] {
+    Sha256::new()
+        .chain([1u8])
+        .chain(left)
+        .chain(right)
+        .finalize()
+        .into()
+}
+
+/// RFC 6962 merkle root of `items`, split at the largest power of two less
+/// than the number of items
+pub fn simple_merkle_root(items: &[Vec<u8>]) -> [u8; 32] {
+    match items.len() {
+        0 => Sha256::digest(&[]).into(),
+        1 => leaf_hash(&items[0]),
+        n => {
+            let split = n.next_power_of_two() / 2;
+            inner_hash(
+                &simple_merkle_root(&items[..split]),
+                &simple_merkle_root(&items[split..]),
+            )
+        }
+    }
+}
+
+/// Block hash: merkle root of the encoded header fields
+pub fn header_hash(header: &Header) -> [u8; 32] {
+    simple_merkle_root(&[
+        encode_version(&header.version),
+        encode_wrapped_bytes(header.chain_id.as_bytes()),
+        encode_wrapped_varint(header.height),
+        encode_timestamp(&header.time),
+        encode_block_id(&header.last_block_id),
+        encode_wrapped_bytes(&header.last_commit_hash),
+        encode_wrapped_bytes(&header.data_hash),
+        encode_wrapped_bytes(&header.validators_hash),
+        encode_wrapped_bytes(&header.next_validators_hash),
+        encode_wrapped_bytes(&header.consensus_hash),
+        encode_wrapped_bytes(&header.app_hash),
+        encode_wrapped_bytes(&header.last_results_hash),
+        encode_wrapped_bytes(&header.evidence_hash),
+        encode_wrapped_bytes(&header.proposer_address),
+    ])
+}
+
+/// Encode validator as `SimpleValidator`
+fn encode_validator(validator: &Validator) -> Vec<u8> {
+    let mut pub_key = vec![];
+    encode_bytes_field(1, &validator.pub_key, &mut pub_key);
+
+    let mut buf = vec![];
+    encode_message_field(1, &pub_key, &mut buf);
+    encode_varint_field(2, validator.voting_power, &mut buf);
+    buf
+}
+
+/// Merkle root of validator set, in set order
+pub fn validator_set_hash(validators: &[Validator]) -> [u8; 32] {
+    let items: Vec<Vec<u8>> = validators.iter().map(encode_validator).collect();
+    simple_merkle_root(&items)
+}
+
+/// Validator address derived from its ed25519 public key
+pub fn validator_address(pub_key: &[u8]) -> Vec<u8> {
+    Sha256::digest(pub_key)[..ADDRESS_BYTES].to_vec()
+}
+
+/// Length-prefixed `CanonicalVote` signed by the validator behind
+/// `commit_sig`
+pub fn vote_sign_bytes(chain_id: &str, commit: &Commit, commit_sig: &CommitSig) -> Vec<u8> {
+    let mut vote = vec![];
+    encode_varint_field(1, SIGNED_MSG_TYPE_PRECOMMIT, &mut vote);
+    encode_sfixed64_field(2, commit.height as i64, &mut vote);
+    encode_sfixed64_field(3, commit.round as i64, &mut vote);
+    encode_message_field(4, &encode_block_id(&commit.block_id), &mut vote);
+    encode_message_field(5, &encode_timestamp(&commit_sig.timestamp), &mut vote);
+    encode_bytes_field(6, chain_id.as_bytes(), &mut vote);
+
+    let mut buf = vec![];
+    encode_varint(vote.len() as u64, &mut buf);
+    buf.extend(vote);
+    buf
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn computes_rfc6962_roots() {
+        assert_eq!(
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            hex::encode(simple_merkle_root(&[]))
+        );
+        assert_eq!(
+            "6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d",
+            hex::encode(simple_merkle_root(&[vec![]]))
+        );
+
+        // Three items split 2 + 1
+        let items = vec![vec![1u8], vec![2u8], vec![3u8]];
+        let expected = inner_hash(
+            &inner_hash(&leaf_hash(&[1]), &leaf_hash(&[2])),
+            &leaf_hash(&[3]),
+        );
+        assert_eq!(expected, simple_merkle_root(&items));
+    }
+
+    #[test]
+    fn encodes_varints() {
+        let mut buf = vec![];
+        encode_varint(300, &mut buf);
+        assert_eq!(vec![0xac, 0x02], buf);
+
+        // Zero values are omitted, embedded messages are not
+        let mut buf = vec![];
+        encode_varint_field(1, 0, &mut buf);
+        encode_bytes_field(2, &[], &mut buf);
+        encode_message_field(3, &[], &mut buf);
+        assert_eq!(vec![0x1a, 0x00], buf);
+    }
+}
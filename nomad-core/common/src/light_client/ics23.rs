@@ -0,0 +1,389 @@
+//! ICS-23 existence proofs for IAVL stores and the Tendermint simple merkle
+//! tree committing store roots to the app hash
+
+use cosmwasm_std::Binary;
+use prost::Message;
+use sha2::{Digest, Sha256};
+
+/// `HashOp::NO_HASH`
+pub const HASH_OP_NO_HASH: i32 = 0;
+/// `HashOp::SHA256`
+pub const HASH_OP_SHA256: i32 = 1;
+/// `LengthOp::VAR_PROTO`
+pub const LENGTH_OP_VAR_PROTO: i32 = 1;
+
+/// Error types for ICS-23 proof verification
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum Ics23Error {
+    #[error("Failed to decode commitment proof: {0}")]
+    Decode(String),
+
+    #[error("Commitment proof is not an existence proof")]
+    NotExistenceProof {},
+
+    #[error("Expected {expected} proofs. Got {count}")]
+    WrongProofCount { expected: usize, count: usize },
+
+    #[error("Proof key does not match")]
+    KeyMismatch {},
+
+    #[error("Proof value does not match")]
+    ValueMismatch {},
+
+    #[error("Leaf op does not match proof spec")]
+    InvalidLeaf {},
+
+    #[error("Inner op {index} does not match proof spec")]
+    InvalidInner { index: usize },
+
+    #[error("Calculated root does not match")]
+    RootMismatch {},
+}
+
+/// `CommitmentProof`, decoding only the `exist` variant of its oneof
+#[derive(Clone, PartialEq, Message)]
+pub struct CommitmentProof {
+    #[prost(message, optional, tag = "1")]
+    pub exist: Option<ExistenceProof>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ExistenceProof {
+    #[prost(bytes = "vec", tag = "1")]
+    pub key: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub value: Vec<u8>,
+    #[prost(message, optional, tag = "3")]
+    pub leaf: Option<LeafOp>,
+    #[prost(message, repeated, tag = "4")]
+    pub path: Vec<InnerOp>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct LeafOp {
+    #[prost(int32, tag = "1")]
+    pub hash: i32,
+    #[prost(int32, tag = "2")]
+    pub prehash_key: i32,
+    #[prost(int32, tag = "3")]
+    pub prehash_value: i32,
+    #[prost(int32, tag = "4")]
+    pub length: i32,
+    #[prost(bytes = "vec", tag = "5")]
+    pub prefix: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct InnerOp {
+    #[prost(int32, tag = "1")]
+    pub hash: i32,
+    #[prost(bytes = "vec", tag = "2")]
+    pub prefix: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub suffix: Vec<u8>,
+}
+
+/// Subset of ICS-23 `ProofSpec` needed for SHA-256 binary trees. Leaves are
+/// always sha256(prefix || len(key) || key || len(sha256(value)) ||
+/// sha256(value))
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProofSpec {
+    pub leaf_prefix: &'static [u8],
+    pub child_size: usize,
+    pub min_prefix_length: usize,
+    pub max_prefix_length: usize,
+    /// Validate IAVL height, size and version varints in op prefixes
+    pub iavl: bool,
+}
+
+/// Cosmos SDK IAVL store spec
+pub const IAVL_SPEC: ProofSpec = ProofSpec {
+    leaf_prefix: &[0],
+    child_size: 33,
+    min_prefix_length: 4,
+    max_prefix_length: 12,
+    iavl: true,
+};
+
+/// Tendermint simple merkle tree spec, used by the SDK multistore
+pub const TENDERMINT_SPEC: ProofSpec = ProofSpec {
+    leaf_prefix: &[0],
+    child_size: 32,
+    min_prefix_length: 1,
+    max_prefix_length: 1,
+    iavl: false,
+};
+
+/// Name of the wasmd contract store in the SDK multistore
+pub const WASM_STORE_NAME: &[u8] = b"wasm";
+/// wasmd prefix of contract storage keys
+const WASM_CONTRACT_STORE_PREFIX: u8 = 0x03;
+
+/// Key of contract storage entry `key` in the wasm store, for contract with
+/// canonical address `contract`
+pub fn wasm_contract_store_key(contract: &[u8], key: &[u8]) -> Vec<u8> {
+    let mut store_key = vec![WASM_CONTRACT_STORE_PREFIX];
+    store_key.extend_from_slice(contract);
+    store_key.extend_from_slice(key);
+    store_key
+}
+
+/// Decode protobuf `CommitmentProof` holding an existence proof
+pub fn decode_existence_proof(bytes: &[u8]) -> Result<ExistenceProof, Ics23Error> {
+    CommitmentProof::decode(bytes)
+        .map_err(|e| Ics23Error::Decode(e.to_string()))?
+        .exist
+        .ok_or(Ics23Error::NotExistenceProof {})
+}
+
+/// Verify `proof` shows `key` maps to `value` under `root`
+pub fn verify_membership(
+    spec: &ProofSpec,
+    root: &[u8],
+    proof: &ExistenceProof,
+    key: &[u8],
+    value: &[u8],
+) -> Result<(), Ics23Error> {
+    if existence_root(spec, proof, key, value)? != root {
+        return Err(Ics23Error::RootMismatch {});
+    }
+    Ok(())
+}
+
+/// Root committing to `key` and `value`, if `proof` is for them
+fn existence_root(
+    spec: &ProofSpec,
+    proof: &ExistenceProof,
+    key: &[u8],
+    value: &[u8],
+) -> Result<Vec<u8>, Ics23Error> {
+    if proof.key != key {
+        return Err(Ics23Error::KeyMismatch {});
+    }
+    if proof.value != value {
+        return Err(Ics23Error::ValueMismatch {});
+    }
+    calculate_root(spec, proof)
+}
+
+/// Verify Cosmos SDK proof that `key` maps to `value` in store `store_name`
+/// under `app_hash`. `proofs` are the encoded IAVL proof of `key` followed by
+/// the multistore proof of the store root, as returned by ABCI queries
+pub fn verify_sdk_membership(
+    app_hash: &[u8],
+    store_name: &[u8],
+    key: &[u8],
+    value: &[u8],
+    proofs: &[Binary],
+) -> Result<(), Ics23Error> {
+    if proofs.len() != 2 {
+        return Err(Ics23Error::WrongProofCount {
+            expected: 2,
+            count: proofs.len(),
+        });
+    }
+
+    let store_proof = decode_existence_proof(proofs[0].as_slice())?;
+    let store_root = existence_root(&IAVL_SPEC, &store_proof, key, value)?;
+
+    let multistore_proof = decode_existence_proof(proofs[1].as_slice())?;
+    verify_membership(
+        &TENDERMINT_SPEC,
+        app_hash,
+        &multistore_proof,
+        store_name,
+        &store_root,
+    )
+}
+
+/// Root committing to `proof` key and value, after checking ops against
+/// `spec`
+pub fn calculate_root(spec: &ProofSpec, proof: &ExistenceProof) -> Result<Vec<u8>, Ics23Error> {
+    if proof.key.is_empty() || proof.value.is_empty() {
+        return Err(Ics23Error::InvalidLeaf {});
+    }
+
+    let leaf = proof.leaf.as_ref().ok_or(Ics23Error::InvalidLeaf {})?;
+    check_leaf(spec, leaf)?;
+
+    let mut node = Sha256::new()
+        .chain(&leaf.prefix)
+        .chain(length_prefixed(&proof.key))
+        .chain(length_prefixed(&Sha256::digest(&proof.value)))
+        .finalize()
+        .to_vec();
+
+    for (index, inner) in proof.path.iter().enumerate() {
+        check_inner(spec, inner, index)?;
+        node = Sha256::new()
+            .chain(&inner.prefix)
+            .chain(&node)
+            .chain(&inner.suffix)
+            .finalize()
+            .to_vec();
+    }
+
+    Ok(node)
+}
+
+fn check_leaf(spec: &ProofSpec, leaf: &LeafOp) -> Result<(), Ics23Error> {
+    let valid = leaf.hash == HASH_OP_SHA256
+        && leaf.prehash_key == HASH_OP_NO_HASH
+        && leaf.prehash_value == HASH_OP_SHA256
+        && leaf.length == LENGTH_OP_VAR_PROTO
+        && leaf.prefix.starts_with(spec.leaf_prefix)
+        && (!spec.iavl || iavl_prefix_remainder(&leaf.prefix, 0) == Some(0));
+
+    if !valid {
+        return Err(Ics23Error::InvalidLeaf {});
+    }
+    Ok(())
+}
+
+fn check_inner(spec: &ProofSpec, inner: &InnerOp, index: usize) -> Result<(), Ics23Error> {
+    // Binary trees: at most one left sibling in the prefix
+    let max_prefix_length = spec.max_prefix_length + spec.child_size;
+    let valid = inner.hash == HASH_OP_SHA256
+        && !inner.prefix.starts_with(spec.leaf_prefix)
+        && inner.prefix.len() >= spec.min_prefix_length
+        && inner.prefix.len() <= max_prefix_length
+        && inner.suffix.len() % spec.child_size == 0
+        && (!spec.iavl || iavl_prefix_remainder(&inner.prefix, index as i64 + 1).is_some());
+
+    if !valid {
+        return Err(Ics23Error::InvalidInner { index });
+    }
+    Ok(())
+}
+
+/// Parse IAVL node prefix (zigzag varint height, size and version). Returns
+/// number of bytes after them, or None if height is below `min_height` or
+/// any value is malformed or negative
+fn iavl_prefix_remainder(prefix: &[u8], min_height: i64) -> Option<usize> {
+    let mut rest = prefix;
+    let height = read_zigzag_varint(&mut rest)?;
+    let size = read_zigzag_varint(&mut rest)?;
+    let version = read_zigzag_varint(&mut rest)?;
+
+    if height < min_height || size < 0 || version < 0 {
+        return None;
+    }
+    Some(rest.len())
+}
+
+fn read_zigzag_varint(buf: &mut &[u8]) -> Option<i64> {
+    let mut value = 0u64;
+    for (i, byte) in buf.iter().enumerate().take(10) {
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            *buf = &buf[i + 1..];
+            return Some((value >> 1) as i64 ^ -((value & 1) as i64));
+        }
+    }
+    None
+}
+
+fn length_prefixed(data: &[u8]) -> Vec<u8> {
+    let mut buf = vec![];
+    let mut len = data.len() as u64;
+    while len >= 0x80 {
+        buf.push((len as u8) | 0x80);
+        len >>= 7;
+    }
+    buf.push(len as u8);
+    buf.extend_from_slice(data);
+    buf
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn iavl_leaf(version: u8) -> LeafOp {
+        LeafOp {
+            hash: HASH_OP_SHA256,
+            prehash_key: HASH_OP_NO_HASH,
+            prehash_value: HASH_OP_SHA256,
+            length: LENGTH_OP_VAR_PROTO,
+            prefix: vec![0, 2, version * 2],
+        }
+    }
+
+    #[test]
+    fn reads_zigzag_varints() {
+        let mut buf: &[u8] = &[0x00, 0x01, 0x02, 0xac, 0x02, 0x07];
+        assert_eq!(Some(0), read_zigzag_varint(&mut buf));
+        assert_eq!(Some(-1), read_zigzag_varint(&mut buf));
+        assert_eq!(Some(1), read_zigzag_varint(&mut buf));
+        assert_eq!(Some(150), read_zigzag_varint(&mut buf));
+        assert_eq!(&[0x07], buf);
+
+        let mut truncated: &[u8] = &[0x80];
+        assert_eq!(None, read_zigzag_varint(&mut truncated));
+    }
+
+    #[test]
+    fn verifies_single_leaf_tree() {
+        let leaf = iavl_leaf(1);
+        let mut proof = ExistenceProof {
+            key: b"key".to_vec(),
+            value: b"value".to_vec(),
+            leaf: Some(leaf.clone()),
+            path: vec![],
+        };
+
+        let root = Sha256::new()
+            .chain(&leaf.prefix)
+            .chain([3u8])
+            .chain(b"key")
+            .chain([32u8])
+            .chain(Sha256::digest(b"value"))
+            .finalize()
+            .to_vec();
+        assert_eq!(
+            Ok(()),
+            verify_membership(&IAVL_SPEC, &root, &proof, b"key", b"value")
+        );
+        assert_eq!(
+            Err(Ics23Error::ValueMismatch {}),
+            verify_membership(&IAVL_SPEC, &root, &proof, b"key", b"other")
+        );
+
+        // Leaf prefix claiming nonzero height
+        proof.leaf.as_mut().unwrap().prefix = vec![0, 2, 2, 2];
+        assert_eq!(
+            Err(Ics23Error::InvalidLeaf {}),
+            calculate_root(&IAVL_SPEC, &proof)
+        );
+    }
+
+    #[test]
+    fn rejects_inner_ops_outside_spec() {
+        let mut proof = ExistenceProof {
+            key: b"key".to_vec(),
+            value: b"value".to_vec(),
+            leaf: Some(iavl_leaf(1)),
+            path: vec![InnerOp {
+                hash: HASH_OP_SHA256,
+                prefix: vec![1],
+                suffix: vec![0; 32],
+            }],
+        };
+        assert!(calculate_root(&TENDERMINT_SPEC, &proof).is_ok());
+
+        // Suffix not a whole number of children
+        proof.path[0].suffix = vec![0; 31];
+        assert_eq!(
+            Err(Ics23Error::InvalidInner { index: 0 }),
+            calculate_root(&TENDERMINT_SPEC, &proof)
+        );
+
+        // Inner op disguised as leaf
+        proof.path[0].prefix = vec![0];
+        proof.path[0].suffix = vec![0; 32];
+        assert_eq!(
+            Err(Ics23Error::InvalidInner { index: 0 }),
+            calculate_root(&TENDERMINT_SPEC, &proof)
+        );
+    }
+}
@@ -0,0 +1,13 @@
+//! Tendermint light client verification of origin chain headers and ICS-23
+//! proofs of origin chain state
+
+mod encoding;
+pub use encoding::*;
+
+pub mod ics23;
+
+mod types;
+pub use types::*;
+
+mod verify;
+pub use verify::*;
@@ -0,0 +1,104 @@
+use cosmwasm_std::Binary;
+use serde::{Deserialize, Serialize};
+
+/// Validator did not sign
+pub const BLOCK_ID_FLAG_ABSENT: u8 = 1;
+/// Validator signed the committed block
+pub const BLOCK_ID_FLAG_COMMIT: u8 = 2;
+/// Validator voted nil
+pub const BLOCK_ID_FLAG_NIL: u8 = 3;
+
+/// Protobuf timestamp (seconds and nanos since unix epoch)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Timestamp {
+    pub seconds: i64,
+    pub nanos: i32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct PartSetHeader {
+    pub total: u32,
+    pub hash: Binary,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct BlockId {
+    pub hash: Binary,
+    pub part_set_header: PartSetHeader,
+}
+
+/// Block and app protocol versions
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Version {
+    pub block: u64,
+    pub app: u64,
+}
+
+/// Tendermint block header. Hashes are raw bytes, not hex
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Header {
+    pub version: Version,
+    pub chain_id: String,
+    pub height: u64,
+    pub time: Timestamp,
+    pub last_block_id: BlockId,
+    pub last_commit_hash: Binary,
+    pub data_hash: Binary,
+    pub validators_hash: Binary,
+    pub next_validators_hash: Binary,
+    pub consensus_hash: Binary,
+    pub app_hash: Binary,
+    pub last_results_hash: Binary,
+    pub evidence_hash: Binary,
+    pub proposer_address: Binary,
+}
+
+/// Precommit of a single validator, in validator set order
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct CommitSig {
+    pub block_id_flag: u8,
+    pub validator_address: Binary,
+    pub timestamp: Timestamp,
+    pub signature: Binary,
+}
+
+/// Precommits for the block at `height`
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Commit {
+    pub height: u64,
+    pub round: u32,
+    pub block_id: BlockId,
+    pub signatures: Vec<CommitSig>,
+}
+
+/// Ed25519 validator
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Validator {
+    pub pub_key: Binary,
+    pub voting_power: u64,
+}
+
+/// Header fields light client keeps for each verified height
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ConsensusState {
+    pub time: Timestamp,
+    pub app_hash: Binary,
+    pub next_validators_hash: Binary,
+}
+
+impl From<&Header> for ConsensusState {
+    fn from(header: &Header) -> Self {
+        Self {
+            time: header.time,
+            app_hash: header.app_hash.clone(),
+            next_validators_hash: header.next_validators_hash.clone(),
+        }
+    }
+}
+
+/// Header with the commit that signed it
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SignedHeader {
+    pub header: Header,
+    pub commit: Commit,
+}
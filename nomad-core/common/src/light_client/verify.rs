@@ -0,0 +1,227 @@
+use cosmwasm_std::Api;
+
+use super::{
+    header_hash, validator_address, validator_set_hash, vote_sign_bytes, Commit, CommitSig,
+    ConsensusState, SignedHeader, Validator, BLOCK_ID_FLAG_COMMIT,
+};
+
+/// Error types for header verification
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum LightClientError {
+    #[error("Header chain id {chain_id} does not match {expected}")]
+    WrongChainId { chain_id: String, expected: String },
+
+    #[error("Header height {height} not after trusted height {trusted_height}")]
+    NonIncreasingHeight { height: u64, trusted_height: u64 },
+
+    #[error("Header time not after trusted header time")]
+    NonIncreasingTime {},
+
+    #[error("Trusted state at height {height} expired")]
+    TrustExpired { height: u64 },
+
+    #[error("Validator set does not match header validators hash")]
+    ValidatorsHashMismatch {},
+
+    #[error("Trusted validator set does not match trusted next validators hash")]
+    TrustedValidatorsHashMismatch {},
+
+    #[error("Adjacent header validators do not match trusted next validators")]
+    AdjacentValidatorsMismatch {},
+
+    #[error("Commit does not sign header")]
+    CommitMismatch {},
+
+    #[error("Commit has {signatures} signatures for {validators} validators")]
+    SignatureCountMismatch {
+        signatures: usize,
+        validators: usize,
+    },
+
+    #[error("Validator {index} signed more than once")]
+    DuplicateSignature { index: usize },
+
+    #[error("Invalid signature from validator {index}")]
+    InvalidSignature { index: usize },
+
+    #[error("Insufficient voting power signed: {signed}, need more than {needed}")]
+    InsufficientVotingPower { signed: u128, needed: u128 },
+}
+
+/// Verified state the next header is checked against
+pub struct TrustedState<'a> {
+    pub height: u64,
+    pub consensus_state: &'a ConsensusState,
+    /// Validator set matching `consensus_state.next_validators_hash`
+    pub next_validators: &'a [Validator],
+}
+
+/// Verify `signed_header` from `trusted` state. Adjacent headers must be
+/// signed by the trusted next validator set. Non-adjacent headers must be
+/// signed by more than 1/3 of the trusted next validator set's power. Either
+/// way, more than 2/3 of `validators` (the header's own set) must sign.
+/// `now` is in unix seconds
+pub fn verify_header(
+    api: &dyn Api,
+    chain_id: &str,
+    trusting_period: u64,
+    now: u64,
+    trusted: &TrustedState,
+    signed_header: &SignedHeader,
+    validators: &[Validator],
+) -> Result<(), LightClientError> {
+    let SignedHeader { header, commit } = signed_header;
+
+    if header.chain_id != chain_id {
+        return Err(LightClientError::WrongChainId {
+            chain_id: header.chain_id.clone(),
+            expected: chain_id.to_owned(),
+        });
+    }
+    if header.height <= trusted.height {
+        return Err(LightClientError::NonIncreasingHeight {
+            height: header.height,
+            trusted_height: trusted.height,
+        });
+    }
+    if header.time <= trusted.consensus_state.time {
+        return Err(LightClientError::NonIncreasingTime {});
+    }
+
+    let trusted_until = trusted
+        .consensus_state
+        .time
+        .seconds
+        .saturating_add(trusting_period as i64);
+    if trusted_until <= now as i64 {
+        return Err(LightClientError::TrustExpired {
+            height: trusted.height,
+        });
+    }
+
+    if validator_set_hash(validators)[..] != header.validators_hash[..] {
+        return Err(LightClientError::ValidatorsHashMismatch {});
+    }
+    if commit.height != header.height || commit.block_id.hash[..] != header_hash(header)[..] {
+        return Err(LightClientError::CommitMismatch {});
+    }
+
+    if header.height == trusted.height + 1 {
+        if header.validators_hash != trusted.consensus_state.next_validators_hash {
+            return Err(LightClientError::AdjacentValidatorsMismatch {});
+        }
+    } else {
+        if validator_set_hash(trusted.next_validators)[..]
+            != trusted.consensus_state.next_validators_hash[..]
+        {
+            return Err(LightClientError::TrustedValidatorsHashMismatch {});
+        }
+        verify_commit_trusting(api, chain_id, trusted.next_validators, commit)?;
+    }
+
+    verify_commit_light(api, chain_id, validators, commit)
+}
+
+/// Verify more than 2/3 of `validators` power signed `commit`. Signatures
+/// are in validator set order
+pub fn verify_commit_light(
+    api: &dyn Api,
+    chain_id: &str,
+    validators: &[Validator],
+    commit: &Commit,
+) -> Result<(), LightClientError> {
+    if commit.signatures.len() != validators.len() {
+        return Err(LightClientError::SignatureCountMismatch {
+            signatures: commit.signatures.len(),
+            validators: validators.len(),
+        });
+    }
+
+    let needed = total_power(validators) * 2 / 3;
+    let mut signed = 0u128;
+    for (index, (commit_sig, validator)) in commit.signatures.iter().zip(validators).enumerate() {
+        if commit_sig.block_id_flag != BLOCK_ID_FLAG_COMMIT {
+            continue;
+        }
+        if commit_sig.validator_address[..] != validator_address(&validator.pub_key)[..] {
+            return Err(LightClientError::InvalidSignature { index });
+        }
+        verify_commit_sig(api, chain_id, commit, commit_sig, validator, index)?;
+
+        signed += validator.voting_power as u128;
+        if signed > needed {
+            return Ok(());
+        }
+    }
+
+    Err(LightClientError::InsufficientVotingPower { signed, needed })
+}
+
+/// Verify more than 1/3 of `trusted_validators` power signed `commit`.
+/// Signatures are matched to validators by address
+pub fn verify_commit_trusting(
+    api: &dyn Api,
+    chain_id: &str,
+    trusted_validators: &[Validator],
+    commit: &Commit,
+) -> Result<(), LightClientError> {
+    let addresses: Vec<Vec<u8>> = trusted_validators
+        .iter()
+        .map(|validator| validator_address(&validator.pub_key))
+        .collect();
+
+    let needed = total_power(trusted_validators) / 3;
+    let mut signed = 0u128;
+    let mut seen = vec![false; trusted_validators.len()];
+    for commit_sig in commit.signatures.iter() {
+        if commit_sig.block_id_flag != BLOCK_ID_FLAG_COMMIT {
+            continue;
+        }
+        let index = match addresses
+            .iter()
+            .position(|address| address[..] == commit_sig.validator_address[..])
+        {
+            Some(index) => index,
+            None => continue,
+        };
+        if seen[index] {
+            return Err(LightClientError::DuplicateSignature { index });
+        }
+        seen[index] = true;
+
+        let validator = &trusted_validators[index];
+        verify_commit_sig(api, chain_id, commit, commit_sig, validator, index)?;
+
+        signed += validator.voting_power as u128;
+        if signed > needed {
+            return Ok(());
+        }
+    }
+
+    Err(LightClientError::InsufficientVotingPower { signed, needed })
+}
+
+fn verify_commit_sig(
+    api: &dyn Api,
+    chain_id: &str,
+    commit: &Commit,
+    commit_sig: &CommitSig,
+    validator: &Validator,
+    index: usize,
+) -> Result<(), LightClientError> {
+    let sign_bytes = vote_sign_bytes(chain_id, commit, commit_sig);
+    let verified = api
+        .ed25519_verify(&sign_bytes, &commit_sig.signature, &validator.pub_key)
+        .unwrap_or(false);
+    if !verified {
+        return Err(LightClientError::InvalidSignature { index });
+    }
+    Ok(())
+}
+
+fn total_power(validators: &[Validator]) -> u128 {
+    validators
+        .iter()
+        .map(|validator| validator.voting_power as u128)
+        .sum()
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use ethers_core::types::H256;
+
+use super::{hash_concat, TREE_DEPTH};
+
+/// Path of a leaf up to its root, or up to the point where it joins a path
+/// already accepted by a `BatchVerifier`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchPath {
+    root: H256,
+    nodes: Vec<((usize, usize), H256)>,
+}
+
+impl BatchPath {
+    /// Root the leaf proves against
+    pub fn root(&self) -> H256 {
+        self.root
+    }
+}
+
+/// Verifies many leaves of the same tree, reusing nodes of accepted paths.
+/// Proofs may leave out siblings already on an accepted path, and hashing
+/// stops as soon as a path joins one.
+#[derive(Debug, Default)]
+pub struct BatchVerifier {
+    /// (height, index) --> (node, root of accepted path through node)
+    nodes: HashMap<(usize, usize), (H256, H256)>,
+}
+
+impl BatchVerifier {
+    /// Compute path of `leaf` at `index`. `branch` holds the lowest
+    /// siblings, missing ones are taken from accepted paths. Returns `None`
+    /// if a sibling is neither given nor known.
+    pub fn path(&self, leaf: H256, branch: &[H256], index: usize) -> Option<BatchPath> {
+        if branch.len() > TREE_DEPTH {
+            return None;
+        }
+
+        let mut current = leaf;
+        let mut nodes = Vec::with_capacity(2 * TREE_DEPTH);
+
+        for height in 0..TREE_DEPTH {
+            let position = index >> height;
+
+            // Rest of the path is shared with an accepted one
+            if let Some((node, root)) = self.nodes.get(&(height, position)) {
+                if *node == current {
+                    return Some(BatchPath { root: *root, nodes });
+                }
+            }
+
+            let sibling = match branch.get(height) {
+                Some(sibling) => *sibling,
+                None => self.nodes.get(&(height, position ^ 1))?.0,
+            };
+            nodes.push(((height, position), current));
+            nodes.push(((height, position ^ 1), sibling));
+
+            current = if position & 0x01 == 1 {
+                hash_concat(sibling, current)
+            } else {
+                hash_concat(current, sibling)
+            };
+        }
+
+        Some(BatchPath {
+            root: current,
+            nodes,
+        })
+    }
+
+    /// Record nodes of a path whose root was accepted
+    pub fn accept(&mut self, path: BatchPath) {
+        for (position, node) in path.nodes {
+            self.nodes.entry(position).or_insert((node, path.root));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::merkle_tree::FullMerkle;
+
+    #[test]
+    fn verifies_leaves_sharing_path_segments() {
+        let leaves: Vec<H256> = (0..8u8).map(H256::repeat_byte).collect();
+        let tree = FullMerkle::from_leaves(&leaves).unwrap();
+        let root = tree.root();
+
+        let mut verifier = BatchVerifier::default();
+
+        // Unknown siblings cannot be filled in
+        assert_eq!(None, verifier.path(leaves[1], &[], 1));
+
+        // First leaf needs its full proof
+        let proof = tree.prove(0).unwrap().path;
+        let path = verifier.path(leaves[0], &proof, 0).unwrap();
+        assert_eq!(root, path.root());
+        verifier.accept(path);
+
+        // Neighbour is a sibling on the first path, nothing left to hash
+        let path = verifier.path(leaves[1], &[], 1).unwrap();
+        assert_eq!(root, path.root());
+        verifier.accept(path);
+
+        // Leaf 5 joins the first path at height 2, needs two lowest siblings
+        let proof = tree.prove(5).unwrap().path;
+        let path = verifier.path(leaves[5], &proof[..2], 5).unwrap();
+        assert_eq!(root, path.root());
+
+        // Wrong leaf hashes to a different root
+        let path = verifier.path(H256::repeat_byte(9), &proof[..2], 5).unwrap();
+        assert_ne!(root, path.root());
+    }
+}
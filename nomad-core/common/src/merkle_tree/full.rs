@@ -0,0 +1,274 @@
+use ethers_core::types::H256;
+
+use super::{hash_concat, Proof, TREE_DEPTH, ZERO_HASHES};
+
+/// Tree capacity, matching `IncrementalMerkle`
+const MAX_LEAVES: usize = u32::MAX as usize;
+
+/// Error types for `FullMerkle`
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq)]
+pub enum ProverError {
+    /// Tree holds the maximum number of leaves
+    #[error("Merkle tree full")]
+    TreeFull,
+
+    /// Requested proof of a leaf not in the tree
+    #[error("Leaf index {index} out of range. Tree has {count} leaves")]
+    IndexOutOfRange {
+        /// Requested index
+        index: usize,
+        /// Number of leaves in tree
+        count: usize,
+    },
+
+    /// Rollback target larger than current tree
+    #[error("Cannot roll back to {target}. Tree has {count} leaves")]
+    InvalidRollback {
+        /// Requested leaf count
+        target: usize,
+        /// Number of leaves in tree
+        count: usize,
+    },
+
+    /// Proof does not evaluate to the tree root
+    #[error("Proof verification failed. Proof root {proof_root:?}, tree root {root:?}")]
+    VerificationFailed {
+        /// Root produced by evaluating proof
+        proof_root: H256,
+        /// Current tree root
+        root: H256,
+    },
+}
+
+/// A full merkle tree storing every non-empty node, suitable for running
+/// off-chain. Produces proofs for any leaf and can roll back to an earlier
+/// leaf count
+#[derive(Clone, Debug, PartialEq)]
+pub struct FullMerkle {
+    /// Non-empty nodes per level. Level 0 holds leaves, level `TREE_DEPTH`
+    /// holds the root once a leaf is ingested
+    levels: Vec<Vec<H256>>,
+}
+
+impl Default for FullMerkle {
+    fn default() -> Self {
+        Self {
+            levels: vec![vec![]; TREE_DEPTH + 1],
+        }
+    }
+}
+
+impl FullMerkle {
+    /// Build a tree from `leaves` in order
+    pub fn from_leaves(leaves: &[H256]) -> Result<Self, ProverError> {
+        let mut tree = Self::default();
+        for leaf in leaves {
+            tree.ingest(*leaf)?;
+        }
+        Ok(tree)
+    }
+
+    /// Ingest a leaf into the tree, returning the new root
+    pub fn ingest(&mut self, element: H256) -> Result<H256, ProverError> {
+        if self.count() >= MAX_LEAVES {
+            return Err(ProverError::TreeFull);
+        }
+
+        self.levels[0].push(element);
+        self.update_frontier();
+        Ok(self.root())
+    }
+
+    /// Calculate the current tree root
+    pub fn root(&self) -> H256 {
+        self.levels[TREE_DEPTH]
+            .first()
+            .copied()
+            .unwrap_or(ZERO_HASHES[TREE_DEPTH])
+    }
+
+    /// Get the number of items in the tree
+    pub fn count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Get leaf at `index`
+    pub fn leaf(&self, index: usize) -> Option<H256> {
+        self.levels[0].get(index).copied()
+    }
+
+    /// Generate a proof of the leaf at `index` against the current root
+    pub fn prove(&self, index: usize) -> Result<Proof, ProverError> {
+        let count = self.count();
+        if index >= count {
+            return Err(ProverError::IndexOutOfRange { index, count });
+        }
+
+        let mut path = [H256::zero(); TREE_DEPTH];
+        for (level, sibling) in path.iter_mut().enumerate() {
+            // Missing siblings are empty subtrees
+            *sibling = self.levels[level]
+                .get((index >> level) ^ 1)
+                .copied()
+                .unwrap_or(ZERO_HASHES[level]);
+        }
+
+        Ok(Proof {
+            leaf: self.levels[0][index],
+            index,
+            path,
+        })
+    }
+
+    /// Verify a proof against the current root
+    pub fn verify(&self, proof: &Proof) -> Result<(), ProverError> {
+        let proof_root = proof.root();
+        let root = self.root();
+        if proof_root != root {
+            return Err(ProverError::VerificationFailed { proof_root, root });
+        }
+        Ok(())
+    }
+
+    /// Drop every leaf at or after `count`, restoring the tree to the state
+    /// it had after ingesting `count` leaves
+    pub fn rollback(&mut self, count: usize) -> Result<(), ProverError> {
+        let current = self.count();
+        if count > current {
+            return Err(ProverError::InvalidRollback {
+                target: count,
+                count: current,
+            });
+        }
+
+        for (level, nodes) in self.levels.iter_mut().enumerate() {
+            // Nodes at `level` covering any of the first `count` leaves
+            let len = match count {
+                0 => 0,
+                count => (count - 1).checked_shr(level as u32).unwrap_or(0) + 1,
+            };
+            nodes.truncate(len);
+        }
+
+        if count > 0 {
+            self.update_frontier();
+        }
+        Ok(())
+    }
+
+    /// Recompute ancestors of the last leaf. Everything to its right is
+    /// empty so right-hand siblings are zero hashes
+    fn update_frontier(&mut self) {
+        let mut index = self.count() - 1;
+        let mut node = self.levels[0][index];
+
+        for level in 0..TREE_DEPTH {
+            node = if index & 1 == 1 {
+                hash_concat(self.levels[level][index - 1], node)
+            } else {
+                hash_concat(node, ZERO_HASHES[level])
+            };
+            index >>= 1;
+
+            let parents = &mut self.levels[level + 1];
+            if index < parents.len() {
+                parents[index] = node;
+            } else {
+                parents.push(node);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::merkle_tree::{IncrementalMerkle, INITIAL_ROOT};
+
+    /// Subset of `vectors/merkle.json` test case fields
+    #[derive(serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct MerkleTestCase {
+        proofs: Vec<Proof>,
+        expected_root: H256,
+    }
+
+    fn load_merkle_test_json() -> Vec<MerkleTestCase> {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../vectors/merkle.json");
+        let data = std::fs::read_to_string(path).unwrap();
+        serde_json::from_str(&data).unwrap()
+    }
+
+    fn leaves(n: u8) -> Vec<H256> {
+        (0..n).map(H256::repeat_byte).collect()
+    }
+
+    #[test]
+    fn matches_incremental_root() {
+        let mut full = FullMerkle::default();
+        let mut incremental = IncrementalMerkle::default();
+        assert_eq!(*INITIAL_ROOT, full.root());
+
+        for leaf in leaves(70) {
+            incremental.ingest(leaf);
+            assert_eq!(incremental.root(), full.ingest(leaf).unwrap());
+            assert_eq!(incremental.count(), full.count());
+        }
+    }
+
+    #[test]
+    fn proves_every_leaf() {
+        let full = FullMerkle::from_leaves(&leaves(37)).unwrap();
+
+        for index in 0..full.count() {
+            let proof = full.prove(index).unwrap();
+            assert_eq!(full.leaf(index), Some(proof.leaf));
+            full.verify(&proof).unwrap();
+        }
+
+        assert_eq!(
+            Err(ProverError::IndexOutOfRange {
+                index: 37,
+                count: 37
+            }),
+            full.prove(37)
+        );
+    }
+
+    #[test]
+    fn rolls_back_to_earlier_count() {
+        let all = leaves(21);
+        let mut full = FullMerkle::from_leaves(&all).unwrap();
+
+        for count in (0..=21).rev() {
+            full.rollback(count).unwrap();
+            assert_eq!(FullMerkle::from_leaves(&all[..count]).unwrap(), full);
+        }
+        assert_eq!(*INITIAL_ROOT, full.root());
+
+        // Tree can grow again after rollback
+        full.ingest(H256::repeat_byte(9)).unwrap();
+        full.verify(&full.prove(0).unwrap()).unwrap();
+
+        assert_eq!(
+            Err(ProverError::InvalidRollback {
+                target: 2,
+                count: 1
+            }),
+            full.rollback(2)
+        );
+    }
+
+    #[test]
+    fn matches_vectors() {
+        for test_case in load_merkle_test_json() {
+            let leaves: Vec<H256> = test_case.proofs.iter().map(|proof| proof.leaf).collect();
+            let full = FullMerkle::from_leaves(&leaves).unwrap();
+            assert_eq!(test_case.expected_root, full.root());
+
+            for expected in test_case.proofs {
+                assert_eq!(expected, full.prove(expected.index).unwrap());
+            }
+        }
+    }
+}
@@ -3,9 +3,18 @@
 pub mod incremental;
 pub use incremental::*;
 
+/// A full merkle tree producing proofs for any leaf, suitable for running
+/// off-chain. Stores O(n) data
+pub mod full;
+pub use full::*;
+
 pub mod proof;
 pub use proof::*;
 
+/// Verification of many leaves against one tree sharing path segments
+pub mod batch;
+pub use batch::*;
+
 use ethers_core::types::H256;
 use lazy_static::lazy_static;
 use sha3::{digest::Update, Digest, Keccak256};
@@ -33,7 +42,8 @@ pub(super) fn hash(preimage: impl AsRef<[u8]>) -> H256 {
     H256::from_slice(Keccak256::digest(preimage.as_ref()).as_slice()).into()
 }
 
-pub(super) fn hash_concat(left: impl AsRef<[u8]>, right: impl AsRef<[u8]>) -> H256 {
+/// Hash of `left` and `right` concatenated
+pub fn hash_concat(left: impl AsRef<[u8]>, right: impl AsRef<[u8]>) -> H256 {
     H256::from_slice(
         Keccak256::new()
             .chain(left.as_ref())
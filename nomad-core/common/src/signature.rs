@@ -0,0 +1,242 @@
+use cosmwasm_std::{Api, Binary, RecoverPubkeyError};
+use ethers_core::types::{H160, H256};
+use serde::{Deserialize, Serialize};
+use sha3::{digest::Update, Digest, Keccak256};
+
+/// Signatures are 65 bytes: r (32) || s (32) || v (1)
+const SIGNATURE_BYTES: usize = 65;
+/// Uncompressed secp256k1 public keys are 0x04 || x (32) || y (32)
+const UNCOMPRESSED_PUBKEY_BYTES: usize = 65;
+
+/// Error types for signature recovery
+#[derive(Debug, thiserror::Error)]
+pub enum SignatureError {
+    #[error("{0}")]
+    RecoverPubkey(#[from] RecoverPubkeyError),
+
+    #[error("Signature must be 65 bytes. Got {length} bytes")]
+    InvalidLength { length: usize },
+
+    #[error("Invalid signature recovery id {v}")]
+    InvalidRecoveryId { v: u8 },
+
+    #[error("Public key must be 65 byte uncompressed secp256k1 key. Got {length} bytes")]
+    InvalidPubkey { length: usize },
+}
+
+/// Identity of an updater or watcher, tagged by the scheme it signs with
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerId {
+    /// Ethereum address. Signs the digest with the Ethereum signed message
+    /// prefix, 65 byte r || s || v signatures
+    EthAddress(H160),
+    /// Compressed or uncompressed secp256k1 public key. Signs the SHA-256 of
+    /// the digest (Cosmos SDK convention), 64 byte r || s signatures
+    Secp256k1PubKey(Binary),
+    /// Ed25519 public key. Signs the digest itself, 64 byte signatures
+    Ed25519PubKey(Binary),
+}
+
+impl SignerId {
+    /// Whether `signature` over `digest` was made by this signer. Signatures
+    /// malformed for this signer's scheme do not verify
+    pub fn verify(&self, api: &dyn Api, digest: H256, signature: &[u8]) -> bool {
+        match self {
+            SignerId::EthAddress(address) => {
+                matches!(
+                    recover_eth_address(api, digest.as_bytes(), signature),
+                    Ok(signer) if signer == *address
+                )
+            }
+            SignerId::Secp256k1PubKey(pubkey) => {
+                let message_hash = <sha2::Sha256 as sha2::Digest>::digest(digest.as_bytes());
+                api.secp256k1_verify(&message_hash, signature, pubkey)
+                    .unwrap_or(false)
+            }
+            SignerId::Ed25519PubKey(pubkey) => api
+                .ed25519_verify(digest.as_bytes(), signature, pubkey)
+                .unwrap_or(false),
+        }
+    }
+
+    /// Address or public key bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            SignerId::EthAddress(address) => address.as_bytes(),
+            SignerId::Secp256k1PubKey(pubkey) | SignerId::Ed25519PubKey(pubkey) => {
+                pubkey.as_slice()
+            }
+        }
+    }
+
+    /// Signer as bytes32. Ethereum addresses are left-padded and public keys
+    /// are keccak256 hashed
+    pub fn to_h256(&self) -> H256 {
+        match self {
+            SignerId::EthAddress(address) => H256::from(*address),
+            _ => H256::from_slice(Keccak256::digest(self.as_bytes()).as_slice()),
+        }
+    }
+}
+
+impl From<H160> for SignerId {
+    fn from(address: H160) -> Self {
+        SignerId::EthAddress(address)
+    }
+}
+
+/// Hash `message` with the Ethereum signed message prefix, matching what
+/// `Signer::sign_message` signs over
+pub fn eth_signed_message_hash(message: &[u8]) -> H256 {
+    H256::from_slice(
+        Keccak256::new()
+            .chain(b"\x19Ethereum Signed Message:\n")
+            .chain(message.len().to_string().as_bytes())
+            .chain(message)
+            .finalize()
+            .as_slice(),
+    )
+}
+
+/// Derive Ethereum address from uncompressed secp256k1 public key (last 20
+/// bytes of keccak256 of the key without its 0x04 prefix)
+pub fn eth_address(pubkey: &[u8]) -> Result<H160, SignatureError> {
+    if pubkey.len() != UNCOMPRESSED_PUBKEY_BYTES || pubkey[0] != 0x04 {
+        return Err(SignatureError::InvalidPubkey {
+            length: pubkey.len(),
+        });
+    }
+
+    let hash = Keccak256::digest(&pubkey[1..]);
+    Ok(H160::from_slice(&hash[12..]))
+}
+
+/// Recover Ethereum address that signed `message` (with the Ethereum signed
+/// message prefix) using the host's secp256k1 implementation. `signature`
+/// is the 65 byte r || s || v encoding produced by `Signature::to_vec`
+pub fn recover_eth_address(
+    api: &dyn Api,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<H160, SignatureError> {
+    if signature.len() != SIGNATURE_BYTES {
+        return Err(SignatureError::InvalidLength {
+            length: signature.len(),
+        });
+    }
+
+    let recovery_param = normalize_recovery_id(signature[64])?;
+    let message_hash = eth_signed_message_hash(message);
+    let pubkey =
+        api.secp256k1_recover_pubkey(message_hash.as_bytes(), &signature[..64], recovery_param)?;
+
+    eth_address(&pubkey)
+}
+
+/// Map v to a 0 or 1 recovery id. Accepts raw (0/1), legacy (27/28) and
+/// EIP-155 (>= 35) values
+fn normalize_recovery_id(v: u8) -> Result<u8, SignatureError> {
+    match v {
+        0 | 1 => Ok(v),
+        27 | 28 => Ok(v - 27),
+        v if v >= 35 => Ok((v - 1) % 2),
+        _ => Err(SignatureError::InvalidRecoveryId { v }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::testing::MockApi;
+    use ethers_core::utils::hash_message;
+
+    // Generator point, i.e. public key of private key 1
+    const PUBKEY_1: &str = "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+    const ADDRESS_1: &str = "7e5f4552091a69125d5dfcb7b8c2659029395bdf";
+
+    #[test]
+    fn hashes_eth_signed_message() {
+        let message = H256::repeat_byte(7);
+        assert_eq!(
+            hash_message(message.as_bytes()),
+            eth_signed_message_hash(message.as_bytes())
+        );
+    }
+
+    #[test]
+    fn derives_eth_address() {
+        let pubkey = hex::decode(PUBKEY_1).unwrap();
+        let address = H160::from_slice(&hex::decode(ADDRESS_1).unwrap());
+        assert_eq!(address, eth_address(&pubkey).unwrap());
+
+        // Compressed keys are rejected
+        assert!(matches!(
+            eth_address(&pubkey[..33]),
+            Err(SignatureError::InvalidPubkey { length: 33 })
+        ));
+    }
+
+    #[test]
+    fn normalizes_recovery_ids() {
+        assert_eq!(0, normalize_recovery_id(27).unwrap());
+        assert_eq!(1, normalize_recovery_id(28).unwrap());
+        assert_eq!(1, normalize_recovery_id(1).unwrap());
+        assert_eq!(0, normalize_recovery_id(37).unwrap());
+        assert_eq!(1, normalize_recovery_id(38).unwrap());
+        assert!(normalize_recovery_id(2).is_err());
+    }
+
+    #[test]
+    fn encodes_signer_ids_as_bytes32() {
+        let address = H160::repeat_byte(7);
+        let eth = SignerId::from(address);
+        assert_eq!(address.as_bytes(), eth.as_bytes());
+        assert_eq!(H256::from(address), eth.to_h256());
+
+        let pubkey = hex::decode(PUBKEY_1).unwrap();
+        let secp256k1 = SignerId::Secp256k1PubKey(Binary::from(pubkey.clone()));
+        assert_eq!(pubkey.as_slice(), secp256k1.as_bytes());
+        assert_eq!(
+            H256::from_slice(Keccak256::digest(&pubkey).as_slice()),
+            secp256k1.to_h256()
+        );
+    }
+
+    #[test]
+    fn malformed_signatures_do_not_verify() {
+        let api = MockApi::default();
+        let digest = H256::repeat_byte(7);
+        let pubkey = Binary::from(hex::decode(PUBKEY_1).unwrap());
+
+        let signers = [
+            SignerId::from(H160::from_slice(&hex::decode(ADDRESS_1).unwrap())),
+            SignerId::Secp256k1PubKey(pubkey),
+            SignerId::Ed25519PubKey(Binary::from(vec![1u8; 32])),
+        ];
+        for signer in signers.iter() {
+            assert!(!signer.verify(&api, digest, &[1u8; 12]));
+            assert!(!signer.verify(&api, digest, &[1u8; 65]));
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_signatures() {
+        let api = MockApi::default();
+        let message = H256::repeat_byte(7);
+
+        let res = recover_eth_address(&api, message.as_bytes(), &[1u8; 64]);
+        assert!(matches!(
+            res,
+            Err(SignatureError::InvalidLength { length: 64 })
+        ));
+
+        let mut signature = [1u8; 65];
+        signature[64] = 29;
+        let res = recover_eth_address(&api, message.as_bytes(), &signature);
+        assert!(matches!(
+            res,
+            Err(SignatureError::InvalidRecoveryId { v: 29 })
+        ));
+    }
+}
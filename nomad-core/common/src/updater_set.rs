@@ -0,0 +1,75 @@
+use crate::SignerId;
+
+/// Error types for updater set validation
+#[derive(Debug, thiserror::Error, Clone, PartialEq)]
+pub enum UpdaterSetError {
+    #[error("Threshold {threshold} invalid for {updaters} updaters")]
+    InvalidThreshold { threshold: u32, updaters: usize },
+
+    #[error("Updater {updater:?} appears more than once")]
+    DuplicateUpdater { updater: SignerId },
+}
+
+/// Check `updaters` has no duplicates and `threshold` is between 1 and the
+/// number of updaters
+pub fn validate_updater_set(updaters: &[SignerId], threshold: u32) -> Result<(), UpdaterSetError> {
+    if threshold == 0 || threshold as usize > updaters.len() {
+        return Err(UpdaterSetError::InvalidThreshold {
+            threshold,
+            updaters: updaters.len(),
+        });
+    }
+
+    for (i, updater) in updaters.iter().enumerate() {
+        if updaters[..i].contains(updater) {
+            return Err(UpdaterSetError::DuplicateUpdater {
+                updater: updater.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ethers_core::types::H160;
+
+    #[test]
+    fn validates_updater_set() {
+        let updaters = [
+            SignerId::from(H160::repeat_byte(1)),
+            SignerId::Ed25519PubKey(vec![2u8; 32].into()),
+        ];
+        assert_eq!(Ok(()), validate_updater_set(&updaters, 1));
+        assert_eq!(Ok(()), validate_updater_set(&updaters, 2));
+
+        assert_eq!(
+            Err(UpdaterSetError::InvalidThreshold {
+                threshold: 0,
+                updaters: 2
+            }),
+            validate_updater_set(&updaters, 0)
+        );
+        assert_eq!(
+            Err(UpdaterSetError::InvalidThreshold {
+                threshold: 3,
+                updaters: 2
+            }),
+            validate_updater_set(&updaters, 3)
+        );
+
+        let duplicated = [
+            updaters[0].clone(),
+            updaters[1].clone(),
+            updaters[0].clone(),
+        ];
+        assert_eq!(
+            Err(UpdaterSetError::DuplicateUpdater {
+                updater: updaters[0].clone()
+            }),
+            validate_updater_set(&duplicated, 2)
+        );
+    }
+}
@@ -0,0 +1,732 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, BankMsg, Binary, Coin, ContractResult, CosmosMsg, Deps, DepsMut,
+    Env, Event, MessageInfo, Reply, ReplyOn, Response, StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{Cw20Coin, Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
+use ethers_core::types::H256;
+use ethers_core::utils::keccak256;
+
+use crate::error::ContractError;
+use crate::state::{
+    CANONICAL_TOKENS, ESCROWED, LOCAL_DOMAIN, LOCAL_TOKENS, PENDING_REPRESENTATION,
+    REPRESENTATIONS, ROUTER, TOKEN_CODE_ID,
+};
+use common::bridge_router::{
+    CanonicalTokenResponse, EscrowedResponse, ExecuteMsg, InstantiateMsg, LocalToken,
+    LocalTokenResponse, QueryMsg, ReceiveMsg, RepresentationResponse, RouterResponse,
+};
+use common::nomad_base::LocalDomainResponse;
+use common::{addr_to_h256, h256_to_addr, Decode, Encode, TokenId, TransferMessage};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:bridge-router";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub const DEPLOY_REPRESENTATION_ID: u64 = 1;
+
+const REPRESENTATION_NAME: &str = "Nomad Bridged Token";
+const REPRESENTATION_SYMBOL: &str = "NOMAD";
+const REPRESENTATION_DECIMALS: u8 = 6;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    ROUTER.instantiate(deps.branch(), &info, &msg.home, &msg.connection_manager)?;
+    LOCAL_DOMAIN.save(deps.storage, &msg.local_domain)?;
+    TOKEN_CODE_ID.save(deps.storage, &msg.token_code_id)?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::SendNative {
+            destination,
+            recipient,
+        } => execute_send_native(deps, info, destination, recipient),
+        ExecuteMsg::Receive(wrapper) => execute_receive(deps, info, wrapper),
+        ExecuteMsg::HandleMsg {
+            origin,
+            nonce,
+            sender,
+            message,
+        } => execute_handle(deps, env, info, origin, nonce, sender, message),
+        ExecuteMsg::SetRouter { domain, router } => {
+            Ok(ROUTER.execute_enroll_remote_router(deps, info, domain, router)?)
+        }
+        ExecuteMsg::EnrollRepresentation {
+            token,
+            representation,
+        } => execute_enroll_representation(deps, info, token, representation),
+        ExecuteMsg::RenounceOwnership {} => Ok(ownable::execute_renounce_ownership(deps, info)?),
+        ExecuteMsg::TransferOwnership { new_owner } => {
+            Ok(ownable::execute_transfer_ownership(deps, info, new_owner)?)
+        }
+    }
+}
+
+pub fn execute_send_native(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    destination: u32,
+    recipient: H256,
+) -> Result<Response, ContractError> {
+    let Coin { denom, amount } = match info.funds.as_slice() {
+        [coin] => coin.clone(),
+        _ => return Err(ContractError::InvalidFunds),
+    };
+
+    let id = native_token_id(&denom);
+    let token = _escrow_local_token(deps.branch(), id, LocalToken::Native { denom }, amount)?;
+
+    _send(deps, token, destination, recipient, amount)
+}
+
+pub fn execute_receive(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let ReceiveMsg::Send {
+        destination,
+        recipient,
+    } = from_binary(&wrapper.msg)?;
+    let amount = wrapper.amount;
+
+    // Representations are burned, local cw20s are escrowed
+    match CANONICAL_TOKENS.may_load(deps.storage, info.sender.clone())? {
+        Some(token) => {
+            let burn_msg = WasmMsg::Execute {
+                contract_addr: info.sender.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Burn { amount })?,
+                funds: vec![],
+            };
+
+            Ok(_send(deps, token, destination, recipient, amount)?.add_message(burn_msg))
+        }
+        None => {
+            let id = addr_to_h256(deps.api, &info.sender)?;
+            let local_token = LocalToken::Cw20 {
+                address: info.sender.to_string(),
+            };
+            let token = _escrow_local_token(deps.branch(), id, local_token, amount)?;
+
+            _send(deps, token, destination, recipient, amount)
+        }
+    }
+}
+
+pub fn execute_handle(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    origin: u32,
+    nonce: u32,
+    sender: H256,
+    message: Vec<u8>,
+) -> Result<Response, ContractError> {
+    ROUTER.only_replica(deps.as_ref(), &info, origin)?;
+    ROUTER.only_remote_router(deps.as_ref(), origin, sender)?;
+
+    let TransferMessage {
+        token,
+        recipient,
+        amount,
+    } = TransferMessage::read_from(&mut message.as_slice())?;
+
+    let recipient_addr = h256_to_addr(deps.api, recipient)?;
+
+    let local_domain = LOCAL_DOMAIN.load(deps.storage)?;
+    let resp = if token.domain == local_domain {
+        _release(deps, token.id, recipient_addr.clone(), amount)?
+    } else {
+        let representation =
+            REPRESENTATIONS.may_load(deps.storage, (token.domain, token.id.as_bytes()))?;
+        match representation {
+            Some(representation) => {
+                let mint_msg = WasmMsg::Execute {
+                    contract_addr: representation.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Mint {
+                        recipient: recipient_addr.to_string(),
+                        amount,
+                    })?,
+                    funds: vec![],
+                };
+                Response::new().add_message(mint_msg)
+            }
+            None => _deploy_representation(deps, env, token, recipient_addr.clone(), amount)?,
+        }
+    };
+
+    Ok(resp.add_event(
+        Event::new("Receive")
+            .add_attribute("origin", origin.to_string())
+            .add_attribute("nonce", nonce.to_string())
+            .add_attribute("token_domain", token.domain.to_string())
+            .add_attribute("token_id", format!("{:?}", token.id))
+            .add_attribute("recipient", recipient_addr.to_string())
+            .add_attribute("amount", amount.to_string()),
+    ))
+}
+
+pub fn execute_enroll_representation(
+    deps: DepsMut,
+    info: MessageInfo,
+    token: TokenId,
+    representation: String,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+
+    let representation_addr = deps.api.addr_validate(&representation)?;
+    _enroll_representation(deps, token, representation_addr)
+}
+
+/// Canonical id of a native denom (denoms may exceed 32 bytes)
+pub fn native_token_id(denom: &str) -> H256 {
+    keccak256(denom.as_bytes()).into()
+}
+
+pub fn _escrow_local_token(
+    deps: DepsMut,
+    id: H256,
+    local_token: LocalToken,
+    amount: Uint128,
+) -> Result<TokenId, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount);
+    }
+
+    LOCAL_TOKENS.save(deps.storage, id.as_bytes(), &local_token)?;
+    ESCROWED.update(deps.storage, id.as_bytes(), |escrowed| -> StdResult<_> {
+        Ok(escrowed.unwrap_or_default().checked_add(amount)?)
+    })?;
+
+    Ok(TokenId {
+        domain: LOCAL_DOMAIN.load(deps.storage)?,
+        id,
+    })
+}
+
+pub fn _send(
+    deps: DepsMut,
+    token: TokenId,
+    destination: u32,
+    recipient: H256,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount);
+    }
+
+    let transfer = TransferMessage {
+        token,
+        recipient,
+        amount,
+    };
+    let dispatch_msg = ROUTER.dispatch_to_remote(deps.as_ref(), destination, transfer.to_vec())?;
+
+    Ok(Response::new().add_submessage(dispatch_msg).add_event(
+        Event::new("Send")
+            .add_attribute("token_domain", token.domain.to_string())
+            .add_attribute("token_id", format!("{:?}", token.id))
+            .add_attribute("destination", destination.to_string())
+            .add_attribute("recipient", format!("{:?}", recipient))
+            .add_attribute("amount", amount.to_string()),
+    ))
+}
+
+pub fn _release(
+    deps: DepsMut,
+    id: H256,
+    recipient: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let local_token = LOCAL_TOKENS
+        .may_load(deps.storage, id.as_bytes())?
+        .ok_or(ContractError::UnknownLocalToken { id })?;
+
+    let escrowed = ESCROWED
+        .may_load(deps.storage, id.as_bytes())?
+        .unwrap_or_default();
+    let remaining = escrowed
+        .checked_sub(amount)
+        .map_err(|_| ContractError::InsufficientEscrow { id, escrowed })?;
+    ESCROWED.save(deps.storage, id.as_bytes(), &remaining)?;
+
+    let release_msg: CosmosMsg = match local_token {
+        LocalToken::Native { denom } => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin { denom, amount }],
+        }
+        .into(),
+        LocalToken::Cw20 { address } => WasmMsg::Execute {
+            contract_addr: address,
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    };
+
+    Ok(Response::new().add_message(release_msg))
+}
+
+/// Instantiate cw20 representation with recipient's balance as initial
+/// supply. Representation is registered once instantiate reply returns.
+pub fn _deploy_representation(
+    deps: DepsMut,
+    env: Env,
+    token: TokenId,
+    recipient: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    PENDING_REPRESENTATION.save(deps.storage, &token)?;
+
+    let init_msg = cw20_base::msg::InstantiateMsg {
+        name: REPRESENTATION_NAME.to_owned(),
+        symbol: REPRESENTATION_SYMBOL.to_owned(),
+        decimals: REPRESENTATION_DECIMALS,
+        initial_balances: vec![Cw20Coin {
+            address: recipient.to_string(),
+            amount,
+        }],
+        mint: Some(MinterResponse {
+            minter: env.contract.address.to_string(),
+            cap: None,
+        }),
+        marketing: None,
+    };
+
+    let sub_msg = SubMsg {
+        id: DEPLOY_REPRESENTATION_ID,
+        msg: CosmosMsg::Wasm(WasmMsg::Instantiate {
+            admin: None,
+            code_id: TOKEN_CODE_ID.load(deps.storage)?,
+            msg: to_binary(&init_msg)?,
+            funds: vec![],
+            label: format!("nomad representation {}:{:?}", token.domain, token.id),
+        }),
+        gas_limit: None,
+        reply_on: ReplyOn::Success,
+    };
+
+    Ok(Response::new().add_submessage(sub_msg))
+}
+
+pub fn _enroll_representation(
+    deps: DepsMut,
+    token: TokenId,
+    representation: Addr,
+) -> Result<Response, ContractError> {
+    let key = (token.domain, token.id.as_bytes());
+    if REPRESENTATIONS.may_load(deps.storage, key)?.is_some() {
+        return Err(ContractError::RepresentationExists {
+            domain: token.domain,
+            id: token.id,
+        });
+    }
+
+    REPRESENTATIONS.save(deps.storage, key, &representation)?;
+    CANONICAL_TOKENS.save(deps.storage, representation.clone(), &token)?;
+
+    Ok(Response::new().add_event(
+        Event::new("RepresentationEnrolled")
+            .add_attribute("token_domain", token.domain.to_string())
+            .add_attribute("token_id", format!("{:?}", token.id))
+            .add_attribute("representation", representation.to_string()),
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        DEPLOY_REPRESENTATION_ID => reply_deploy_representation(deps, msg),
+        _ => Err(ContractError::UnknownReplyMessage { id: msg.id }),
+    }
+}
+
+pub fn reply_deploy_representation(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let res = match msg.result {
+        ContractResult::Ok(res) => res,
+        ContractResult::Err(e) => return Err(ContractError::FailedDeployRepresentation(e)),
+    };
+
+    let representation = res
+        .events
+        .iter()
+        .filter(|event| event.ty == "instantiate")
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "_contract_address")
+        .map(|attr| attr.value.clone())
+        .ok_or_else(|| {
+            ContractError::FailedDeployRepresentation("missing contract address".to_owned())
+        })?;
+    let representation_addr = deps.api.addr_validate(&representation)?;
+
+    let token = PENDING_REPRESENTATION.load(deps.storage)?;
+    PENDING_REPRESENTATION.remove(deps.storage);
+
+    _enroll_representation(deps, token, representation_addr)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Router { domain } => to_binary(&query_router(deps, domain)?),
+        QueryMsg::Representation { token } => to_binary(&query_representation(deps, token)?),
+        QueryMsg::CanonicalToken { representation } => {
+            to_binary(&query_canonical_token(deps, representation)?)
+        }
+        QueryMsg::LocalToken { id } => to_binary(&query_local_token(deps, id)?),
+        QueryMsg::Escrowed { id } => to_binary(&query_escrowed(deps, id)?),
+        QueryMsg::LocalDomain {} => to_binary(&query_local_domain(deps)?),
+        QueryMsg::Owner {} => to_binary(&ownable::query_owner(deps)?),
+    }
+}
+
+pub fn query_router(deps: Deps, domain: u32) -> StdResult<RouterResponse> {
+    let router = ROUTER
+        .remote_router(deps, domain)?
+        .unwrap_or_else(H256::zero);
+    Ok(RouterResponse { router })
+}
+
+pub fn query_representation(deps: Deps, token: TokenId) -> StdResult<RepresentationResponse> {
+    let representation = REPRESENTATIONS
+        .may_load(deps.storage, (token.domain, token.id.as_bytes()))?
+        .unwrap_or_else(|| Addr::unchecked("0x0"));
+    Ok(RepresentationResponse {
+        representation: representation.to_string(),
+    })
+}
+
+pub fn query_canonical_token(
+    deps: Deps,
+    representation: String,
+) -> StdResult<CanonicalTokenResponse> {
+    let representation_addr = deps.api.addr_validate(&representation)?;
+    let token = CANONICAL_TOKENS.may_load(deps.storage, representation_addr)?;
+    Ok(CanonicalTokenResponse { token })
+}
+
+pub fn query_local_token(deps: Deps, id: H256) -> StdResult<LocalTokenResponse> {
+    let token = LOCAL_TOKENS.may_load(deps.storage, id.as_bytes())?;
+    Ok(LocalTokenResponse { token })
+}
+
+pub fn query_escrowed(deps: Deps, id: H256) -> StdResult<EscrowedResponse> {
+    let amount = ESCROWED
+        .may_load(deps.storage, id.as_bytes())?
+        .unwrap_or_default();
+    Ok(EscrowedResponse { amount })
+}
+
+pub fn query_local_domain(deps: Deps) -> StdResult<LocalDomainResponse> {
+    let local_domain = LOCAL_DOMAIN.load(deps.storage)?;
+    Ok(LocalDomainResponse { local_domain })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::home;
+    use cosmwasm_std::testing::{mock_env, mock_info};
+    use cosmwasm_std::{coins, from_binary};
+    use ethers_core::types::H160;
+    use test_utils::mock_padded_dependencies;
+    use xapp::XAppError;
+
+    const LOCAL: u32 = 1000;
+    const REMOTE: u32 = 2000;
+
+    fn instantiate_router(deps: DepsMut) {
+        let msg = InstantiateMsg {
+            local_domain: LOCAL,
+            home: "home".to_owned(),
+            connection_manager: "connection_manager".to_owned(),
+            token_code_id: 1,
+        };
+        let info = mock_info("owner", &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    /// 20 byte EVM router, left-padded
+    fn remote_router() -> H256 {
+        H256::from(H160::repeat_byte(0xee))
+    }
+
+    fn set_remote_router(deps: DepsMut) {
+        let msg = ExecuteMsg::SetRouter {
+            domain: REMOTE,
+            router: remote_router(),
+        };
+        execute(deps, mock_env(), mock_info("owner", &[]), msg).unwrap();
+    }
+
+    #[test]
+    fn accepts_messages_only_from_remote_router() {
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+        instantiate_router(deps.as_mut());
+
+        let res = ROUTER.only_remote_router(deps.as_ref(), REMOTE, remote_router());
+        assert!(matches!(res.unwrap_err(), XAppError::NoRouter { .. }));
+
+        set_remote_router(deps.as_mut());
+        ROUTER
+            .only_remote_router(deps.as_ref(), REMOTE, remote_router())
+            .unwrap();
+
+        let res = ROUTER.only_remote_router(deps.as_ref(), REMOTE, H256::repeat_byte(0xee));
+        assert!(matches!(
+            res.unwrap_err(),
+            XAppError::NotRemoteRouter { .. }
+        ));
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Router { domain: REMOTE },
+        )
+        .unwrap();
+        let value: RouterResponse = from_binary(&res).unwrap();
+        assert_eq!(remote_router(), value.router);
+    }
+
+    #[test]
+    fn escrows_native_tokens_on_send() {
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+        instantiate_router(deps.as_mut());
+        set_remote_router(deps.as_mut());
+
+        let msg = ExecuteMsg::SendNative {
+            destination: REMOTE,
+            recipient: H256::repeat_byte(1),
+        };
+
+        // Must send exactly one coin
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("sender", &[]),
+            msg.clone(),
+        );
+        assert!(matches!(res.unwrap_err(), ContractError::InvalidFunds));
+
+        let info = mock_info("sender", &coins(100, "earth"));
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(1, res.messages.len());
+
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                match from_binary::<home::ExecuteMsg>(msg).unwrap() {
+                    home::ExecuteMsg::Dispatch {
+                        destination,
+                        recipient,
+                        message_body,
+                    } => {
+                        assert_eq!(REMOTE, destination);
+                        assert_eq!(remote_router(), recipient);
+
+                        let transfer =
+                            TransferMessage::read_from(&mut message_body.as_slice()).unwrap();
+                        assert_eq!(LOCAL, transfer.token.domain);
+                        assert_eq!(native_token_id("earth"), transfer.token.id);
+                        assert_eq!(Uint128::new(100), transfer.amount);
+                    }
+                    _ => panic!("Expected dispatch message"),
+                }
+            }
+            _ => panic!("Expected wasm execute message"),
+        }
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Escrowed {
+                id: native_token_id("earth"),
+            },
+        )
+        .unwrap();
+        let value: EscrowedResponse = from_binary(&res).unwrap();
+        assert_eq!(Uint128::new(100), value.amount);
+    }
+
+    #[test]
+    fn burns_representation_on_send() {
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+        instantiate_router(deps.as_mut());
+        set_remote_router(deps.as_mut());
+
+        let token = TokenId {
+            domain: REMOTE,
+            id: H256::repeat_byte(3),
+        };
+        let msg = ExecuteMsg::EnrollRepresentation {
+            token,
+            representation: "representation".to_owned(),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "sender".to_owned(),
+            amount: Uint128::new(50),
+            msg: to_binary(&ReceiveMsg::Send {
+                destination: REMOTE,
+                recipient: H256::repeat_byte(1),
+            })
+            .unwrap(),
+        });
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("representation", &[]),
+            msg,
+        )
+        .unwrap();
+
+        // Dispatch and burn
+        assert_eq!(2, res.messages.len());
+        assert_eq!(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "representation".to_owned(),
+                msg: to_binary(&Cw20ExecuteMsg::Burn {
+                    amount: Uint128::new(50)
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            res.messages[1].msg
+        );
+
+        // Nothing escrowed for representations
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::LocalToken {
+                id: addr_to_h256(&deps.api, &Addr::unchecked("representation")).unwrap(),
+            },
+        )
+        .unwrap();
+        let value: LocalTokenResponse = from_binary(&res).unwrap();
+        assert_eq!(None, value.token);
+    }
+
+    #[test]
+    fn only_owner_enrolls_representation() {
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+        instantiate_router(deps.as_mut());
+
+        let token = TokenId {
+            domain: REMOTE,
+            id: H256::repeat_byte(3),
+        };
+        let msg = ExecuteMsg::EnrollRepresentation {
+            token,
+            representation: "representation".to_owned(),
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("not_owner", &[]),
+            msg.clone(),
+        );
+        assert!(res.is_err());
+        assert!(res.err().unwrap().to_string().contains("Unauthorized"));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            msg.clone(),
+        )
+        .unwrap();
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Representation { token },
+        )
+        .unwrap();
+        let value: RepresentationResponse = from_binary(&res).unwrap();
+        assert_eq!("representation", value.representation);
+
+        // Cannot overwrite existing representation
+        let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::RepresentationExists { .. }
+        ));
+    }
+
+    #[test]
+    fn release_fails_with_insufficient_escrow() {
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+        instantiate_router(deps.as_mut());
+
+        let id = native_token_id("earth");
+        let res = _release(
+            deps.as_mut(),
+            id,
+            Addr::unchecked("recipient"),
+            Uint128::new(1),
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::UnknownLocalToken { .. }
+        ));
+
+        _escrow_local_token(
+            deps.as_mut(),
+            id,
+            LocalToken::Native {
+                denom: "earth".to_owned(),
+            },
+            Uint128::new(10),
+        )
+        .unwrap();
+
+        let res = _release(
+            deps.as_mut(),
+            id,
+            Addr::unchecked("recipient"),
+            Uint128::new(11),
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::InsufficientEscrow { .. }
+        ));
+
+        let res = _release(
+            deps.as_mut(),
+            id,
+            Addr::unchecked("recipient"),
+            Uint128::new(10),
+        )
+        .unwrap();
+        assert_eq!(
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "recipient".to_owned(),
+                amount: coins(10, "earth"),
+            }),
+            res.messages[0].msg
+        );
+    }
+}
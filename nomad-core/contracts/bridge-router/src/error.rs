@@ -0,0 +1,42 @@
+use cosmwasm_std::{StdError, Uint128};
+use ethers_core::types::H256;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    XAppError(#[from] xapp::XAppError),
+
+    #[error("Must send exactly one native coin")]
+    InvalidFunds,
+
+    #[error("Cannot transfer zero amount")]
+    ZeroAmount,
+
+    #[error("Unknown local token {id:?}")]
+    UnknownLocalToken { id: H256 },
+
+    #[error("Insufficient escrow for token {id:?}. Escrowed: {escrowed}")]
+    InsufficientEscrow { id: H256, escrowed: Uint128 },
+
+    #[error("Representation for token {domain}:{id:?} already enrolled")]
+    RepresentationExists { domain: u32, id: H256 },
+
+    #[error("Failed to deploy representation token: {0}")]
+    FailedDeployRepresentation(String),
+
+    #[error("Unknown reply message id received: {id}")]
+    UnknownReplyMessage { id: u64 },
+
+    #[error("{0}")]
+    NomadError(#[from] common::NomadError),
+
+    #[error("{0}")]
+    OwnableError(#[from] ownable::ContractError),
+
+    #[error("{0}")]
+    AddressError(#[from] common::AddressError),
+}
@@ -0,0 +1,25 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
+use xapp::Router;
+
+use common::bridge_router::LocalToken;
+use common::TokenId;
+
+pub const LOCAL_DOMAIN: Item<u32> = Item::new("bridge_router_local_domain");
+pub const ROUTER: Router<'static> = Router::new(
+    "bridge_router_home",
+    "bridge_router_connection_manager",
+    "bridge_router_routers",
+);
+pub const TOKEN_CODE_ID: Item<u64> = Item::new("bridge_router_token_code_id");
+
+// Tokens originating on this domain, keyed by canonical id
+pub const LOCAL_TOKENS: Map<&[u8], LocalToken> = Map::new("bridge_router_local_tokens");
+pub const ESCROWED: Map<&[u8], Uint128> = Map::new("bridge_router_escrowed");
+
+// Remote (domain, id) <-> local representation
+pub const REPRESENTATIONS: Map<(u32, &[u8]), Addr> = Map::new("bridge_router_representations");
+pub const CANONICAL_TOKENS: Map<Addr, TokenId> = Map::new("bridge_router_canonical_tokens");
+
+// Canonical token of representation being deployed (awaiting reply)
+pub const PENDING_REPRESENTATION: Item<TokenId> = Item::new("bridge_router_pending_representation");
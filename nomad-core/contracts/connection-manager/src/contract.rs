@@ -1,23 +1,21 @@
-use common::nomad_base::{HomeDomainHashResponse, LocalDomainResponse, UpdaterResponse};
-use common::{addr_to_h256, h256_to_n_byte_addr, home, replica};
+use common::nomad_base::{HomeDomainHashResponse, LocalDomainResponse, UpdatersResponse};
+use common::{addr_to_h256, h256_to_addr, home, replica, replica_hub, SignerId};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, Deps, DepsMut, Env, Event, MessageInfo, Response, StdResult,
+    to_binary, Addr, Api, Binary, Deps, DepsMut, Env, Event, MessageInfo, Response, StdResult,
 };
-use cw2::set_contract_version;
-use ethers_core::types::{RecoveryMessage, Signature, H160, H256};
+use cw2::{get_contract_version, set_contract_version};
+use ethers_core::types::{H160, H256};
 use ethers_core::utils::keccak256;
+use semver::Version;
 use sha3::{digest::Update, Digest, Keccak256};
-use std::convert::TryFrom;
 
 use crate::error::ContractError;
-use crate::state::{
-    CHAIN_ADDR_LENGTH_BYTES, DOMAIN_TO_REPLICA, HOME, REPLICA_TO_DOMAIN, WATCHER_PERMISSIONS,
-};
+use crate::state::{DOMAIN_TO_REPLICA, HOME, REPLICA_TO_DOMAIN, WATCHER_PERMISSIONS};
 use common::connection_manager::{
-    DomainToReplicaResponse, ExecuteMsg, HomeResponse, InstantiateMsg, IsReplicaResponse, QueryMsg,
-    ReplicaToDomainResponse, WatcherPermissionResponse,
+    DomainToReplicaResponse, ExecuteMsg, HomeResponse, InstantiateMsg, IsReplicaResponse,
+    MigrateMsg, QueryMsg, ReplicaToDomainResponse, WatcherPermissionResponse,
 };
 
 // version info for migration info
@@ -29,12 +27,11 @@ pub fn instantiate(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    msg: InstantiateMsg,
+    _msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     ownable::instantiate(deps.branch(), env, info, common::ownable::InstantiateMsg {})?;
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    CHAIN_ADDR_LENGTH_BYTES.save(deps.storage, &msg.chain_addr_length_bytes)?;
 
     Ok(Response::new())
 }
@@ -51,13 +48,17 @@ pub fn execute(
             domain,
             updater,
             signature,
-        } => execute_unenroll_replica(deps, domain, updater, signature),
+            watcher,
+        } => execute_unenroll_replica(deps, domain, updater, signature, watcher),
         ExecuteMsg::OwnerEnrollReplica { domain, replica } => {
             execute_owner_enroll_replica(deps, info, domain, replica)
         }
         ExecuteMsg::OwnerUnenrollReplica { replica } => {
             execute_owner_unenroll_replica(deps, info, replica)
         }
+        ExecuteMsg::OwnerEnrollHub { domain, hub } => {
+            execute_owner_enroll_hub(deps, info, domain, hub)
+        }
         ExecuteMsg::SetWatcherPermission {
             watcher,
             domain,
@@ -76,31 +77,51 @@ pub fn execute_unenroll_replica(
     domain: u32,
     updater: H256,
     signature: Vec<u8>,
+    watcher: Option<SignerId>,
 ) -> Result<Response, ContractError> {
     let replica = query_domain_to_replica(deps.as_ref(), domain)?.replica;
     let replica_addr = deps.api.addr_validate(&replica)?;
     if replica_addr == Addr::unchecked("0x0") {
         return Err(ContractError::NotReplicaExists { domain });
     }
-    let replica_h256 = addr_to_h256(replica_addr.clone());
-
-    let replica_updater_resp: UpdaterResponse = deps
-        .querier
-        .query_wasm_smart(replica, &replica::QueryMsg::Updater {})?;
-    let replica_updater = replica_updater_resp.updater;
-
-    let provided_updater_addr: H160 = updater.into();
-    if replica_updater != provided_updater_addr {
+    let replica_h256 = addr_to_h256(deps.api, &replica_addr)?;
+
+    // Replica hubs keep one updater set per served domain
+    let resp: UpdatersResponse = if is_hub(deps.as_ref(), &replica_addr, domain)? {
+        deps.querier
+            .query_wasm_smart(replica, &replica_hub::QueryMsg::Updaters { domain })?
+    } else {
+        deps.querier
+            .query_wasm_smart(replica, &replica::QueryMsg::Updaters {})?
+    };
+    if !resp.updaters.iter().any(|u| u.to_h256() == updater) {
         return Err(ContractError::NotCurrentUpdater {
-            address: provided_updater_addr.to_string(),
+            address: format!("{:?}", updater),
         });
     }
 
-    let watcher =
-        recover_watcher_from_sig(deps.as_ref(), domain, replica_h256, updater, &signature)?;
+    // Ethereum watchers can be recovered from the signature. Other signers
+    // must be named and are verified against their public key
+    let watcher = match watcher {
+        Some(watcher) => {
+            if !verify_watcher_sig(
+                deps.as_ref(),
+                domain,
+                replica_h256,
+                updater,
+                &watcher,
+                &signature,
+            )? {
+                return Err(ContractError::InvalidWatcherSignature { watcher });
+            }
+            watcher
+        }
+        None => recover_watcher_from_sig(deps.as_ref(), domain, replica_h256, updater, &signature)?
+            .into(),
+    };
 
     let watcher_permission =
-        query_watcher_permission(deps.as_ref(), watcher, domain)?.has_permission;
+        query_watcher_permission(deps.as_ref(), watcher.clone(), domain)?.has_permission;
     if !watcher_permission {
         return Err(ContractError::NotWatcherPermission {
             watcher,
@@ -109,7 +130,7 @@ pub fn execute_unenroll_replica(
         });
     }
 
-    _unenroll_replica(deps, replica_addr)
+    _unenroll_domain(deps, domain)
 }
 
 pub fn execute_owner_enroll_replica(
@@ -141,16 +162,40 @@ pub fn execute_owner_unenroll_replica(
     _unenroll_replica(deps.branch(), replica_addr.clone())
 }
 
+/// Enroll a replica hub for one of the remote domains it serves. A hub may be
+/// enrolled for many domains, unlike a single-domain replica.
+pub fn execute_owner_enroll_hub(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    domain: u32,
+    hub: String,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+
+    let hub_addr = deps.api.addr_validate(&hub)?;
+
+    // Unenroll existing replica for domain
+    _unenroll_domain(deps.branch(), domain)?;
+
+    DOMAIN_TO_REPLICA.save(deps.storage, domain, &hub_addr)?;
+
+    Ok(Response::new().add_event(
+        Event::new("ReplicaEnrolled")
+            .add_attribute("domain", domain.to_string())
+            .add_attribute("replica", hub_addr.to_string()),
+    ))
+}
+
 pub fn execute_set_watcher_permission(
     deps: DepsMut,
     info: MessageInfo,
-    watcher: H160,
+    watcher: SignerId,
     domain: u32,
     access: bool,
 ) -> Result<Response, ContractError> {
     ownable::only_owner(deps.as_ref(), info)?;
 
-    let watcher_domain_hash = watcher_domain_hash(watcher, domain);
+    let watcher_domain_hash = watcher_domain_hash(&watcher, domain);
     WATCHER_PERMISSIONS.save(deps.storage, watcher_domain_hash.as_bytes(), &access)?;
 
     Ok(Response::new().add_event(
@@ -204,6 +249,58 @@ pub fn _unenroll_replica(deps: DepsMut, replica: Addr) -> Result<Response, Contr
     ))
 }
 
+pub fn _unenroll_domain(deps: DepsMut, domain: u32) -> Result<Response, ContractError> {
+    let replica = DOMAIN_TO_REPLICA
+        .may_load(deps.storage, domain)?
+        .unwrap_or(Addr::unchecked("0x0"));
+    DOMAIN_TO_REPLICA.save(deps.storage, domain, &Addr::unchecked("0x0"))?;
+
+    // Hubs have no reverse entry
+    let replica_domain = REPLICA_TO_DOMAIN
+        .may_load(deps.storage, replica.clone())?
+        .unwrap_or_default();
+    if replica_domain == domain {
+        REPLICA_TO_DOMAIN.save(deps.storage, replica.clone(), &0u32)?;
+    }
+
+    Ok(Response::new().add_event(
+        Event::new("ReplicaUnenrolled")
+            .add_attribute("domain", domain.to_string())
+            .add_attribute("replica", replica.to_string()),
+    ))
+}
+
+/// Guard for message handlers: checks that `replica` is enrolled and that
+/// the claimed message `origin` is the remote domain it was enrolled for.
+/// Home is accepted for messages dispatched to its own domain.
+pub fn only_replica(deps: Deps, replica: Addr, origin: u32) -> Result<(), ContractError> {
+    let origin_replica = DOMAIN_TO_REPLICA.may_load(deps.storage, origin)?;
+    if origin_replica.as_ref() == Some(&replica) {
+        return Ok(());
+    }
+
+    if HOME.may_load(deps.storage)?.as_ref() == Some(&replica)
+        && origin == query_local_domain(deps)?.local_domain
+    {
+        return Ok(());
+    }
+
+    let domain = REPLICA_TO_DOMAIN
+        .may_load(deps.storage, replica.clone())?
+        .unwrap_or_default();
+    if domain == 0 {
+        return Err(ContractError::NotReplica {
+            address: replica.to_string(),
+        });
+    }
+
+    if domain != origin {
+        return Err(ContractError::ReplicaOriginMismatch { origin, domain });
+    }
+
+    Ok(())
+}
+
 pub fn recover_watcher_from_sig(
     deps: Deps,
     domain: u32,
@@ -211,33 +308,128 @@ pub fn recover_watcher_from_sig(
     updater: H256,
     signature: &[u8],
 ) -> Result<H160, ContractError> {
-    let addr_length = CHAIN_ADDR_LENGTH_BYTES.load(deps.storage)?;
-    let replica_addr = h256_to_n_byte_addr(deps.clone(), addr_length, replica);
+    let home_domain_hash = query_replica_home_domain_hash(deps, domain, replica)?;
+    recover_failure_notification_signer(deps.api, home_domain_hash, domain, updater, signature)
+}
+
+/// Whether `watcher` signed failure notification of `updater` on `domain`'s
+/// home
+pub fn verify_watcher_sig(
+    deps: Deps,
+    domain: u32,
+    replica: H256,
+    updater: H256,
+    watcher: &SignerId,
+    signature: &[u8],
+) -> Result<bool, ContractError> {
+    let home_domain_hash = query_replica_home_domain_hash(deps, domain, replica)?;
+    let digest = failure_notification_digest(home_domain_hash, domain, updater);
+    Ok(watcher.verify(deps.api, digest, signature))
+}
+
+/// Whether `replica` is a replica hub serving `domain`. Hubs are not mapped
+/// back to a single domain.
+fn is_hub(deps: Deps, replica: &Addr, domain: u32) -> StdResult<bool> {
+    let replica_domain = REPLICA_TO_DOMAIN
+        .may_load(deps.storage, replica.clone())?
+        .unwrap_or_default();
+    Ok(replica_domain != domain)
+}
+
+fn query_replica_home_domain_hash(
+    deps: Deps,
+    domain: u32,
+    replica: H256,
+) -> Result<H256, ContractError> {
+    let replica_addr = h256_to_addr(deps.api, replica)?;
+
+    let home_domain_hash_resp: HomeDomainHashResponse = if is_hub(deps, &replica_addr, domain)? {
+        deps.querier.query_wasm_smart(
+            replica_addr,
+            &replica_hub::QueryMsg::HomeDomainHash { domain },
+        )?
+    } else {
+        deps.querier
+            .query_wasm_smart(replica_addr, &replica::QueryMsg::HomeDomainHash {})?
+    };
+    Ok(home_domain_hash_resp.home_domain_hash)
+}
+
+/// Recover watcher that signed failure notification of `updater` on `domain`'s
+/// home. Recovery runs through the host's secp256k1 api
+pub fn recover_failure_notification_signer(
+    api: &dyn Api,
+    home_domain_hash: H256,
+    domain: u32,
+    updater: H256,
+    signature: &[u8],
+) -> Result<H160, ContractError> {
+    let digest = failure_notification_digest(home_domain_hash, domain, updater);
 
-    let home_domain_hash_resp: HomeDomainHashResponse = deps
-        .querier
-        .query_wasm_smart(replica_addr, &replica::QueryMsg::HomeDomainHash {})?;
-    let home_domain_hash = home_domain_hash_resp.home_domain_hash;
+    Ok(common::recover_eth_address(
+        api,
+        digest.as_bytes(),
+        signature,
+    )?)
+}
 
-    let digest = H256::from_slice(
+/// Digest watchers sign to notify that `updater` of `domain`'s home failed
+pub fn failure_notification_digest(home_domain_hash: H256, domain: u32, updater: H256) -> H256 {
+    H256::from_slice(
         Keccak256::new()
             .chain(home_domain_hash)
             .chain(domain.to_be_bytes())
             .chain(updater)
             .finalize()
             .as_slice(),
-    );
-
-    let sig = Signature::try_from(signature)?;
-    Ok(sig.recover(RecoveryMessage::Data(digest.as_bytes().to_vec()))?)
+    )
 }
 
-pub fn watcher_domain_hash(watcher: H160, domain: u32) -> H256 {
-    let mut buf = watcher.to_fixed_bytes().to_vec();
+pub fn watcher_domain_hash(watcher: &SignerId, domain: u32) -> H256 {
+    let mut buf = watcher.as_bytes().to_vec();
     buf.append(&mut domain.to_be_bytes().to_vec());
     keccak256(buf).into()
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored_version = ensure_upgrade(deps.as_ref())?;
+
+    // Per-version storage migrations, oldest first. None yet since v0.1.0 is
+    // the initial storage layout.
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_event(
+        Event::new("Migrate")
+            .add_attribute("from_version", stored_version.to_string())
+            .add_attribute("to_version", CONTRACT_VERSION),
+    ))
+}
+
+/// Checks that stored cw2 info belongs to this contract and that stored
+/// version is not newer than this code (no downgrades). Returns stored version.
+pub fn ensure_upgrade(deps: Deps) -> Result<Version, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::WrongContract {
+            expected: CONTRACT_NAME.to_owned(),
+            found: stored.contract,
+        });
+    }
+
+    let stored_version: Version = stored.version.parse()?;
+    let version: Version = CONTRACT_VERSION.parse()?;
+    if stored_version > version {
+        return Err(ContractError::CannotDowngrade {
+            stored: stored.version,
+            version: CONTRACT_VERSION.to_owned(),
+        });
+    }
+
+    Ok(stored_version)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -252,7 +444,6 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::IsReplica { replica } => to_binary(&query_is_replica(deps, replica)?),
         QueryMsg::LocalDomain {} => to_binary(&query_local_domain(deps)?),
         QueryMsg::Owner {} => to_binary(&ownable::query_owner(deps)?),
-        QueryMsg::ChainAddrLengthBytes {} => to_binary(&query_chain_addr_length_bytes(deps)?),
     }
 }
 
@@ -284,10 +475,10 @@ pub fn query_replica_to_domain(deps: Deps, replica: String) -> StdResult<Replica
 
 pub fn query_watcher_permission(
     deps: Deps,
-    watcher: H160,
+    watcher: SignerId,
     domain: u32,
 ) -> StdResult<WatcherPermissionResponse> {
-    let watcher_domain_hash = watcher_domain_hash(watcher, domain);
+    let watcher_domain_hash = watcher_domain_hash(&watcher, domain);
     let has_permission = WATCHER_PERMISSIONS
         .may_load(deps.storage, watcher_domain_hash.as_bytes())?
         .unwrap_or(false);
@@ -313,19 +504,17 @@ pub fn query_local_domain(deps: Deps) -> StdResult<LocalDomainResponse> {
     Ok(local_domain_resp)
 }
 
-pub fn query_chain_addr_length_bytes(deps: Deps) -> StdResult<usize> {
-    CHAIN_ADDR_LENGTH_BYTES.load(deps.storage)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use common::ownable::OwnerResponse;
     use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
-    use cosmwasm_std::{coins, from_binary};
+    use cosmwasm_std::{coins, from_binary, ContractResult, SystemError, SystemResult, WasmQuery};
+    use ethers_core::types::RecoveryMessage;
     use ethers_signers::{LocalWallet, Signer};
+    use test_utils::{NativeKey, NativeWatcher, Watcher};
 
-    const CHAIN_ADDR_LENGTH_BYTES: usize = 42;
+    const LOCAL_DOMAIN: u32 = 1000;
     const REPLICA_DOMAIN: u32 = 2000;
     const WATCHER_PRIVKEY: &str =
         "1111111111111111111111111111111111111111111111111111111111111111";
@@ -334,9 +523,7 @@ mod tests {
     fn proper_initialization() {
         let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
 
-        let msg = InstantiateMsg {
-            chain_addr_length_bytes: CHAIN_ADDR_LENGTH_BYTES,
-        };
+        let msg = InstantiateMsg {};
         let info = mock_info("owner", &coins(100, "earth"));
 
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -357,9 +544,7 @@ mod tests {
     fn only_owner_restricts_access() {
         let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
 
-        let msg = InstantiateMsg {
-            chain_addr_length_bytes: CHAIN_ADDR_LENGTH_BYTES,
-        };
+        let msg = InstantiateMsg {};
         let info = mock_info("owner", &coins(100, "earth"));
 
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -378,9 +563,7 @@ mod tests {
     fn owner_sets_home() {
         let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
 
-        let msg = InstantiateMsg {
-            chain_addr_length_bytes: CHAIN_ADDR_LENGTH_BYTES,
-        };
+        let msg = InstantiateMsg {};
         let info = mock_info("owner", &coins(100, "earth"));
 
         let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
@@ -402,9 +585,7 @@ mod tests {
     fn onwer_enrolls_and_unenrolls_replica() {
         let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
 
-        let msg = InstantiateMsg {
-            chain_addr_length_bytes: CHAIN_ADDR_LENGTH_BYTES,
-        };
+        let msg = InstantiateMsg {};
         let info = mock_info("owner", &coins(100, "earth"));
 
         let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
@@ -486,9 +667,7 @@ mod tests {
 
         let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
 
-        let msg = InstantiateMsg {
-            chain_addr_length_bytes: CHAIN_ADDR_LENGTH_BYTES,
-        };
+        let msg = InstantiateMsg {};
         let info = mock_info("owner", &coins(100, "earth"));
 
         let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
@@ -499,7 +678,7 @@ mod tests {
             deps.as_ref(),
             mock_env(),
             QueryMsg::WatcherPermission {
-                watcher: watcher.address(),
+                watcher: watcher.address().into(),
                 domain: REPLICA_DOMAIN,
             },
         )
@@ -509,7 +688,7 @@ mod tests {
 
         // Set watcher permission
         let msg = ExecuteMsg::SetWatcherPermission {
-            watcher: watcher.address(),
+            watcher: watcher.address().into(),
             domain: REPLICA_DOMAIN,
             access: true,
         };
@@ -520,7 +699,7 @@ mod tests {
             deps.as_ref(),
             mock_env(),
             QueryMsg::WatcherPermission {
-                watcher: watcher.address(),
+                watcher: watcher.address().into(),
                 domain: REPLICA_DOMAIN,
             },
         )
@@ -528,4 +707,155 @@ mod tests {
         let value: WatcherPermissionResponse = from_binary(&res).unwrap();
         assert!(value.has_permission);
     }
+
+    #[test]
+    fn only_replica_checks_enrollment_and_origin() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let msg = InstantiateMsg {};
+        let info = mock_info("owner", &coins(100, "earth"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let replica_addr = Addr::unchecked("replica");
+
+        // Not yet enrolled
+        let res = only_replica(deps.as_ref(), replica_addr.clone(), REPLICA_DOMAIN);
+        assert!(matches!(res.unwrap_err(), ContractError::NotReplica { .. }));
+
+        let msg = ExecuteMsg::OwnerEnrollReplica {
+            domain: REPLICA_DOMAIN,
+            replica: replica_addr.to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        only_replica(deps.as_ref(), replica_addr.clone(), REPLICA_DOMAIN).unwrap();
+
+        // Origin must match enrolled domain
+        let res = only_replica(deps.as_ref(), replica_addr, REPLICA_DOMAIN + 1);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::ReplicaOriginMismatch { .. }
+        ));
+
+        // Home only delivers messages from local domain
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { .. } => SystemResult::Ok(ContractResult::Ok(
+                to_binary(&LocalDomainResponse {
+                    local_domain: LOCAL_DOMAIN,
+                })
+                .unwrap(),
+            )),
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "wasm".to_owned(),
+            }),
+        });
+        let msg = ExecuteMsg::SetHome {
+            home: "home".to_owned(),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let home_addr = Addr::unchecked("home");
+        only_replica(deps.as_ref(), home_addr.clone(), LOCAL_DOMAIN).unwrap();
+        let res = only_replica(deps.as_ref(), home_addr, REPLICA_DOMAIN);
+        assert!(matches!(res.unwrap_err(), ContractError::NotReplica { .. }));
+    }
+
+    #[test]
+    fn enrolls_hub_for_many_domains() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let msg = InstantiateMsg {};
+        let info = mock_info("owner", &coins(100, "earth"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        // Replica enrolled for first domain is replaced by hub
+        let msg = ExecuteMsg::OwnerEnrollReplica {
+            domain: REPLICA_DOMAIN,
+            replica: "replica".to_owned(),
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let hub_addr = Addr::unchecked("hub");
+        for domain in [REPLICA_DOMAIN, REPLICA_DOMAIN + 1] {
+            let msg = ExecuteMsg::OwnerEnrollHub {
+                domain,
+                hub: hub_addr.to_string(),
+            };
+            execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        }
+
+        only_replica(deps.as_ref(), hub_addr.clone(), REPLICA_DOMAIN).unwrap();
+        only_replica(deps.as_ref(), hub_addr.clone(), REPLICA_DOMAIN + 1).unwrap();
+
+        let res = only_replica(deps.as_ref(), hub_addr, REPLICA_DOMAIN + 2);
+        assert!(matches!(res.unwrap_err(), ContractError::NotReplica { .. }));
+
+        let res = only_replica(deps.as_ref(), Addr::unchecked("replica"), REPLICA_DOMAIN);
+        assert!(matches!(res.unwrap_err(), ContractError::NotReplica { .. }));
+    }
+
+    #[tokio::test]
+    async fn recovers_same_watcher_as_ethers() {
+        let deps = mock_dependencies_with_balance(&coins(2, "token"));
+        let home_domain_hash = H256::from_slice(
+            Keccak256::new()
+                .chain(REPLICA_DOMAIN.to_be_bytes())
+                .chain("NOMAD".as_bytes())
+                .finalize()
+                .as_slice(),
+        );
+
+        for i in 1..=8u8 {
+            let watcher = Watcher::from_privkey(&format!("{:02x}", i).repeat(32), REPLICA_DOMAIN);
+            let updater = H256::repeat_byte(i);
+            let signed = watcher.sign_failure_notification(updater).await.unwrap();
+
+            let digest = failure_notification_digest(home_domain_hash, REPLICA_DOMAIN, updater);
+            let expected = signed
+                .signature
+                .recover(RecoveryMessage::Data(digest.as_bytes().to_vec()))
+                .unwrap();
+
+            let signer = recover_failure_notification_signer(
+                &deps.api,
+                home_domain_hash,
+                REPLICA_DOMAIN,
+                updater,
+                &signed.signature.to_vec(),
+            )
+            .unwrap();
+            assert_eq!(expected, signer);
+            assert_eq!(watcher.address(), signer);
+        }
+    }
+
+    #[test]
+    fn verifies_native_watcher_signatures() {
+        let api = mock_dependencies_with_balance(&coins(2, "token")).api;
+        let home_domain_hash = H256::repeat_byte(1);
+        let updater = H256::repeat_byte(2);
+        let digest = failure_notification_digest(home_domain_hash, REPLICA_DOMAIN, updater);
+
+        for key in [
+            NativeKey::secp256k1(WATCHER_PRIVKEY),
+            NativeKey::ed25519(WATCHER_PRIVKEY),
+        ] {
+            let watcher = NativeWatcher::new(key, REPLICA_DOMAIN);
+            let signature = watcher.key.sign_digest(digest);
+            assert!(watcher.signer_id().verify(&api, digest, &signature));
+
+            // Signature over another updater does not verify
+            let other = failure_notification_digest(home_domain_hash, REPLICA_DOMAIN, H256::zero());
+            assert!(!watcher.signer_id().verify(&api, other, &signature));
+        }
+
+        // Permissions keyed by Ethereum address are unchanged
+        let watcher: LocalWallet = WATCHER_PRIVKEY.parse().unwrap();
+        let mut buf = watcher.address().to_fixed_bytes().to_vec();
+        buf.extend_from_slice(&REPLICA_DOMAIN.to_be_bytes());
+        assert_eq!(
+            H256::from(keccak256(buf)),
+            watcher_domain_hash(&watcher.address().into(), REPLICA_DOMAIN)
+        );
+    }
 }
@@ -1,5 +1,6 @@
+use common::SignerId;
 use cosmwasm_std::StdError;
-use ethers_core::types::{H160, H256};
+use ethers_core::types::H256;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -7,24 +8,45 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("Cannot migrate from contract {found}, expected {expected}")]
+    WrongContract { expected: String, found: String },
+
+    #[error("Cannot downgrade from version {stored} to {version}")]
+    CannotDowngrade { stored: String, version: String },
+
+    #[error("{0}")]
+    SemVer(#[from] semver::Error),
+
     #[error("Replica for domain {domain} does not exist")]
     NotReplicaExists { domain: u32 },
 
+    #[error("Caller {address} is not an enrolled replica")]
+    NotReplica { address: String },
+
+    #[error("Message origin {origin} does not match replica domain {domain}")]
+    ReplicaOriginMismatch { origin: u32, domain: u32 },
+
     #[error("Not current updater: {address}")]
     NotCurrentUpdater { address: String },
 
     #[error(
-        "Watcher {watcher} does not have permissions for replica {replica} on domain {domain}"
+        "Watcher {watcher:?} does not have permissions for replica {replica} on domain {domain}"
     )]
     NotWatcherPermission {
-        watcher: H160,
+        watcher: SignerId,
         replica: H256,
         domain: u32,
     },
 
+    #[error("Signature does not verify for watcher {watcher:?}")]
+    InvalidWatcherSignature { watcher: SignerId },
+
     #[error("{0}")]
-    SignatureError(#[from] ethers_core::types::SignatureError),
+    SignatureError(#[from] common::SignatureError),
 
     #[error("{0}")]
     OwnableError(#[from] ownable::ContractError),
+
+    #[error("{0}")]
+    AddressError(#[from] common::AddressError),
 }
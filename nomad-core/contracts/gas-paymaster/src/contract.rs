@@ -0,0 +1,406 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    coins, to_binary, BankMsg, Binary, Coin, Deps, DepsMut, Env, Event, MessageInfo, Response,
+    StdError, StdResult, Uint128,
+};
+use cw2::set_contract_version;
+use ethers_core::types::H256;
+use ethers_core::utils::keccak256;
+
+use crate::error::ContractError;
+use crate::state::{ATTESTER, CLAIMED, DENOM, PAYMENTS, QUOTES};
+use common::gas_paymaster::{
+    AttesterResponse, ExecuteMsg, InstantiateMsg, Payment, PaymentResponse, QueryMsg, QuoteResponse,
+};
+use common::{addr_to_h256, SignerId};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:gas-paymaster";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    ownable::instantiate(deps.branch(), env, info, common::ownable::InstantiateMsg {})?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    DENOM.save(deps.storage, &msg.denom)?;
+    ATTESTER.save(deps.storage, &msg.attester)?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::PayForMessage { leaf, destination } => {
+            execute_pay_for_message(deps, info, leaf, destination)
+        }
+        ExecuteMsg::Claim { leaf, signature } => execute_claim(deps, env, info, leaf, signature),
+        ExecuteMsg::SetQuote {
+            destination,
+            amount,
+        } => execute_set_quote(deps, info, destination, amount),
+        ExecuteMsg::SetAttester { attester } => execute_set_attester(deps, info, attester),
+        ExecuteMsg::RenounceOwnership {} => Ok(ownable::execute_renounce_ownership(deps, info)?),
+        ExecuteMsg::TransferOwnership { new_owner } => {
+            Ok(ownable::execute_transfer_ownership(deps, info, new_owner)?)
+        }
+    }
+}
+
+pub fn execute_pay_for_message(
+    deps: DepsMut,
+    info: MessageInfo,
+    leaf: H256,
+    destination: u32,
+) -> Result<Response, ContractError> {
+    let denom = DENOM.load(deps.storage)?;
+    let amount = match info.funds.as_slice() {
+        [Coin {
+            denom: coin_denom,
+            amount,
+        }] if *coin_denom == denom => *amount,
+        _ => return Err(ContractError::InvalidFunds { denom }),
+    };
+
+    let quote = QUOTES
+        .may_load(deps.storage, destination)?
+        .ok_or(ContractError::NoQuote { destination })?;
+    if amount < quote {
+        return Err(ContractError::InsufficientPayment { amount, quote });
+    }
+
+    // Attestation for a claimed leaf could be replayed to claim new payment
+    if CLAIMED.has(deps.storage, leaf.as_bytes()) {
+        return Err(ContractError::AlreadyClaimed { leaf });
+    }
+
+    let payment = match PAYMENTS.may_load(deps.storage, leaf.as_bytes())? {
+        Some(payment) if payment.destination != destination => {
+            return Err(ContractError::DestinationMismatch {
+                leaf,
+                destination: payment.destination,
+            })
+        }
+        Some(payment) => Payment {
+            destination,
+            amount: payment.amount.checked_add(amount).map_err(StdError::from)?,
+        },
+        None => Payment {
+            destination,
+            amount,
+        },
+    };
+    PAYMENTS.save(deps.storage, leaf.as_bytes(), &payment)?;
+
+    Ok(Response::new().add_event(
+        Event::new("GasPayment")
+            .add_attribute("leaf", format!("{:?}", leaf))
+            .add_attribute("destination", destination.to_string())
+            .add_attribute("payer", info.sender)
+            .add_attribute("amount", amount.to_string()),
+    ))
+}
+
+/// Pay out payment for `leaf` to caller if attester signed that caller
+/// delivered the message
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    leaf: H256,
+    signature: Vec<u8>,
+) -> Result<Response, ContractError> {
+    let payment = PAYMENTS
+        .may_load(deps.storage, leaf.as_bytes())?
+        .ok_or(ContractError::NoPayment { leaf })?;
+
+    let digest = delivery_digest(
+        addr_to_h256(deps.api, &env.contract.address)?,
+        leaf,
+        addr_to_h256(deps.api, &info.sender)?,
+    );
+    let attester = ATTESTER.load(deps.storage)?;
+    if !attester.verify(deps.api, digest, &signature) {
+        return Err(ContractError::InvalidAttestation { leaf });
+    }
+
+    PAYMENTS.remove(deps.storage, leaf.as_bytes());
+    CLAIMED.save(deps.storage, leaf.as_bytes(), &true)?;
+
+    let denom = DENOM.load(deps.storage)?;
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(payment.amount.u128(), denom),
+        })
+        .add_event(
+            Event::new("GasPaymentClaimed")
+                .add_attribute("leaf", format!("{:?}", leaf))
+                .add_attribute("processor", info.sender)
+                .add_attribute("amount", payment.amount.to_string()),
+        ))
+}
+
+pub fn execute_set_quote(
+    deps: DepsMut,
+    info: MessageInfo,
+    destination: u32,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+
+    QUOTES.save(deps.storage, destination, &amount)?;
+
+    Ok(Response::new().add_event(
+        Event::new("SetQuote")
+            .add_attribute("destination", destination.to_string())
+            .add_attribute("amount", amount.to_string()),
+    ))
+}
+
+pub fn execute_set_attester(
+    deps: DepsMut,
+    info: MessageInfo,
+    attester: SignerId,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+
+    ATTESTER.save(deps.storage, &attester)?;
+
+    Ok(Response::new().add_event(
+        Event::new("SetAttester").add_attribute("attester", format!("{:?}", attester.to_h256())),
+    ))
+}
+
+/// Digest attester signs to confirm `processor` delivered message `leaf`
+/// paid for on `paymaster`
+pub fn delivery_digest(paymaster: H256, leaf: H256, processor: H256) -> H256 {
+    keccak256([paymaster.as_bytes(), leaf.as_bytes(), processor.as_bytes()].concat()).into()
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Quote { destination } => to_binary(&query_quote(deps, destination)?),
+        QueryMsg::Payment { leaf } => to_binary(&query_payment(deps, leaf)?),
+        QueryMsg::Attester {} => to_binary(&query_attester(deps)?),
+        QueryMsg::Owner {} => to_binary(&ownable::query_owner(deps)?),
+    }
+}
+
+pub fn query_quote(deps: Deps, destination: u32) -> StdResult<QuoteResponse> {
+    let amount = QUOTES.may_load(deps.storage, destination)?;
+    let denom = DENOM.load(deps.storage)?;
+    Ok(QuoteResponse { amount, denom })
+}
+
+pub fn query_payment(deps: Deps, leaf: H256) -> StdResult<PaymentResponse> {
+    let payment = PAYMENTS.may_load(deps.storage, leaf.as_bytes())?;
+    Ok(PaymentResponse { payment })
+}
+
+pub fn query_attester(deps: Deps) -> StdResult<AttesterResponse> {
+    let attester = ATTESTER.load(deps.storage)?;
+    Ok(AttesterResponse { attester })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+    use cosmwasm_std::testing::{MockQuerier, MockStorage};
+    use cosmwasm_std::{from_binary, Addr, CosmosMsg, OwnedDeps};
+    use test_utils::{mock_padded_dependencies, NativeKey, PaddedMockApi};
+
+    const PAYMENT_DENOM: &str = "earth";
+    const DESTINATION: u32 = 2000;
+    const ATTESTER_PRIVKEY: &str =
+        "2222222222222222222222222222222222222222222222222222222222222222";
+
+    fn instantiate_paymaster(
+        attester: &NativeKey,
+    ) -> OwnedDeps<MockStorage, PaddedMockApi, MockQuerier> {
+        let mut deps = mock_padded_dependencies(&[]);
+        let msg = InstantiateMsg {
+            denom: PAYMENT_DENOM.to_owned(),
+            attester: attester.signer_id(),
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::SetQuote {
+            destination: DESTINATION,
+            amount: Uint128::new(100),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+        deps
+    }
+
+    fn attest(deps: Deps, attester: &NativeKey, leaf: H256, processor: &str) -> Vec<u8> {
+        let digest = delivery_digest(
+            addr_to_h256(deps.api, &Addr::unchecked(MOCK_CONTRACT_ADDR)).unwrap(),
+            leaf,
+            addr_to_h256(deps.api, &Addr::unchecked(processor)).unwrap(),
+        );
+        attester.sign_digest(digest)
+    }
+
+    #[test]
+    fn pays_at_least_quote() {
+        let attester = NativeKey::ed25519(ATTESTER_PRIVKEY);
+        let mut deps = instantiate_paymaster(&attester);
+        let leaf = H256::repeat_byte(1);
+
+        let msg = ExecuteMsg::SetQuote {
+            destination: DESTINATION,
+            amount: Uint128::new(1),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("payer", &[]), msg);
+        assert!(matches!(res.unwrap_err(), ContractError::OwnableError(_)));
+
+        let pay_msg = ExecuteMsg::PayForMessage {
+            leaf,
+            destination: DESTINATION,
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("payer", &coins(100, "moon")),
+            pay_msg.clone(),
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::InvalidFunds { .. }
+        ));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("payer", &coins(99, PAYMENT_DENOM)),
+            pay_msg.clone(),
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::InsufficientPayment { .. }
+        ));
+
+        let msg = ExecuteMsg::PayForMessage {
+            leaf,
+            destination: 3000,
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("payer", &coins(100, PAYMENT_DENOM)),
+            msg,
+        );
+        assert!(matches!(res.unwrap_err(), ContractError::NoQuote { .. }));
+
+        // Payments for same leaf add up
+        for _ in 0..2 {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("payer", &coins(150, PAYMENT_DENOM)),
+                pay_msg.clone(),
+            )
+            .unwrap();
+        }
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Payment { leaf }).unwrap();
+        let value: PaymentResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            Some(Payment {
+                destination: DESTINATION,
+                amount: Uint128::new(300),
+            }),
+            value.payment
+        );
+    }
+
+    #[test]
+    fn processor_claims_with_attestation() {
+        let attester = NativeKey::ed25519(ATTESTER_PRIVKEY);
+        let mut deps = instantiate_paymaster(&attester);
+        let leaf = H256::repeat_byte(1);
+
+        let msg = ExecuteMsg::PayForMessage {
+            leaf,
+            destination: DESTINATION,
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("payer", &coins(100, PAYMENT_DENOM)),
+            msg,
+        )
+        .unwrap();
+
+        // Attestation is bound to processor
+        let signature = attest(deps.as_ref(), &attester, leaf, "processor");
+        let claim_msg = ExecuteMsg::Claim { leaf, signature };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("thief", &[]),
+            claim_msg.clone(),
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::InvalidAttestation { .. }
+        ));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("processor", &[]),
+            claim_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "processor".to_owned(),
+                amount: coins(100, PAYMENT_DENOM),
+            }),
+            res.messages[0].msg
+        );
+
+        // Payment only claimable once
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("processor", &[]),
+            claim_msg,
+        );
+        assert!(matches!(res.unwrap_err(), ContractError::NoPayment { .. }));
+
+        // Claimed leaf cannot be paid for again, so old attestation cannot
+        // be replayed
+        let msg = ExecuteMsg::PayForMessage {
+            leaf,
+            destination: DESTINATION,
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("payer", &coins(100, PAYMENT_DENOM)),
+            msg,
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::AlreadyClaimed { .. }
+        ));
+    }
+}
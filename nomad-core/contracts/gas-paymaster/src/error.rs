@@ -0,0 +1,36 @@
+use cosmwasm_std::{StdError, Uint128};
+use ethers_core::types::H256;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Must send exactly one coin of denom {denom}")]
+    InvalidFunds { denom: String },
+
+    #[error("No quote for destination {destination}")]
+    NoQuote { destination: u32 },
+
+    #[error("Payment of {amount} is below quote of {quote}")]
+    InsufficientPayment { amount: Uint128, quote: Uint128 },
+
+    #[error("Payment for message {leaf:?} is for destination {destination}")]
+    DestinationMismatch { leaf: H256, destination: u32 },
+
+    #[error("No payment for message {leaf:?}")]
+    NoPayment { leaf: H256 },
+
+    #[error("Payment for message {leaf:?} already claimed")]
+    AlreadyClaimed { leaf: H256 },
+
+    #[error("Invalid delivery attestation for message {leaf:?}")]
+    InvalidAttestation { leaf: H256 },
+
+    #[error("{0}")]
+    OwnableError(#[from] ownable::ContractError),
+
+    #[error("{0}")]
+    AddressError(#[from] common::AddressError),
+}
@@ -0,0 +1,16 @@
+use cosmwasm_std::Uint128;
+use cw_storage_plus::{Item, Map};
+
+use common::gas_paymaster::Payment;
+use common::SignerId;
+
+pub const DENOM: Item<String> = Item::new("gas_paymaster_denom");
+pub const ATTESTER: Item<SignerId> = Item::new("gas_paymaster_attester");
+
+// Minimum payment per message, keyed by destination
+pub const QUOTES: Map<u32, Uint128> = Map::new("gas_paymaster_quotes");
+// Unclaimed payments keyed by message leaf
+pub const PAYMENTS: Map<&[u8], Payment> = Map::new("gas_paymaster_payments");
+// Leaves whose payment was claimed. Delivery attestations never expire, so
+// claimed leaves cannot be paid for again
+pub const CLAIMED: Map<&[u8], bool> = Map::new("gas_paymaster_claimed");
@@ -0,0 +1,437 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_slice, to_binary, to_vec, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, Event, MessageInfo,
+    Response, StdResult, SubMsg, WasmMsg,
+};
+use cw2::set_contract_version;
+use ethers_core::types::H256;
+
+use crate::error::ContractError;
+use crate::state::{DOMAINS, GOVERNOR, GOVERNOR_DOMAIN, LOCAL_DOMAIN, ROUTER};
+use common::governance_router::{
+    ExecuteMsg, GovernanceMessage, GovernorResponse, InstantiateMsg, QueryMsg, RouterResponse,
+};
+use common::nomad_base::LocalDomainResponse;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:governance-router";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    LOCAL_DOMAIN.save(deps.storage, &msg.local_domain)?;
+    ROUTER.save_contracts(deps.branch(), &msg.home, &msg.connection_manager)?;
+    DOMAINS.save(deps.storage, &vec![])?;
+
+    _set_governor(deps.branch(), msg.governor_domain, msg.governor)?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ExecuteCalls { calls } => execute_execute_calls(deps, env, info, calls),
+        ExecuteMsg::DispatchCalls { domain, calls } => {
+            execute_dispatch_calls(deps, env, info, domain, calls)
+        }
+        ExecuteMsg::TransferGovernor { domain, governor } => {
+            execute_transfer_governor(deps, env, info, domain, governor)
+        }
+        ExecuteMsg::SetRouter { domain, router } => {
+            execute_set_router(deps, env, info, domain, router)
+        }
+        ExecuteMsg::HandleMsg {
+            origin,
+            nonce,
+            sender,
+            message,
+        } => execute_handle(deps, info, origin, nonce, sender, message),
+    }
+}
+
+pub fn execute_execute_calls(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    calls: Vec<WasmMsg>,
+) -> Result<Response, ContractError> {
+    only_governor(deps.as_ref(), &env, &info)?;
+
+    Ok(_execute_calls(calls))
+}
+
+pub fn execute_dispatch_calls(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    domain: u32,
+    calls: Vec<WasmMsg>,
+) -> Result<Response, ContractError> {
+    only_governor(deps.as_ref(), &env, &info)?;
+
+    let num_calls = calls.len();
+    let dispatch_msg =
+        _dispatch_to_router(deps.as_ref(), domain, &GovernanceMessage::Batch { calls })?;
+
+    Ok(Response::new().add_submessage(dispatch_msg).add_event(
+        Event::new("DispatchCalls")
+            .add_attribute("domain", domain.to_string())
+            .add_attribute("num_calls", num_calls.to_string()),
+    ))
+}
+
+pub fn execute_transfer_governor(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    domain: u32,
+    governor: Option<String>,
+) -> Result<Response, ContractError> {
+    only_governor(deps.as_ref(), &env, &info)?;
+
+    let set_governor_resp = _set_governor(deps.branch(), domain, governor.clone())?;
+
+    // Broadcast new governor to all enrolled routers
+    let gov_msg = GovernanceMessage::TransferGovernor { domain, governor };
+    let dispatch_msgs = DOMAINS
+        .load(deps.storage)?
+        .into_iter()
+        .map(|router_domain| _dispatch_to_router(deps.as_ref(), router_domain, &gov_msg))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(set_governor_resp.add_submessages(dispatch_msgs))
+}
+
+pub fn execute_set_router(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    domain: u32,
+    router: H256,
+) -> Result<Response, ContractError> {
+    only_governor(deps.as_ref(), &env, &info)?;
+
+    let resp = ROUTER.enroll_remote_router(deps.branch(), domain, router)?;
+
+    let mut domains = DOMAINS.load(deps.storage)?;
+    if !domains.contains(&domain) {
+        domains.push(domain);
+        DOMAINS.save(deps.storage, &domains)?;
+    }
+
+    Ok(resp)
+}
+
+pub fn execute_handle(
+    deps: DepsMut,
+    info: MessageInfo,
+    origin: u32,
+    nonce: u32,
+    sender: H256,
+    message: Vec<u8>,
+) -> Result<Response, ContractError> {
+    ROUTER.only_replica(deps.as_ref(), &info, origin)?;
+    only_governor_router(deps.as_ref(), origin, sender)?;
+
+    let gov_msg: GovernanceMessage = from_slice(&message)?;
+    let resp = match gov_msg {
+        GovernanceMessage::Batch { calls } => _execute_calls(calls),
+        GovernanceMessage::TransferGovernor { domain, governor } => {
+            _set_governor(deps, domain, governor)?
+        }
+    };
+
+    Ok(resp.add_event(
+        Event::new("HandleGovernanceMessage")
+            .add_attribute("origin", origin.to_string())
+            .add_attribute("nonce", nonce.to_string()),
+    ))
+}
+
+/// Checks that caller is the local governor or the router itself (governance
+/// actions received from a remote governor execute against the router)
+pub fn only_governor(deps: Deps, env: &Env, info: &MessageInfo) -> Result<(), ContractError> {
+    let governor = GOVERNOR.load(deps.storage)?;
+    if info.sender != governor && info.sender != env.contract.address {
+        return Err(ContractError::NotGovernor);
+    }
+
+    Ok(())
+}
+
+/// Checks that message originated from the governor domain's router
+pub fn only_governor_router(deps: Deps, origin: u32, sender: H256) -> Result<(), ContractError> {
+    let governor_domain = GOVERNOR_DOMAIN.load(deps.storage)?;
+    if origin != governor_domain {
+        return Err(ContractError::NotGovernorDomain {
+            origin,
+            governor_domain,
+        });
+    }
+
+    ROUTER.only_remote_router(deps, origin, sender)?;
+    Ok(())
+}
+
+pub fn _set_governor(
+    deps: DepsMut,
+    domain: u32,
+    governor: Option<String>,
+) -> Result<Response, ContractError> {
+    let local_domain = LOCAL_DOMAIN.load(deps.storage)?;
+
+    let governor_addr = match (domain == local_domain, governor) {
+        (true, Some(governor)) => deps.api.addr_validate(&governor)?,
+        (false, None) => Addr::unchecked("0x0"),
+        _ => return Err(ContractError::InvalidGovernor { domain }),
+    };
+
+    GOVERNOR_DOMAIN.save(deps.storage, &domain)?;
+    GOVERNOR.save(deps.storage, &governor_addr)?;
+
+    Ok(Response::new().add_event(
+        Event::new("TransferGovernor")
+            .add_attribute("domain", domain.to_string())
+            .add_attribute("governor", governor_addr.to_string()),
+    ))
+}
+
+pub fn _execute_calls(calls: Vec<WasmMsg>) -> Response {
+    let num_calls = calls.len();
+    Response::new()
+        .add_messages(calls.into_iter().map(CosmosMsg::Wasm))
+        .add_event(Event::new("ExecuteCalls").add_attribute("num_calls", num_calls.to_string()))
+}
+
+pub fn _dispatch_to_router(
+    deps: Deps,
+    domain: u32,
+    gov_msg: &GovernanceMessage,
+) -> Result<SubMsg, ContractError> {
+    Ok(ROUTER.dispatch_to_remote(deps, domain, to_vec(gov_msg)?)?)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Governor {} => to_binary(&query_governor(deps)?),
+        QueryMsg::Router { domain } => to_binary(&query_router(deps, domain)?),
+        QueryMsg::LocalDomain {} => to_binary(&query_local_domain(deps)?),
+    }
+}
+
+pub fn query_governor(deps: Deps) -> StdResult<GovernorResponse> {
+    let domain = GOVERNOR_DOMAIN.load(deps.storage)?;
+    let governor = GOVERNOR.load(deps.storage)?;
+    Ok(GovernorResponse {
+        domain,
+        governor: governor.to_string(),
+    })
+}
+
+pub fn query_router(deps: Deps, domain: u32) -> StdResult<RouterResponse> {
+    let router = ROUTER
+        .remote_router(deps, domain)?
+        .unwrap_or_else(H256::zero);
+    Ok(RouterResponse { router })
+}
+
+pub fn query_local_domain(deps: Deps) -> StdResult<LocalDomainResponse> {
+    let local_domain = LOCAL_DOMAIN.load(deps.storage)?;
+    Ok(LocalDomainResponse { local_domain })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::home;
+    use cosmwasm_std::testing::{mock_env, mock_info};
+    use cosmwasm_std::{coins, from_binary};
+    use test_utils::mock_padded_dependencies;
+    use xapp::XAppError;
+
+    const LOCAL_DOMAIN: u32 = 1000;
+    const REMOTE_DOMAIN: u32 = 2000;
+
+    fn instantiate_local_governor(deps: DepsMut) {
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            home: "home".to_owned(),
+            connection_manager: "connection_manager".to_owned(),
+            governor_domain: LOCAL_DOMAIN,
+            governor: Some("governor".to_owned()),
+        };
+        let info = mock_info("owner", &[]);
+        instantiate(deps, mock_env(), info, msg).unwrap();
+    }
+
+    #[test]
+    fn proper_initialization() {
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+        instantiate_local_governor(deps.as_mut());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Governor {}).unwrap();
+        let value: GovernorResponse = from_binary(&res).unwrap();
+        assert_eq!(LOCAL_DOMAIN, value.domain);
+        assert_eq!("governor", value.governor);
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Router {
+                domain: REMOTE_DOMAIN,
+            },
+        )
+        .unwrap();
+        let value: RouterResponse = from_binary(&res).unwrap();
+        assert_eq!(H256::zero(), value.router);
+    }
+
+    #[test]
+    fn rejects_invalid_governor() {
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+
+        // Remote governor domain but local governor provided
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            home: "home".to_owned(),
+            connection_manager: "connection_manager".to_owned(),
+            governor_domain: REMOTE_DOMAIN,
+            governor: Some("governor".to_owned()),
+        };
+        let info = mock_info("owner", &[]);
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::InvalidGovernor { .. }
+        ));
+    }
+
+    #[test]
+    fn only_governor_executes_calls() {
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+        instantiate_local_governor(deps.as_mut());
+
+        let call = WasmMsg::Execute {
+            contract_addr: "target".to_owned(),
+            msg: to_binary(&"call").unwrap(),
+            funds: vec![],
+        };
+
+        let msg = ExecuteMsg::ExecuteCalls {
+            calls: vec![call.clone()],
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("not_governor", &[]),
+            msg.clone(),
+        );
+        assert!(matches!(res.unwrap_err(), ContractError::NotGovernor));
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("governor", &[]), msg).unwrap();
+        assert_eq!(vec![SubMsg::new(call)], res.messages);
+    }
+
+    #[test]
+    fn governor_dispatches_calls_to_router() {
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+        instantiate_local_governor(deps.as_mut());
+
+        let info = mock_info("governor", &[]);
+        let call = WasmMsg::Execute {
+            contract_addr: "target".to_owned(),
+            msg: to_binary(&"call").unwrap(),
+            funds: vec![],
+        };
+
+        // No router enrolled
+        let msg = ExecuteMsg::DispatchCalls {
+            domain: REMOTE_DOMAIN,
+            calls: vec![call.clone()],
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::XAppError(XAppError::NoRouter { .. })
+        ));
+
+        let set_router_msg = ExecuteMsg::SetRouter {
+            domain: REMOTE_DOMAIN,
+            router: H256::repeat_byte(1),
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), set_router_msg).unwrap();
+
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(1, res.messages.len());
+
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => {
+                assert_eq!("home", contract_addr);
+                let dispatch: home::ExecuteMsg = from_binary(msg).unwrap();
+                match dispatch {
+                    home::ExecuteMsg::Dispatch {
+                        destination,
+                        recipient,
+                        message_body,
+                    } => {
+                        assert_eq!(REMOTE_DOMAIN, destination);
+                        assert_eq!(H256::repeat_byte(1), recipient);
+                        let gov_msg: GovernanceMessage = from_slice(&message_body).unwrap();
+                        assert_eq!(GovernanceMessage::Batch { calls: vec![call] }, gov_msg);
+                    }
+                    _ => panic!("Expected dispatch message"),
+                }
+            }
+            _ => panic!("Expected wasm execute message"),
+        }
+    }
+
+    #[test]
+    fn transfer_governor_broadcasts_to_routers() {
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+        instantiate_local_governor(deps.as_mut());
+
+        let info = mock_info("governor", &[]);
+        for (domain, router) in [(REMOTE_DOMAIN, 1), (3000, 2)] {
+            let msg = ExecuteMsg::SetRouter {
+                domain,
+                router: H256::repeat_byte(router),
+            };
+            execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        }
+
+        let msg = ExecuteMsg::TransferGovernor {
+            domain: REMOTE_DOMAIN,
+            governor: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert_eq!(2, res.messages.len());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Governor {}).unwrap();
+        let value: GovernorResponse = from_binary(&res).unwrap();
+        assert_eq!(REMOTE_DOMAIN, value.domain);
+        assert_eq!("0x0", value.governor);
+
+        // Former governor no longer has access
+        let msg = ExecuteMsg::ExecuteCalls { calls: vec![] };
+        let res = execute(deps.as_mut(), mock_env(), info, msg);
+        assert!(matches!(res.unwrap_err(), ContractError::NotGovernor));
+    }
+}
@@ -0,0 +1,20 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    XAppError(#[from] xapp::XAppError),
+
+    #[error("Caller is not the governor")]
+    NotGovernor,
+
+    #[error("Message origin {origin} is not the governor domain {governor_domain}")]
+    NotGovernorDomain { origin: u32, governor_domain: u32 },
+
+    #[error("Governor must be set iff governor domain {domain} is local domain")]
+    InvalidGovernor { domain: u32 },
+}
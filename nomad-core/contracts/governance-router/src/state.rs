@@ -0,0 +1,17 @@
+use cosmwasm_std::Addr;
+use cw_storage_plus::Item;
+use xapp::Router;
+
+pub const LOCAL_DOMAIN: Item<u32> = Item::new("governance_router_local_domain");
+pub const ROUTER: Router<'static> = Router::new(
+    "governance_router_home",
+    "governance_router_connection_manager",
+    "governance_router_routers",
+);
+
+pub const GOVERNOR_DOMAIN: Item<u32> = Item::new("governance_router_governor_domain");
+// Local governor, 0x0 if governor lives on remote domain
+pub const GOVERNOR: Item<Addr> = Item::new("governance_router_governor");
+
+// Domains with enrolled routers (used to broadcast governor transfers)
+pub const DOMAINS: Item<Vec<u32>> = Item::new("governance_router_domains");
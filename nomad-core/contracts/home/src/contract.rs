@@ -1,26 +1,41 @@
-use common::{addr_to_h256, destination_and_nonce, Encode, NomadMessage};
+use common::{
+    addr_to_h256, destination_and_nonce, h256_to_addr, Decode, Encode, Envelope, HandleExecuteMsg,
+    MessageStatus, NomadMessage, NomadPacket, SignerId,
+};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
     from_binary, to_binary, Addr, Binary, ContractResult, CosmosMsg, Deps, DepsMut, Env, Event,
-    MessageInfo, Reply, ReplyOn, Response, StdResult, SubMsg, WasmMsg,
+    MessageInfo, Order, Reply, ReplyOn, Response, StdResult, SubMsg, WasmMsg,
 };
-use cw2::set_contract_version;
-use ethers_core::types::{H160, H256};
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Bound;
+use ethers_core::types::H256;
+use semver::Version;
 
 use crate::error::ContractError;
-use crate::state::{NONCES, UPDATER_MANAGER};
+use crate::ibc;
+use crate::state::{
+    StoredMessage, DEFAULT_PROCESS_GAS, DESTINATION_NONCE_LEAVES, LEAF_INDEX_LEAVES,
+    LOOPBACK_MESSAGES, LOOPBACK_PROCESSING, MESSAGES, MESSAGE_INDEXING, NONCES, PROCESS_GAS,
+    UPDATER_MANAGER,
+};
 use common::home::{
-    ExecuteMsg, InstantiateMsg, NoncesResponse, QueryMsg, SuggestUpdateResponse,
-    UpdaterManagerResponse,
+    DispatchedMessage, ExecuteMsg, InstantiateMsg, LeafIndexResponse, LoopbackStatusResponse,
+    MessageIndexingResponse, MessageResponse, MessagesResponse, MigrateMsg, NoncesResponse,
+    ProcessGasResponse, QueryMsg, SuggestUpdateResponse, UpdaterManagerResponse,
 };
 
 const CONTRACT_NAME: &str = "crates.io:home";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub const SLASH_UPDATER_ID: u64 = 1;
+pub const LOOPBACK_ID: u64 = 2;
 const MAX_MESSAGE_BODY_BYTES: u64 = 2 * u64::pow(2, 10);
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     mut deps: DepsMut,
@@ -34,6 +49,7 @@ pub fn instantiate(
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     UPDATER_MANAGER.save(deps.storage, &Addr::unchecked("0x0"))?;
+    MESSAGE_INDEXING.save(deps.storage, &false)?;
 
     Ok(Response::new())
 }
@@ -52,7 +68,7 @@ fn only_updater_manager(deps: Deps, info: MessageInfo) -> Result<Response, Contr
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
@@ -61,35 +77,59 @@ pub fn execute(
             destination,
             recipient,
             message_body,
-        } => execute_dispatch(deps, info, destination, recipient, message_body),
+        } => execute_dispatch(deps, env, info, destination, recipient, message_body),
         ExecuteMsg::Update {
             committed_root,
             new_root,
-            signature,
-        } => execute_update(deps, info, committed_root, new_root, signature),
+            signatures,
+        } => execute_update(deps, env, info, committed_root, new_root, signatures),
         ExecuteMsg::DoubleUpdate {
             old_root,
             new_roots,
-            signature,
-            signature_2,
+            signatures,
+            signatures_2,
         } => Ok(nomad_base::execute_double_update(
             deps,
             info,
             old_root,
             new_roots,
-            signature,
-            signature_2,
+            signatures,
+            signatures_2,
             _fail,
         )?),
         ExecuteMsg::ImproperUpdate {
             old_root,
             new_root,
-            signature,
-        } => execute_improper_update(deps, info, old_root, new_root, &signature),
-        ExecuteMsg::SetUpdater { updater } => execute_set_updater(deps, info, updater),
+            signatures,
+        } => execute_improper_update(deps, info, old_root, new_root, &signatures),
+        ExecuteMsg::SetUpdaters {
+            updaters,
+            threshold,
+        } => execute_set_updaters(deps, info, updaters, threshold),
         ExecuteMsg::SetUpdaterManager { updater_manager } => {
             execute_set_updater_manager(deps, info, updater_manager)
         }
+        ExecuteMsg::SetMessageIndexing { enabled } => {
+            execute_set_message_indexing(deps, info, enabled)
+        }
+        ExecuteMsg::SetFullTreeStorage { enabled } => {
+            execute_set_full_tree_storage(deps, info, enabled)
+        }
+        ExecuteMsg::SetIbcCounterparty {
+            connection_id,
+            port_id,
+            allowed,
+        } => ibc::execute_set_ibc_counterparty(deps, info, connection_id, port_id, allowed),
+        ExecuteMsg::RelayMessage {
+            channel_id,
+            message,
+            proof,
+            index,
+        } => ibc::execute_relay_message(deps, env, channel_id, message, proof, index),
+        ExecuteMsg::SetProcessGas { process_gas } => {
+            execute_set_process_gas(deps, info, process_gas)
+        }
+        ExecuteMsg::RetryLoopback { message } => execute_retry_loopback(deps, env, info, message),
         ExecuteMsg::RenounceOwnership {} => Ok(ownable::execute_renounce_ownership(deps, info)?),
         ExecuteMsg::TransferOwnership { new_owner } => {
             Ok(ownable::execute_transfer_ownership(deps, info, new_owner)?)
@@ -99,9 +139,10 @@ pub fn execute(
 
 pub fn execute_dispatch(
     mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     destination: u32,
-    recipient: String,
+    recipient: H256,
     message: Vec<u8>,
 ) -> Result<Response, ContractError> {
     nomad_base::not_failed(deps.as_ref())?;
@@ -115,17 +156,22 @@ pub fn execute_dispatch(
     NONCES.save(deps.storage, destination, &(nonce + 1))?;
 
     let origin = nomad_base::query_local_domain(deps.as_ref())?.local_domain;
-    let recipient_addr = deps.api.addr_validate(&recipient)?;
 
     let nomad_message = NomadMessage {
         origin,
-        sender: addr_to_h256(info.sender),
+        sender: addr_to_h256(deps.api, &info.sender)?,
         nonce,
         destination,
-        recipient: addr_to_h256(recipient_addr),
+        recipient,
         body: message.clone(),
     };
 
+    // No replica exists for the local domain, deliver immediately instead of
+    // committing to the tree
+    if destination == origin {
+        return _dispatch_loopback(deps, env, info, nomad_message);
+    }
+
     // Get state before mutations
     let leaf_index = merkle::query_count(deps.as_ref())?.count;
     let committed_root = nomad_base::query_committed_root(deps.as_ref())?.committed_root;
@@ -134,6 +180,10 @@ pub fn execute_dispatch(
     let hash: H256 = nomad_message.to_leaf();
     merkle::execute_insert(deps.branch(), hash)?;
 
+    if query_message_indexing(deps.as_ref())?.enabled {
+        _index_message(deps.branch(), &nomad_message, hash, leaf_index)?;
+    }
+
     // Enqueue merkle root
     let root = merkle::query_root(deps.as_ref())?.root;
     queue::execute_enqueue(deps.branch(), root)?;
@@ -153,39 +203,48 @@ pub fn execute_dispatch(
 
 pub fn execute_update(
     mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     committed_root: H256,
     new_root: H256,
-    signature: Vec<u8>,
+    signatures: Vec<Vec<u8>>,
 ) -> Result<Response, ContractError> {
     nomad_base::not_failed(deps.as_ref())?;
 
     let improper_update_res =
-        execute_improper_update(deps.branch(), info, committed_root, new_root, &signature)?;
+        execute_improper_update(deps.branch(), info, committed_root, new_root, &signatures)?;
     let improper_update: bool = from_binary(&improper_update_res.clone().data.unwrap())?;
 
     if improper_update {
         return Ok(improper_update_res);
     }
 
-    loop {
-        let next_res = queue::execute_dequeue(deps.branch())?;
-        let next: H256 = from_binary(&next_res.data.unwrap())?;
-        if next == new_root {
-            break;
-        }
-    }
+    // Roots never repeat, so this stops where dequeuing until `new_root`
+    // would
+    queue::execute_dequeue_through(deps.branch(), new_root)?;
 
     nomad_base::_set_committed_root(deps.branch(), new_root)?;
 
     let local_domain = nomad_base::query_local_domain(deps.as_ref())?.local_domain;
 
-    Ok(Response::new().add_event(
+    // Forward update to replicas connected over IBC
+    let packet = NomadPacket::Update {
+        committed_root,
+        new_root,
+        signatures: signatures.clone(),
+    };
+    let send_msgs = ibc::query_ibc_channels(deps.as_ref())?
+        .channels
+        .into_iter()
+        .map(|channel_id| ibc::send_packet_msg(&env, channel_id, &packet))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new().add_messages(send_msgs).add_event(
         Event::new("Update")
             .add_attribute("local_domain", local_domain.to_string())
             .add_attribute("committed_root", format!("{:?}", committed_root))
             .add_attribute("new_root", format!("{:?}", new_root))
-            .add_attribute("signature", format!("{:?}", signature)),
+            .add_attribute("signatures", format!("{:?}", signatures)),
     ))
 }
 
@@ -194,11 +253,11 @@ pub fn execute_improper_update(
     info: MessageInfo,
     old_root: H256,
     new_root: H256,
-    signature: &[u8],
+    signatures: &[Vec<u8>],
 ) -> Result<Response, ContractError> {
     nomad_base::not_failed(deps.as_ref())?;
 
-    if !nomad_base::is_updater_signature(deps.as_ref(), old_root, new_root, signature)? {
+    if !nomad_base::is_updater_signature(deps.as_ref(), old_root, new_root, signatures)? {
         return Err(ContractError::NotUpdaterSignature {});
     }
 
@@ -208,14 +267,15 @@ pub fn execute_improper_update(
     }
 
     if !queue::query_contains(deps.as_ref(), new_root)?.contains {
-        let sub_msgs = _fail(deps, info)?.messages;
+        let signers = nomad_base::update_signers(deps.as_ref(), old_root, new_root, signatures)?;
+        let sub_msgs = _fail(deps, info, signers)?.messages;
         return Ok(Response::new()
             .set_data(to_binary(&true)?)
             .add_event(
                 Event::new("ImproperUpdate")
                     .add_attribute("old_root", format!("{:?}", old_root))
                     .add_attribute("new_root", format!("{:?}", new_root))
-                    .add_attribute("signature", format!("{:?}", signature)),
+                    .add_attribute("signatures", format!("{:?}", signatures)),
             )
             .add_submessages(sub_msgs));
     }
@@ -223,13 +283,14 @@ pub fn execute_improper_update(
     Ok(Response::new().set_data(to_binary(&false)?))
 }
 
-pub fn execute_set_updater(
+pub fn execute_set_updaters(
     deps: DepsMut,
     info: MessageInfo,
-    updater: H160,
+    updaters: Vec<SignerId>,
+    threshold: u32,
 ) -> Result<Response, ContractError> {
-    only_updater_manager(deps.as_ref(), info.clone())?;
-    Ok(nomad_base::_set_updater(deps, updater)?)
+    only_updater_manager(deps.as_ref(), info)?;
+    Ok(nomad_base::_set_updaters(deps, updaters, threshold)?)
 }
 
 pub fn execute_set_updater_manager(
@@ -247,11 +308,167 @@ pub fn execute_set_updater_manager(
     ))
 }
 
-fn _fail(mut deps: DepsMut, info: MessageInfo) -> Result<Response, nomad_base::ContractError> {
+pub fn execute_set_message_indexing(
+    deps: DepsMut,
+    info: MessageInfo,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+    MESSAGE_INDEXING.save(deps.storage, &enabled)?;
+
+    Ok(Response::new()
+        .add_event(Event::new("SetMessageIndexing").add_attribute("enabled", enabled.to_string())))
+}
+
+/// Toggle storing the full merkle tree to serve proofs (owner only)
+pub fn execute_set_full_tree_storage(
+    deps: DepsMut,
+    info: MessageInfo,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+    Ok(merkle::execute_set_full_tree_storage(deps, enabled)?)
+}
+
+pub fn execute_set_process_gas(
+    deps: DepsMut,
+    info: MessageInfo,
+    process_gas: u64,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+    PROCESS_GAS.save(deps.storage, &process_gas)?;
+    Ok(Response::new().add_event(
+        Event::new("SetProcessGas").add_attribute("process_gas", process_gas.to_string()),
+    ))
+}
+
+/// Store dispatched message under its destination and nonce, leaf index and
+/// leaf
+fn _index_message(
+    deps: DepsMut,
+    nomad_message: &NomadMessage,
+    leaf: H256,
+    leaf_index: usize,
+) -> StdResult<()> {
+    let leaf_index = leaf_index as u64;
+    DESTINATION_NONCE_LEAVES.save(
+        deps.storage,
+        destination_and_nonce(nomad_message.destination, nomad_message.nonce),
+        &leaf,
+    )?;
+    LEAF_INDEX_LEAVES.save(deps.storage, leaf_index, &leaf)?;
+    MESSAGES.save(
+        deps.storage,
+        leaf.as_bytes(),
+        &StoredMessage {
+            leaf_index,
+            message: nomad_message.to_vec(),
+        },
+    )
+}
+
+/// Delivers message dispatched to local domain to its recipient in a
+/// submessage with the same `HandleMsg` a replica would send. The recipient
+/// sees home as the delivering replica.
+fn _dispatch_loopback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    nomad_message: NomadMessage,
+) -> Result<Response, ContractError> {
+    let leaf = nomad_message.to_leaf();
+    let event = Event::new("LoopbackDispatch")
+        .add_attribute("message_hash", format!("{:?}", leaf))
+        .add_attribute(
+            "destination_and_nonce",
+            destination_and_nonce(nomad_message.destination, nomad_message.nonce).to_string(),
+        )
+        .add_attribute("message", format!("{:?}", nomad_message.to_vec()));
+
+    let res = _deliver_loopback(deps, env, info, nomad_message)?;
+    Ok(res.add_event(event))
+}
+
+pub fn execute_retry_loopback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    message: Vec<u8>,
+) -> Result<Response, ContractError> {
+    let nomad_message = NomadMessage::read_from(&mut message.as_slice())
+        .map_err(|_| ContractError::MalformedMessage {})?;
+
+    let leaf = nomad_message.to_leaf();
+    match query_loopback_status(deps.as_ref(), leaf)?.status {
+        MessageStatus::Failed(_) => _deliver_loopback(deps, env, info, nomad_message),
+        _ => Err(ContractError::LoopbackNotFailed { leaf }),
+    }
+}
+
+/// Marks loopback message processed and calls recipient's handle with the
+/// process gas limit. `reply_loopback` marks it failed if the recipient
+/// errors or runs out of gas, so a recipient cannot exhaust the caller's
+/// gas. Expired
+/// envelopes are never delivered, as on a replica.
+fn _deliver_loopback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    mut nomad_message: NomadMessage,
+) -> Result<Response, ContractError> {
+    let leaf = nomad_message.to_leaf();
+
+    if let Some(envelope) = Envelope::parse(&nomad_message.body)? {
+        if envelope.is_expired(env.block.time.seconds()) {
+            LOOPBACK_MESSAGES.save(deps.storage, leaf.as_bytes(), &MessageStatus::Expired)?;
+            return Ok(Response::new().add_event(
+                Event::new("LoopbackExpired")
+                    .add_attribute("leaf", format!("{:?}", leaf))
+                    .add_attribute("expires_at", envelope.expires_at.to_string()),
+            ));
+        }
+        nomad_message.body = envelope.body;
+    }
+
+    LOOPBACK_MESSAGES.save(deps.storage, leaf.as_bytes(), &MessageStatus::Processed)?;
+
+    let mut processing = LOOPBACK_PROCESSING
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    processing.push(leaf);
+    LOOPBACK_PROCESSING.save(deps.storage, &processing)?;
+
+    let process_gas = query_process_gas(deps.as_ref())?.process_gas;
+    let recipient = h256_to_addr(deps.api, nomad_message.recipient)?;
+    let handle_msg: HandleExecuteMsg = nomad_message.into();
+    let wasm_msg = WasmMsg::Execute {
+        contract_addr: recipient.to_string(),
+        msg: to_binary(&handle_msg)?,
+        funds: info.funds,
+    };
+    let cosmos_msg = CosmosMsg::Wasm(wasm_msg);
+
+    let sub_msg = SubMsg {
+        id: LOOPBACK_ID,
+        msg: cosmos_msg,
+        gas_limit: Some(process_gas),
+        reply_on: ReplyOn::Always,
+    };
+
+    Ok(Response::new().add_submessage(sub_msg))
+}
+
+/// Fail home and slash `updaters` that signed the fraudulent update
+fn _fail(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    updaters: Vec<SignerId>,
+) -> Result<Response, nomad_base::ContractError> {
     nomad_base::_set_failed(deps.branch())?;
 
-    let slash_updater_msg = common::updater_manager::ExecuteMsg::SlashUpdater {
+    let slash_updater_msg = common::updater_manager::ExecuteMsg::SlashUpdaters {
         reporter: info.sender.to_string(),
+        updaters,
     };
     let wasm_msg = WasmMsg::Execute {
         contract_addr: query_updater_manager(deps.as_ref())?.updater_manager,
@@ -274,6 +491,7 @@ fn _fail(mut deps: DepsMut, info: MessageInfo) -> Result<Response, nomad_base::C
 pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
         SLASH_UPDATER_ID => reply_slash_updater(deps.as_ref(), env, msg),
+        LOOPBACK_ID => reply_loopback(deps, env, msg),
         _ => Err(ContractError::UnknownReplyMessage { id: msg.id }),
     }
 }
@@ -285,6 +503,81 @@ pub fn reply_slash_updater(_deps: Deps, _env: Env, msg: Reply) -> Result<Respons
     }
 }
 
+/// Records failed delivery of innermost in-flight loopback message.
+/// Recipient state changes are already reverted so message can be retried.
+pub fn reply_loopback(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let mut processing = LOOPBACK_PROCESSING
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let leaf = processing
+        .pop()
+        .ok_or(ContractError::NotProcessingLoopback {})?;
+    LOOPBACK_PROCESSING.save(deps.storage, &processing)?;
+
+    match msg.result {
+        ContractResult::Ok(_) => Ok(Response::new()),
+        ContractResult::Err(error) => {
+            LOOPBACK_MESSAGES.save(
+                deps.storage,
+                leaf.as_bytes(),
+                &MessageStatus::Failed(error.clone()),
+            )?;
+
+            Ok(Response::new().add_event(
+                Event::new("LoopbackFailed")
+                    .add_attribute("leaf", format!("{:?}", leaf))
+                    .add_attribute("error", error),
+            ))
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored_version = ensure_upgrade(deps.as_ref())?;
+
+    // Per-version storage migrations, oldest first
+    if stored_version < Version::new(0, 2, 0) {
+        // v0.2 moves root queue from single item to indexed storage
+        queue::migrate_legacy_queue(deps.branch())?;
+    }
+    if stored_version < Version::new(0, 3, 0) {
+        // v0.3 replaces single updater with threshold updater set
+        nomad_base::migrate_legacy_updater(deps.branch())?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_event(
+        Event::new("Migrate")
+            .add_attribute("from_version", stored_version.to_string())
+            .add_attribute("to_version", CONTRACT_VERSION),
+    ))
+}
+
+/// Checks that stored cw2 info belongs to this contract and that stored
+/// version is not newer than this code (no downgrades). Returns stored version.
+pub fn ensure_upgrade(deps: Deps) -> Result<Version, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::WrongContract {
+            expected: CONTRACT_NAME.to_owned(),
+            found: stored.contract,
+        });
+    }
+
+    let stored_version: Version = stored.version.parse()?;
+    let version: Version = CONTRACT_VERSION.parse()?;
+    if stored_version > version {
+        return Err(ContractError::CannotDowngrade {
+            stored: stored.version,
+            version: CONTRACT_VERSION.to_owned(),
+        });
+    }
+
+    Ok(stored_version)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -295,15 +588,34 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::CommittedRoot {} => to_binary(&nomad_base::query_committed_root(deps)?),
         QueryMsg::HomeDomainHash {} => to_binary(&nomad_base::query_home_domain_hash(deps)?),
         QueryMsg::LocalDomain {} => to_binary(&nomad_base::query_local_domain(deps)?),
-        QueryMsg::Updater {} => to_binary(&nomad_base::query_updater(deps)?),
+        QueryMsg::Updaters {} => to_binary(&nomad_base::query_updaters(deps)?),
         QueryMsg::Count {} => to_binary(&merkle::query_count(deps)?),
         QueryMsg::Root {} => to_binary(&merkle::query_root(deps)?),
-        // QueryMsg::Tree {} => to_binary(&merkle::query_tree(deps)?),
         QueryMsg::QueueContains { item } => to_binary(&queue::query_contains(deps, item)?),
         QueryMsg::QueueEnd {} => to_binary(&queue::query_last_item(deps)?),
         QueryMsg::QueueLength {} => to_binary(&queue::query_length(deps)?),
         QueryMsg::Owner {} => to_binary(&ownable::query_owner(deps)?),
         QueryMsg::MaxMessageBodyBytes {} => to_binary(&query_max_message_body_bytes()?),
+        QueryMsg::MessageIndexing {} => to_binary(&query_message_indexing(deps)?),
+        QueryMsg::MessagesByDestination {
+            destination,
+            start_after,
+            limit,
+        } => to_binary(&query_messages_by_destination(
+            deps,
+            destination,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::MessageByLeafIndex { leaf_index } => {
+            to_binary(&query_message_by_leaf_index(deps, leaf_index)?)
+        }
+        QueryMsg::LeafIndex { leaf } => to_binary(&query_leaf_index(deps, leaf)?),
+        QueryMsg::FullTreeStorage {} => to_binary(&merkle::query_full_tree_storage(deps)?),
+        QueryMsg::Proof { leaf_index } => to_binary(&merkle::query_proof(deps, leaf_index)?),
+        QueryMsg::IbcChannels {} => to_binary(&ibc::query_ibc_channels(deps)?),
+        QueryMsg::LoopbackStatus { leaf } => to_binary(&query_loopback_status(deps, leaf)?),
+        QueryMsg::ProcessGas {} => to_binary(&query_process_gas(deps)?),
     }
 }
 
@@ -333,19 +645,103 @@ pub fn query_max_message_body_bytes() -> StdResult<u64> {
     Ok(MAX_MESSAGE_BODY_BYTES)
 }
 
+pub fn query_message_indexing(deps: Deps) -> StdResult<MessageIndexingResponse> {
+    // Unset on homes migrated from before message indexing
+    let enabled = MESSAGE_INDEXING.may_load(deps.storage)?.unwrap_or(false);
+    Ok(MessageIndexingResponse { enabled })
+}
+
+pub fn query_messages_by_destination(
+    deps: Deps,
+    destination: u32,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<MessagesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    // Keys are destination in upper 32 bits, nonce in lower 32 bits
+    let start = match start_after {
+        Some(nonce) => Bound::exclusive(
+            destination_and_nonce(destination, nonce)
+                .to_be_bytes()
+                .to_vec(),
+        ),
+        None => Bound::inclusive(destination_and_nonce(destination, 0).to_be_bytes().to_vec()),
+    };
+    let end = Bound::inclusive(
+        destination_and_nonce(destination, u32::MAX - 1)
+            .to_be_bytes()
+            .to_vec(),
+    );
+
+    let messages = DESTINATION_NONCE_LEAVES
+        .range(deps.storage, Some(start), Some(end), Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (_, leaf) = item?;
+            _load_dispatched_message(deps, leaf)
+        })
+        .collect::<StdResult<Vec<DispatchedMessage>>>()?;
+
+    Ok(MessagesResponse { messages })
+}
+
+pub fn query_message_by_leaf_index(deps: Deps, leaf_index: u64) -> StdResult<MessageResponse> {
+    let message = match LEAF_INDEX_LEAVES.may_load(deps.storage, leaf_index)? {
+        Some(leaf) => Some(_load_dispatched_message(deps, leaf)?),
+        None => None,
+    };
+    Ok(MessageResponse { message })
+}
+
+pub fn query_leaf_index(deps: Deps, leaf: H256) -> StdResult<LeafIndexResponse> {
+    let leaf_index = MESSAGES
+        .may_load(deps.storage, leaf.as_bytes())?
+        .map(|stored| stored.leaf_index);
+    Ok(LeafIndexResponse { leaf_index })
+}
+
+pub fn query_loopback_status(deps: Deps, leaf: H256) -> StdResult<LoopbackStatusResponse> {
+    let status = LOOPBACK_MESSAGES
+        .may_load(deps.storage, leaf.as_bytes())?
+        .unwrap_or_default();
+    Ok(LoopbackStatusResponse { status })
+}
+
+pub fn query_process_gas(deps: Deps) -> StdResult<ProcessGasResponse> {
+    let process_gas = PROCESS_GAS
+        .may_load(deps.storage)?
+        .unwrap_or(DEFAULT_PROCESS_GAS);
+    Ok(ProcessGasResponse { process_gas })
+}
+
+fn _load_dispatched_message(deps: Deps, leaf: H256) -> StdResult<DispatchedMessage> {
+    let stored = MESSAGES.load(deps.storage, leaf.as_bytes())?;
+    Ok(DispatchedMessage {
+        leaf,
+        leaf_index: stored.leaf_index,
+        message: stored.message,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use common::merkle::RootResponse;
-    use common::merkle_tree::INITIAL_ROOT;
+    use common::merkle_tree::{Proof, INITIAL_ROOT};
     use common::nomad_base::{
-        CommittedRootResponse, LocalDomainResponse, StateResponse, UpdaterResponse,
+        CommittedRootResponse, LocalDomainResponse, StateResponse, UpdatersResponse,
+    };
+    use common::queue::{
+        ContainsResponse, EndResponse as QueueEndResponse, LengthResponse as QueueLengthResponse,
     };
-    use common::queue::{EndResponse as QueueEndResponse, LengthResponse as QueueLengthResponse};
     use common::{h256_to_string, States};
-    use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+    use cosmwasm_std::testing::{mock_env, mock_info};
     use cosmwasm_std::{coins, from_binary};
-    use test_utils::{event_attr_value_by_key, Updater};
+    use nomad_base::state::{LEGACY_UPDATER, THRESHOLD, UPDATERS};
+    use queue::state::LEGACY_QUEUE;
+    use std::collections::VecDeque;
+    use test_utils::{event_attr_value_by_key, mock_padded_dependencies, Updater};
 
     const LOCAL_DOMAIN: u32 = 1000;
     const UPDATER_PRIVKEY: &str =
@@ -355,11 +751,12 @@ mod tests {
     fn proper_initialization() {
         let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
 
-        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
 
         let msg = InstantiateMsg {
             local_domain: LOCAL_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -395,9 +792,10 @@ mod tests {
         assert_eq!(LOCAL_DOMAIN, value.local_domain);
 
         // Updater
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::Updater {}).unwrap();
-        let value: UpdaterResponse = from_binary(&res).unwrap();
-        assert_eq!(updater.address(), value.updater);
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Updaters {}).unwrap();
+        let value: UpdatersResponse = from_binary(&res).unwrap();
+        assert_eq!(vec![updater.signer_id()], value.updaters);
+        assert_eq!(1, value.threshold);
 
         // ------ MERKLE ------
         // Initial root valid
@@ -421,11 +819,12 @@ mod tests {
     fn does_not_dispatch_messages_too_large() {
         let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
 
-        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
 
         let msg = InstantiateMsg {
             local_domain: LOCAL_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -436,7 +835,7 @@ mod tests {
         let info = mock_info("dispatcher", &coins(100, "earth"));
         let msg = ExecuteMsg::Dispatch {
             destination: 2000,
-            recipient: "recipient".to_owned(),
+            recipient: H256::repeat_byte(1),
             message_body: [0u8].repeat(3000),
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg);
@@ -447,11 +846,12 @@ mod tests {
     fn dispatches_message() {
         let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
 
-        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
 
         let msg = InstantiateMsg {
             local_domain: LOCAL_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -478,7 +878,7 @@ mod tests {
         let info = mock_info(&h256_to_string(sender), &coins(100, "earth"));
         let msg = ExecuteMsg::Dispatch {
             destination,
-            recipient: h256_to_string(recipient),
+            recipient,
             message_body,
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -507,15 +907,364 @@ mod tests {
         );
     }
 
+    #[test]
+    fn delivers_local_messages_by_loopback() {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+        };
+        let info = mock_info("owner", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let sender = Addr::unchecked("sender");
+        let recipient = Addr::unchecked("recipient");
+        let nomad_message = NomadMessage {
+            origin: LOCAL_DOMAIN,
+            sender: addr_to_h256(&deps.api, &sender).unwrap(),
+            nonce: 0,
+            destination: LOCAL_DOMAIN,
+            recipient: addr_to_h256(&deps.api, &recipient).unwrap(),
+            body: vec![1, 2, 3],
+        };
+        let leaf = nomad_message.to_leaf();
+
+        // Dispatch to local domain delivers immediately
+        let msg = ExecuteMsg::Dispatch {
+            destination: LOCAL_DOMAIN,
+            recipient: addr_to_h256(&deps.api, &recipient).unwrap(),
+            message_body: vec![1, 2, 3],
+        };
+        let info = mock_info(sender.as_str(), &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let sub_msg = &res.messages[0];
+        assert_eq!(LOOPBACK_ID, sub_msg.id);
+        assert_eq!(ReplyOn::Always, sub_msg.reply_on);
+        match &sub_msg.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => {
+                assert_eq!(recipient.as_str(), contract_addr);
+                let handle_msg: HandleExecuteMsg = from_binary(msg).unwrap();
+                assert_eq!(HandleExecuteMsg::from(nomad_message.clone()), handle_msg);
+            }
+            _ => panic!("Expected wasm execute message"),
+        }
+
+        let event = &res.events[0];
+        assert_eq!("LoopbackDispatch", event.ty);
+        assert_eq!(
+            format!("{:?}", leaf),
+            event_attr_value_by_key(&event, "message_hash").unwrap()
+        );
+
+        // Nonce consumed but nothing committed to tree
+        assert_eq!(
+            1,
+            query_nonces(deps.as_ref(), LOCAL_DOMAIN)
+                .unwrap()
+                .next_nonce
+        );
+        assert_eq!(0, merkle::query_count(deps.as_ref()).unwrap().count);
+        assert_eq!(
+            MessageStatus::Processed,
+            query_loopback_status(deps.as_ref(), leaf).unwrap().status
+        );
+
+        // Cannot retry message that has not failed
+        let retry_msg = ExecuteMsg::RetryLoopback {
+            message: nomad_message.to_vec(),
+        };
+        let info = mock_info("processor", &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, retry_msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::LoopbackNotFailed { .. }));
+
+        // Recipient errors
+        let reply_msg = Reply {
+            id: LOOPBACK_ID,
+            result: ContractResult::Err("recipient error".to_owned()),
+        };
+        let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+        assert_eq!("LoopbackFailed", res.events[0].ty);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::LoopbackStatus { leaf }).unwrap();
+        let value: LoopbackStatusResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            MessageStatus::Failed("recipient error".to_owned()),
+            value.status
+        );
+
+        // Retry redelivers message
+        let info = mock_info("processor", &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, retry_msg).unwrap();
+        assert_eq!(LOOPBACK_ID, res.messages[0].id);
+        assert_eq!(
+            MessageStatus::Processed,
+            query_loopback_status(deps.as_ref(), leaf).unwrap().status
+        );
+    }
+
+    #[test]
+    fn limits_loopback_delivery_to_process_gas() {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+        };
+        let info = mock_info("owner", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // Default process gas
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ProcessGas {}).unwrap();
+        let value: ProcessGasResponse = from_binary(&res).unwrap();
+        assert_eq!(DEFAULT_PROCESS_GAS, value.process_gas);
+
+        // Only owner can set process gas
+        let msg = ExecuteMsg::SetProcessGas {
+            process_gas: 500_000,
+        };
+        let info = mock_info("not_owner", &[]);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg.clone()).is_err());
+
+        let info = mock_info("owner", &[]);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let sender = Addr::unchecked("sender");
+        let recipient = addr_to_h256(&deps.api, &Addr::unchecked("recipient")).unwrap();
+        let nomad_message = NomadMessage {
+            origin: LOCAL_DOMAIN,
+            sender: addr_to_h256(&deps.api, &sender).unwrap(),
+            nonce: 0,
+            destination: LOCAL_DOMAIN,
+            recipient,
+            body: vec![1, 2, 3],
+        };
+        let leaf = nomad_message.to_leaf();
+
+        let msg = ExecuteMsg::Dispatch {
+            destination: LOCAL_DOMAIN,
+            recipient,
+            message_body: vec![1, 2, 3],
+        };
+        let info = mock_info(sender.as_str(), &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(Some(500_000), res.messages[0].gas_limit);
+
+        // Recipient runs out of gas
+        let reply_msg = Reply {
+            id: LOOPBACK_ID,
+            result: ContractResult::Err("out of gas".to_owned()),
+        };
+        let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+        let event = &res.events[0];
+        assert_eq!("LoopbackFailed", event.ty);
+        assert_eq!(
+            "out of gas",
+            event_attr_value_by_key(&event, "error").unwrap()
+        );
+        assert_eq!(
+            MessageStatus::Failed("out of gas".to_owned()),
+            query_loopback_status(deps.as_ref(), leaf).unwrap().status
+        );
+
+        // Retry redelivers message with same limit
+        let retry_msg = ExecuteMsg::RetryLoopback {
+            message: nomad_message.to_vec(),
+        };
+        let info = mock_info("processor", &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, retry_msg).unwrap();
+        assert_eq!(Some(500_000), res.messages[0].gas_limit);
+        assert_eq!(
+            MessageStatus::Processed,
+            query_loopback_status(deps.as_ref(), leaf).unwrap().status
+        );
+    }
+
+    #[test]
+    fn indexes_dispatched_messages() {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+        };
+        let info = mock_info("owner", &coins(100, "earth"));
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let sender = H256::repeat_byte(0);
+        let recipient = H256::repeat_byte(1);
+        let dispatch = |deps: DepsMut, destination: u32| {
+            let info = mock_info(&h256_to_string(sender), &coins(100, "earth"));
+            let msg = ExecuteMsg::Dispatch {
+                destination,
+                recipient,
+                message_body: destination.to_be_bytes().to_vec(),
+            };
+            execute(deps, mock_env(), info, msg).unwrap();
+        };
+
+        // Messages dispatched while indexing disabled are not stored
+        dispatch(deps.as_mut(), 2000);
+        assert!(!query_message_indexing(deps.as_ref()).unwrap().enabled);
+        assert_eq!(
+            None,
+            query_message_by_leaf_index(deps.as_ref(), 0)
+                .unwrap()
+                .message
+        );
+
+        // Only owner can enable indexing
+        let msg = ExecuteMsg::SetMessageIndexing { enabled: true };
+        let info = mock_info("not_owner", &[]);
+        execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+
+        let info = mock_info("owner", &[]);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert!(query_message_indexing(deps.as_ref()).unwrap().enabled);
+
+        // Leaf indices 1..=3 go to 2000, leaf index 4 goes to 3000
+        dispatch(deps.as_mut(), 2000);
+        dispatch(deps.as_mut(), 2000);
+        dispatch(deps.as_mut(), 2000);
+        dispatch(deps.as_mut(), 3000);
+
+        let messages = query_messages_by_destination(deps.as_ref(), 2000, None, Some(2))
+            .unwrap()
+            .messages;
+        assert_eq!(
+            vec![1, 2],
+            messages.iter().map(|m| m.leaf_index).collect::<Vec<_>>()
+        );
+
+        // Paginate from nonce 2 (leaf index 2)
+        let messages = query_messages_by_destination(deps.as_ref(), 2000, Some(2), None)
+            .unwrap()
+            .messages;
+        assert_eq!(1, messages.len());
+        assert_eq!(3, messages[0].leaf_index);
+
+        let expected = NomadMessage {
+            origin: LOCAL_DOMAIN,
+            sender,
+            nonce: 0,
+            destination: 3000,
+            recipient,
+            body: 3000u32.to_be_bytes().to_vec(),
+        };
+        let messages = query_messages_by_destination(deps.as_ref(), 3000, None, None)
+            .unwrap()
+            .messages;
+        assert_eq!(
+            vec![DispatchedMessage {
+                leaf: expected.to_leaf(),
+                leaf_index: 4,
+                message: expected.to_vec(),
+            }],
+            messages
+        );
+
+        let by_index = query_message_by_leaf_index(deps.as_ref(), 4)
+            .unwrap()
+            .message
+            .unwrap();
+        assert_eq!(expected.to_leaf(), by_index.leaf);
+
+        assert_eq!(
+            Some(4),
+            query_leaf_index(deps.as_ref(), expected.to_leaf())
+                .unwrap()
+                .leaf_index
+        );
+        assert_eq!(
+            None,
+            query_leaf_index(deps.as_ref(), H256::repeat_byte(9))
+                .unwrap()
+                .leaf_index
+        );
+    }
+
+    #[test]
+    fn serves_proofs_of_dispatched_messages() {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+        };
+        let info = mock_info("owner", &coins(100, "earth"));
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // Only owner can enable full tree storage
+        let msg = ExecuteMsg::SetFullTreeStorage { enabled: true };
+        let info = mock_info("not_owner", &[]);
+        execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+
+        let info = mock_info("owner", &[]);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let sender = H256::repeat_byte(0);
+        let mut leaves = vec![];
+        for i in 0..4u8 {
+            let info = mock_info(&h256_to_string(sender), &[]);
+            let msg = ExecuteMsg::Dispatch {
+                destination: 2000,
+                recipient: H256::repeat_byte(1),
+                message_body: vec![i],
+            };
+            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+            let nomad_message = NomadMessage {
+                origin: LOCAL_DOMAIN,
+                sender,
+                nonce: i as u32,
+                destination: 2000,
+                recipient: H256::repeat_byte(1),
+                body: vec![i],
+            };
+            leaves.push(nomad_message.to_leaf());
+        }
+
+        let root = merkle::query_root(deps.as_ref()).unwrap().root;
+        for (leaf_index, leaf) in leaves.into_iter().enumerate() {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Proof {
+                    leaf_index: leaf_index as u64,
+                },
+            )
+            .unwrap();
+            let proof: Proof = from_binary(&res).unwrap();
+            assert_eq!(leaf, proof.leaf);
+            assert_eq!(root, proof.root());
+        }
+    }
+
     #[test]
     fn suggests_updates() {
         let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
 
-        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
 
         let msg = InstantiateMsg {
             local_domain: LOCAL_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -532,7 +1281,7 @@ mod tests {
         let info = mock_info("dispatcher", &coins(100, "earth"));
         let msg = ExecuteMsg::Dispatch {
             destination: 2000,
-            recipient: "recipient".to_owned(),
+            recipient: H256::repeat_byte(1),
             message_body: [0u8].repeat(100),
         };
         let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -553,11 +1302,12 @@ mod tests {
     fn suggests_zero_update_values_on_empty_queue() {
         let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
 
-        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
 
         let msg = InstantiateMsg {
             local_domain: LOCAL_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -580,11 +1330,12 @@ mod tests {
     async fn accepts_valid_update() {
         let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
 
-        let mut deps = mock_dependencies_with_balance(&coins(100, "token"));
+        let mut deps = mock_padded_dependencies(&coins(100, "token"));
 
         let init_msg = InstantiateMsg {
             local_domain: LOCAL_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -595,7 +1346,7 @@ mod tests {
         let info = mock_info("dispatcher", &coins(100, "earth"));
         let msg = ExecuteMsg::Dispatch {
             destination: 2000,
-            recipient: "recipient".to_owned(),
+            recipient: H256::repeat_byte(1),
             message_body: [0u8].repeat(100),
         };
         let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -613,7 +1364,7 @@ mod tests {
         let msg = ExecuteMsg::Update {
             committed_root,
             new_root,
-            signature: update.signature.to_vec(),
+            signatures: vec![update.signature.to_vec()],
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -629,8 +1380,8 @@ mod tests {
             event_attr_value_by_key(&event, "new_root").unwrap()
         );
         assert_eq!(
-            format!("{:?}", update.signature.to_vec()),
-            event_attr_value_by_key(&event, "signature").unwrap()
+            format!("{:?}", vec![update.signature.to_vec()]),
+            event_attr_value_by_key(&event, "signatures").unwrap()
         );
 
         // Expect queue is empty
@@ -643,11 +1394,12 @@ mod tests {
     async fn batch_accepts_updates() {
         let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
 
-        let mut deps = mock_dependencies_with_balance(&coins(100, "token"));
+        let mut deps = mock_padded_dependencies(&coins(100, "token"));
 
         let init_msg = InstantiateMsg {
             local_domain: LOCAL_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -659,7 +1411,7 @@ mod tests {
         for i in 1..3 {
             let msg = ExecuteMsg::Dispatch {
                 destination: i * 1000,
-                recipient: "recipient".to_owned(),
+                recipient: H256::repeat_byte(1),
                 message_body: [i as u8].repeat(100),
             };
 
@@ -679,7 +1431,7 @@ mod tests {
         let msg = ExecuteMsg::Update {
             committed_root,
             new_root,
-            signature: update.signature.to_vec(),
+            signatures: vec![update.signature.to_vec()],
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -695,8 +1447,8 @@ mod tests {
             event_attr_value_by_key(&event, "new_root").unwrap()
         );
         assert_eq!(
-            format!("{:?}", update.signature.to_vec()),
-            event_attr_value_by_key(&event, "signature").unwrap()
+            format!("{:?}", vec![update.signature.to_vec()]),
+            event_attr_value_by_key(&event, "signatures").unwrap()
         );
 
         // Expect queue is empty
@@ -709,11 +1461,12 @@ mod tests {
     async fn rejects_update_not_building_off_current_committed() {
         let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
 
-        let mut deps = mock_dependencies_with_balance(&coins(100, "token"));
+        let mut deps = mock_padded_dependencies(&coins(100, "token"));
 
         let init_msg = InstantiateMsg {
             local_domain: LOCAL_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -725,7 +1478,7 @@ mod tests {
         for i in 1..3 {
             let msg = ExecuteMsg::Dispatch {
                 destination: i * 1000,
-                recipient: "recipient".to_owned(),
+                recipient: H256::repeat_byte(1),
                 message_body: [i as u8].repeat(100),
             };
 
@@ -747,7 +1500,7 @@ mod tests {
         let msg = ExecuteMsg::Update {
             committed_root: random_root,
             new_root,
-            signature: update.signature.to_vec(),
+            signatures: vec![update.signature.to_vec()],
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg);
 
@@ -758,11 +1511,12 @@ mod tests {
     async fn catches_improper_update() {
         let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
 
-        let mut deps = mock_dependencies_with_balance(&coins(100, "token"));
+        let mut deps = mock_padded_dependencies(&coins(100, "token"));
 
         let init_msg = InstantiateMsg {
             local_domain: LOCAL_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -774,7 +1528,7 @@ mod tests {
         for i in 1..3 {
             let msg = ExecuteMsg::Dispatch {
                 destination: i * 1000,
-                recipient: "recipient".to_owned(),
+                recipient: H256::repeat_byte(1),
                 message_body: [i as u8].repeat(100),
             };
 
@@ -799,7 +1553,7 @@ mod tests {
         let msg = ExecuteMsg::Update {
             committed_root,
             new_root: improper_root,
-            signature: update.signature.to_vec(),
+            signatures: vec![update.signature.to_vec()],
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -820,11 +1574,12 @@ mod tests {
             "2111111111111111111111111111111111111111111111111111111111111111";
         let not_updater: Updater = Updater::from_privkey(not_updater_privkey, LOCAL_DOMAIN);
 
-        let mut deps = mock_dependencies_with_balance(&coins(100, "token"));
+        let mut deps = mock_padded_dependencies(&coins(100, "token"));
 
         let init_msg = InstantiateMsg {
             local_domain: LOCAL_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -836,7 +1591,7 @@ mod tests {
         for i in 1..3 {
             let msg = ExecuteMsg::Dispatch {
                 destination: i * 1000,
-                recipient: "recipient".to_owned(),
+                recipient: H256::repeat_byte(1),
                 message_body: [i as u8].repeat(100),
             };
 
@@ -859,7 +1614,7 @@ mod tests {
         let msg = ExecuteMsg::Update {
             committed_root,
             new_root,
-            signature: update.signature.to_vec(),
+            signatures: vec![update.signature.to_vec()],
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg);
         assert!(res.is_err());
@@ -878,11 +1633,12 @@ mod tests {
     async fn failed_on_valid_double_update() {
         let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
 
-        let mut deps = mock_dependencies_with_balance(&coins(100, "token"));
+        let mut deps = mock_padded_dependencies(&coins(100, "token"));
 
         let init_msg = InstantiateMsg {
             local_domain: LOCAL_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -894,7 +1650,7 @@ mod tests {
         for i in 1..3 {
             let msg = ExecuteMsg::Dispatch {
                 destination: i * 1000,
-                recipient: "recipient".to_owned(),
+                recipient: H256::repeat_byte(1),
                 message_body: [i as u8].repeat(100),
             };
 
@@ -917,8 +1673,8 @@ mod tests {
         let msg = ExecuteMsg::DoubleUpdate {
             old_root: committed_root,
             new_roots: [new_root, bad_root],
-            signature: update.signature.to_vec(),
-            signature_2: bad_update.signature.to_vec(),
+            signatures: vec![update.signature.to_vec()],
+            signatures_2: vec![bad_update.signature.to_vec()],
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -934,12 +1690,12 @@ mod tests {
             event_attr_value_by_key(&event, "new_roots").unwrap()
         );
         assert_eq!(
-            format!("{:?}", update.signature.to_vec()),
-            event_attr_value_by_key(&event, "signature").unwrap()
+            format!("{:?}", vec![update.signature.to_vec()]),
+            event_attr_value_by_key(&event, "signatures").unwrap()
         );
         assert_eq!(
-            format!("{:?}", bad_update.signature.to_vec()),
-            event_attr_value_by_key(&event, "signature_2").unwrap()
+            format!("{:?}", vec![bad_update.signature.to_vec()]),
+            event_attr_value_by_key(&event, "signatures_2").unwrap()
         );
 
         // Check home failed
@@ -952,11 +1708,12 @@ mod tests {
     fn only_owner_sets_updater_manager() {
         let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
 
-        let mut deps = mock_dependencies_with_balance(&coins(100, "token"));
+        let mut deps = mock_padded_dependencies(&coins(100, "token"));
 
         let init_msg = InstantiateMsg {
             local_domain: LOCAL_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -982,4 +1739,99 @@ mod tests {
             .updater_manager;
         assert_eq!("new_updater_manager".to_owned(), updater_manager);
     }
+
+    #[test]
+    fn only_updater_manager_sets_updaters() {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+
+        let mut deps = mock_padded_dependencies(&coins(100, "token"));
+
+        let init_msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+        };
+        let info = mock_info("owner", &coins(100, "earth"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+        let msg = ExecuteMsg::SetUpdaterManager {
+            updater_manager: "updater_manager".to_owned(),
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let updaters = vec![
+            updater.signer_id(),
+            SignerId::Ed25519PubKey(vec![2u8; 32].into()),
+        ];
+        let msg = ExecuteMsg::SetUpdaters {
+            updaters: updaters.clone(),
+            threshold: 2,
+        };
+
+        // Owner cannot set updaters directly
+        let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+        assert!(res.is_err());
+
+        let info = mock_info("updater_manager", &[]);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Updaters {}).unwrap();
+        let value: UpdatersResponse = from_binary(&res).unwrap();
+        assert_eq!(updaters, value.updaters);
+        assert_eq!(2, value.threshold);
+    }
+
+    #[test]
+    fn migrates_legacy_queue() {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+
+        let mut deps = mock_padded_dependencies(&coins(100, "token"));
+
+        let init_msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+        };
+        let info = mock_info("owner", &coins(100, "earth"));
+        instantiate(deps.as_mut(), mock_env(), info, init_msg).unwrap();
+
+        // Simulate v0.1.0 storage with roots in single item queue and single
+        // updater
+        let roots: VecDeque<H256> = (1..=3).map(H256::repeat_byte).collect();
+        LEGACY_QUEUE.save(&mut deps.storage, &roots).unwrap();
+        UPDATERS.remove(&mut deps.storage);
+        THRESHOLD.remove(&mut deps.storage);
+        LEGACY_UPDATER
+            .save(&mut deps.storage, &updater.address())
+            .unwrap();
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert!(LEGACY_QUEUE.may_load(&deps.storage).unwrap().is_none());
+        assert!(LEGACY_UPDATER.may_load(&deps.storage).unwrap().is_none());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Updaters {}).unwrap();
+        let value: UpdatersResponse = from_binary(&res).unwrap();
+        assert_eq!(vec![updater.signer_id()], value.updaters);
+        assert_eq!(1, value.threshold);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::QueueLength {}).unwrap();
+        let value: QueueLengthResponse = from_binary(&res).unwrap();
+        assert_eq!(3, value.length);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::QueueEnd {}).unwrap();
+        let value: QueueEndResponse = from_binary(&res).unwrap();
+        assert_eq!(H256::repeat_byte(3), value.item);
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::QueueContains {
+                item: H256::repeat_byte(2),
+            },
+        )
+        .unwrap();
+        let value: ContainsResponse = from_binary(&res).unwrap();
+        assert!(value.contains);
+    }
 }
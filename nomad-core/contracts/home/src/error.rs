@@ -7,6 +7,15 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("Cannot migrate from contract {found}, expected {expected}")]
+    WrongContract { expected: String, found: String },
+
+    #[error("Cannot downgrade from version {stored} to {version}")]
+    CannotDowngrade { stored: String, version: String },
+
+    #[error("{0}")]
+    SemVer(#[from] semver::Error),
+
     #[error("Message length {length} too long")]
     MsgTooLong { length: u64 },
 
@@ -25,6 +34,45 @@ pub enum ContractError {
     #[error("Failed to call slash updater on updater manager: {0}")]
     FailedSlashUpdaterCall(String),
 
+    #[error("Home does not receive IBC packets")]
+    PacketNotAccepted {},
+
+    #[error("IBC channel {channel_id} not connected")]
+    ChannelNotConnected { channel_id: String },
+
+    #[error("Counterparty port {port_id} on {connection_id} not allowed")]
+    CounterpartyNotAllowed {
+        connection_id: String,
+        port_id: String,
+    },
+
+    #[error("Counterparty port {port_id} on {connection_id} already connected")]
+    CounterpartyAlreadyConnected {
+        connection_id: String,
+        port_id: String,
+    },
+
+    #[error("Malformed message")]
+    MalformedMessage {},
+
+    #[error("Loopback message with leaf {leaf} has not failed")]
+    LoopbackNotFailed { leaf: H256 },
+
+    #[error("No loopback message processing")]
+    NotProcessingLoopback {},
+
+    #[error("Message not in committed root: {committed_root}")]
+    MessageNotCommitted { committed_root: H256 },
+
+    #[error("{0}")]
+    IbcChannelError(#[from] common::IbcChannelError),
+
+    #[error("{0}")]
+    AddressError(#[from] common::AddressError),
+
+    #[error("{0}")]
+    NomadError(#[from] common::NomadError),
+
     #[error("{0}")]
     OwnableError(#[from] ownable::ContractError),
 
@@ -0,0 +1,506 @@
+use common::home::IbcChannelsResponse;
+use common::{merkle_tree, Decode, NomadAck, NomadMessage, NomadPacket};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Deps, DepsMut, Env, Event, IbcBasicResponse, IbcChannel,
+    IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg, IbcMsg, IbcPacketAckMsg,
+    IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse, IbcTimeout, MessageInfo, Order,
+    Response, StdResult,
+};
+use ethers_core::types::H256;
+
+use crate::error::ContractError;
+use crate::state::{IBC_CHANNELS, IBC_COUNTERPARTIES};
+
+/// Seconds before an unrelayed packet times out. Timeouts close ordered
+/// channels, so this is generous
+pub const PACKET_LIFETIME_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_open(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<(), ContractError> {
+    common::check_nomad_channel(msg.channel(), msg.counterparty_version())?;
+    only_free_counterparty(deps.as_ref(), msg.channel())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_connect(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel = msg.channel();
+    common::check_nomad_channel(channel, msg.counterparty_version())?;
+    only_free_counterparty(deps.as_ref(), channel)?;
+
+    let channel_id = channel.endpoint.channel_id.clone();
+    let connection_id = channel.connection_id.as_str();
+    let port_id = channel.counterparty_endpoint.port_id.as_str();
+    IBC_COUNTERPARTIES.save(
+        deps.storage,
+        (connection_id, port_id),
+        &Some(channel_id.clone()),
+    )?;
+    IBC_CHANNELS.save(
+        deps.storage,
+        &channel_id,
+        &(connection_id.to_owned(), port_id.to_owned()),
+    )?;
+
+    Ok(IbcBasicResponse::new()
+        .add_event(Event::new("IbcChannelConnect").add_attribute("channel_id", channel_id)))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_close(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel_id = msg.channel().endpoint.channel_id.clone();
+    _disconnect_channel(deps, &channel_id)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_event(Event::new("IbcChannelClose").add_attribute("channel_id", channel_id)))
+}
+
+/// Checks that channel's counterparty was allowed by owner and has no
+/// channel yet. Bounds the channels every update is sent over.
+fn only_free_counterparty(deps: Deps, channel: &IbcChannel) -> Result<(), ContractError> {
+    let connection_id = channel.connection_id.clone();
+    let port_id = channel.counterparty_endpoint.port_id.clone();
+    match IBC_COUNTERPARTIES.may_load(deps.storage, (&connection_id, &port_id))? {
+        None => Err(ContractError::CounterpartyNotAllowed {
+            connection_id,
+            port_id,
+        }),
+        Some(Some(connected)) if connected != channel.endpoint.channel_id => {
+            Err(ContractError::CounterpartyAlreadyConnected {
+                connection_id,
+                port_id,
+            })
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+/// Forget connected channel and free its counterparty if still allowed
+fn _disconnect_channel(deps: DepsMut, channel_id: &str) -> StdResult<()> {
+    if let Some((connection_id, port_id)) = IBC_CHANNELS.may_load(deps.storage, channel_id)? {
+        IBC_CHANNELS.remove(deps.storage, channel_id);
+        let key = (connection_id.as_str(), port_id.as_str());
+        if IBC_COUNTERPARTIES.may_load(deps.storage, key)?.is_some() {
+            IBC_COUNTERPARTIES.save(deps.storage, key, &None)?;
+        }
+    }
+    Ok(())
+}
+
+/// Allow or disallow counterparty to connect. Disallowing stops updates over
+/// its connected channel.
+pub fn execute_set_ibc_counterparty(
+    deps: DepsMut,
+    info: MessageInfo,
+    connection_id: String,
+    port_id: String,
+    allowed: bool,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+
+    let key = (connection_id.as_str(), port_id.as_str());
+    let connected = IBC_COUNTERPARTIES.may_load(deps.storage, key)?;
+    match (allowed, connected) {
+        (true, None) => IBC_COUNTERPARTIES.save(deps.storage, key, &None)?,
+        (false, Some(channel_id)) => {
+            IBC_COUNTERPARTIES.remove(deps.storage, key);
+            if let Some(channel_id) = channel_id {
+                IBC_CHANNELS.remove(deps.storage, &channel_id);
+            }
+        }
+        _ => {}
+    }
+
+    Ok(Response::new().add_event(
+        Event::new("SetIbcCounterparty")
+            .add_attribute("connection_id", connection_id)
+            .add_attribute("port_id", port_id)
+            .add_attribute("allowed", allowed.to_string()),
+    ))
+}
+
+/// Home only sends packets. Anything received is acknowledged with an error
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_receive(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, ContractError> {
+    Ok(IbcReceiveResponse::new().set_ack(NomadAck::error(ContractError::PacketNotAccepted {})?))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_ack(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let packet = &msg.original_packet;
+    let event = Event::new("IbcPacketAck")
+        .add_attribute("channel_id", packet.src.channel_id.clone())
+        .add_attribute("sequence", packet.sequence.to_string());
+
+    // Replica rejections (e.g. root not yet acceptable) are surfaced for
+    // relayers to resend
+    let event = match from_binary(&msg.acknowledgement.data)? {
+        NomadAck::Result(_) => event.add_attribute("success", "true"),
+        NomadAck::Error(error) => event
+            .add_attribute("success", "false")
+            .add_attribute("error", error),
+    };
+    Ok(IbcBasicResponse::new().add_event(event))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_timeout(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Ok(IbcBasicResponse::new().add_event(
+        Event::new("IbcPacketTimeout")
+            .add_attribute("channel_id", msg.packet.src.channel_id.clone())
+            .add_attribute("sequence", msg.packet.sequence.to_string()),
+    ))
+}
+
+/// Send `message` to replica on `channel_id` if `proof` shows it is in the
+/// committed root
+pub fn execute_relay_message(
+    deps: DepsMut,
+    env: Env,
+    channel_id: String,
+    message: Vec<u8>,
+    proof: [H256; 32],
+    index: u64,
+) -> Result<Response, ContractError> {
+    nomad_base::not_failed(deps.as_ref())?;
+
+    if !IBC_CHANNELS.has(deps.storage, &channel_id) {
+        return Err(ContractError::ChannelNotConnected { channel_id });
+    }
+
+    let leaf = NomadMessage::read_from(&mut message.as_slice())
+        .map_err(|_| ContractError::MalformedMessage {})?
+        .to_leaf();
+    let calculated_root = merkle_tree::merkle_root_from_branch(
+        leaf,
+        &proof[..],
+        merkle_tree::TREE_DEPTH,
+        index as usize,
+    );
+    let committed_root = nomad_base::query_committed_root(deps.as_ref())?.committed_root;
+    if calculated_root != committed_root {
+        return Err(ContractError::MessageNotCommitted { committed_root });
+    }
+
+    let packet = NomadPacket::Message {
+        message,
+        proof,
+        index,
+    };
+    Ok(Response::new()
+        .add_message(send_packet_msg(&env, channel_id.clone(), &packet)?)
+        .add_event(
+            Event::new("RelayMessage")
+                .add_attribute("channel_id", channel_id)
+                .add_attribute("leaf", format!("{:?}", leaf))
+                .add_attribute("index", index.to_string()),
+        ))
+}
+
+pub fn send_packet_msg(env: &Env, channel_id: String, packet: &NomadPacket) -> StdResult<IbcMsg> {
+    Ok(IbcMsg::SendPacket {
+        channel_id,
+        data: to_binary(packet)?,
+        timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(PACKET_LIFETIME_SECONDS)),
+    })
+}
+
+pub fn query_ibc_channels(deps: Deps) -> StdResult<IbcChannelsResponse> {
+    let channels = IBC_CHANNELS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|key| Ok(String::from_utf8(key)?))
+        .collect::<StdResult<Vec<String>>>()?;
+    Ok(IbcChannelsResponse { channels })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract::{execute, instantiate, query};
+    use common::home::{ExecuteMsg, InstantiateMsg, QueryMsg, SuggestUpdateResponse};
+    use common::merkle_tree::Proof;
+    use common::{h256_to_string, Encode, IbcChannelError, NOMAD_IBC_VERSION};
+    use cosmwasm_std::testing::{
+        mock_env, mock_ibc_channel_close_init, mock_ibc_channel_connect_ack,
+        mock_ibc_channel_open_init, mock_ibc_channel_open_try, mock_ibc_packet_ack,
+        mock_ibc_packet_recv, mock_info,
+    };
+    use cosmwasm_std::{coins, CosmosMsg, IbcAcknowledgement, IbcOrder, SubMsg};
+    use test_utils::{event_attr_value_by_key, mock_padded_dependencies, Updater};
+
+    const LOCAL_DOMAIN: u32 = 1000;
+    const UPDATER_PRIVKEY: &str =
+        "1111111111111111111111111111111111111111111111111111111111111111";
+    const CHANNEL_ID: &str = "channel-0";
+
+    fn sent_packet(msg: &SubMsg) -> (String, NomadPacket) {
+        match &msg.msg {
+            CosmosMsg::Ibc(IbcMsg::SendPacket {
+                channel_id, data, ..
+            }) => (channel_id.clone(), from_binary(data).unwrap()),
+            msg => panic!("Unexpected message {:?}", msg),
+        }
+    }
+
+    /// Allow counterparty of `mock_ibc_channel` to connect
+    fn allow_mock_counterparty(deps: DepsMut, allowed: bool) {
+        let msg = ExecuteMsg::SetIbcCounterparty {
+            connection_id: "connection-2".to_owned(),
+            port_id: "their_port".to_owned(),
+            allowed,
+        };
+        execute(deps, mock_env(), mock_info("owner", &[]), msg).unwrap();
+    }
+
+    fn instantiate_home(deps: DepsMut) {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+        };
+        instantiate(deps, mock_env(), mock_info("owner", &[]), msg).unwrap();
+    }
+
+    #[test]
+    fn only_opens_ordered_nomad_channels() {
+        let mut deps = mock_padded_dependencies(&[]);
+        instantiate_home(deps.as_mut());
+        allow_mock_counterparty(deps.as_mut(), true);
+
+        let msg = mock_ibc_channel_open_init(CHANNEL_ID, IbcOrder::Unordered, NOMAD_IBC_VERSION);
+        let res = ibc_channel_open(deps.as_mut(), mock_env(), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::IbcChannelError(IbcChannelError::InvalidOrder {})
+        ));
+
+        let msg = mock_ibc_channel_open_try(CHANNEL_ID, IbcOrder::Ordered, "ics20-1");
+        let res = ibc_channel_open(deps.as_mut(), mock_env(), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::IbcChannelError(IbcChannelError::InvalidVersion { .. })
+        ));
+
+        let msg = mock_ibc_channel_open_init(CHANNEL_ID, IbcOrder::Ordered, NOMAD_IBC_VERSION);
+        ibc_channel_open(deps.as_mut(), mock_env(), msg).unwrap();
+
+        let msg = mock_ibc_channel_connect_ack(CHANNEL_ID, IbcOrder::Ordered, NOMAD_IBC_VERSION);
+        ibc_channel_connect(deps.as_mut(), mock_env(), msg).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::IbcChannels {}).unwrap();
+        let value: IbcChannelsResponse = from_binary(&res).unwrap();
+        assert_eq!(vec![CHANNEL_ID.to_owned()], value.channels);
+
+        // Home does not accept packets
+        let packet = NomadPacket::Update {
+            committed_root: H256::zero(),
+            new_root: H256::repeat_byte(1),
+            signatures: vec![],
+        };
+        let msg = mock_ibc_packet_recv(CHANNEL_ID, &packet).unwrap();
+        let res = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+        assert_eq!(
+            NomadAck::error(ContractError::PacketNotAccepted {}).unwrap(),
+            res.acknowledgement
+        );
+
+        let msg = mock_ibc_channel_close_init(CHANNEL_ID, IbcOrder::Ordered, NOMAD_IBC_VERSION);
+        ibc_channel_close(deps.as_mut(), mock_env(), msg).unwrap();
+        let value = query_ibc_channels(deps.as_ref()).unwrap();
+        assert!(value.channels.is_empty());
+    }
+
+    #[test]
+    fn only_connects_allowed_counterparties() {
+        let mut deps = mock_padded_dependencies(&[]);
+        instantiate_home(deps.as_mut());
+
+        // Counterparty not allowed by owner
+        let msg = mock_ibc_channel_open_init(CHANNEL_ID, IbcOrder::Ordered, NOMAD_IBC_VERSION);
+        let res = ibc_channel_open(deps.as_mut(), mock_env(), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::CounterpartyNotAllowed { .. }
+        ));
+        let msg = mock_ibc_channel_connect_ack(CHANNEL_ID, IbcOrder::Ordered, NOMAD_IBC_VERSION);
+        let res = ibc_channel_connect(deps.as_mut(), mock_env(), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::CounterpartyNotAllowed { .. }
+        ));
+
+        // Only owner can allow counterparties
+        let msg = ExecuteMsg::SetIbcCounterparty {
+            connection_id: "connection-2".to_owned(),
+            port_id: "their_port".to_owned(),
+            allowed: true,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("not_owner", &[]), msg);
+        assert!(matches!(res.unwrap_err(), ContractError::OwnableError(_)));
+
+        allow_mock_counterparty(deps.as_mut(), true);
+        let msg = mock_ibc_channel_connect_ack(CHANNEL_ID, IbcOrder::Ordered, NOMAD_IBC_VERSION);
+        ibc_channel_connect(deps.as_mut(), mock_env(), msg).unwrap();
+
+        // Counterparty holds at most one channel
+        let msg = mock_ibc_channel_open_init("channel-1", IbcOrder::Ordered, NOMAD_IBC_VERSION);
+        let res = ibc_channel_open(deps.as_mut(), mock_env(), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::CounterpartyAlreadyConnected { .. }
+        ));
+
+        // Disallowing counterparty drops its channel
+        allow_mock_counterparty(deps.as_mut(), false);
+        let value = query_ibc_channels(deps.as_ref()).unwrap();
+        assert!(value.channels.is_empty());
+    }
+
+    #[tokio::test]
+    async fn sends_updates_and_relayed_messages() {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+        let mut deps = mock_padded_dependencies(&coins(100, "token"));
+
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+        let msg = ExecuteMsg::SetFullTreeStorage { enabled: true };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        allow_mock_counterparty(deps.as_mut(), true);
+        let msg = mock_ibc_channel_connect_ack(CHANNEL_ID, IbcOrder::Ordered, NOMAD_IBC_VERSION);
+        ibc_channel_connect(deps.as_mut(), mock_env(), msg).unwrap();
+
+        let sender = H256::repeat_byte(0);
+        let msg = ExecuteMsg::Dispatch {
+            destination: 2000,
+            recipient: H256::repeat_byte(1),
+            message_body: vec![1, 2, 3],
+        };
+        let info = mock_info(&h256_to_string(sender), &[]);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let message = NomadMessage {
+            origin: LOCAL_DOMAIN,
+            sender,
+            nonce: 0,
+            destination: 2000,
+            recipient: H256::repeat_byte(1),
+            body: vec![1, 2, 3],
+        };
+
+        // Update is forwarded over connected channels
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::SuggestUpdate {}).unwrap();
+        let SuggestUpdateResponse {
+            committed_root,
+            new_root,
+        } = from_binary(&res).unwrap();
+        let update = updater.sign_update(committed_root, new_root).await.unwrap();
+        let signatures = vec![update.signature.to_vec()];
+        let msg = ExecuteMsg::Update {
+            committed_root,
+            new_root,
+            signatures: signatures.clone(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("submitter", &[]), msg).unwrap();
+        assert_eq!(1, res.messages.len());
+        assert_eq!(
+            (
+                CHANNEL_ID.to_owned(),
+                NomadPacket::Update {
+                    committed_root,
+                    new_root,
+                    signatures,
+                }
+            ),
+            sent_packet(&res.messages[0])
+        );
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Proof { leaf_index: 0 }).unwrap();
+        let proof: Proof = from_binary(&res).unwrap();
+        let relay_msg = |channel_id: &str, index: u64| ExecuteMsg::RelayMessage {
+            channel_id: channel_id.to_owned(),
+            message: message.to_vec(),
+            proof: proof.path,
+            index,
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("relayer", &[]),
+            relay_msg("channel-1", 0),
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::ChannelNotConnected { .. }
+        ));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("relayer", &[]),
+            relay_msg(CHANNEL_ID, 1),
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::MessageNotCommitted { .. }
+        ));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("relayer", &[]),
+            relay_msg(CHANNEL_ID, 0),
+        )
+        .unwrap();
+        assert_eq!(
+            (
+                CHANNEL_ID.to_owned(),
+                NomadPacket::Message {
+                    message: message.to_vec(),
+                    proof: proof.path,
+                    index: 0,
+                }
+            ),
+            sent_packet(&res.messages[0])
+        );
+
+        // Replica rejection is surfaced
+        let packet = sent_packet(&res.messages[0]).1;
+        let ack = IbcAcknowledgement::new(NomadAck::error("root not acceptable").unwrap());
+        let msg = mock_ibc_packet_ack(CHANNEL_ID, &packet, ack).unwrap();
+        let res = ibc_packet_ack(deps.as_mut(), mock_env(), msg).unwrap();
+        assert_eq!(
+            "root not acceptable",
+            event_attr_value_by_key(&res.events[0], "error").unwrap()
+        );
+    }
+}
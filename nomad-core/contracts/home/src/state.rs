@@ -1,5 +1,38 @@
+use common::MessageStatus;
 use cosmwasm_std::Addr;
 use cw_storage_plus::{Item, Map};
+use ethers_core::types::H256;
+use serde::{Deserialize, Serialize};
+
+/// Loopback process gas used when owner has not set one. Matches replica
+pub const DEFAULT_PROCESS_GAS: u64 = 850_000;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredMessage {
+    pub leaf_index: u64,
+    pub message: Vec<u8>,
+}
 
 pub const UPDATER_MANAGER: Item<Addr> = Item::new("updater_manager");
 pub const NONCES: Map<u32, u32> = Map::new("nonces");
+
+// Message index, only written while MESSAGE_INDEXING is enabled
+pub const MESSAGE_INDEXING: Item<bool> = Item::new("message_indexing");
+// destination_and_nonce(destination, nonce) --> leaf
+pub const DESTINATION_NONCE_LEAVES: Map<u64, H256> = Map::new("destination_nonce_leaves");
+pub const LEAF_INDEX_LEAVES: Map<u64, H256> = Map::new("leaf_index_leaves");
+pub const MESSAGES: Map<&[u8], StoredMessage> = Map::new("messages");
+
+/// Delivery status of messages dispatched to local domain, keyed by leaf
+pub const LOOPBACK_MESSAGES: Map<&[u8], MessageStatus> = Map::new("home_loopback_messages");
+/// Leaves of in-flight loopback deliveries, innermost last
+pub const LOOPBACK_PROCESSING: Item<Vec<H256>> = Item::new("home_loopback_processing");
+/// Gas limit of loopback recipient handle submessage
+pub const PROCESS_GAS: Item<u64> = Item::new("home_process_gas");
+
+/// (connection id, counterparty port) allowed to open a channel --> its
+/// connected channel
+pub const IBC_COUNTERPARTIES: Map<(&str, &str), Option<String>> =
+    Map::new("home_ibc_counterparties");
+/// Connected IBC channel id --> (connection id, counterparty port)
+pub const IBC_CHANNELS: Map<&str, (String, String)> = Map::new("home_ibc_channels");
@@ -0,0 +1,475 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_slice, to_binary, to_vec, Addr, Binary, ContractResult, CosmosMsg, Deps, DepsMut, Env,
+    Event, MessageInfo, Reply, ReplyOn, Response, StdResult, SubMsg, WasmMsg,
+};
+use cw2::set_contract_version;
+use ethers_core::types::H256;
+
+use crate::error::ContractError;
+use crate::state::{
+    PendingCalls, ACCOUNTS, ACCOUNT_CODE_ID, CONNECTION_MANAGER, HOME, LOCAL_DOMAIN, PENDING_CALLS,
+};
+use common::interchain_account_router::{
+    AccountCodeIdResponse, AccountResponse, CallsResult, ExecuteMsg, InstantiateMsg,
+    InterchainCalls, QueryMsg,
+};
+use common::nomad_base::LocalDomainResponse;
+use common::{home, interchain_account};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:interchain-account-router";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub const DEPLOY_ACCOUNT_ID: u64 = 1;
+pub const EXECUTE_CALLS_ID: u64 = 2;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    LOCAL_DOMAIN.save(deps.storage, &msg.local_domain)?;
+    HOME.save(deps.storage, &deps.api.addr_validate(&msg.home)?)?;
+    CONNECTION_MANAGER.save(
+        deps.storage,
+        &deps.api.addr_validate(&msg.connection_manager)?,
+    )?;
+    ACCOUNT_CODE_ID.save(deps.storage, &msg.account_code_id)?;
+    PENDING_CALLS.save(deps.storage, &vec![])?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::HandleMsg {
+            origin,
+            nonce,
+            sender,
+            message,
+        } => execute_handle(deps, info, origin, nonce, sender, message),
+    }
+}
+
+pub fn execute_handle(
+    deps: DepsMut,
+    info: MessageInfo,
+    origin: u32,
+    nonce: u32,
+    sender: H256,
+    message: Vec<u8>,
+) -> Result<Response, ContractError> {
+    let connection_manager = CONNECTION_MANAGER.load(deps.storage)?;
+    nomad_recipient::only_enrolled_replica(
+        deps.as_ref(),
+        &connection_manager,
+        &info.sender,
+        origin,
+    )?;
+
+    let InterchainCalls { calls, reply } = from_slice(&message)?;
+    let event = Event::new("HandleInterchainCalls")
+        .add_attribute("origin", origin.to_string())
+        .add_attribute("nonce", nonce.to_string())
+        .add_attribute("sender", format!("{:?}", sender))
+        .add_attribute("num_calls", calls.len().to_string());
+
+    let pending = PendingCalls {
+        origin,
+        nonce,
+        sender,
+        calls,
+        reply,
+    };
+    let resp = match ACCOUNTS.may_load(deps.storage, (origin, sender.as_bytes()))? {
+        Some(account) => _execute_calls(deps, account, pending)?,
+        None => _deploy_account(deps, pending)?,
+    };
+
+    Ok(resp.add_event(event))
+}
+
+/// Instantiate account for calls' origin and sender. Calls are executed once
+/// instantiate reply returns.
+pub fn _deploy_account(
+    mut deps: DepsMut,
+    pending: PendingCalls,
+) -> Result<Response, ContractError> {
+    let init_msg = interchain_account::InstantiateMsg {
+        origin: pending.origin,
+        sender: pending.sender,
+    };
+    let label = format!(
+        "nomad interchain account {}:{:?}",
+        pending.origin, pending.sender
+    );
+    _push_pending(deps.branch(), pending)?;
+
+    let sub_msg = SubMsg {
+        id: DEPLOY_ACCOUNT_ID,
+        msg: CosmosMsg::Wasm(WasmMsg::Instantiate {
+            admin: None,
+            code_id: ACCOUNT_CODE_ID.load(deps.storage)?,
+            msg: to_binary(&init_msg)?,
+            funds: vec![],
+            label,
+        }),
+        gas_limit: None,
+        reply_on: ReplyOn::Success,
+    };
+
+    Ok(Response::new().add_submessage(sub_msg))
+}
+
+/// Execute calls through `account`. If sender asked for a reply, failed
+/// calls are caught by reply and reported back instead of reverting.
+pub fn _execute_calls(
+    deps: DepsMut,
+    account: Addr,
+    pending: PendingCalls,
+) -> Result<Response, ContractError> {
+    let execute_msg = WasmMsg::Execute {
+        contract_addr: account.to_string(),
+        msg: to_binary(&interchain_account::ExecuteMsg::ExecuteCalls {
+            calls: pending.calls.clone(),
+        })?,
+        funds: vec![],
+    };
+
+    if !pending.reply {
+        return Ok(Response::new().add_message(execute_msg));
+    }
+
+    _push_pending(deps, pending)?;
+    let sub_msg = SubMsg {
+        id: EXECUTE_CALLS_ID,
+        msg: CosmosMsg::Wasm(execute_msg),
+        gas_limit: None,
+        reply_on: ReplyOn::Always,
+    };
+
+    Ok(Response::new().add_submessage(sub_msg))
+}
+
+pub fn _push_pending(deps: DepsMut, pending: PendingCalls) -> Result<(), ContractError> {
+    let mut pending_calls = PENDING_CALLS.load(deps.storage)?;
+    pending_calls.push(pending);
+    PENDING_CALLS.save(deps.storage, &pending_calls)?;
+    Ok(())
+}
+
+pub fn _pop_pending(deps: DepsMut) -> Result<PendingCalls, ContractError> {
+    let mut pending_calls = PENDING_CALLS.load(deps.storage)?;
+    let pending = pending_calls.pop().ok_or(ContractError::NoPendingCalls)?;
+    PENDING_CALLS.save(deps.storage, &pending_calls)?;
+    Ok(pending)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        DEPLOY_ACCOUNT_ID => reply_deploy_account(deps, msg),
+        EXECUTE_CALLS_ID => reply_execute_calls(deps, msg),
+        _ => Err(ContractError::UnknownReplyMessage { id: msg.id }),
+    }
+}
+
+pub fn reply_deploy_account(mut deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let res = match msg.result {
+        ContractResult::Ok(res) => res,
+        ContractResult::Err(e) => return Err(ContractError::FailedDeployAccount(e)),
+    };
+
+    let account = res
+        .events
+        .iter()
+        .filter(|event| event.ty == "instantiate")
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "_contract_address")
+        .map(|attr| attr.value.clone())
+        .ok_or_else(|| ContractError::FailedDeployAccount("missing contract address".to_owned()))?;
+    let account_addr = deps.api.addr_validate(&account)?;
+
+    let pending = _pop_pending(deps.branch())?;
+    ACCOUNTS.save(
+        deps.storage,
+        (pending.origin, pending.sender.as_bytes()),
+        &account_addr,
+    )?;
+
+    let event = Event::new("AccountDeployed")
+        .add_attribute("origin", pending.origin.to_string())
+        .add_attribute("sender", format!("{:?}", pending.sender))
+        .add_attribute("account", account_addr.to_string());
+
+    Ok(_execute_calls(deps, account_addr, pending)?.add_event(event))
+}
+
+/// Dispatch outcome of calls back to their sender
+pub fn reply_execute_calls(mut deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let pending = _pop_pending(deps.branch())?;
+    let account = ACCOUNTS.load(deps.storage, (pending.origin, pending.sender.as_bytes()))?;
+
+    let error = match msg.result {
+        ContractResult::Ok(_) => None,
+        ContractResult::Err(e) => Some(e),
+    };
+    let result = CallsResult {
+        nonce: pending.nonce,
+        account: account.to_string(),
+        success: error.is_none(),
+        error,
+    };
+
+    let dispatch_msg = home::ExecuteMsg::Dispatch {
+        destination: pending.origin,
+        recipient: pending.sender,
+        message_body: to_vec(&result)?,
+    };
+    let home = HOME.load(deps.storage)?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: home.to_string(),
+            msg: to_binary(&dispatch_msg)?,
+            funds: vec![],
+        })
+        .add_event(
+            Event::new("CallsResult")
+                .add_attribute("origin", pending.origin.to_string())
+                .add_attribute("nonce", pending.nonce.to_string())
+                .add_attribute("success", result.success.to_string()),
+        ))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Account { origin, sender } => to_binary(&query_account(deps, origin, sender)?),
+        QueryMsg::AccountCodeId {} => to_binary(&query_account_code_id(deps)?),
+        QueryMsg::LocalDomain {} => to_binary(&query_local_domain(deps)?),
+    }
+}
+
+pub fn query_account(deps: Deps, origin: u32, sender: H256) -> StdResult<AccountResponse> {
+    let account = ACCOUNTS
+        .may_load(deps.storage, (origin, sender.as_bytes()))?
+        .map(|account| account.to_string())
+        .unwrap_or_else(|| "0x0".to_owned());
+    Ok(AccountResponse { account })
+}
+
+pub fn query_account_code_id(deps: Deps) -> StdResult<AccountCodeIdResponse> {
+    let account_code_id = ACCOUNT_CODE_ID.load(deps.storage)?;
+    Ok(AccountCodeIdResponse { account_code_id })
+}
+
+pub fn query_local_domain(deps: Deps) -> StdResult<LocalDomainResponse> {
+    let local_domain = LOCAL_DOMAIN.load(deps.storage)?;
+    Ok(LocalDomainResponse { local_domain })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::addr_to_h256;
+    use common::connection_manager::{
+        self, DomainToReplicaResponse, HomeResponse, ReplicaToDomainResponse,
+    };
+    use cosmwasm_std::testing::{mock_env, mock_info, MockQuerier, MockStorage};
+    use cosmwasm_std::{
+        coins, from_binary, BankMsg, OwnedDeps, SubMsgExecutionResponse, SystemError, SystemResult,
+        WasmQuery,
+    };
+    use test_utils::{mock_padded_dependencies, PaddedMockApi};
+
+    const LOCAL: u32 = 1000;
+    const REMOTE: u32 = 2000;
+    const CODE_ID: u64 = 7;
+
+    fn instantiate_router() -> OwnedDeps<MockStorage, PaddedMockApi, MockQuerier> {
+        let mut deps = mock_padded_dependencies(&[]);
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { msg, .. } => {
+                let res = match from_binary::<connection_manager::QueryMsg>(msg).unwrap() {
+                    connection_manager::QueryMsg::DomainToReplica { domain } => {
+                        let replica = if domain == REMOTE { "replica" } else { "0x0" };
+                        to_binary(&DomainToReplicaResponse {
+                            replica: replica.to_owned(),
+                        })
+                    }
+                    connection_manager::QueryMsg::ReplicaToDomain { replica } => {
+                        let domain = if replica == "replica" { REMOTE } else { 0 };
+                        to_binary(&ReplicaToDomainResponse { domain })
+                    }
+                    connection_manager::QueryMsg::Home {} => to_binary(&HomeResponse {
+                        home: "0x0".to_owned(),
+                    }),
+                    _ => panic!("unexpected query"),
+                };
+                SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+            }
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "wasm".to_owned(),
+            }),
+        });
+
+        let msg = InstantiateMsg {
+            local_domain: LOCAL,
+            home: "home".to_owned(),
+            connection_manager: "connection_manager".to_owned(),
+            account_code_id: CODE_ID,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+        deps
+    }
+
+    fn handle_calls(
+        deps: DepsMut,
+        caller: &str,
+        sender: H256,
+        calls: Vec<CosmosMsg>,
+        reply: bool,
+    ) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::HandleMsg {
+            origin: REMOTE,
+            nonce: 3,
+            sender,
+            message: to_vec(&InterchainCalls { calls, reply }).unwrap(),
+        };
+        execute(deps, mock_env(), mock_info(caller, &[]), msg)
+    }
+
+    fn deployed_reply(account: &str) -> Reply {
+        Reply {
+            id: DEPLOY_ACCOUNT_ID,
+            result: ContractResult::Ok(SubMsgExecutionResponse {
+                events: vec![Event::new("instantiate").add_attribute("_contract_address", account)],
+                data: None,
+            }),
+        }
+    }
+
+    fn bank_call() -> CosmosMsg {
+        BankMsg::Send {
+            to_address: "recipient".to_owned(),
+            amount: coins(50, "token"),
+        }
+        .into()
+    }
+
+    #[test]
+    fn deploys_account_then_executes_calls() {
+        let mut deps = instantiate_router();
+        let sender = H256::repeat_byte(1);
+        let calls = vec![bank_call()];
+
+        let res = handle_calls(deps.as_mut(), "not_replica", sender, calls.clone(), false);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::RecipientError(nomad_recipient::RecipientError::NotReplica { .. })
+        ));
+
+        // First message from sender deploys its account
+        let res = handle_calls(deps.as_mut(), "replica", sender, calls.clone(), false).unwrap();
+        assert_eq!(1, res.messages.len());
+        assert_eq!(DEPLOY_ACCOUNT_ID, res.messages[0].id);
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Instantiate { code_id, msg, .. }) => {
+                assert_eq!(CODE_ID, *code_id);
+                let init_msg: interchain_account::InstantiateMsg = from_binary(msg).unwrap();
+                assert_eq!(REMOTE, init_msg.origin);
+                assert_eq!(sender, init_msg.sender);
+            }
+            _ => panic!("Expected wasm instantiate message"),
+        }
+
+        let execute_msg = SubMsg::new(WasmMsg::Execute {
+            contract_addr: "account".to_owned(),
+            msg: to_binary(&interchain_account::ExecuteMsg::ExecuteCalls {
+                calls: calls.clone(),
+            })
+            .unwrap(),
+            funds: vec![],
+        });
+
+        let res = reply(deps.as_mut(), mock_env(), deployed_reply("account")).unwrap();
+        assert_eq!(vec![execute_msg.clone()], res.messages);
+
+        let msg = QueryMsg::Account {
+            origin: REMOTE,
+            sender,
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value: AccountResponse = from_binary(&res).unwrap();
+        assert_eq!("account", value.account);
+
+        // Later messages execute through existing account
+        let res = handle_calls(deps.as_mut(), "replica", sender, calls, false).unwrap();
+        assert_eq!(vec![execute_msg], res.messages);
+        assert!(PENDING_CALLS.load(&deps.storage).unwrap().is_empty());
+    }
+
+    #[test]
+    fn dispatches_calls_result_to_sender() {
+        let mut deps = instantiate_router();
+        let sender = addr_to_h256(&deps.api, &Addr::unchecked("remote_dao")).unwrap();
+
+        handle_calls(deps.as_mut(), "replica", sender, vec![bank_call()], true).unwrap();
+        let res = reply(deps.as_mut(), mock_env(), deployed_reply("account")).unwrap();
+        assert_eq!(1, res.messages.len());
+        assert_eq!(EXECUTE_CALLS_ID, res.messages[0].id);
+        assert_eq!(ReplyOn::Always, res.messages[0].reply_on);
+
+        // Account reverts calls
+        let reply_msg = Reply {
+            id: EXECUTE_CALLS_ID,
+            result: ContractResult::Err("insufficient funds".to_owned()),
+        };
+        let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+        assert_eq!(1, res.messages.len());
+
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => {
+                assert_eq!("home", contract_addr);
+                match from_binary::<home::ExecuteMsg>(msg).unwrap() {
+                    home::ExecuteMsg::Dispatch {
+                        destination,
+                        recipient,
+                        message_body,
+                    } => {
+                        assert_eq!(REMOTE, destination);
+                        assert_eq!(sender, recipient);
+                        let result: CallsResult = from_slice(&message_body).unwrap();
+                        assert_eq!(
+                            CallsResult {
+                                nonce: 3,
+                                account: "account".to_owned(),
+                                success: false,
+                                error: Some("insufficient funds".to_owned()),
+                            },
+                            result
+                        );
+                    }
+                    _ => panic!("Expected dispatch message"),
+                }
+            }
+            _ => panic!("Expected wasm execute message"),
+        }
+        assert!(PENDING_CALLS.load(&deps.storage).unwrap().is_empty());
+    }
+}
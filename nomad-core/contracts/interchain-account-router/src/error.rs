@@ -0,0 +1,23 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    RecipientError(#[from] nomad_recipient::RecipientError),
+
+    #[error("Failed to deploy interchain account: {0}")]
+    FailedDeployAccount(String),
+
+    #[error("No calls awaiting reply")]
+    NoPendingCalls,
+
+    #[error("Unknown reply message id received: {id}")]
+    UnknownReplyMessage { id: u64 },
+
+    #[error("{0}")]
+    AddressError(#[from] common::AddressError),
+}
@@ -0,0 +1,26 @@
+use cosmwasm_std::{Addr, CosmosMsg};
+use cw_storage_plus::{Item, Map};
+use ethers_core::types::H256;
+use serde::{Deserialize, Serialize};
+
+pub const LOCAL_DOMAIN: Item<u32> = Item::new("interchain_account_router_local_domain");
+pub const HOME: Item<Addr> = Item::new("interchain_account_router_home");
+pub const CONNECTION_MANAGER: Item<Addr> =
+    Item::new("interchain_account_router_connection_manager");
+pub const ACCOUNT_CODE_ID: Item<u64> = Item::new("interchain_account_router_account_code_id");
+
+// Remote (origin, sender) -> local proxy account
+pub const ACCOUNTS: Map<(u32, &[u8]), Addr> = Map::new("interchain_account_router_accounts");
+
+// Calls awaiting account deployment or execution reply, innermost last
+pub const PENDING_CALLS: Item<Vec<PendingCalls>> =
+    Item::new("interchain_account_router_pending_calls");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PendingCalls {
+    pub origin: u32,
+    pub nonce: u32,
+    pub sender: H256,
+    pub calls: Vec<CosmosMsg>,
+    pub reply: bool,
+}
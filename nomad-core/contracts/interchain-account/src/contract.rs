@@ -0,0 +1,126 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, Event, MessageInfo, Response, StdResult,
+};
+use cw2::set_contract_version;
+
+use crate::error::ContractError;
+use crate::state::{ORIGIN, ROUTER, SENDER};
+use common::interchain_account::{AccountResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:interchain-account";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    ROUTER.save(deps.storage, &info.sender)?;
+    ORIGIN.save(deps.storage, &msg.origin)?;
+    SENDER.save(deps.storage, &msg.sender)?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ExecuteCalls { calls } => execute_execute_calls(deps, info, calls),
+    }
+}
+
+pub fn execute_execute_calls(
+    deps: DepsMut,
+    info: MessageInfo,
+    calls: Vec<CosmosMsg>,
+) -> Result<Response, ContractError> {
+    only_router(deps.as_ref(), &info)?;
+
+    let num_calls = calls.len();
+    Ok(Response::new()
+        .add_messages(calls)
+        .add_event(Event::new("ExecuteCalls").add_attribute("num_calls", num_calls.to_string())))
+}
+
+pub fn only_router(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    let router = ROUTER.load(deps.storage)?;
+    if info.sender != router {
+        return Err(ContractError::NotRouter);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Account {} => to_binary(&query_account(deps)?),
+    }
+}
+
+pub fn query_account(deps: Deps) -> StdResult<AccountResponse> {
+    Ok(AccountResponse {
+        router: ROUTER.load(deps.storage)?.to_string(),
+        origin: ORIGIN.load(deps.storage)?,
+        sender: SENDER.load(deps.storage)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+    use cosmwasm_std::{coins, from_binary, BankMsg, SubMsg};
+    use ethers_core::types::H256;
+
+    const REMOTE_DOMAIN: u32 = 1000;
+
+    #[test]
+    fn only_router_executes_calls() {
+        let mut deps = mock_dependencies_with_balance(&coins(100, "token"));
+
+        let msg = InstantiateMsg {
+            origin: REMOTE_DOMAIN,
+            sender: H256::repeat_byte(1),
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("router", &[]), msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Account {}).unwrap();
+        let value: AccountResponse = from_binary(&res).unwrap();
+        assert_eq!("router", value.router);
+        assert_eq!(REMOTE_DOMAIN, value.origin);
+        assert_eq!(H256::repeat_byte(1), value.sender);
+
+        let call: CosmosMsg = BankMsg::Send {
+            to_address: "recipient".to_owned(),
+            amount: coins(50, "token"),
+        }
+        .into();
+        let msg = ExecuteMsg::ExecuteCalls {
+            calls: vec![call.clone()],
+        };
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("not_router", &[]),
+            msg.clone(),
+        );
+        assert!(matches!(res.unwrap_err(), ContractError::NotRouter));
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("router", &[]), msg).unwrap();
+        assert_eq!(vec![SubMsg::new(call)], res.messages);
+    }
+}
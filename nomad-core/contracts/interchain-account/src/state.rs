@@ -0,0 +1,9 @@
+use cosmwasm_std::Addr;
+use cw_storage_plus::Item;
+use ethers_core::types::H256;
+
+// Router that deployed and controls the account
+pub const ROUTER: Item<Addr> = Item::new("interchain_account_router");
+// Remote domain and sender the account acts for
+pub const ORIGIN: Item<u32> = Item::new("interchain_account_origin");
+pub const SENDER: Item<H256> = Item::new("interchain_account_sender");
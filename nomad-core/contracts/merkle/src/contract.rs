@@ -1,13 +1,18 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, Event, MessageInfo, Response, StdError, StdResult,
+    Storage,
+};
 use cw2::set_contract_version;
 use ethers_core::types::H256;
 
 use crate::error::ContractError;
-use crate::state::MERKLE;
-use common::merkle::{CountResponse, ExecuteMsg, InstantiateMsg, QueryMsg, RootResponse};
-use common::merkle_tree::IncrementalMerkle;
+use crate::state::{node_key, FULL_TREE_START, MERKLE, NODES};
+use common::merkle::{
+    CountResponse, ExecuteMsg, FullTreeStorageResponse, InstantiateMsg, QueryMsg, RootResponse,
+};
+use common::merkle_tree::{hash_concat, IncrementalMerkle, Proof, TREE_DEPTH, ZERO_HASHES};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:merkle";
@@ -36,22 +41,92 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::Insert { element } => execute_insert(deps, element),
+        ExecuteMsg::SetFullTreeStorage { enabled } => execute_set_full_tree_storage(deps, enabled),
     }
 }
 
 pub fn execute_insert(deps: DepsMut, element: H256) -> Result<Response, ContractError> {
     let mut merkle = MERKLE.load(deps.storage)?;
+    let index = merkle.count() as u64;
     merkle.ingest(element);
     MERKLE.save(deps.storage, &merkle)?;
+
+    if FULL_TREE_START.may_load(deps.storage)?.is_some() {
+        _store_nodes(deps.storage, element, index)?;
+    }
+
     Ok(Response::new().add_attribute("element", format!("{:?}", element)))
 }
 
+/// Enable or disable full tree storage. Enabling seeds the left-hand nodes
+/// of the incremental branch so every leaf inserted afterwards is provable.
+/// Earlier leaves are not provable
+pub fn execute_set_full_tree_storage(
+    deps: DepsMut,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    if !enabled {
+        FULL_TREE_START.remove(deps.storage);
+        return Ok(Response::new().add_event(
+            Event::new("SetFullTreeStorage").add_attribute("enabled", enabled.to_string()),
+        ));
+    }
+
+    // Already enabled, keep existing start index
+    if let Some(start) = FULL_TREE_START.may_load(deps.storage)? {
+        return Ok(Response::new().add_event(
+            Event::new("SetFullTreeStorage")
+                .add_attribute("enabled", enabled.to_string())
+                .add_attribute("start_index", start.to_string()),
+        ));
+    }
+
+    let merkle = MERKLE.load(deps.storage)?;
+    let count = merkle.count() as u64;
+    for (level, node) in merkle.branch().iter().enumerate() {
+        // Branch holds the complete left subtree at each level where the
+        // corresponding bit of count is set
+        if (count >> level) & 1 == 1 {
+            NODES.save(deps.storage, node_key(level, (count >> level) - 1), node)?;
+        }
+    }
+    FULL_TREE_START.save(deps.storage, &count)?;
+
+    Ok(Response::new().add_event(
+        Event::new("SetFullTreeStorage")
+            .add_attribute("enabled", enabled.to_string())
+            .add_attribute("start_index", count.to_string()),
+    ))
+}
+
+/// Store `leaf` at `index` and recompute its ancestors below the root
+fn _store_nodes(storage: &mut dyn Storage, leaf: H256, index: u64) -> StdResult<()> {
+    let mut node = leaf;
+    let mut index = index;
+    NODES.save(storage, node_key(0, index), &node)?;
+
+    for level in 0..TREE_DEPTH - 1 {
+        // Newest leaf so right-hand sibling is always empty
+        node = if index & 1 == 1 {
+            let sibling = NODES.load(storage, node_key(level, index - 1))?;
+            hash_concat(sibling, node)
+        } else {
+            hash_concat(node, ZERO_HASHES[level])
+        };
+        index >>= 1;
+        NODES.save(storage, node_key(level + 1, index), &node)?;
+    }
+
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Root {} => to_binary(&query_root(deps)?),
         QueryMsg::Count {} => to_binary(&query_count(deps)?),
-        // QueryMsg::Tree {} => to_binary(&query_tree(deps)?),
+        QueryMsg::FullTreeStorage {} => to_binary(&query_full_tree_storage(deps)?),
+        QueryMsg::Proof { leaf_index } => to_binary(&query_proof(deps, leaf_index)?),
     }
 }
 
@@ -69,10 +144,39 @@ pub fn query_count(deps: Deps) -> StdResult<CountResponse> {
     })
 }
 
-// pub fn query_tree(deps: Deps) -> StdResult<TreeResponse> {
-//     let tree = MERKLE.load(deps.storage)?;
-//     Ok(TreeResponse { tree })
-// }
+pub fn query_full_tree_storage(deps: Deps) -> StdResult<FullTreeStorageResponse> {
+    let start_index = FULL_TREE_START.may_load(deps.storage)?;
+    Ok(FullTreeStorageResponse { start_index })
+}
+
+pub fn query_proof(deps: Deps, leaf_index: u64) -> StdResult<Proof> {
+    let start = FULL_TREE_START
+        .may_load(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("Full tree storage not enabled"))?;
+    let count = MERKLE.load(deps.storage)?.count() as u64;
+    if leaf_index < start || leaf_index >= count {
+        return Err(StdError::generic_err(format!(
+            "Leaf index {} not provable. Provable range: [{}, {})",
+            leaf_index, start, count
+        )));
+    }
+
+    let leaf = NODES.load(deps.storage, node_key(0, leaf_index))?;
+
+    let mut path = [H256::zero(); TREE_DEPTH];
+    for (level, sibling) in path.iter_mut().enumerate() {
+        // Missing siblings are empty subtrees
+        *sibling = NODES
+            .may_load(deps.storage, node_key(level, (leaf_index >> level) ^ 1))?
+            .unwrap_or(ZERO_HASHES[level]);
+    }
+
+    Ok(Proof {
+        leaf,
+        index: leaf_index as usize,
+        path,
+    })
+}
 
 #[cfg(test)]
 mod tests {
@@ -96,4 +200,84 @@ mod tests {
         let value: RootResponse = from_binary(&res).unwrap();
         assert_eq!(*INITIAL_ROOT, value.root);
     }
+
+    #[test]
+    fn serves_proofs_when_full_tree_enabled() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let info = mock_info("owner", &coins(100, "earth"));
+        instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
+
+        let insert = |deps: DepsMut, i: u8| {
+            execute_insert(deps, H256::repeat_byte(i)).unwrap();
+        };
+
+        // Proofs unavailable while disabled
+        insert(deps.as_mut(), 1);
+        query_proof(deps.as_ref(), 0).unwrap_err();
+
+        // Enable mid-tree (count 3 sets bits 0 and 1)
+        insert(deps.as_mut(), 2);
+        insert(deps.as_mut(), 3);
+        execute_set_full_tree_storage(deps.as_mut(), true).unwrap();
+        assert_eq!(
+            Some(3),
+            query_full_tree_storage(deps.as_ref()).unwrap().start_index
+        );
+
+        for i in 4..=12 {
+            insert(deps.as_mut(), i);
+        }
+
+        let merkle = MERKLE.load(&deps.storage).unwrap();
+        for leaf_index in 3..12 {
+            let res = query(deps.as_ref(), mock_env(), QueryMsg::Proof { leaf_index }).unwrap();
+            let proof: Proof = from_binary(&res).unwrap();
+            assert_eq!(H256::repeat_byte(leaf_index as u8 + 1), proof.leaf);
+            assert_eq!(leaf_index as usize, proof.index);
+            assert!(merkle.verify(&proof));
+        }
+
+        // Leaves before start and past count not provable
+        query_proof(deps.as_ref(), 2).unwrap_err();
+        query_proof(deps.as_ref(), 12).unwrap_err();
+    }
+
+    #[test]
+    fn reenabling_full_tree_resets_start() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let info = mock_info("owner", &coins(100, "earth"));
+        instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
+
+        execute_set_full_tree_storage(deps.as_mut(), true).unwrap();
+        for i in 0..5 {
+            execute_insert(deps.as_mut(), H256::repeat_byte(i)).unwrap();
+        }
+
+        // Leaves inserted while disabled leave stored nodes stale
+        execute_set_full_tree_storage(deps.as_mut(), false).unwrap();
+        assert_eq!(
+            None,
+            query_full_tree_storage(deps.as_ref()).unwrap().start_index
+        );
+        for i in 5..7 {
+            execute_insert(deps.as_mut(), H256::repeat_byte(i)).unwrap();
+        }
+
+        execute_set_full_tree_storage(deps.as_mut(), true).unwrap();
+        assert_eq!(
+            Some(7),
+            query_full_tree_storage(deps.as_ref()).unwrap().start_index
+        );
+        for i in 7..10 {
+            execute_insert(deps.as_mut(), H256::repeat_byte(i)).unwrap();
+        }
+
+        let root = query_root(deps.as_ref()).unwrap().root;
+        for leaf_index in 7..10 {
+            assert_eq!(root, query_proof(deps.as_ref(), leaf_index).unwrap().root());
+        }
+        query_proof(deps.as_ref(), 4).unwrap_err();
+    }
 }
@@ -1,5 +1,19 @@
 use common::merkle_tree::IncrementalMerkle;
+use ethers_core::types::H256;
 
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 
-pub const MERKLE: Item<IncrementalMerkle> = Item::new("merkle_incremental_merkle");
+/// Raw storage key of `MERKLE`, proven by light client replicas
+pub const MERKLE_KEY: &str = "merkle_incremental_merkle";
+pub const MERKLE: Item<IncrementalMerkle> = Item::new(MERKLE_KEY);
+
+/// Leaf index full tree storage was (re)enabled at. Absent if disabled
+pub const FULL_TREE_START: Item<u64> = Item::new("merkle_full_tree_start");
+/// Tree nodes keyed by `node_key(level, index)`. Level 0 holds leaves
+pub const NODES: Map<u64, H256> = Map::new("merkle_nodes");
+
+/// Key for node at `index` within `level`. Level in upper 32 bits, index in
+/// lower 32 bits
+pub fn node_key(level: usize, index: u64) -> u64 {
+    ((level as u64) << 32) | index
+}
@@ -1,19 +1,18 @@
-use common::States;
+use common::{SignerId, States};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, Event, MessageInfo, Response, StdResult,
+    to_binary, Api, Binary, Deps, DepsMut, Env, Event, MessageInfo, Response, StdResult,
 };
 use cw2::set_contract_version;
-use ethers_core::types::{RecoveryMessage, Signature, H160, H256};
+use ethers_core::types::{H160, H256};
 use sha3::{digest::Update, Digest, Keccak256};
-use std::convert::TryFrom;
 
 use crate::error::ContractError;
-use crate::state::{COMMITTED_ROOT, LOCAL_DOMAIN, STATE, UPDATER};
+use crate::state::{COMMITTED_ROOT, LEGACY_UPDATER, LOCAL_DOMAIN, STATE, THRESHOLD, UPDATERS};
 use common::nomad_base::{
     CommittedRootResponse, ExecuteMsg, HomeDomainHashResponse, InstantiateMsg, LocalDomainResponse,
-    QueryMsg, StateResponse, UpdaterResponse,
+    QueryMsg, StateResponse, UpdatersResponse,
 };
 
 // version info for migration info
@@ -29,16 +28,20 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     ownable::instantiate(deps.branch(), env, info, msg.clone().into())?;
 
+    common::validate_updater_set(&msg.updaters, msg.threshold)?;
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     LOCAL_DOMAIN.save(deps.storage, &msg.local_domain)?;
-    UPDATER.save(deps.storage, &msg.updater)?;
+    UPDATERS.save(deps.storage, &msg.updaters)?;
+    THRESHOLD.save(deps.storage, &msg.threshold)?;
     STATE.save(deps.storage, &States::Active)?;
     COMMITTED_ROOT.save(deps.storage, &H256::zero())?;
 
     Ok(Response::new()
         .add_attribute("method", "instantiate")
         .add_attribute("local_domain", msg.local_domain.to_string())
-        .add_attribute("updater", format!("{:?}", msg.updater)))
+        .add_attribute("updaters", format!("{:?}", msg.updaters))
+        .add_attribute("threshold", msg.threshold.to_string()))
 }
 
 pub fn not_failed(deps: Deps) -> Result<Response, ContractError> {
@@ -65,55 +68,129 @@ pub fn execute(
     }
 }
 
+/// Fail contract if two conflicting updates from `old_root` are each signed by
+/// at least threshold updaters. `fail` is passed the updaters that signed
+/// both updates.
 pub fn execute_double_update(
     deps: DepsMut,
     info: MessageInfo,
     old_root: H256,
     new_roots: [H256; 2],
-    signature: Vec<u8>,
-    signature_2: Vec<u8>,
-    fail: fn(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError>,
+    signatures: Vec<Vec<u8>>,
+    signatures_2: Vec<Vec<u8>>,
+    fail: fn(
+        deps: DepsMut,
+        info: MessageInfo,
+        updaters: Vec<SignerId>,
+    ) -> Result<Response, ContractError>,
 ) -> Result<Response, ContractError> {
     not_failed(deps.as_ref())?;
 
-    if is_updater_signature(deps.as_ref(), old_root, new_roots[0], &signature)?
-        && is_updater_signature(deps.as_ref(), old_root, new_roots[1], &signature_2)?
-        && new_roots[0] != new_roots[1]
+    if new_roots[0] != new_roots[1]
+        && is_updater_signature(deps.as_ref(), old_root, new_roots[0], &signatures)?
+        && is_updater_signature(deps.as_ref(), old_root, new_roots[1], &signatures_2)?
     {
-        fail(deps, info)?;
-        return Ok(Response::new().add_event(
-            Event::new("DoubleUpdate")
-                .add_attribute("old_root", format!("{:?}", old_root))
-                .add_attribute("new_roots", format!("{:?}", new_roots))
-                .add_attribute("signature", format!("{:?}", signature))
-                .add_attribute("signature_2", format!("{:?}", signature_2)),
-        ));
+        let signers_2 = update_signers(deps.as_ref(), old_root, new_roots[1], &signatures_2)?;
+        let equivocators: Vec<SignerId> =
+            update_signers(deps.as_ref(), old_root, new_roots[0], &signatures)?
+                .into_iter()
+                .filter(|signer| signers_2.contains(signer))
+                .collect();
+
+        let sub_msgs = fail(deps, info, equivocators.clone())?.messages;
+        return Ok(Response::new()
+            .add_event(
+                Event::new("DoubleUpdate")
+                    .add_attribute("old_root", format!("{:?}", old_root))
+                    .add_attribute("new_roots", format!("{:?}", new_roots))
+                    .add_attribute("signatures", format!("{:?}", signatures))
+                    .add_attribute("signatures_2", format!("{:?}", signatures_2))
+                    .add_attribute("equivocators", format!("{:?}", equivocators)),
+            )
+            .add_submessages(sub_msgs));
     }
 
     Err(ContractError::InvalidDoubleUpdate {})
 }
 
+/// Whether at least threshold distinct updaters signed update from `old_root`
+/// to `new_root`
 pub fn is_updater_signature(
     deps: Deps,
     old_root: H256,
     new_root: H256,
-    signature: &[u8],
+    signatures: &[Vec<u8>],
 ) -> Result<bool, ContractError> {
+    let threshold = THRESHOLD.load(deps.storage)?;
+    let signers = update_signers(deps, old_root, new_root, signatures)?;
+    Ok(signers.len() >= threshold as usize)
+}
+
+/// Distinct members of updater set that signed update from `old_root` to
+/// `new_root`. Signatures by non-updaters are ignored.
+pub fn update_signers(
+    deps: Deps,
+    old_root: H256,
+    new_root: H256,
+    signatures: &[Vec<u8>],
+) -> Result<Vec<SignerId>, ContractError> {
     let home_domain_hash = query_home_domain_hash(deps)?.home_domain_hash;
-    let updater = query_updater(deps)?.updater;
+    let digest = update_digest(home_domain_hash, old_root, new_root);
+    let updaters = UPDATERS.load(deps.storage)?;
+
+    Ok(verified_signers(deps.api, digest, &updaters, signatures))
+}
+
+/// Distinct members of `set` that signed `digest`. Each signature counts for
+/// the first member not yet counted that it verifies against.
+pub fn verified_signers(
+    api: &dyn Api,
+    digest: H256,
+    set: &[SignerId],
+    signatures: &[Vec<u8>],
+) -> Vec<SignerId> {
+    let mut signers: Vec<SignerId> = vec![];
+    for signature in signatures {
+        let signer = set
+            .iter()
+            .find(|member| !signers.contains(member) && member.verify(api, digest, signature));
+        if let Some(signer) = signer {
+            signers.push(signer.clone());
+        }
+    }
+
+    signers
+}
 
-    let digest = H256::from_slice(
+/// Digest updaters sign for update from `old_root` to `new_root` on home with
+/// `home_domain_hash`
+pub fn update_digest(home_domain_hash: H256, old_root: H256, new_root: H256) -> H256 {
+    H256::from_slice(
         Keccak256::new()
             .chain(home_domain_hash)
             .chain(old_root)
             .chain(new_root)
             .finalize()
             .as_slice(),
-    );
+    )
+}
 
-    let sig = Signature::try_from(signature)?;
-    let recovered_address = sig.recover(RecoveryMessage::Data(digest.as_bytes().to_vec()))?;
-    Ok(updater == recovered_address)
+/// Recover address that signed update from `old_root` to `new_root` for home
+/// with `home_domain_hash`. Recovery runs through the host's secp256k1 api
+pub fn recover_update_signer(
+    api: &dyn Api,
+    home_domain_hash: H256,
+    old_root: H256,
+    new_root: H256,
+    signature: &[u8],
+) -> Result<H160, ContractError> {
+    let digest = update_digest(home_domain_hash, old_root, new_root);
+
+    Ok(common::recover_eth_address(
+        api,
+        digest.as_bytes(),
+        signature,
+    )?)
 }
 
 pub fn domain_hash(domain: u32) -> H256 {
@@ -131,11 +208,30 @@ pub fn _set_failed(deps: DepsMut) -> Result<Response, ContractError> {
     Ok(Response::new())
 }
 
-pub fn _set_updater(deps: DepsMut, updater: H160) -> Result<Response, ContractError> {
-    UPDATER.save(deps.storage, &updater)?;
+pub fn _set_updaters(
+    deps: DepsMut,
+    updaters: Vec<SignerId>,
+    threshold: u32,
+) -> Result<Response, ContractError> {
+    common::validate_updater_set(&updaters, threshold)?;
 
-    Ok(Response::new()
-        .add_event(Event::new("SetUpdater").add_attribute("new_updater", format!("{:?}", updater))))
+    UPDATERS.save(deps.storage, &updaters)?;
+    THRESHOLD.save(deps.storage, &threshold)?;
+
+    Ok(Response::new().add_event(
+        Event::new("SetUpdaters")
+            .add_attribute("new_updaters", format!("{:?}", updaters))
+            .add_attribute("threshold", threshold.to_string()),
+    ))
+}
+
+/// Move single updater stored before updater sets into a 1-of-1 set
+pub fn migrate_legacy_updater(deps: DepsMut) -> Result<(), ContractError> {
+    let updater = LEGACY_UPDATER.load(deps.storage)?;
+    UPDATERS.save(deps.storage, &vec![SignerId::from(updater)])?;
+    THRESHOLD.save(deps.storage, &1)?;
+    LEGACY_UPDATER.remove(deps.storage);
+    Ok(())
 }
 
 pub fn _set_committed_root(deps: DepsMut, root: H256) -> Result<Response, ContractError> {
@@ -151,7 +247,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::HomeDomainHash {} => to_binary(&query_home_domain_hash(deps)?),
         QueryMsg::LocalDomain {} => to_binary(&query_local_domain(deps)?),
         QueryMsg::State {} => to_binary(&query_state(deps)?),
-        QueryMsg::Updater {} => to_binary(&query_updater(deps)?),
+        QueryMsg::Updaters {} => to_binary(&query_updaters(deps)?),
         QueryMsg::Owner {} => to_binary(&ownable::query_owner(deps)?),
     }
 }
@@ -178,9 +274,13 @@ pub fn query_state(deps: Deps) -> StdResult<StateResponse> {
     Ok(StateResponse { state })
 }
 
-pub fn query_updater(deps: Deps) -> StdResult<UpdaterResponse> {
-    let updater = UPDATER.load(deps.storage)?;
-    Ok(UpdaterResponse { updater })
+pub fn query_updaters(deps: Deps) -> StdResult<UpdatersResponse> {
+    let updaters = UPDATERS.load(deps.storage)?;
+    let threshold = THRESHOLD.load(deps.storage)?;
+    Ok(UpdatersResponse {
+        updaters,
+        threshold,
+    })
 }
 
 #[cfg(test)]
@@ -189,13 +289,18 @@ mod tests {
     use common::ownable::OwnerResponse;
     use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
     use cosmwasm_std::{coins, from_binary};
-    use test_utils::Updater;
+    use ethers_core::types::RecoveryMessage;
+    use test_utils::{event_attr_value_by_key, NativeKey, NativeUpdater, Updater};
 
     const LOCAL_DOMAIN: u32 = 1000;
     const UPDATER_PRIVKEY: &str =
         "1111111111111111111111111111111111111111111111111111111111111111";
 
-    fn mock_fail_fn(_deps: DepsMut, _info: MessageInfo) -> Result<Response, ContractError> {
+    fn mock_fail_fn(
+        _deps: DepsMut,
+        _info: MessageInfo,
+        _updaters: Vec<SignerId>,
+    ) -> Result<Response, ContractError> {
         Ok(Response::new())
     }
 
@@ -207,7 +312,8 @@ mod tests {
 
         let init_msg = InstantiateMsg {
             local_domain: LOCAL_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -229,10 +335,11 @@ mod tests {
         let value: LocalDomainResponse = from_binary(&res).unwrap();
         assert_eq!(LOCAL_DOMAIN, value.local_domain);
 
-        // Updater
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::Updater {}).unwrap();
-        let value: UpdaterResponse = from_binary(&res).unwrap();
-        assert_eq!(updater.address(), value.updater);
+        // Updaters
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Updaters {}).unwrap();
+        let value: UpdatersResponse = from_binary(&res).unwrap();
+        assert_eq!(vec![updater.signer_id()], value.updaters);
+        assert_eq!(1, value.threshold);
     }
 
     #[tokio::test]
@@ -243,7 +350,8 @@ mod tests {
 
         let init_msg = InstantiateMsg {
             local_domain: LOCAL_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -258,12 +366,49 @@ mod tests {
             deps.as_ref(),
             old_root,
             new_root,
-            &update.signature.to_vec(),
+            &[update.signature.to_vec()],
         )
         .unwrap();
         assert!(is_updater_sig)
     }
 
+    #[tokio::test]
+    async fn recovers_same_signer_as_ethers() {
+        let deps = mock_dependencies_with_balance(&coins(100, "token"));
+
+        for i in 1..=8u8 {
+            let updater: Updater =
+                Updater::from_privkey(&format!("{:02x}", i).repeat(32), LOCAL_DOMAIN);
+            let old_root = H256::repeat_byte(i);
+            let new_root = H256::repeat_byte(i.wrapping_mul(31));
+            let update = updater.sign_update(old_root, new_root).await.unwrap();
+
+            let digest = H256::from_slice(
+                Keccak256::new()
+                    .chain(domain_hash(LOCAL_DOMAIN))
+                    .chain(old_root)
+                    .chain(new_root)
+                    .finalize()
+                    .as_slice(),
+            );
+            let expected = update
+                .signature
+                .recover(RecoveryMessage::Data(digest.as_bytes().to_vec()))
+                .unwrap();
+
+            let signer = recover_update_signer(
+                &deps.api,
+                domain_hash(LOCAL_DOMAIN),
+                old_root,
+                new_root,
+                &update.signature.to_vec(),
+            )
+            .unwrap();
+            assert_eq!(expected, signer);
+            assert_eq!(updater.address(), signer);
+        }
+    }
+
     #[tokio::test]
     async fn rejects_invalid_updater_signature() {
         let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
@@ -276,7 +421,8 @@ mod tests {
 
         let init_msg = InstantiateMsg {
             local_domain: LOCAL_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -291,7 +437,7 @@ mod tests {
             deps.as_ref(),
             old_root,
             new_root,
-            &update.signature.to_vec(),
+            &[update.signature.to_vec()],
         )
         .unwrap();
         assert!(!is_updater_sig);
@@ -305,7 +451,8 @@ mod tests {
 
         let init_msg = InstantiateMsg {
             local_domain: LOCAL_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -323,8 +470,8 @@ mod tests {
             info.clone(),
             old_root,
             [new_root, bad_new_root],
-            update.signature.to_vec(),
-            double_update.signature.to_vec(),
+            vec![update.signature.to_vec()],
+            vec![double_update.signature.to_vec()],
             mock_fail_fn,
         );
 
@@ -339,7 +486,8 @@ mod tests {
 
         let init_msg = InstantiateMsg {
             local_domain: LOCAL_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -355,11 +503,183 @@ mod tests {
             info.clone(),
             old_root,
             [new_root, new_root],
-            update.signature.to_vec(),
-            update.signature.to_vec(),
+            vec![update.signature.to_vec()],
+            vec![update.signature.to_vec()],
             mock_fail_fn,
         );
 
         assert!(double_update_res.is_err());
     }
+
+    #[tokio::test]
+    async fn requires_threshold_updater_signatures() {
+        let updaters: Vec<Updater> = (1..=3)
+            .map(|i| Updater::from_privkey(&format!("{}", i).repeat(64), LOCAL_DOMAIN))
+            .collect();
+        let not_updater: Updater = Updater::from_privkey(&"4".repeat(64), LOCAL_DOMAIN);
+
+        let mut deps = mock_dependencies_with_balance(&coins(100, "token"));
+
+        // Threshold must be reachable by set
+        let init_msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            updaters: updaters.iter().map(|updater| updater.signer_id()).collect(),
+            threshold: 4,
+        };
+        let info = mock_info("owner", &coins(100, "earth"));
+        assert!(instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).is_err());
+
+        let init_msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            updaters: updaters.iter().map(|updater| updater.signer_id()).collect(),
+            threshold: 2,
+        };
+        instantiate(deps.as_mut(), mock_env(), info, init_msg).unwrap();
+
+        let old_root = H256::zero();
+        let new_root = H256::repeat_byte(1);
+        let mut signatures = vec![];
+        for signer in [&updaters[0], &updaters[2], &not_updater] {
+            let update = signer.sign_update(old_root, new_root).await.unwrap();
+            signatures.push(update.signature.to_vec());
+        }
+
+        // One updater signature and a duplicate of it are below threshold
+        let below_threshold = vec![signatures[0].clone(), signatures[0].clone()];
+        assert!(
+            !is_updater_signature(deps.as_ref(), old_root, new_root, &below_threshold).unwrap()
+        );
+
+        // Non-updater signature does not count towards threshold
+        let with_outsider = vec![signatures[0].clone(), signatures[2].clone()];
+        assert!(!is_updater_signature(deps.as_ref(), old_root, new_root, &with_outsider).unwrap());
+
+        assert!(is_updater_signature(deps.as_ref(), old_root, new_root, &signatures).unwrap());
+        assert_eq!(
+            vec![updaters[0].signer_id(), updaters[2].signer_id()],
+            update_signers(deps.as_ref(), old_root, new_root, &signatures).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_equivocators_of_quorum_double_update() {
+        let updaters: Vec<Updater> = (1..=3)
+            .map(|i| Updater::from_privkey(&format!("{}", i).repeat(64), LOCAL_DOMAIN))
+            .collect();
+
+        let mut deps = mock_dependencies_with_balance(&coins(100, "token"));
+
+        let init_msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            updaters: updaters.iter().map(|updater| updater.signer_id()).collect(),
+            threshold: 2,
+        };
+        let info = mock_info("owner", &coins(100, "earth"));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+        let old_root = H256::zero();
+        let new_root = H256::repeat_byte(1);
+        let bad_new_root = H256::repeat_byte(2);
+
+        // Updaters 0 and 1 sign new root, updaters 1 and 2 sign conflicting root
+        let mut signatures = vec![];
+        for signer in &updaters[..2] {
+            let update = signer.sign_update(old_root, new_root).await.unwrap();
+            signatures.push(update.signature.to_vec());
+        }
+        let mut signatures_2 = vec![];
+        for signer in &updaters[1..] {
+            let update = signer.sign_update(old_root, bad_new_root).await.unwrap();
+            signatures_2.push(update.signature.to_vec());
+        }
+
+        // Conflicting update below threshold is not a double update
+        let res = execute_double_update(
+            deps.as_mut(),
+            info.clone(),
+            old_root,
+            [new_root, bad_new_root],
+            signatures.clone(),
+            signatures_2[..1].to_vec(),
+            mock_fail_fn,
+        );
+        assert!(res.is_err());
+
+        let res = execute_double_update(
+            deps.as_mut(),
+            info,
+            old_root,
+            [new_root, bad_new_root],
+            signatures,
+            signatures_2,
+            mock_fail_fn,
+        )
+        .unwrap();
+
+        let event = &res.events[0];
+        assert_eq!("DoubleUpdate", event.ty);
+        assert_eq!(
+            format!("{:?}", vec![updaters[1].signer_id()]),
+            event_attr_value_by_key(&event, "equivocators").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn counts_signatures_of_each_signer_kind() {
+        let eth_updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+        let secp256k1_updater =
+            NativeUpdater::new(NativeKey::secp256k1(&"2".repeat(64)), LOCAL_DOMAIN);
+        let ed25519_updater = NativeUpdater::new(NativeKey::ed25519(&"3".repeat(64)), LOCAL_DOMAIN);
+        let not_updater = NativeUpdater::new(NativeKey::ed25519(&"4".repeat(64)), LOCAL_DOMAIN);
+
+        let mut deps = mock_dependencies_with_balance(&coins(100, "token"));
+
+        let init_msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            updaters: vec![
+                eth_updater.signer_id(),
+                secp256k1_updater.signer_id(),
+                ed25519_updater.signer_id(),
+            ],
+            threshold: 3,
+        };
+        let info = mock_info("owner", &coins(100, "earth"));
+        instantiate(deps.as_mut(), mock_env(), info, init_msg).unwrap();
+
+        let old_root = H256::zero();
+        let new_root = H256::repeat_byte(1);
+        let eth_signature = eth_updater
+            .sign_update(old_root, new_root)
+            .await
+            .unwrap()
+            .signature
+            .to_vec();
+        let secp256k1_signature = secp256k1_updater.sign_update(old_root, new_root);
+        let ed25519_signature = ed25519_updater.sign_update(old_root, new_root);
+
+        // Native signatures verify regardless of order
+        let signatures = vec![
+            ed25519_signature.clone(),
+            eth_signature.clone(),
+            secp256k1_signature.clone(),
+        ];
+        assert!(is_updater_signature(deps.as_ref(), old_root, new_root, &signatures).unwrap());
+        assert_eq!(
+            vec![
+                ed25519_updater.signer_id(),
+                eth_updater.signer_id(),
+                secp256k1_updater.signer_id(),
+            ],
+            update_signers(deps.as_ref(), old_root, new_root, &signatures).unwrap()
+        );
+
+        // Non-updater and signature over different root do not count
+        let signatures = vec![
+            eth_signature,
+            secp256k1_signature,
+            not_updater.sign_update(old_root, new_root),
+            ed25519_updater.sign_update(old_root, H256::repeat_byte(2)),
+        ];
+        assert!(!is_updater_signature(deps.as_ref(), old_root, new_root, &signatures).unwrap());
+    }
 }
@@ -13,7 +13,10 @@ pub enum ContractError {
     FailedState {},
 
     #[error("{0}")]
-    SignatureError(#[from] ethers_core::types::SignatureError),
+    SignatureError(#[from] common::SignatureError),
+
+    #[error("{0}")]
+    UpdaterSetError(#[from] common::UpdaterSetError),
 
     #[error("{0}")]
     OwnableError(#[from] ownable::ContractError),
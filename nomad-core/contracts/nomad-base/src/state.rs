@@ -1,7 +1,7 @@
 use ethers_core::types::{H160, H256};
 use serde::{Deserialize, Serialize};
 
-use common::States;
+use common::{SignerId, States};
 use cosmwasm_std::Addr;
 use cw_storage_plus::Item;
 
@@ -14,6 +14,10 @@ pub struct State {
 }
 
 pub const LOCAL_DOMAIN: Item<u32> = Item::new("nomad_base_local_domain");
-pub const UPDATER: Item<H160> = Item::new("nomad_base_updater");
+pub const UPDATERS: Item<Vec<SignerId>> = Item::new("nomad_base_updaters");
+pub const THRESHOLD: Item<u32> = Item::new("nomad_base_threshold");
 pub const STATE: Item<States> = Item::new("nomad_base_state");
 pub const COMMITTED_ROOT: Item<H256> = Item::new("nomad_base_committed_root");
+
+// Single updater stored before updater sets. Only read by migration
+pub const LEGACY_UPDATER: Item<H160> = Item::new("nomad_base_updater");
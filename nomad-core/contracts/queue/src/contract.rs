@@ -1,13 +1,13 @@
-use std::collections::VecDeque;
-
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage,
+};
 use cw2::set_contract_version;
 use ethers_core::types::H256;
 
 use crate::error::ContractError;
-use crate::state::QUEUE;
+use crate::state::{HEAD, INDEX, ITEMS, LEGACY_QUEUE, TAIL};
 use common::queue::{
     ContainsResponse, EndResponse, ExecuteMsg, FrontResponse, InstantiateMsg, IsEmptyResponse,
     LengthResponse, QueryMsg,
@@ -24,9 +24,9 @@ pub fn instantiate(
     _info: MessageInfo,
     _msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    let queue = VecDeque::<H256>::new();
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    QUEUE.save(deps.storage, &queue)?;
+    HEAD.save(deps.storage, &0)?;
+    TAIL.save(deps.storage, &0)?;
 
     Ok(Response::new())
 }
@@ -43,38 +43,118 @@ pub fn execute(
         ExecuteMsg::Dequeue {} => execute_dequeue(deps),
         ExecuteMsg::EnqueueBatch { items } => execute_enqueue_batch(deps, items),
         ExecuteMsg::DequeueBatch { number } => execute_dequeue_batch(deps, number),
+        ExecuteMsg::DequeueThrough { item } => execute_dequeue_through(deps, item),
     }
 }
 
 pub fn execute_enqueue(deps: DepsMut, item: H256) -> Result<Response, ContractError> {
-    let mut queue = QUEUE.load(deps.storage)?;
-    queue.push_back(item);
-    QUEUE.save(deps.storage, &queue)?;
+    let tail = TAIL.load(deps.storage)?;
+    _enqueue_at(deps.storage, tail, item)?;
+    TAIL.save(deps.storage, &(tail + 1))?;
     Ok(Response::new().add_attribute("action", "enqueue"))
 }
 
 pub fn execute_dequeue(deps: DepsMut) -> Result<Response, ContractError> {
-    let mut queue = QUEUE.load(deps.storage)?;
-    let item = queue.pop_front().ok_or(ContractError::QueueEmpty {})?;
-    QUEUE.save(deps.storage, &queue)?;
+    let head = HEAD.load(deps.storage)?;
+    let tail = TAIL.load(deps.storage)?;
+    if head == tail {
+        return Err(ContractError::QueueEmpty {});
+    }
+
+    let item = _remove_at(deps.storage, head)?;
+    HEAD.save(deps.storage, &(head + 1))?;
     Ok(Response::new().set_data(to_binary(&item)?))
 }
 
 pub fn execute_enqueue_batch(deps: DepsMut, items: Vec<H256>) -> Result<Response, ContractError> {
-    let mut queue = QUEUE.load(deps.storage)?;
-    queue.extend(items.iter());
-    QUEUE.save(deps.storage, &queue)?;
+    let mut tail = TAIL.load(deps.storage)?;
+    for item in items {
+        _enqueue_at(deps.storage, tail, item)?;
+        tail += 1;
+    }
+    TAIL.save(deps.storage, &tail)?;
     Ok(Response::new().add_attribute("action", "enqueue_batch"))
 }
 
 pub fn execute_dequeue_batch(deps: DepsMut, number: u64) -> Result<Response, ContractError> {
-    let mut queue = QUEUE.load(deps.storage)?;
-    let drained: Vec<H256> = queue.drain(0..(number as usize)).collect();
-    QUEUE.save(deps.storage, &queue)?;
+    let head = HEAD.load(deps.storage)?;
+    let tail = TAIL.load(deps.storage)?;
+    if number > tail - head {
+        return Err(ContractError::QueueEmpty {});
+    }
+
+    let drained = (head..head + number)
+        .map(|index| _remove_at(deps.storage, index))
+        .collect::<StdResult<Vec<H256>>>()?;
+    HEAD.save(deps.storage, &(head + number))?;
 
     Ok(Response::new().set_data(to_binary(&drained)?))
 }
 
+/// Dequeue every item up to and including the most recent occurrence of
+/// `item`. Cost scales with the number of items removed, not queue length.
+/// Returns number of items dequeued.
+///
+/// Unlike dequeuing one item at a time until `item` comes out, which stops
+/// at its first occurrence, this also removes earlier duplicates and any
+/// items between them. The two only differ for repeated items; home's
+/// roots never repeat as each commits to a larger tree.
+pub fn execute_dequeue_through(deps: DepsMut, item: H256) -> Result<Response, ContractError> {
+    let head = HEAD.load(deps.storage)?;
+    let position =
+        _position(deps.storage, head, item)?.ok_or(ContractError::ItemNotInQueue { item })?;
+
+    for index in head..=position {
+        _remove_at(deps.storage, index)?;
+    }
+    HEAD.save(deps.storage, &(position + 1))?;
+
+    let dequeued = position + 1 - head;
+    Ok(Response::new().set_data(to_binary(&dequeued)?))
+}
+
+/// Move queue stored in pre-v0.2 single-item layout into indexed storage.
+/// No-op if there is no legacy queue. Meant to be called from the migrate
+/// entry point of contracts embedding the queue.
+pub fn migrate_legacy_queue(deps: DepsMut) -> Result<(), ContractError> {
+    let legacy = match LEGACY_QUEUE.may_load(deps.storage)? {
+        Some(legacy) => legacy,
+        None => return Ok(()),
+    };
+
+    HEAD.save(deps.storage, &0)?;
+    TAIL.save(deps.storage, &(legacy.len() as u64))?;
+    for (index, item) in legacy.into_iter().enumerate() {
+        _enqueue_at(deps.storage, index as u64, item)?;
+    }
+    LEGACY_QUEUE.remove(deps.storage);
+
+    Ok(())
+}
+
+fn _enqueue_at(storage: &mut dyn Storage, index: u64, item: H256) -> StdResult<()> {
+    ITEMS.save(storage, index, &item)?;
+    INDEX.save(storage, item.as_bytes(), &index)
+}
+
+/// Remove item at `index`, clearing its reverse index entry unless the item
+/// was enqueued again later
+fn _remove_at(storage: &mut dyn Storage, index: u64) -> StdResult<H256> {
+    let item = ITEMS.load(storage, index)?;
+    ITEMS.remove(storage, index);
+    if INDEX.may_load(storage, item.as_bytes())? == Some(index) {
+        INDEX.remove(storage, item.as_bytes());
+    }
+    Ok(item)
+}
+
+/// Index of most recent occurrence of `item` if it is still queued
+fn _position(storage: &dyn Storage, head: u64, item: H256) -> StdResult<Option<u64>> {
+    Ok(INDEX
+        .may_load(storage, item.as_bytes())?
+        .filter(|index| *index >= head))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -87,45 +167,100 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 }
 
 pub fn query_contains(deps: Deps, item: H256) -> StdResult<ContainsResponse> {
-    let queue = QUEUE.load(deps.storage)?;
+    let head = HEAD.load(deps.storage)?;
     Ok(ContainsResponse {
-        contains: queue.contains(&item),
+        contains: _position(deps.storage, head, item)?.is_some(),
     })
 }
 
 pub fn query_last_item(deps: Deps) -> StdResult<EndResponse> {
-    let queue = QUEUE.load(deps.storage)?;
-    Ok(EndResponse {
-        item: queue.back().map_or(H256::zero(), |item| item.clone()),
-    })
+    let head = HEAD.load(deps.storage)?;
+    let tail = TAIL.load(deps.storage)?;
+    let item = if head == tail {
+        H256::zero()
+    } else {
+        ITEMS.load(deps.storage, tail - 1)?
+    };
+    Ok(EndResponse { item })
 }
 
 pub fn query_peek(deps: Deps) -> StdResult<FrontResponse> {
-    let queue = QUEUE.load(deps.storage)?;
+    let head = HEAD.load(deps.storage)?;
     Ok(FrontResponse {
-        item: queue.front().expect("queue empty").to_owned(),
+        item: ITEMS.load(deps.storage, head)?,
     })
 }
 
 pub fn query_is_empty(deps: Deps) -> StdResult<IsEmptyResponse> {
-    let queue = QUEUE.load(deps.storage)?;
+    let head = HEAD.load(deps.storage)?;
+    let tail = TAIL.load(deps.storage)?;
     Ok(IsEmptyResponse {
-        is_empty: queue.is_empty(),
+        is_empty: head == tail,
     })
 }
 
 pub fn query_length(deps: Deps) -> StdResult<LengthResponse> {
-    let queue = QUEUE.load(deps.storage)?;
+    let head = HEAD.load(deps.storage)?;
+    let tail = TAIL.load(deps.storage)?;
     Ok(LengthResponse {
-        length: queue.len(),
+        length: (tail - head) as usize,
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
-    use cosmwasm_std::{coins, from_binary};
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coins, from_binary, Order, QuerierWrapper};
+    use std::cell::Cell;
+
+    /// Storage wrapper charging gas using the Cosmos SDK default KV gas
+    /// config, used to check queue operation costs do not grow with length
+    #[derive(Default)]
+    struct MeteredStorage {
+        inner: MockStorage,
+        gas: Cell<u64>,
+    }
+
+    impl MeteredStorage {
+        fn charge(&self, amount: usize) {
+            self.gas.set(self.gas.get() + amount as u64);
+        }
+
+        fn take_gas(&self) -> u64 {
+            self.gas.replace(0)
+        }
+    }
+
+    impl Storage for MeteredStorage {
+        fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+            let value = self.inner.get(key);
+            let value_len = value.as_ref().map_or(0, |value| value.len());
+            self.charge(1000 + 3 * (key.len() + value_len));
+            value
+        }
+
+        fn range<'a>(
+            &'a self,
+            start: Option<&[u8]>,
+            end: Option<&[u8]>,
+            order: Order,
+        ) -> Box<dyn Iterator<Item = (Vec<u8>, Vec<u8>)> + 'a> {
+            self.inner.range(start, end, order)
+        }
+
+        fn set(&mut self, key: &[u8], value: &[u8]) {
+            self.charge(2000 + 30 * (key.len() + value.len()));
+            self.inner.set(key, value)
+        }
+
+        fn remove(&mut self, key: &[u8]) {
+            self.charge(1000);
+            self.inner.remove(key)
+        }
+    }
 
     #[test]
     fn proper_initialization() {
@@ -224,4 +359,170 @@ mod tests {
         let value: LengthResponse = from_binary(&res).unwrap();
         assert_eq!(1, value.length);
     }
+
+    #[test]
+    fn dequeues_through_item() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let msg = InstantiateMsg {};
+        let info = mock_info("owner", &coins(100, "earth"));
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let items: Vec<H256> = (1..=5).map(H256::repeat_byte).collect();
+        execute_enqueue_batch(deps.as_mut(), items).unwrap();
+
+        // Dequeue first 3
+        let res = execute_dequeue_through(deps.as_mut(), H256::repeat_byte(3)).unwrap();
+        let dequeued: u64 = from_binary(&res.data.unwrap()).unwrap();
+        assert_eq!(3, dequeued);
+
+        let res = query_length(deps.as_ref()).unwrap();
+        assert_eq!(2, res.length);
+        let res = query_peek(deps.as_ref()).unwrap();
+        assert_eq!(H256::repeat_byte(4), res.item);
+
+        // Dequeued items no longer contained
+        let res = query_contains(deps.as_ref(), H256::repeat_byte(2)).unwrap();
+        assert!(!res.contains);
+        let res = query_contains(deps.as_ref(), H256::repeat_byte(5)).unwrap();
+        assert!(res.contains);
+
+        // Cannot dequeue through item no longer in queue
+        let err = execute_dequeue_through(deps.as_mut(), H256::repeat_byte(3)).unwrap_err();
+        assert!(matches!(err, ContractError::ItemNotInQueue { .. }));
+
+        // Dequeue batch past end fails
+        let err = execute_dequeue_batch(deps.as_mut(), 3).unwrap_err();
+        assert!(matches!(err, ContractError::QueueEmpty {}));
+    }
+
+    #[test]
+    fn tracks_duplicate_items() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let msg = InstantiateMsg {};
+        let info = mock_info("owner", &coins(100, "earth"));
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let duplicate = H256::repeat_byte(1);
+        let items = vec![duplicate, H256::repeat_byte(2), duplicate];
+        execute_enqueue_batch(deps.as_mut(), items).unwrap();
+
+        // Dequeuing first occurrence keeps later occurrence contained
+        execute_dequeue(deps.as_mut()).unwrap();
+        let res = query_contains(deps.as_ref(), duplicate).unwrap();
+        assert!(res.contains);
+
+        // Dequeue through goes to most recent occurrence
+        let res = execute_dequeue_through(deps.as_mut(), duplicate).unwrap();
+        let dequeued: u64 = from_binary(&res.data.unwrap()).unwrap();
+        assert_eq!(2, dequeued);
+
+        let res = query_is_empty(deps.as_ref()).unwrap();
+        assert!(res.is_empty);
+        let res = query_contains(deps.as_ref(), duplicate).unwrap();
+        assert!(!res.contains);
+
+        // Dequeue through at head skips past first occurrence to most recent
+        let items = vec![
+            duplicate,
+            H256::repeat_byte(2),
+            duplicate,
+            H256::repeat_byte(3),
+        ];
+        execute_enqueue_batch(deps.as_mut(), items).unwrap();
+
+        let res = execute_dequeue_through(deps.as_mut(), duplicate).unwrap();
+        let dequeued: u64 = from_binary(&res.data.unwrap()).unwrap();
+        assert_eq!(3, dequeued);
+
+        let res = query_length(deps.as_ref()).unwrap();
+        assert_eq!(1, res.length);
+        let res = query_contains(deps.as_ref(), H256::repeat_byte(2)).unwrap();
+        assert!(!res.contains);
+    }
+
+    #[test]
+    fn gas_flat_over_10k_queued_roots() {
+        let mut storage = MeteredStorage::default();
+        let api = MockApi::default();
+        let querier = MockQuerier::new(&[]);
+        macro_rules! deps_mut {
+            () => {
+                DepsMut {
+                    storage: &mut storage,
+                    api: &api,
+                    querier: QuerierWrapper::new(&querier),
+                }
+            };
+        }
+
+        let info = mock_info("owner", &coins(100, "earth"));
+        instantiate(deps_mut!(), mock_env(), info, InstantiateMsg {}).unwrap();
+
+        let root = |index: u64| H256::from_low_u64_be(index + 1);
+
+        // Gas of enqueue, contains and dequeue through the front root
+        let measure = |storage: &mut MeteredStorage, next: u64| -> (u64, u64, u64) {
+            storage.take_gas();
+            execute_enqueue(
+                DepsMut {
+                    storage: &mut *storage,
+                    api: &api,
+                    querier: QuerierWrapper::new(&querier),
+                },
+                root(next),
+            )
+            .unwrap();
+            let enqueue_gas = storage.take_gas();
+
+            let head = HEAD.load(&*storage).unwrap();
+            storage.take_gas();
+            let deps = Deps {
+                storage: &*storage,
+                api: &api,
+                querier: QuerierWrapper::new(&querier),
+            };
+            assert!(query_contains(deps, root(next)).unwrap().contains);
+            let contains_gas = storage.take_gas();
+
+            execute_dequeue_through(
+                DepsMut {
+                    storage: &mut *storage,
+                    api: &api,
+                    querier: QuerierWrapper::new(&querier),
+                },
+                root(head),
+            )
+            .unwrap();
+            let dequeue_gas = storage.take_gas();
+
+            (enqueue_gas, contains_gas, dequeue_gas)
+        };
+
+        // Baseline with a single queued root
+        execute_enqueue(deps_mut!(), root(0)).unwrap();
+        let baseline = measure(&mut storage, 1);
+
+        // Fill queue to 10k roots
+        let items: Vec<H256> = (2..10_002).map(root).collect();
+        execute_enqueue_batch(deps_mut!(), items).unwrap();
+        let full = measure(&mut storage, 10_002);
+
+        // Only stored index digits grow, not number of storage accesses.
+        // Gas with 10k queued roots stays within 5% of a single root
+        for (op, baseline, full) in [
+            ("enqueue", baseline.0, full.0),
+            ("contains", baseline.1, full.1),
+            ("dequeue through", baseline.2, full.2),
+        ] {
+            assert!(
+                full <= baseline + baseline / 20,
+                "{} gas grew from {} to {} over 10k queued roots",
+                op,
+                baseline,
+                full
+            );
+        }
+    }
 }
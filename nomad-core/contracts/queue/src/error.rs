@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use ethers_core::types::H256;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -8,4 +9,7 @@ pub enum ContractError {
 
     #[error("Queue empty")]
     QueueEmpty {},
+
+    #[error("Item not in queue: {item}")]
+    ItemNotInQueue { item: H256 },
 }
@@ -1,5 +1,16 @@
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 use ethers_core::types::H256;
 use std::collections::VecDeque;
 
-pub const QUEUE: Item<VecDeque<H256>> = Item::new("queue_queue");
+/// Index of front item
+pub const HEAD: Item<u64> = Item::new("queue_head");
+/// Index one past the last item. Queue is empty when `HEAD == TAIL`.
+pub const TAIL: Item<u64> = Item::new("queue_tail");
+/// Queue index -> item
+pub const ITEMS: Map<u64, H256> = Map::new("queue_items");
+/// Item -> index of its most recent enqueue
+pub const INDEX: Map<&[u8], u64> = Map::new("queue_index");
+
+/// Pre-v0.2 layout storing the whole queue in a single item. Only read when
+/// migrating.
+pub const LEGACY_QUEUE: Item<VecDeque<H256>> = Item::new("queue_queue");
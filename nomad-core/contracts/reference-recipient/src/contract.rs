@@ -0,0 +1,106 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, Event, MessageInfo, Response, StdResult,
+};
+use cw2::set_contract_version;
+use ethers_core::types::H256;
+
+use crate::error::ContractError;
+use crate::state::{LAST_MESSAGE, MESSAGE_COUNT};
+use common::reference_recipient::{
+    ConnectionManagerResponse, ExecuteMsg, InstantiateMsg, LastMessageResponse,
+    MessageCountResponse, QueryMsg, ReceivedMessage,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:reference-recipient";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    nomad_recipient::set_connection_manager(deps.branch(), &msg.connection_manager)?;
+    MESSAGE_COUNT.save(deps.storage, &0)?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::HandleMsg {
+            origin,
+            nonce,
+            sender,
+            message,
+        } => execute_handle(deps, info, origin, nonce, sender, message),
+    }
+}
+
+pub fn execute_handle(
+    deps: DepsMut,
+    info: MessageInfo,
+    origin: u32,
+    nonce: u32,
+    sender: H256,
+    message: Vec<u8>,
+) -> Result<Response, ContractError> {
+    // Caller must be enrolled replica for claimed origin
+    nomad_recipient::only_replica(deps.as_ref(), &info, origin)?;
+
+    MESSAGE_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+    LAST_MESSAGE.save(
+        deps.storage,
+        &ReceivedMessage {
+            origin,
+            nonce,
+            sender,
+            message,
+        },
+    )?;
+
+    Ok(Response::new().add_event(
+        Event::new("Handle")
+            .add_attribute("origin", origin.to_string())
+            .add_attribute("nonce", nonce.to_string())
+            .add_attribute("sender", format!("{:?}", sender)),
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::ConnectionManager {} => to_binary(&query_connection_manager(deps)?),
+        QueryMsg::MessageCount {} => to_binary(&query_message_count(deps)?),
+        QueryMsg::LastMessage {} => to_binary(&query_last_message(deps)?),
+    }
+}
+
+pub fn query_connection_manager(deps: Deps) -> StdResult<ConnectionManagerResponse> {
+    let connection_manager = nomad_recipient::CONNECTION_MANAGER.load(deps.storage)?;
+    Ok(ConnectionManagerResponse {
+        connection_manager: connection_manager.to_string(),
+    })
+}
+
+pub fn query_message_count(deps: Deps) -> StdResult<MessageCountResponse> {
+    let count = MESSAGE_COUNT.load(deps.storage)?;
+    Ok(MessageCountResponse { count })
+}
+
+pub fn query_last_message(deps: Deps) -> StdResult<LastMessageResponse> {
+    let message = LAST_MESSAGE.may_load(deps.storage)?;
+    Ok(LastMessageResponse { message })
+}
@@ -0,0 +1,6 @@
+use cw_storage_plus::Item;
+
+use common::reference_recipient::ReceivedMessage;
+
+pub const MESSAGE_COUNT: Item<u64> = Item::new("reference_recipient_message_count");
+pub const LAST_MESSAGE: Item<ReceivedMessage> = Item::new("reference_recipient_last_message");
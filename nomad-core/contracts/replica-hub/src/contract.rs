@@ -0,0 +1,978 @@
+use common::nomad_base::{
+    CommittedRootResponse, HomeDomainHashResponse, StateResponse, UpdatersResponse,
+};
+use common::{merkle_tree, Decode, MessageStatus, NomadMessage, SignerId, States};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Binary, Deps, DepsMut, Env, Event, MessageInfo, Reply, Response,
+    StdError, StdResult,
+};
+use cw2::{get_contract_version, set_contract_version};
+use ethers_core::types::H256;
+use semver::Version;
+
+use crate::error::ContractError;
+use crate::state::{
+    confirm_at_key, COMMITTED_ROOTS, CONFIRM_AT, LEGACY_UPDATERS, OPTIMISTIC_SECONDS,
+    REMOTE_DOMAINS, STATES, THRESHOLDS, UPDATERS,
+};
+use common::replica::{
+    AcceptableRootResponse, ConfirmAtResponse, MessageStatusResponse, OptimisticSecondsResponse,
+};
+use common::replica_hub::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, RemoteDomainsResponse, RemoteHome,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:replica-hub";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    ownable::instantiate(deps.branch(), env, info, common::ownable::InstantiateMsg {})?;
+
+    nomad_base::state::LOCAL_DOMAIN.save(deps.storage, &msg.local_domain)?;
+    REMOTE_DOMAINS.save(deps.storage, &vec![])?;
+
+    for remote in msg.remotes {
+        _enroll_remote(deps.branch(), remote)?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Update {
+            domain,
+            committed_root,
+            new_root,
+            signatures,
+        } => execute_update(deps, env, domain, committed_root, new_root, signatures),
+        ExecuteMsg::DoubleUpdate {
+            domain,
+            old_root,
+            new_roots,
+            signatures,
+            signatures_2,
+        } => execute_double_update(deps, domain, old_root, new_roots, signatures, signatures_2),
+        ExecuteMsg::Prove {
+            message,
+            proof,
+            index,
+        } => execute_prove(deps, env, message, proof, index),
+        ExecuteMsg::Process { message } => execute_process(deps, env, info, message),
+        ExecuteMsg::ProveAndProcess {
+            message,
+            proof,
+            index,
+        } => execute_prove_and_process(deps, env, info, message, proof, index),
+        ExecuteMsg::RetryProcess { message } => {
+            Ok(replica::execute_retry_process(deps, env, info, message)?)
+        }
+        ExecuteMsg::EnrollRemote { remote } => execute_enroll_remote(deps, info, remote),
+        ExecuteMsg::SetConfirmation {
+            domain,
+            root,
+            confirm_at,
+        } => execute_set_confirmation(deps, info, domain, root, confirm_at),
+        ExecuteMsg::SetOptimisticTimeout {
+            domain,
+            optimistic_seconds,
+        } => execute_set_optimistic_timeout(deps, info, domain, optimistic_seconds),
+        ExecuteMsg::SetUpdaters {
+            domain,
+            updaters,
+            threshold,
+        } => execute_set_updaters(deps, info, domain, updaters, threshold),
+        ExecuteMsg::SetProcessGas { process_gas } => {
+            Ok(replica::execute_set_process_gas(deps, info, process_gas)?)
+        }
+        ExecuteMsg::RenounceOwnership {} => Ok(ownable::execute_renounce_ownership(deps, info)?),
+        ExecuteMsg::TransferOwnership { new_owner } => {
+            Ok(ownable::execute_transfer_ownership(deps, info, new_owner)?)
+        }
+    }
+}
+
+pub fn execute_update(
+    deps: DepsMut,
+    env: Env,
+    domain: u32,
+    old_root: H256,
+    new_root: H256,
+    signatures: Vec<Vec<u8>>,
+) -> Result<Response, ContractError> {
+    not_failed(deps.as_ref(), domain)?;
+
+    let committed_root = COMMITTED_ROOTS.load(deps.storage, domain)?;
+    if old_root != committed_root {
+        return Err(ContractError::NotCurrentCommittedRoot { domain, old_root });
+    }
+
+    if !is_updater_signature(deps.as_ref(), domain, old_root, new_root, &signatures)? {
+        return Err(ContractError::NotUpdaterSignature {});
+    }
+
+    let optimistic_seconds = OPTIMISTIC_SECONDS.load(deps.storage, domain)?;
+    let confirm_at = env.block.time.seconds() + optimistic_seconds;
+    CONFIRM_AT.save(deps.storage, &confirm_at_key(domain, new_root), &confirm_at)?;
+
+    COMMITTED_ROOTS.save(deps.storage, domain, &new_root)?;
+
+    Ok(Response::new().add_event(
+        Event::new("Update")
+            .add_attribute("home_domain", domain.to_string())
+            .add_attribute("committed_root", format!("{:?}", committed_root))
+            .add_attribute("new_root", format!("{:?}", new_root))
+            .add_attribute("signatures", format!("{:?}", signatures)),
+    ))
+}
+
+/// Fail `domain` if two conflicting updates from `old_root` are each signed
+/// by at least threshold of its updaters. Updaters that signed both are
+/// reported as equivocators.
+pub fn execute_double_update(
+    deps: DepsMut,
+    domain: u32,
+    old_root: H256,
+    new_roots: [H256; 2],
+    signatures: Vec<Vec<u8>>,
+    signatures_2: Vec<Vec<u8>>,
+) -> Result<Response, ContractError> {
+    not_failed(deps.as_ref(), domain)?;
+
+    if new_roots[0] != new_roots[1]
+        && is_updater_signature(deps.as_ref(), domain, old_root, new_roots[0], &signatures)?
+        && is_updater_signature(deps.as_ref(), domain, old_root, new_roots[1], &signatures_2)?
+    {
+        let signers_2 =
+            update_signers(deps.as_ref(), domain, old_root, new_roots[1], &signatures_2)?;
+        let equivocators: Vec<SignerId> =
+            update_signers(deps.as_ref(), domain, old_root, new_roots[0], &signatures)?
+                .into_iter()
+                .filter(|signer| signers_2.contains(signer))
+                .collect();
+
+        // Only the domain with the fraudulent updaters fails
+        STATES.save(deps.storage, domain, &States::Failed)?;
+        return Ok(Response::new().add_event(
+            Event::new("DoubleUpdate")
+                .add_attribute("home_domain", domain.to_string())
+                .add_attribute("old_root", format!("{:?}", old_root))
+                .add_attribute("new_roots", format!("{:?}", new_roots))
+                .add_attribute("signatures", format!("{:?}", signatures))
+                .add_attribute("signatures_2", format!("{:?}", signatures_2))
+                .add_attribute("equivocators", format!("{:?}", equivocators)),
+        ));
+    }
+
+    Err(ContractError::InvalidDoubleUpdate {})
+}
+
+pub fn execute_prove(
+    deps: DepsMut,
+    env: Env,
+    message: Vec<u8>,
+    proof: [H256; 32],
+    index: u64,
+) -> Result<Response, ContractError> {
+    let nomad_message = NomadMessage::read_from(&mut message.as_slice())
+        .map_err(|_| ContractError::MalformedMessage {})?;
+    let leaf = nomad_message.to_leaf();
+
+    // Roots of a failed domain may be fraudulent
+    not_failed(deps.as_ref(), nomad_message.origin)?;
+
+    let message_status = replica::query_message_status(deps.as_ref(), leaf)?.status;
+    if message_status != MessageStatus::None {
+        return Err(ContractError::MessageAlreadyProven { leaf });
+    }
+
+    let calculated_root = merkle_tree::merkle_root_from_branch(
+        leaf,
+        &proof[..],
+        merkle_tree::TREE_DEPTH,
+        index as usize,
+    );
+
+    let acceptable_root =
+        query_acceptable_root(deps.as_ref(), env, nomad_message.origin, calculated_root)?
+            .acceptable;
+    if acceptable_root {
+        replica::_set_message_proven(deps, leaf)?;
+        return Ok(Response::new().set_data(to_binary(&true)?));
+    }
+
+    Ok(Response::new().set_data(to_binary(&false)?))
+}
+
+/// Dispatch proven message to its recipient. Message statuses are not keyed
+/// by origin since leaves already commit to it.
+pub fn execute_process(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    message: Vec<u8>,
+) -> Result<Response, ContractError> {
+    Ok(replica::execute_process(deps, env, info, message)?)
+}
+
+pub fn execute_prove_and_process(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    message: Vec<u8>,
+    proof: [H256; 32],
+    index: u64,
+) -> Result<Response, ContractError> {
+    let ret = execute_prove(deps.branch(), env.clone(), message.clone(), proof, index)?.data;
+    let prove_success: bool = from_binary(&ret.unwrap())?;
+
+    if !prove_success {
+        let leaf = NomadMessage::read_from(&mut message.as_slice())
+            .map_err(|_| ContractError::MalformedMessage {})?
+            .to_leaf();
+        return Err(ContractError::FailedProveCall { leaf, index });
+    }
+
+    execute_process(deps.branch(), env, info, message)
+}
+
+pub fn execute_enroll_remote(
+    deps: DepsMut,
+    info: MessageInfo,
+    remote: RemoteHome,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+    _enroll_remote(deps, remote)
+}
+
+pub fn execute_set_confirmation(
+    deps: DepsMut,
+    info: MessageInfo,
+    domain: u32,
+    root: H256,
+    confirm_at: u64,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+    only_enrolled(deps.as_ref(), domain)?;
+
+    let key = confirm_at_key(domain, root);
+    let prev_confirm_at = CONFIRM_AT.may_load(deps.storage, &key)?.unwrap_or_default();
+    CONFIRM_AT.save(deps.storage, &key, &confirm_at)?;
+
+    Ok(Response::new().add_event(
+        Event::new("SetConfirmation")
+            .add_attribute("home_domain", domain.to_string())
+            .add_attribute("root", format!("{:?}", root))
+            .add_attribute("previous_confirm_at", prev_confirm_at.to_string())
+            .add_attribute("new_confirm_at", confirm_at.to_string()),
+    ))
+}
+
+pub fn execute_set_optimistic_timeout(
+    deps: DepsMut,
+    info: MessageInfo,
+    domain: u32,
+    optimistic_seconds: u64,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+    only_enrolled(deps.as_ref(), domain)?;
+
+    OPTIMISTIC_SECONDS.save(deps.storage, domain, &optimistic_seconds)?;
+    Ok(Response::new().add_event(
+        Event::new("SetOptimisticTimeout")
+            .add_attribute("home_domain", domain.to_string())
+            .add_attribute("optimistic_seconds", optimistic_seconds.to_string()),
+    ))
+}
+
+pub fn execute_set_updaters(
+    deps: DepsMut,
+    info: MessageInfo,
+    domain: u32,
+    updaters: Vec<SignerId>,
+    threshold: u32,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+    only_enrolled(deps.as_ref(), domain)?;
+
+    _set_updaters(deps, domain, &updaters, threshold)?;
+    Ok(Response::new().add_event(
+        Event::new("SetUpdaters")
+            .add_attribute("home_domain", domain.to_string())
+            .add_attribute("new_updaters", format!("{:?}", updaters))
+            .add_attribute("threshold", threshold.to_string()),
+    ))
+}
+
+pub fn _set_updaters(
+    deps: DepsMut,
+    domain: u32,
+    updaters: &[SignerId],
+    threshold: u32,
+) -> Result<(), ContractError> {
+    common::validate_updater_set(updaters, threshold)?;
+
+    UPDATERS.save(deps.storage, domain, &updaters.to_vec())?;
+    THRESHOLDS.save(deps.storage, domain, &threshold)?;
+    Ok(())
+}
+
+pub fn _enroll_remote(mut deps: DepsMut, remote: RemoteHome) -> Result<Response, ContractError> {
+    let mut domains = REMOTE_DOMAINS.load(deps.storage)?;
+    if domains.contains(&remote.domain) {
+        return Err(ContractError::DomainAlreadyEnrolled {
+            domain: remote.domain,
+        });
+    }
+    domains.push(remote.domain);
+    REMOTE_DOMAINS.save(deps.storage, &domains)?;
+
+    _set_updaters(
+        deps.branch(),
+        remote.domain,
+        &remote.updaters,
+        remote.threshold,
+    )?;
+    COMMITTED_ROOTS.save(deps.storage, remote.domain, &remote.committed_root)?;
+    OPTIMISTIC_SECONDS.save(deps.storage, remote.domain, &remote.optimistic_seconds)?;
+    STATES.save(deps.storage, remote.domain, &States::Active)?;
+
+    Ok(Response::new().add_event(
+        Event::new("EnrollRemote")
+            .add_attribute("home_domain", remote.domain.to_string())
+            .add_attribute("updaters", format!("{:?}", remote.updaters))
+            .add_attribute("threshold", remote.threshold.to_string())
+            .add_attribute("committed_root", format!("{:?}", remote.committed_root)),
+    ))
+}
+
+pub fn only_enrolled(deps: Deps, domain: u32) -> Result<(), ContractError> {
+    if STATES.may_load(deps.storage, domain)?.is_none() {
+        return Err(ContractError::UnknownDomain { domain });
+    }
+    Ok(())
+}
+
+pub fn not_failed(deps: Deps, domain: u32) -> Result<(), ContractError> {
+    let state = STATES
+        .may_load(deps.storage, domain)?
+        .ok_or(ContractError::UnknownDomain { domain })?;
+    if state == States::Failed {
+        return Err(ContractError::FailedState { domain });
+    }
+    Ok(())
+}
+
+/// Whether at least threshold distinct updaters of `domain` signed update of
+/// its home from `old_root` to `new_root`
+pub fn is_updater_signature(
+    deps: Deps,
+    domain: u32,
+    old_root: H256,
+    new_root: H256,
+    signatures: &[Vec<u8>],
+) -> Result<bool, ContractError> {
+    let threshold = THRESHOLDS.load(deps.storage, domain)?;
+    let signers = update_signers(deps, domain, old_root, new_root, signatures)?;
+    Ok(signers.len() >= threshold as usize)
+}
+
+/// Distinct members of `domain`'s updater set that signed update from
+/// `old_root` to `new_root`
+pub fn update_signers(
+    deps: Deps,
+    domain: u32,
+    old_root: H256,
+    new_root: H256,
+    signatures: &[Vec<u8>],
+) -> Result<Vec<SignerId>, ContractError> {
+    let digest = nomad_base::update_digest(nomad_base::domain_hash(domain), old_root, new_root);
+    let updaters = UPDATERS.load(deps.storage, domain)?;
+    Ok(nomad_base::verified_signers(
+        deps.api, digest, &updaters, signatures,
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    Ok(replica::reply(deps, env, msg)?)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored_version = ensure_upgrade(deps.as_ref())?;
+
+    // Per-version storage migrations, oldest first
+    if stored_version < Version::new(0, 2, 0) {
+        // v0.2 replaces single updater per domain with threshold updater sets
+        _migrate_legacy_updaters(deps.branch())?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_event(
+        Event::new("Migrate")
+            .add_attribute("from_version", stored_version.to_string())
+            .add_attribute("to_version", CONTRACT_VERSION),
+    ))
+}
+
+/// Move each domain's single updater stored before updater sets into a
+/// 1-of-1 set
+fn _migrate_legacy_updaters(deps: DepsMut) -> Result<(), ContractError> {
+    for domain in REMOTE_DOMAINS.load(deps.storage)? {
+        let updater = LEGACY_UPDATERS.load(deps.storage, domain)?;
+        UPDATERS.save(deps.storage, domain, &vec![SignerId::from(updater)])?;
+        THRESHOLDS.save(deps.storage, domain, &1)?;
+        LEGACY_UPDATERS.remove(deps.storage, domain);
+    }
+    Ok(())
+}
+
+/// Checks that stored cw2 info belongs to this contract and that stored
+/// version is not newer than this code (no downgrades). Returns stored version.
+pub fn ensure_upgrade(deps: Deps) -> Result<Version, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::WrongContract {
+            expected: CONTRACT_NAME.to_owned(),
+            found: stored.contract,
+        });
+    }
+
+    let stored_version: Version = stored.version.parse()?;
+    let version: Version = CONTRACT_VERSION.parse()?;
+    if stored_version > version {
+        return Err(ContractError::CannotDowngrade {
+            stored: stored.version,
+            version: CONTRACT_VERSION.to_owned(),
+        });
+    }
+
+    Ok(stored_version)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::AcceptableRoot { domain, root } => {
+            to_binary(&query_acceptable_root(deps, env, domain, root)?)
+        }
+        QueryMsg::ConfirmAt { domain, root } => to_binary(&query_confirm_at(deps, domain, root)?),
+        QueryMsg::MessageStatus { leaf } => to_binary(&query_message_status(deps, leaf)?),
+        QueryMsg::OptimisticSeconds { domain } => {
+            to_binary(&query_optimistic_seconds(deps, domain)?)
+        }
+        QueryMsg::CommittedRoot { domain } => to_binary(&query_committed_root(deps, domain)?),
+        QueryMsg::HomeDomainHash { domain } => to_binary(&query_home_domain_hash(deps, domain)?),
+        QueryMsg::State { domain } => to_binary(&query_state(deps, domain)?),
+        QueryMsg::Updaters { domain } => to_binary(&query_updaters(deps, domain)?),
+        QueryMsg::ProcessGas {} => to_binary(&replica::query_process_gas(deps)?),
+        QueryMsg::RemoteDomains {} => to_binary(&query_remote_domains(deps)?),
+        QueryMsg::LocalDomain {} => to_binary(&nomad_base::query_local_domain(deps)?),
+        QueryMsg::Owner {} => to_binary(&ownable::query_owner(deps)?),
+    }
+}
+
+pub fn query_acceptable_root(
+    deps: Deps,
+    env: Env,
+    domain: u32,
+    root: H256,
+) -> StdResult<AcceptableRootResponse> {
+    let confirm_at = query_confirm_at(deps, domain, root)?.confirm_at;
+    if confirm_at == 0 {
+        return Ok(AcceptableRootResponse { acceptable: false });
+    }
+
+    Ok(AcceptableRootResponse {
+        acceptable: env.block.time.seconds() >= confirm_at,
+    })
+}
+
+pub fn query_confirm_at(deps: Deps, domain: u32, root: H256) -> StdResult<ConfirmAtResponse> {
+    let confirm_at = CONFIRM_AT
+        .may_load(deps.storage, &confirm_at_key(domain, root))?
+        .unwrap_or_default();
+
+    Ok(ConfirmAtResponse { confirm_at })
+}
+
+pub fn query_message_status(deps: Deps, leaf: H256) -> StdResult<MessageStatusResponse> {
+    replica::query_message_status(deps, leaf)
+}
+
+pub fn query_optimistic_seconds(deps: Deps, domain: u32) -> StdResult<OptimisticSecondsResponse> {
+    let optimistic_seconds = OPTIMISTIC_SECONDS.load(deps.storage, domain)?;
+    Ok(OptimisticSecondsResponse { optimistic_seconds })
+}
+
+pub fn query_committed_root(deps: Deps, domain: u32) -> StdResult<CommittedRootResponse> {
+    let committed_root = COMMITTED_ROOTS.load(deps.storage, domain)?;
+    Ok(CommittedRootResponse { committed_root })
+}
+
+pub fn query_home_domain_hash(deps: Deps, domain: u32) -> StdResult<HomeDomainHashResponse> {
+    if STATES.may_load(deps.storage, domain)?.is_none() {
+        return Err(StdError::not_found(format!("remote domain {}", domain)));
+    }
+    Ok(HomeDomainHashResponse {
+        home_domain_hash: nomad_base::domain_hash(domain),
+    })
+}
+
+pub fn query_state(deps: Deps, domain: u32) -> StdResult<StateResponse> {
+    let state = STATES.load(deps.storage, domain)?;
+    Ok(StateResponse { state })
+}
+
+pub fn query_updaters(deps: Deps, domain: u32) -> StdResult<UpdatersResponse> {
+    let updaters = UPDATERS.load(deps.storage, domain)?;
+    let threshold = THRESHOLDS.load(deps.storage, domain)?;
+    Ok(UpdatersResponse {
+        updaters,
+        threshold,
+    })
+}
+
+pub fn query_remote_domains(deps: Deps) -> StdResult<RemoteDomainsResponse> {
+    let domains = REMOTE_DOMAINS.load(deps.storage)?;
+    Ok(RemoteDomainsResponse { domains })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Encode;
+    use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
+    use cosmwasm_std::{coins, from_binary};
+    use test_utils::Updater;
+
+    const LOCAL_DOMAIN: u32 = 3000;
+    const DOMAIN_A: u32 = 1000;
+    const DOMAIN_B: u32 = 2000;
+    const UPDATER_A_PRIVKEY: &str =
+        "1111111111111111111111111111111111111111111111111111111111111111";
+    const UPDATER_B_PRIVKEY: &str =
+        "2111111111111111111111111111111111111111111111111111111111111111";
+
+    fn remote(domain: u32, updater: &Updater) -> RemoteHome {
+        RemoteHome {
+            domain,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+            committed_root: H256::zero(),
+            optimistic_seconds: 100,
+        }
+    }
+
+    fn instantiate_hub(
+        deps: DepsMut,
+        updater_a: &Updater,
+        updater_b: &Updater,
+    ) -> Result<Response, ContractError> {
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            remotes: vec![remote(DOMAIN_A, updater_a), remote(DOMAIN_B, updater_b)],
+        };
+        let info = mock_info("owner", &coins(100, "earth"));
+        instantiate(deps, mock_env(), info, msg)
+    }
+
+    #[test]
+    fn proper_initialization() {
+        let updater_a = Updater::from_privkey(UPDATER_A_PRIVKEY, DOMAIN_A);
+        let updater_b = Updater::from_privkey(UPDATER_B_PRIVKEY, DOMAIN_B);
+
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        let res = instantiate_hub(deps.as_mut(), &updater_a, &updater_b).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::RemoteDomains {}).unwrap();
+        let value: RemoteDomainsResponse = from_binary(&res).unwrap();
+        assert_eq!(vec![DOMAIN_A, DOMAIN_B], value.domains);
+
+        // Per-domain updaters
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Updaters { domain: DOMAIN_B },
+        )
+        .unwrap();
+        let value: UpdatersResponse = from_binary(&res).unwrap();
+        assert_eq!(vec![updater_b.signer_id()], value.updaters);
+        assert_eq!(1, value.threshold);
+
+        // Per-domain state
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::State { domain: DOMAIN_A },
+        )
+        .unwrap();
+        let value: StateResponse = from_binary(&res).unwrap();
+        assert_eq!(States::Active, value.state);
+
+        // Domains cannot be enrolled twice
+        let msg = ExecuteMsg::EnrollRemote {
+            remote: remote(DOMAIN_A, &updater_b),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::DomainAlreadyEnrolled { .. }));
+    }
+
+    #[tokio::test]
+    async fn updates_each_domain_independently() {
+        let updater_a = Updater::from_privkey(UPDATER_A_PRIVKEY, DOMAIN_A);
+        let updater_b = Updater::from_privkey(UPDATER_B_PRIVKEY, DOMAIN_B);
+
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        instantiate_hub(deps.as_mut(), &updater_a, &updater_b).unwrap();
+
+        let new_root = H256::repeat_byte(1);
+        let update_a = updater_a.sign_update(H256::zero(), new_root).await.unwrap();
+
+        // Domain A's update cannot be submitted for domain B
+        let msg = ExecuteMsg::Update {
+            domain: DOMAIN_B,
+            committed_root: H256::zero(),
+            new_root,
+            signatures: vec![update_a.signature.to_vec()],
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("submitter", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::NotUpdaterSignature {}));
+
+        let msg = ExecuteMsg::Update {
+            domain: DOMAIN_A,
+            committed_root: H256::zero(),
+            new_root,
+            signatures: vec![update_a.signature.to_vec()],
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("submitter", &[]), msg).unwrap();
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::CommittedRoot { domain: DOMAIN_A },
+        )
+        .unwrap();
+        let value: CommittedRootResponse = from_binary(&res).unwrap();
+        assert_eq!(new_root, value.committed_root);
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::CommittedRoot { domain: DOMAIN_B },
+        )
+        .unwrap();
+        let value: CommittedRootResponse = from_binary(&res).unwrap();
+        assert_eq!(H256::zero(), value.committed_root);
+
+        // Root only confirmable for domain that submitted it
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ConfirmAt {
+                domain: DOMAIN_A,
+                root: new_root,
+            },
+        )
+        .unwrap();
+        let value: ConfirmAtResponse = from_binary(&res).unwrap();
+        assert_eq!(mock_env().block.time.seconds() + 100, value.confirm_at);
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ConfirmAt {
+                domain: DOMAIN_B,
+                root: new_root,
+            },
+        )
+        .unwrap();
+        let value: ConfirmAtResponse = from_binary(&res).unwrap();
+        assert_eq!(0, value.confirm_at);
+    }
+
+    #[tokio::test]
+    async fn double_update_fails_only_offending_domain() {
+        let updater_a = Updater::from_privkey(UPDATER_A_PRIVKEY, DOMAIN_A);
+        let updater_b = Updater::from_privkey(UPDATER_B_PRIVKEY, DOMAIN_B);
+
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        instantiate_hub(deps.as_mut(), &updater_a, &updater_b).unwrap();
+
+        let new_roots = [H256::repeat_byte(1), H256::repeat_byte(2)];
+        let update = updater_a
+            .sign_update(H256::zero(), new_roots[0])
+            .await
+            .unwrap();
+        let double_update = updater_a
+            .sign_update(H256::zero(), new_roots[1])
+            .await
+            .unwrap();
+
+        let msg = ExecuteMsg::DoubleUpdate {
+            domain: DOMAIN_A,
+            old_root: H256::zero(),
+            new_roots,
+            signatures: vec![update.signature.to_vec()],
+            signatures_2: vec![double_update.signature.to_vec()],
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("watcher", &[]), msg).unwrap();
+        let equivocators = res.events[0]
+            .attributes
+            .iter()
+            .find(|attr| attr.key == "equivocators")
+            .unwrap();
+        assert_eq!(
+            format!("{:?}", vec![updater_a.signer_id()]),
+            equivocators.value
+        );
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::State { domain: DOMAIN_A },
+        )
+        .unwrap();
+        let value: StateResponse = from_binary(&res).unwrap();
+        assert_eq!(States::Failed, value.state);
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::State { domain: DOMAIN_B },
+        )
+        .unwrap();
+        let value: StateResponse = from_binary(&res).unwrap();
+        assert_eq!(States::Active, value.state);
+
+        // Domain B keeps accepting updates
+        let update_b = updater_b
+            .sign_update(H256::zero(), H256::repeat_byte(3))
+            .await
+            .unwrap();
+        let msg = ExecuteMsg::Update {
+            domain: DOMAIN_B,
+            committed_root: H256::zero(),
+            new_root: H256::repeat_byte(3),
+            signatures: vec![update_b.signature.to_vec()],
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("submitter", &[]), msg).unwrap();
+    }
+
+    #[test]
+    fn proves_against_origin_domain_root() {
+        let updater_a = Updater::from_privkey(UPDATER_A_PRIVKEY, DOMAIN_A);
+        let updater_b = Updater::from_privkey(UPDATER_B_PRIVKEY, DOMAIN_B);
+
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        instantiate_hub(deps.as_mut(), &updater_a, &updater_b).unwrap();
+
+        let message_from = |origin: u32| NomadMessage {
+            origin,
+            sender: H256::repeat_byte(1),
+            nonce: 0,
+            destination: LOCAL_DOMAIN,
+            recipient: H256::repeat_byte(2),
+            body: "hello".as_bytes().to_vec(),
+        };
+        let proof = [H256::zero(); 32];
+
+        // Confirm domain A root containing domain A message at index 0
+        let message_a = message_from(DOMAIN_A);
+        let root = merkle_tree::merkle_root_from_branch(
+            message_a.to_leaf(),
+            &proof[..],
+            merkle_tree::TREE_DEPTH,
+            0,
+        );
+        let msg = ExecuteMsg::SetConfirmation {
+            domain: DOMAIN_A,
+            root,
+            confirm_at: 1,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::Prove {
+            message: message_a.to_vec(),
+            proof,
+            index: 0,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("prover", &[]), msg).unwrap();
+        assert!(from_binary::<bool>(&res.data.unwrap()).unwrap());
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::MessageStatus {
+                leaf: message_a.to_leaf(),
+            },
+        )
+        .unwrap();
+        let value: MessageStatusResponse = from_binary(&res).unwrap();
+        assert_eq!(MessageStatus::Pending, value.status);
+
+        // Message claiming origin B cannot prove against domain A's roots
+        let message_b = message_from(DOMAIN_B);
+        let msg = ExecuteMsg::Prove {
+            message: message_b.to_vec(),
+            proof,
+            index: 0,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("prover", &[]), msg).unwrap();
+        assert!(!from_binary::<bool>(&res.data.unwrap()).unwrap());
+
+        // Malformed messages are rejected
+        let msg = ExecuteMsg::Prove {
+            message: vec![1, 2, 3],
+            proof,
+            index: 0,
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("prover", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::MalformedMessage {}));
+
+        // Failed domain's roots no longer prove messages
+        STATES
+            .save(deps.as_mut().storage, DOMAIN_A, &States::Failed)
+            .unwrap();
+        let msg = ExecuteMsg::Prove {
+            message: message_from(DOMAIN_A).to_vec(),
+            proof,
+            index: 0,
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("prover", &[]), msg).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::FailedState { domain: DOMAIN_A }
+        ));
+    }
+
+    #[tokio::test]
+    async fn updates_require_threshold_of_domain_updaters() {
+        let updater_a = Updater::from_privkey(UPDATER_A_PRIVKEY, DOMAIN_A);
+        let updater_b = Updater::from_privkey(UPDATER_B_PRIVKEY, DOMAIN_B);
+        let updater_a_2 = Updater::from_privkey(&"3".repeat(64), DOMAIN_A);
+
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        instantiate_hub(deps.as_mut(), &updater_a, &updater_b).unwrap();
+
+        // Only owner sets updaters, sets must meet their threshold
+        let updaters = vec![updater_a.signer_id(), updater_a_2.signer_id()];
+        let msg = ExecuteMsg::SetUpdaters {
+            domain: DOMAIN_A,
+            updaters: updaters.clone(),
+            threshold: 2,
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("not_owner", &[]),
+            msg.clone(),
+        );
+        assert!(res.is_err());
+
+        let bad_msg = ExecuteMsg::SetUpdaters {
+            domain: DOMAIN_A,
+            updaters: updaters.clone(),
+            threshold: 3,
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), bad_msg).unwrap_err();
+        assert!(matches!(err, ContractError::UpdaterSetError(_)));
+
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Updaters { domain: DOMAIN_A },
+        )
+        .unwrap();
+        let value: UpdatersResponse = from_binary(&res).unwrap();
+        assert_eq!(updaters, value.updaters);
+        assert_eq!(2, value.threshold);
+
+        let new_root = H256::repeat_byte(1);
+        let update = updater_a.sign_update(H256::zero(), new_root).await.unwrap();
+        let update_2 = updater_a_2
+            .sign_update(H256::zero(), new_root)
+            .await
+            .unwrap();
+
+        // Repeated signature of one updater does not meet threshold
+        let msg = ExecuteMsg::Update {
+            domain: DOMAIN_A,
+            committed_root: H256::zero(),
+            new_root,
+            signatures: vec![update.signature.to_vec(), update.signature.to_vec()],
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("submitter", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::NotUpdaterSignature {}));
+
+        let msg = ExecuteMsg::Update {
+            domain: DOMAIN_A,
+            committed_root: H256::zero(),
+            new_root,
+            signatures: vec![update.signature.to_vec(), update_2.signature.to_vec()],
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("submitter", &[]), msg).unwrap();
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::CommittedRoot { domain: DOMAIN_A },
+        )
+        .unwrap();
+        let value: CommittedRootResponse = from_binary(&res).unwrap();
+        assert_eq!(new_root, value.committed_root);
+    }
+
+    #[test]
+    fn migrates_legacy_updaters_to_updater_sets() {
+        let updater_a = Updater::from_privkey(UPDATER_A_PRIVKEY, DOMAIN_A);
+        let updater_b = Updater::from_privkey(UPDATER_B_PRIVKEY, DOMAIN_B);
+
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        instantiate_hub(deps.as_mut(), &updater_a, &updater_b).unwrap();
+
+        // Simulate v0.1.0 storage with single updater per domain
+        for (domain, updater) in [(DOMAIN_A, &updater_a), (DOMAIN_B, &updater_b)] {
+            UPDATERS.remove(&mut deps.storage, domain);
+            THRESHOLDS.remove(&mut deps.storage, domain);
+            LEGACY_UPDATERS
+                .save(&mut deps.storage, domain, &updater.address())
+                .unwrap();
+        }
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        for (domain, updater) in [(DOMAIN_A, &updater_a), (DOMAIN_B, &updater_b)] {
+            let res = query(deps.as_ref(), mock_env(), QueryMsg::Updaters { domain }).unwrap();
+            let value: UpdatersResponse = from_binary(&res).unwrap();
+            assert_eq!(vec![updater.signer_id()], value.updaters);
+            assert_eq!(1, value.threshold);
+            assert!(LEGACY_UPDATERS
+                .may_load(&deps.storage, domain)
+                .unwrap()
+                .is_none());
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use cosmwasm_std::StdError;
+use ethers_core::types::H256;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Cannot migrate from contract {found}, expected {expected}")]
+    WrongContract { expected: String, found: String },
+
+    #[error("Cannot downgrade from version {stored} to {version}")]
+    CannotDowngrade { stored: String, version: String },
+
+    #[error("{0}")]
+    SemVer(#[from] semver::Error),
+
+    #[error("Remote domain {domain} not served by hub")]
+    UnknownDomain { domain: u32 },
+
+    #[error("Remote domain {domain} already enrolled")]
+    DomainAlreadyEnrolled { domain: u32 },
+
+    #[error("Remote domain {domain} failed")]
+    FailedState { domain: u32 },
+
+    #[error("Message with leaf {leaf} already proven")]
+    MessageAlreadyProven { leaf: H256 },
+
+    #[error("Not a current committed root for domain {domain}: {old_root}")]
+    NotCurrentCommittedRoot { domain: u32, old_root: H256 },
+
+    #[error("Not updater signature")]
+    NotUpdaterSignature {},
+
+    #[error("Invalid double update submitted")]
+    InvalidDoubleUpdate {},
+
+    #[error("Malformed message")]
+    MalformedMessage {},
+
+    #[error("Failed to prove message. Leaf: {leaf}. Index: {index}")]
+    FailedProveCall { leaf: H256, index: u64 },
+
+    #[error("{0}")]
+    UpdaterSetError(#[from] common::UpdaterSetError),
+
+    #[error("{0}")]
+    ReplicaError(#[from] replica::ContractError),
+
+    #[error("{0}")]
+    NomadBaseError(#[from] nomad_base::ContractError),
+
+    #[error("{0}")]
+    OwnableError(#[from] ownable::ContractError),
+}
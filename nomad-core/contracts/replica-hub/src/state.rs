@@ -0,0 +1,27 @@
+use common::{SignerId, States};
+use cw_storage_plus::{Item, Map};
+use ethers_core::types::{H160, H256};
+
+// Message statuses and local domain are stored under replica and nomad base
+// keys so message processing is shared with replica
+
+pub const REMOTE_DOMAINS: Item<Vec<u32>> = Item::new("replica_hub_remote_domains");
+
+pub const UPDATERS: Map<u32, Vec<SignerId>> = Map::new("replica_hub_updater_sets");
+pub const THRESHOLDS: Map<u32, u32> = Map::new("replica_hub_thresholds");
+pub const COMMITTED_ROOTS: Map<u32, H256> = Map::new("replica_hub_committed_roots");
+pub const OPTIMISTIC_SECONDS: Map<u32, u64> = Map::new("replica_hub_optimistic_seconds");
+pub const STATES: Map<u32, States> = Map::new("replica_hub_states");
+
+/// v0.1.0 single updater per domain, replaced by updater sets
+pub const LEGACY_UPDATERS: Map<u32, H160> = Map::new("replica_hub_updaters");
+
+// Keyed by `confirm_at_key(domain, root)`
+pub const CONFIRM_AT: Map<&[u8], u64> = Map::new("replica_hub_confirm_at");
+
+/// Big-endian domain followed by root
+pub fn confirm_at_key(domain: u32, root: H256) -> Vec<u8> {
+    let mut key = domain.to_be_bytes().to_vec();
+    key.extend_from_slice(root.as_bytes());
+    key
+}
@@ -1,22 +1,33 @@
+use common::light_client::{self, ics23, ConsensusState, SignedHeader, TrustedState, Validator};
 use common::nomad_base::HomeDomainHashResponse;
-use common::{h256_to_n_byte_addr, Decode, HandleExecuteMsg, MessageStatus, NomadMessage};
+use common::{
+    h256_to_addr, Decode, Envelope, HandleExecuteMsg, MessageStatus, NomadMessage, SignerId,
+};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_binary, to_binary, Binary, ContractResult, CosmosMsg, Deps, DepsMut, Env, Event,
-    MessageInfo, Reply, ReplyOn, Response, StdResult, SubMsg, WasmMsg,
+    from_binary, from_slice, to_binary, Binary, ContractResult, CosmosMsg, Deps, DepsMut, Env,
+    Event, MessageInfo, Reply, ReplyOn, Response, StdResult, SubMsg, WasmMsg,
 };
-use cw2::set_contract_version;
-use ethers_core::types::{H160, H256};
+use cw2::{get_contract_version, set_contract_version};
+use ethers_core::types::H256;
+use ethers_core::utils::keccak256;
+use semver::Version;
+use std::cmp::Ordering;
 
 use crate::error::ContractError;
 use crate::state::{
-    CHAIN_ADDR_LENGTH_BYTES, CONFIRM_AT, MESSAGES, OPTIMISTIC_SECONDS, REMOTE_DOMAIN,
+    CONFIRM_AT, CONSENSUS_STATES, DEFAULT_PROCESS_GAS, LATEST_CONSENSUS_HEIGHT, MESSAGES,
+    NEXT_NONCE, OPTIMISTIC_SECONDS, PROCESSING, PROCESS_GAS, PROVEN_ROOT_HEIGHT, REMOTE_DOMAIN,
+    ROOT_VERIFIER,
 };
-use common::merkle_tree;
+use common::merkle_tree::{self, BatchVerifier, IncrementalMerkle};
 use common::replica::{
-    AcceptableRootResponse, ConfirmAtResponse, ExecuteMsg, InstantiateMsg, MessageStatusResponse,
-    OptimisticSecondsResponse, QueryMsg, RemoteDomainResponse,
+    AcceptableRootResponse, BatchItem, BatchItemResult, BatchProveAndProcessResponse,
+    ConfirmAtResponse, ConsensusStateResponse, ExecuteMsg, InstantiateMsg,
+    LatestConsensusHeightResponse, MessageStatusResponse, MigrateMsg, NextExpectedNonceResponse,
+    OptimisticSecondsResponse, ProcessGasResponse, QueryMsg, RemoteDomainResponse, RootVerifier,
+    RootVerifierResponse,
 };
 
 // version info for migration info
@@ -24,6 +35,7 @@ const CONTRACT_NAME: &str = "crates.io:replica";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub const PROCESS_ID: u64 = 1;
+pub const BATCH_PROCESS_ID: u64 = 2;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -34,10 +46,10 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     nomad_base::instantiate(deps.branch(), env, info, msg.clone().into())?;
 
-    CHAIN_ADDR_LENGTH_BYTES.save(deps.storage, &msg.chain_addr_length_bytes)?;
     REMOTE_DOMAIN.save(deps.storage, &msg.remote_domain)?;
     OPTIMISTIC_SECONDS.save(deps.storage, &msg.optimistic_seconds)?;
     nomad_base::_set_committed_root(deps.branch(), msg.committed_root)?;
+    _set_root_verifier(deps.branch(), msg.root_verifier.unwrap_or_default())?;
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     Ok(Response::new())
@@ -54,36 +66,74 @@ pub fn execute(
         ExecuteMsg::Update {
             committed_root,
             new_root,
-            signature,
-        } => execute_update(deps, env, committed_root, new_root, signature),
+            signatures,
+        } => execute_update(deps, env, committed_root, new_root, signatures),
         ExecuteMsg::DoubleUpdate {
             old_root,
             new_roots,
-            signature,
-            signature_2,
+            signatures,
+            signatures_2,
         } => Ok(nomad_base::execute_double_update(
             deps,
             info,
             old_root,
             new_roots,
-            signature,
-            signature_2,
+            signatures,
+            signatures_2,
             _fail,
         )?),
         ExecuteMsg::Prove { leaf, proof, index } => execute_prove(deps, env, leaf, proof, index),
-        ExecuteMsg::Process { message } => execute_process(deps, info, message),
+        ExecuteMsg::Process { message } => execute_process(deps, env, info, message),
         ExecuteMsg::ProveAndProcess {
             message,
             proof,
             index,
         } => execute_prove_and_process(deps, env, info, message, proof, index),
+        ExecuteMsg::BatchProveAndProcess { items } => {
+            execute_batch_prove_and_process(deps, env, info, items)
+        }
+        ExecuteMsg::RetryProcess { message } => execute_retry_process(deps, env, info, message),
         ExecuteMsg::SetConfirmation { root, confirm_at } => {
             execute_set_confirmation(deps, info, root, confirm_at)
         }
         ExecuteMsg::SetOptimisticTimeout { optimistic_seconds } => {
             execute_set_optimistic_timeout(deps, info, optimistic_seconds)
         }
-        ExecuteMsg::SetUpdater { updater } => execute_set_updater(deps, info, updater),
+        ExecuteMsg::SetUpdaters {
+            updaters,
+            threshold,
+        } => execute_set_updaters(deps, info, updaters, threshold),
+        ExecuteMsg::SetRootVerifier { root_verifier } => {
+            execute_set_root_verifier(deps, info, root_verifier)
+        }
+        ExecuteMsg::SetTrustedConsensusState {
+            height,
+            consensus_state,
+        } => execute_set_trusted_consensus_state(deps, info, height, consensus_state),
+        ExecuteMsg::UpdateLightClient {
+            signed_header,
+            validators,
+            trusted_height,
+            trusted_validators,
+        } => execute_update_light_client(
+            deps,
+            env,
+            signed_header,
+            validators,
+            trusted_height,
+            trusted_validators,
+        ),
+        ExecuteMsg::ProveCommittedRoot {
+            height,
+            incremental_merkle,
+            proofs,
+        } => execute_prove_committed_root(deps, env, height, incremental_merkle, proofs),
+        ExecuteMsg::SetProcessGas { process_gas } => {
+            execute_set_process_gas(deps, info, process_gas)
+        }
+        ExecuteMsg::SetOrderedDelivery { next_nonce } => {
+            execute_set_ordered_delivery(deps, info, next_nonce)
+        }
         ExecuteMsg::RenounceOwnership {} => Ok(ownable::execute_renounce_ownership(deps, info)?),
         ExecuteMsg::TransferOwnership { new_owner } => {
             Ok(ownable::execute_transfer_ownership(deps, info, new_owner)?)
@@ -96,7 +146,7 @@ pub fn execute_update(
     env: Env,
     old_root: H256,
     new_root: H256,
-    signature: Vec<u8>,
+    signatures: Vec<Vec<u8>>,
 ) -> Result<Response, ContractError> {
     nomad_base::not_failed(deps.as_ref())?;
 
@@ -105,14 +155,17 @@ pub fn execute_update(
         return Err(ContractError::NotCurrentCommittedRoot { old_root });
     }
 
-    if !nomad_base::is_updater_signature(deps.as_ref(), old_root, new_root, &signature)? {
-        return Err(ContractError::NotUpdaterSignature {});
-    }
-
     // TODO: _beforeUpdate hook?
 
-    let optimistic_seconds = query_optimistic_seconds(deps.as_ref())?.optimistic_seconds;
-    let confirm_at = env.block.time.seconds() + optimistic_seconds;
+    let root_verifier = query_root_verifier(deps.as_ref())?.root_verifier;
+    let confirm_at = _verify_update(
+        deps.as_ref(),
+        &env,
+        &root_verifier,
+        old_root,
+        new_root,
+        &signatures,
+    )?;
     CONFIRM_AT.save(deps.storage, new_root.as_bytes(), &confirm_at)?;
 
     nomad_base::_set_committed_root(deps.branch(), new_root)?;
@@ -124,10 +177,188 @@ pub fn execute_update(
             .add_attribute("local_domain", remote_domain.to_string())
             .add_attribute("committed_root", format!("{:?}", committed_root))
             .add_attribute("new_root", format!("{:?}", new_root))
-            .add_attribute("signature", format!("{:?}", signature)),
+            .add_attribute("signatures", format!("{:?}", signatures))
+            .add_attribute("confirm_at", confirm_at.to_string()),
+    ))
+}
+
+/// Verify update from `old_root` to `new_root` with `root_verifier`. Returns
+/// time at which `new_root` becomes acceptable.
+pub fn _verify_update(
+    deps: Deps,
+    env: &Env,
+    root_verifier: &RootVerifier,
+    old_root: H256,
+    new_root: H256,
+    signatures: &[Vec<u8>],
+) -> Result<u64, ContractError> {
+    let now = env.block.time.seconds();
+    match root_verifier {
+        RootVerifier::Optimistic {} => {
+            if !nomad_base::is_updater_signature(deps, old_root, new_root, signatures)? {
+                return Err(ContractError::NotUpdaterSignature {});
+            }
+
+            let optimistic_seconds = query_optimistic_seconds(deps)?.optimistic_seconds;
+            Ok(now + optimistic_seconds)
+        }
+        RootVerifier::Attestation {
+            validators,
+            threshold,
+        } => {
+            // Validators sign the same digest as updaters
+            let home_domain_hash = nomad_base::query_home_domain_hash(deps)?.home_domain_hash;
+            let digest = nomad_base::update_digest(home_domain_hash, old_root, new_root);
+            let attesters = nomad_base::verified_signers(deps.api, digest, validators, signatures);
+            if attesters.len() < *threshold as usize {
+                return Err(ContractError::NotValidatorAttestation {});
+            }
+
+            Ok(now)
+        }
+        RootVerifier::LightClient { .. } => Err(ContractError::UpdateInLightClientMode {}),
+    }
+}
+
+pub fn execute_set_trusted_consensus_state(
+    deps: DepsMut,
+    info: MessageInfo,
+    height: u64,
+    consensus_state: ConsensusState,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+    _save_consensus_state(deps, height, &consensus_state)?;
+
+    Ok(Response::new().add_event(
+        Event::new("SetTrustedConsensusState")
+            .add_attribute("height", height.to_string())
+            .add_attribute("app_hash", consensus_state.app_hash.to_base64()),
     ))
 }
 
+pub fn execute_update_light_client(
+    deps: DepsMut,
+    env: Env,
+    signed_header: SignedHeader,
+    validators: Vec<Validator>,
+    trusted_height: u64,
+    trusted_validators: Vec<Validator>,
+) -> Result<Response, ContractError> {
+    let (chain_id, _, trusting_period) = _light_client(deps.as_ref())?;
+
+    let trusted = query_consensus_state(deps.as_ref(), trusted_height)?
+        .consensus_state
+        .ok_or(ContractError::ConsensusStateNotFound {
+            height: trusted_height,
+        })?;
+    light_client::verify_header(
+        deps.api,
+        &chain_id,
+        trusting_period,
+        env.block.time.seconds(),
+        &TrustedState {
+            height: trusted_height,
+            consensus_state: &trusted,
+            next_validators: &trusted_validators,
+        },
+        &signed_header,
+        &validators,
+    )?;
+
+    let height = signed_header.header.height;
+    if CONSENSUS_STATES.may_load(deps.storage, height)?.is_some() {
+        return Err(ContractError::ConsensusStateExists { height });
+    }
+    let consensus_state = ConsensusState::from(&signed_header.header);
+    _save_consensus_state(deps, height, &consensus_state)?;
+
+    Ok(Response::new().add_event(
+        Event::new("UpdateLightClient")
+            .add_attribute("height", height.to_string())
+            .add_attribute("trusted_height", trusted_height.to_string())
+            .add_attribute("app_hash", consensus_state.app_hash.to_base64()),
+    ))
+}
+
+pub fn execute_prove_committed_root(
+    mut deps: DepsMut,
+    env: Env,
+    height: u64,
+    incremental_merkle: Binary,
+    proofs: Vec<Binary>,
+) -> Result<Response, ContractError> {
+    nomad_base::not_failed(deps.as_ref())?;
+    let (_, home, _) = _light_client(deps.as_ref())?;
+
+    // Proving an older tree would roll back committed root
+    let proven_height = PROVEN_ROOT_HEIGHT
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    if height <= proven_height {
+        return Err(ContractError::StaleRootProof {
+            height,
+            proven_height,
+        });
+    }
+
+    let consensus_state = query_consensus_state(deps.as_ref(), height)?
+        .consensus_state
+        .ok_or(ContractError::ConsensusStateNotFound { height })?;
+    let key = ics23::wasm_contract_store_key(&home, merkle::state::MERKLE_KEY.as_bytes());
+    ics23::verify_sdk_membership(
+        &consensus_state.app_hash,
+        ics23::WASM_STORE_NAME,
+        &key,
+        &incremental_merkle,
+        &proofs,
+    )?;
+
+    let tree: IncrementalMerkle = from_slice(&incremental_merkle)?;
+    let new_root = tree.root();
+    let committed_root = nomad_base::query_committed_root(deps.as_ref())?.committed_root;
+
+    let confirm_at = env.block.time.seconds();
+    CONFIRM_AT.save(deps.storage, new_root.as_bytes(), &confirm_at)?;
+    nomad_base::_set_committed_root(deps.branch(), new_root)?;
+    PROVEN_ROOT_HEIGHT.save(deps.storage, &height)?;
+
+    Ok(Response::new().add_event(
+        Event::new("ProveCommittedRoot")
+            .add_attribute("height", height.to_string())
+            .add_attribute("committed_root", format!("{:?}", committed_root))
+            .add_attribute("new_root", format!("{:?}", new_root))
+            .add_attribute("count", tree.count().to_string())
+            .add_attribute("confirm_at", confirm_at.to_string()),
+    ))
+}
+
+/// Light client chain id, home and trusting period. Errors if replica is
+/// not in light client mode
+fn _light_client(deps: Deps) -> Result<(String, Binary, u64), ContractError> {
+    match query_root_verifier(deps)?.root_verifier {
+        RootVerifier::LightClient {
+            chain_id,
+            home,
+            trusting_period,
+        } => Ok((chain_id, home, trusting_period)),
+        _ => Err(ContractError::NotLightClient {}),
+    }
+}
+
+pub fn _save_consensus_state(
+    deps: DepsMut,
+    height: u64,
+    consensus_state: &ConsensusState,
+) -> Result<(), ContractError> {
+    CONSENSUS_STATES.save(deps.storage, height, consensus_state)?;
+
+    let latest = LATEST_CONSENSUS_HEIGHT
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    LATEST_CONSENSUS_HEIGHT.save(deps.storage, &latest.max(height))?;
+    Ok(())
+}
+
 pub fn execute_prove(
     deps: DepsMut,
     env: Env,
@@ -157,12 +388,13 @@ pub fn execute_prove(
 }
 
 pub fn execute_process(
-    deps: DepsMut,
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     message: Vec<u8>,
 ) -> Result<Response, ContractError> {
-    let nomad_message =
-        NomadMessage::read_from(&mut message.as_slice()).expect("!message conversion");
+    let nomad_message = NomadMessage::read_from(&mut message.as_slice())
+        .map_err(|_| ContractError::MalformedMessage {})?;
 
     let local_domain = nomad_base::query_local_domain(deps.as_ref())?.local_domain;
     if nomad_message.destination != local_domain {
@@ -177,29 +409,122 @@ pub fn execute_process(
         return Err(ContractError::MessageNotYetProven { leaf });
     }
 
-    MESSAGES.save(deps.storage, leaf.as_bytes(), &MessageStatus::Processed)?;
+    // Check recipient before consuming the nonce so an undeliverable message
+    // writes nothing, batches rely on it
+    let expired_at = _expired_at(&env, &nomad_message)?;
+    if expired_at.is_none() {
+        h256_to_addr(deps.api, nomad_message.recipient)?;
+    }
+    _use_nonce(deps.branch(), nomad_message.nonce)?;
+
+    match expired_at {
+        Some(expires_at) => _expire_message(deps, &nomad_message, expires_at),
+        None => _dispatch_message(deps, info, nomad_message),
+    }
+}
+
+/// Expiry of message's envelope if it has passed
+pub fn _expired_at(env: &Env, nomad_message: &NomadMessage) -> Result<Option<u64>, ContractError> {
+    Ok(Envelope::parse(&nomad_message.body)?
+        .filter(|envelope| envelope.is_expired(env.block.time.seconds()))
+        .map(|envelope| envelope.expires_at))
+}
+
+/// Marks message expired instead of delivering it. Succeeds so the status
+/// persists and the nonce is consumed; the event lets the sender's xApp
+/// detect the expiry and refund.
+pub fn _expire_message(
+    deps: DepsMut,
+    nomad_message: &NomadMessage,
+    expires_at: u64,
+) -> Result<Response, ContractError> {
+    let leaf = nomad_message.to_leaf();
+    MESSAGES.save(deps.storage, leaf.as_bytes(), &MessageStatus::Expired)?;
+
+    Ok(Response::new()
+        .add_event(
+            Event::new("MessageExpired")
+                .add_attribute("leaf", format!("{:?}", leaf))
+                .add_attribute("origin", nomad_message.origin.to_string())
+                .add_attribute("nonce", nomad_message.nonce.to_string())
+                .add_attribute("sender", format!("{:?}", nomad_message.sender))
+                .add_attribute("expires_at", expires_at.to_string()),
+        )
+        .set_data(to_binary(&false)?))
+}
+
+/// In ordered mode, accept only the next expected nonce and advance it. Home
+/// assigns nonces per destination, so origin's messages to this domain form
+/// one gap-free sequence. Failed deliveries do not block later nonces and
+/// can be retried out of order.
+pub fn _use_nonce(deps: DepsMut, nonce: u32) -> Result<(), ContractError> {
+    let next_nonce = match NEXT_NONCE.may_load(deps.storage)? {
+        Some(next_nonce) => next_nonce,
+        None => return Ok(()),
+    };
 
-    // TODO: check gas limit to ensure rest of tx doesn't fail for gas
-    let addr_length = CHAIN_ADDR_LENGTH_BYTES.load(deps.storage)?;
+    match nonce.cmp(&next_nonce) {
+        Ordering::Greater => Err(ContractError::FutureNonce { nonce, next_nonce }),
+        Ordering::Less => Err(ContractError::StaleNonce { nonce, next_nonce }),
+        Ordering::Equal => {
+            NEXT_NONCE.save(deps.storage, &(next_nonce + 1))?;
+            Ok(())
+        }
+    }
+}
 
-    let handle_msg: HandleExecuteMsg = nomad_message.clone().into();
+pub fn execute_retry_process(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    message: Vec<u8>,
+) -> Result<Response, ContractError> {
+    let nomad_message = NomadMessage::read_from(&mut message.as_slice())
+        .map_err(|_| ContractError::MalformedMessage {})?;
+
+    let leaf = nomad_message.to_leaf();
+    match query_message_status(deps.as_ref(), leaf)?.status {
+        MessageStatus::Failed(_) => match _expired_at(&env, &nomad_message)? {
+            Some(expires_at) => _expire_message(deps, &nomad_message, expires_at),
+            None => _dispatch_message(deps, info, nomad_message),
+        },
+        _ => Err(ContractError::MessageNotFailed { leaf }),
+    }
+}
+
+/// Marks message processed and calls recipient's handle in a submessage
+/// limited to process gas. `reply_process` marks the message failed if the
+/// recipient errors or runs out of gas. Envelopes are stripped so recipients
+/// only see the inner body.
+pub fn _dispatch_message(
+    deps: DepsMut,
+    info: MessageInfo,
+    mut nomad_message: NomadMessage,
+) -> Result<Response, ContractError> {
+    let leaf = nomad_message.to_leaf();
+    let process_gas = query_process_gas(deps.as_ref())?.process_gas;
+    let recipient = h256_to_addr(deps.api, nomad_message.recipient)?;
+
+    nomad_message.body = Envelope::unwrap_body(nomad_message.body)?;
+    let handle_msg: HandleExecuteMsg = nomad_message.into();
     let wasm_msg = WasmMsg::Execute {
-        contract_addr: h256_to_n_byte_addr(deps.as_ref(), addr_length, nomad_message.recipient)
-            .to_string(),
+        contract_addr: recipient.to_string(),
         msg: to_binary(&handle_msg)?,
         funds: info.funds,
     };
     let cosmos_msg = CosmosMsg::Wasm(wasm_msg);
 
-    println!(
-        "wasm execute contract addr: {:?}",
-        h256_to_n_byte_addr(deps.as_ref(), addr_length, nomad_message.recipient).to_string()
-    );
+    // Write only once nothing else can fail, batches rely on it
+    MESSAGES.save(deps.storage, leaf.as_bytes(), &MessageStatus::Processed)?;
+
+    let mut processing = PROCESSING.may_load(deps.storage)?.unwrap_or_default();
+    processing.push(leaf);
+    PROCESSING.save(deps.storage, &processing)?;
 
     let sub_msg = SubMsg {
         id: PROCESS_ID,
         msg: cosmos_msg,
-        gas_limit: None,
+        gas_limit: Some(process_gas),
         reply_on: ReplyOn::Always,
     };
 
@@ -215,16 +540,106 @@ pub fn execute_prove_and_process(
     index: u64,
 ) -> Result<Response, ContractError> {
     let leaf = NomadMessage::read_from(&mut message.as_slice())
-        .expect("!message conversion")
+        .map_err(|_| ContractError::MalformedMessage {})?
         .to_leaf();
-    let ret = execute_prove(deps.branch(), env, leaf, proof, index)?.data;
+    let ret = execute_prove(deps.branch(), env.clone(), leaf, proof, index)?.data;
     let prove_success: bool = from_binary(&ret.unwrap())?;
 
     if !prove_success {
         return Err(ContractError::FailedProveCall { leaf, index });
     }
 
-    execute_process(deps.branch(), info, message)
+    execute_process(deps.branch(), env, info, message)
+}
+
+/// Prove and process each item on its own so a failing item does not revert
+/// the others. Items that prove but fail to process stay proven. Attached
+/// funds are not forwarded to recipients.
+pub fn execute_batch_prove_and_process(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    items: Vec<BatchItem>,
+) -> Result<Response, ContractError> {
+    let batch_start = PROCESSING.may_load(deps.storage)?.unwrap_or_default().len();
+
+    let item_info = MessageInfo {
+        sender: info.sender,
+        funds: vec![],
+    };
+    let mut verifier = BatchVerifier::default();
+    let mut res = Response::new();
+    let mut results = Vec::with_capacity(items.len());
+
+    for item in items {
+        let leaf: H256 = keccak256(&item.message).into();
+        match _batch_prove_and_process(deps.branch(), &env, &item_info, &mut verifier, item) {
+            Ok(item_res) => {
+                // Batch replies must leave the batch response data in place
+                let sub_msgs = item_res.messages.into_iter().map(|sub_msg| SubMsg {
+                    id: BATCH_PROCESS_ID,
+                    ..sub_msg
+                });
+                res = res.add_submessages(sub_msgs).add_events(item_res.events);
+                results.push(BatchItemResult {
+                    leaf,
+                    dispatched: true,
+                    error: None,
+                });
+            }
+            Err(error) => results.push(BatchItemResult {
+                leaf,
+                dispatched: false,
+                error: Some(error.to_string()),
+            }),
+        }
+    }
+
+    // Batch deliveries reply in dispatch order, but `reply_batch_process` pops
+    // the innermost leaf. Reverse the batch's leaves so replies match.
+    let mut processing = PROCESSING.may_load(deps.storage)?.unwrap_or_default();
+    processing[batch_start..].reverse();
+    PROCESSING.save(deps.storage, &processing)?;
+
+    let dispatched = results.iter().filter(|result| result.dispatched).count();
+    Ok(res
+        .add_event(
+            Event::new("BatchProveAndProcess")
+                .add_attribute("items", results.len().to_string())
+                .add_attribute("dispatched", dispatched.to_string()),
+        )
+        .set_data(to_binary(&BatchProveAndProcessResponse { results })?))
+}
+
+/// Prove item against shared batch paths unless already proven, then
+/// process it. Nothing is written unless the step writing it succeeds.
+fn _batch_prove_and_process(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    verifier: &mut BatchVerifier,
+    item: BatchItem,
+) -> Result<Response, ContractError> {
+    let leaf = NomadMessage::read_from(&mut item.message.as_slice())
+        .map_err(|_| ContractError::MalformedMessage {})?
+        .to_leaf();
+
+    if query_message_status(deps.as_ref(), leaf)?.status == MessageStatus::None {
+        let index = item.index;
+        let path = verifier
+            .path(leaf, &item.proof, index as usize)
+            .ok_or(ContractError::IncompleteBatchProof { leaf, index })?;
+
+        let acceptable = query_acceptable_root(deps.as_ref(), env.clone(), path.root())?.acceptable;
+        if !acceptable {
+            return Err(ContractError::FailedProveCall { leaf, index });
+        }
+
+        verifier.accept(path);
+        _set_message_proven(deps.branch(), leaf)?;
+    }
+
+    execute_process(deps, env.clone(), info.clone(), item.message)
 }
 
 pub fn execute_set_confirmation(
@@ -261,13 +676,87 @@ pub fn execute_set_optimistic_timeout(
     ))
 }
 
-pub fn execute_set_updater(
+pub fn execute_set_updaters(
+    deps: DepsMut,
+    info: MessageInfo,
+    updaters: Vec<SignerId>,
+    threshold: u32,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+    Ok(nomad_base::_set_updaters(deps, updaters, threshold)?)
+}
+
+pub fn execute_set_root_verifier(
+    deps: DepsMut,
+    info: MessageInfo,
+    root_verifier: RootVerifier,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+    _set_root_verifier(deps, root_verifier)
+}
+
+pub fn _set_root_verifier(
+    deps: DepsMut,
+    root_verifier: RootVerifier,
+) -> Result<Response, ContractError> {
+    if let RootVerifier::Attestation {
+        validators,
+        threshold,
+    } = &root_verifier
+    {
+        common::validate_updater_set(validators, *threshold)?;
+    }
+    if let RootVerifier::LightClient {
+        chain_id,
+        home,
+        trusting_period,
+    } = &root_verifier
+    {
+        if chain_id.is_empty() || home.is_empty() || *trusting_period == 0 {
+            return Err(ContractError::InvalidLightClient {});
+        }
+    }
+
+    ROOT_VERIFIER.save(deps.storage, &root_verifier)?;
+
+    Ok(Response::new().add_event(
+        Event::new("SetRootVerifier")
+            .add_attribute("root_verifier", format!("{:?}", root_verifier)),
+    ))
+}
+
+pub fn execute_set_process_gas(
     deps: DepsMut,
     info: MessageInfo,
-    updater: H160,
+    process_gas: u64,
 ) -> Result<Response, ContractError> {
     ownable::only_owner(deps.as_ref(), info)?;
-    Ok(nomad_base::_set_updater(deps, updater)?)
+    PROCESS_GAS.save(deps.storage, &process_gas)?;
+    Ok(Response::new().add_event(
+        Event::new("SetProcessGas").add_attribute("process_gas", process_gas.to_string()),
+    ))
+}
+
+pub fn execute_set_ordered_delivery(
+    deps: DepsMut,
+    info: MessageInfo,
+    next_nonce: Option<u32>,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+
+    let next_nonce_attr = match next_nonce {
+        Some(next_nonce) => {
+            NEXT_NONCE.save(deps.storage, &next_nonce)?;
+            next_nonce.to_string()
+        }
+        None => {
+            NEXT_NONCE.remove(deps.storage);
+            "none".to_owned()
+        }
+    };
+
+    Ok(Response::new()
+        .add_event(Event::new("SetOrderedDelivery").add_attribute("next_nonce", next_nonce_attr)))
 }
 
 pub fn _set_message_proven(deps: DepsMut, leaf: H256) -> Result<Response, ContractError> {
@@ -275,23 +764,115 @@ pub fn _set_message_proven(deps: DepsMut, leaf: H256) -> Result<Response, Contra
     Ok(Response::new())
 }
 
-pub fn _fail(mut deps: DepsMut, _info: MessageInfo) -> Result<Response, nomad_base::ContractError> {
+pub fn _fail(
+    mut deps: DepsMut,
+    _info: MessageInfo,
+    _updaters: Vec<SignerId>,
+) -> Result<Response, nomad_base::ContractError> {
     Ok(nomad_base::_set_failed(deps.branch())?)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
-        PROCESS_ID => reply_process(deps.as_ref(), env, msg),
+        PROCESS_ID => reply_process(deps, env, msg),
+        BATCH_PROCESS_ID => reply_batch_process(deps, env, msg),
         _ => Err(ContractError::UnknownReplyMessage { id: msg.id }),
     }
 }
 
-pub fn reply_process(_deps: Deps, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+/// Records delivery of innermost in-flight message and returns whether it
+/// succeeded as data
+pub fn reply_process(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let (res, success) = _record_delivery(deps, msg)?;
+    Ok(res.set_data(to_binary(&success)?))
+}
+
+/// Records delivery of a batch item. Sets no data, as reply data would
+/// replace the batch's per-item results, so the outcome is only reported as
+/// an event and in message status. `BatchItemResult::dispatched` therefore
+/// does not reflect it.
+pub fn reply_batch_process(
+    deps: DepsMut,
+    _env: Env,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    Ok(_record_delivery(deps, msg)?.0)
+}
+
+/// Pops innermost in-flight message and marks it failed if delivery errored.
+/// Recipient state changes are already reverted so message can be retried.
+fn _record_delivery(deps: DepsMut, msg: Reply) -> Result<(Response, bool), ContractError> {
+    let mut processing = PROCESSING.may_load(deps.storage)?.unwrap_or_default();
+    let leaf = processing.pop().ok_or(ContractError::NotProcessing {})?;
+    PROCESSING.save(deps.storage, &processing)?;
+
     match msg.result {
-        ContractResult::Ok(_) => Ok(Response::new().set_data(to_binary(&true)?)),
-        ContractResult::Err(_) => Ok(Response::new().set_data(to_binary(&false)?)),
+        ContractResult::Ok(_) => Ok((
+            Response::new().add_event(
+                Event::new("ProcessSucceeded").add_attribute("leaf", format!("{:?}", leaf)),
+            ),
+            true,
+        )),
+        ContractResult::Err(error) => {
+            MESSAGES.save(
+                deps.storage,
+                leaf.as_bytes(),
+                &MessageStatus::Failed(error.clone()),
+            )?;
+
+            Ok((
+                Response::new().add_event(
+                    Event::new("ProcessFailed")
+                        .add_attribute("leaf", format!("{:?}", leaf))
+                        .add_attribute("error", error),
+                ),
+                false,
+            ))
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored_version = ensure_upgrade(deps.as_ref())?;
+
+    // Per-version storage migrations, oldest first
+    if stored_version < Version::new(0, 2, 0) {
+        // v0.2 replaces single updater with threshold updater set
+        nomad_base::migrate_legacy_updater(deps.branch())?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_event(
+        Event::new("Migrate")
+            .add_attribute("from_version", stored_version.to_string())
+            .add_attribute("to_version", CONTRACT_VERSION),
+    ))
+}
+
+/// Checks that stored cw2 info belongs to this contract and that stored
+/// version is not newer than this code (no downgrades). Returns stored version.
+pub fn ensure_upgrade(deps: Deps) -> Result<Version, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::WrongContract {
+            expected: CONTRACT_NAME.to_owned(),
+            found: stored.contract,
+        });
     }
+
+    let stored_version: Version = stored.version.parse()?;
+    let version: Version = CONTRACT_VERSION.parse()?;
+    if stored_version > version {
+        return Err(ContractError::CannotDowngrade {
+            stored: stored.version,
+            version: CONTRACT_VERSION.to_owned(),
+        });
+    }
+
+    Ok(stored_version)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -304,12 +885,17 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             let opt = query_optimistic_seconds(deps)?;
             return to_binary(&opt);
         }
+        QueryMsg::ProcessGas {} => to_binary(&query_process_gas(deps)?),
+        QueryMsg::NextExpectedNonce {} => to_binary(&query_next_expected_nonce(deps)?),
         QueryMsg::RemoteDomain {} => to_binary(&query_remote_domain(deps)?),
+        QueryMsg::RootVerifier {} => to_binary(&query_root_verifier(deps)?),
+        QueryMsg::ConsensusState { height } => to_binary(&query_consensus_state(deps, height)?),
+        QueryMsg::LatestConsensusHeight {} => to_binary(&query_latest_consensus_height(deps)?),
         QueryMsg::CommittedRoot {} => to_binary(&nomad_base::query_committed_root(deps)?),
         QueryMsg::HomeDomainHash {} => to_binary(&query_home_domain_hash(deps)?),
         QueryMsg::LocalDomain {} => to_binary(&nomad_base::query_local_domain(deps)?),
         QueryMsg::State {} => to_binary(&nomad_base::query_state(deps)?),
-        QueryMsg::Updater {} => to_binary(&nomad_base::query_updater(deps)?),
+        QueryMsg::Updaters {} => to_binary(&nomad_base::query_updaters(deps)?),
         QueryMsg::Owner {} => to_binary(&ownable::query_owner(deps)?),
     }
 }
@@ -356,23 +942,56 @@ pub fn query_optimistic_seconds(deps: Deps) -> StdResult<OptimisticSecondsRespon
     Ok(OptimisticSecondsResponse { optimistic_seconds })
 }
 
+pub fn query_next_expected_nonce(deps: Deps) -> StdResult<NextExpectedNonceResponse> {
+    let next_nonce = NEXT_NONCE.may_load(deps.storage)?;
+    Ok(NextExpectedNonceResponse { next_nonce })
+}
+
+pub fn query_process_gas(deps: Deps) -> StdResult<ProcessGasResponse> {
+    let process_gas = PROCESS_GAS
+        .may_load(deps.storage)?
+        .unwrap_or(DEFAULT_PROCESS_GAS);
+    Ok(ProcessGasResponse { process_gas })
+}
+
 pub fn query_remote_domain(deps: Deps) -> StdResult<RemoteDomainResponse> {
     let remote_domain = REMOTE_DOMAIN.load(deps.storage)?;
     Ok(RemoteDomainResponse { remote_domain })
 }
 
+pub fn query_root_verifier(deps: Deps) -> StdResult<RootVerifierResponse> {
+    let root_verifier = ROOT_VERIFIER.may_load(deps.storage)?.unwrap_or_default();
+    Ok(RootVerifierResponse { root_verifier })
+}
+
+pub fn query_consensus_state(deps: Deps, height: u64) -> StdResult<ConsensusStateResponse> {
+    let consensus_state = CONSENSUS_STATES.may_load(deps.storage, height)?;
+    Ok(ConsensusStateResponse { consensus_state })
+}
+
+pub fn query_latest_consensus_height(deps: Deps) -> StdResult<LatestConsensusHeightResponse> {
+    let height = LATEST_CONSENSUS_HEIGHT
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    Ok(LatestConsensusHeightResponse { height })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::light_client::ics23::Ics23Error;
+    use common::light_client::{LightClientError, BLOCK_ID_FLAG_ABSENT};
     use common::nomad_base::{
-        CommittedRootResponse, LocalDomainResponse, StateResponse, UpdaterResponse,
+        CommittedRootResponse, LocalDomainResponse, StateResponse, UpdatersResponse,
     };
-    use common::States;
+    use common::{Encode, States};
     use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
-    use cosmwasm_std::{coins, from_binary};
-    use test_utils::{event_attr_value_by_key, Updater};
+    use cosmwasm_std::{coins, from_binary, Addr};
+    use nomad_base::state::{LEGACY_UPDATER, THRESHOLD, UPDATERS};
+    use test_utils::{
+        event_attr_value_by_key, mock_padded_dependencies, NativeKey, NativeUpdater, Updater,
+    };
 
-    const CHAIN_ADDR_LENGTH_BYTES: usize = 42;
     const LOCAL_DOMAIN: u32 = 2000;
     const REMOTE_DOMAIN: u32 = 1000;
     const UPDATER_PRIVKEY: &str =
@@ -387,12 +1006,13 @@ mod tests {
         let optimistic_seconds = 100u64;
 
         let msg = InstantiateMsg {
-            chain_addr_length_bytes: CHAIN_ADDR_LENGTH_BYTES,
             local_domain: LOCAL_DOMAIN,
             remote_domain: REMOTE_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
             committed_root: H256::zero(),
             optimistic_seconds,
+            root_verifier: None,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -422,9 +1042,10 @@ mod tests {
         assert_eq!(LOCAL_DOMAIN, value.local_domain);
 
         // Updater
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::Updater {}).unwrap();
-        let value: UpdaterResponse = from_binary(&res).unwrap();
-        assert_eq!(updater.address(), value.updater);
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Updaters {}).unwrap();
+        let value: UpdatersResponse = from_binary(&res).unwrap();
+        assert_eq!(vec![updater.signer_id()], value.updaters);
+        assert_eq!(1, value.threshold);
     }
 
     #[tokio::test]
@@ -436,12 +1057,13 @@ mod tests {
         let optimistic_seconds = 100u64;
 
         let msg = InstantiateMsg {
-            chain_addr_length_bytes: CHAIN_ADDR_LENGTH_BYTES,
             local_domain: LOCAL_DOMAIN,
             remote_domain: REMOTE_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
             committed_root: H256::zero(),
             optimistic_seconds,
+            root_verifier: None,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -449,7 +1071,7 @@ mod tests {
         assert_eq!(0, res.messages.len());
 
         // Set failed
-        _fail(deps.as_mut(), info).unwrap();
+        _fail(deps.as_mut(), info, vec![]).unwrap();
 
         // Try to submit update to replica
         let committed_root = H256::zero();
@@ -460,7 +1082,7 @@ mod tests {
         let msg = ExecuteMsg::Update {
             committed_root,
             new_root,
-            signature: update.signature.to_vec(),
+            signatures: vec![update.signature.to_vec()],
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg);
         assert!(res.is_err());
@@ -475,12 +1097,13 @@ mod tests {
         let optimistic_seconds = 100u64;
 
         let msg = InstantiateMsg {
-            chain_addr_length_bytes: CHAIN_ADDR_LENGTH_BYTES,
             local_domain: LOCAL_DOMAIN,
             remote_domain: REMOTE_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
             committed_root: H256::zero(),
             optimistic_seconds,
+            root_verifier: None,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -506,7 +1129,7 @@ mod tests {
         let msg = ExecuteMsg::Update {
             committed_root,
             new_root: first_new_root,
-            signature: first_update.signature.to_vec(),
+            signatures: vec![first_update.signature.to_vec()],
         };
         execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -519,7 +1142,7 @@ mod tests {
         let msg = ExecuteMsg::Update {
             committed_root: first_new_root,
             new_root: second_new_root,
-            signature: second_update.signature.to_vec(),
+            signatures: vec![second_update.signature.to_vec()],
         };
         execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -543,12 +1166,13 @@ mod tests {
         let optimistic_seconds = 100u64;
 
         let msg = InstantiateMsg {
-            chain_addr_length_bytes: CHAIN_ADDR_LENGTH_BYTES,
             local_domain: LOCAL_DOMAIN,
             remote_domain: REMOTE_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
             committed_root: H256::zero(),
             optimistic_seconds,
+            root_verifier: None,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -569,7 +1193,7 @@ mod tests {
         let msg = ExecuteMsg::Update {
             committed_root,
             new_root,
-            signature: invalid_update.signature.to_vec(),
+            signatures: vec![invalid_update.signature.to_vec()],
         };
         let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
         assert!(res.is_err());
@@ -584,12 +1208,13 @@ mod tests {
         let optimistic_seconds = 100u64;
 
         let msg = InstantiateMsg {
-            chain_addr_length_bytes: CHAIN_ADDR_LENGTH_BYTES,
             local_domain: LOCAL_DOMAIN,
             remote_domain: REMOTE_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
             committed_root: H256::zero(),
             optimistic_seconds,
+            root_verifier: None,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -613,7 +1238,7 @@ mod tests {
         let msg = ExecuteMsg::Update {
             committed_root,
             new_root,
-            signature: first_update.signature.to_vec(),
+            signatures: vec![first_update.signature.to_vec()],
         };
         execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -626,7 +1251,7 @@ mod tests {
         let msg = ExecuteMsg::Update {
             committed_root: invalid_old_root,
             new_root: invalid_new_root,
-            signature: second_update.signature.to_vec(),
+            signatures: vec![second_update.signature.to_vec()],
         };
         let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
         assert!(res.is_err());
@@ -641,12 +1266,13 @@ mod tests {
         let optimistic_seconds = 100u64;
 
         let msg = InstantiateMsg {
-            chain_addr_length_bytes: CHAIN_ADDR_LENGTH_BYTES,
             local_domain: LOCAL_DOMAIN,
             remote_domain: REMOTE_DOMAIN,
-            updater: updater.address(),
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
             committed_root: H256::zero(),
             optimistic_seconds,
+            root_verifier: None,
         };
         let info = mock_info("owner", &coins(100, "earth"));
 
@@ -666,8 +1292,8 @@ mod tests {
         let msg = ExecuteMsg::DoubleUpdate {
             old_root: committed_root,
             new_roots: [new_root, bad_root],
-            signature: update.signature.to_vec(),
-            signature_2: bad_update.signature.to_vec(),
+            signatures: vec![update.signature.to_vec()],
+            signatures_2: vec![bad_update.signature.to_vec()],
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -683,12 +1309,12 @@ mod tests {
             event_attr_value_by_key(&event, "new_roots").unwrap()
         );
         assert_eq!(
-            format!("{:?}", update.signature.to_vec()),
-            event_attr_value_by_key(&event, "signature").unwrap()
+            format!("{:?}", vec![update.signature.to_vec()]),
+            event_attr_value_by_key(&event, "signatures").unwrap()
         );
         assert_eq!(
-            format!("{:?}", bad_update.signature.to_vec()),
-            event_attr_value_by_key(&event, "signature_2").unwrap()
+            format!("{:?}", vec![bad_update.signature.to_vec()]),
+            event_attr_value_by_key(&event, "signatures_2").unwrap()
         );
 
         // Check replica failed
@@ -696,4 +1322,1020 @@ mod tests {
         let state = from_binary::<StateResponse>(&res).unwrap().state;
         assert_eq!(States::Failed, state);
     }
+
+    #[test]
+    fn processes_with_process_gas_and_retries_failed_message() {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            remote_domain: REMOTE_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+            committed_root: H256::zero(),
+            optimistic_seconds: 100,
+            root_verifier: None,
+        };
+        let info = mock_info("owner", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // Default process gas
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ProcessGas {}).unwrap();
+        let value: ProcessGasResponse = from_binary(&res).unwrap();
+        assert_eq!(DEFAULT_PROCESS_GAS, value.process_gas);
+
+        // Only owner can set process gas
+        let msg = ExecuteMsg::SetProcessGas {
+            process_gas: 500_000,
+        };
+        let info = mock_info("not_owner", &[]);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg.clone()).is_err());
+
+        let info = mock_info("owner", &[]);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ProcessGas {}).unwrap();
+        let value: ProcessGasResponse = from_binary(&res).unwrap();
+        assert_eq!(500_000, value.process_gas);
+
+        // Process proven message
+        let recipient = common::addr_to_h256(&deps.api, &Addr::unchecked("recipient")).unwrap();
+        let nomad_message = NomadMessage {
+            origin: REMOTE_DOMAIN,
+            sender: H256::zero(),
+            nonce: 0,
+            destination: LOCAL_DOMAIN,
+            recipient,
+            body: "0x".as_bytes().to_vec(),
+        };
+        let leaf = nomad_message.to_leaf();
+        _set_message_proven(deps.as_mut(), leaf).unwrap();
+
+        let msg = ExecuteMsg::Process {
+            message: nomad_message.to_vec(),
+        };
+        let info = mock_info("processor", &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(Some(500_000), res.messages[0].gas_limit);
+
+        // Malformed messages are rejected without panicking
+        for msg in [
+            ExecuteMsg::Process {
+                message: vec![1, 2, 3],
+            },
+            ExecuteMsg::RetryProcess {
+                message: vec![1, 2, 3],
+            },
+            ExecuteMsg::ProveAndProcess {
+                message: vec![1, 2, 3],
+                proof: [H256::zero(); 32],
+                index: 0,
+            },
+        ] {
+            let info = mock_info("processor", &[]);
+            let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+            assert!(matches!(err, ContractError::MalformedMessage {}));
+        }
+
+        // Cannot retry message that has not failed
+        let retry_msg = ExecuteMsg::RetryProcess {
+            message: nomad_message.to_vec(),
+        };
+        let info = mock_info("processor", &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, retry_msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::MessageNotFailed { .. }));
+
+        // Recipient runs out of gas
+        let reply_msg = Reply {
+            id: PROCESS_ID,
+            result: ContractResult::Err("out of gas".to_owned()),
+        };
+        let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+        assert!(!from_binary::<bool>(&res.data.unwrap()).unwrap());
+
+        let event = &res.events[0];
+        assert_eq!("ProcessFailed".to_owned(), event.ty);
+        assert_eq!(
+            "out of gas",
+            event_attr_value_by_key(&event, "error").unwrap()
+        );
+
+        let status = query_message_status(deps.as_ref(), leaf).unwrap().status;
+        assert_eq!(MessageStatus::Failed("out of gas".to_owned()), status);
+
+        // Retry redelivers message
+        let info = mock_info("processor", &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, retry_msg).unwrap();
+        assert_eq!(Some(500_000), res.messages[0].gas_limit);
+
+        let status = query_message_status(deps.as_ref(), leaf).unwrap().status;
+        assert_eq!(MessageStatus::Processed, status);
+    }
+
+    #[test]
+    fn processes_messages_in_nonce_order() {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            remote_domain: REMOTE_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+            committed_root: H256::zero(),
+            optimistic_seconds: 100,
+            root_verifier: None,
+        };
+        let info = mock_info("owner", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // Unordered by default
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::NextExpectedNonce {}).unwrap();
+        let value: NextExpectedNonceResponse = from_binary(&res).unwrap();
+        assert_eq!(None, value.next_nonce);
+
+        // Only owner can enable ordered delivery
+        let msg = ExecuteMsg::SetOrderedDelivery {
+            next_nonce: Some(1),
+        };
+        let info = mock_info("not_owner", &[]);
+        assert!(execute(deps.as_mut(), mock_env(), info, msg.clone()).is_err());
+
+        let info = mock_info("owner", &[]);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let recipient = common::addr_to_h256(&deps.api, &Addr::unchecked("recipient")).unwrap();
+        let messages: Vec<NomadMessage> = (0..3)
+            .map(|nonce| NomadMessage {
+                origin: REMOTE_DOMAIN,
+                sender: H256::zero(),
+                nonce,
+                destination: LOCAL_DOMAIN,
+                recipient,
+                body: "0x".as_bytes().to_vec(),
+            })
+            .collect();
+        for message in &messages {
+            _set_message_proven(deps.as_mut(), message.to_leaf()).unwrap();
+        }
+
+        let process = |deps: DepsMut, nonce: usize| {
+            let msg = ExecuteMsg::Process {
+                message: messages[nonce].to_vec(),
+            };
+            execute(deps, mock_env(), mock_info("processor", &[]), msg)
+        };
+
+        let err = process(deps.as_mut(), 2).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::FutureNonce {
+                nonce: 2,
+                next_nonce: 1
+            }
+        ));
+
+        let err = process(deps.as_mut(), 0).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::StaleNonce {
+                nonce: 0,
+                next_nonce: 1
+            }
+        ));
+
+        process(deps.as_mut(), 1).unwrap();
+        process(deps.as_mut(), 2).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::NextExpectedNonce {}).unwrap();
+        let value: NextExpectedNonceResponse = from_binary(&res).unwrap();
+        assert_eq!(Some(3), value.next_nonce);
+
+        // Disabling ordered delivery accepts any nonce
+        let msg = ExecuteMsg::SetOrderedDelivery { next_nonce: None };
+        let info = mock_info("owner", &[]);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        process(deps.as_mut(), 0).unwrap();
+    }
+
+    #[test]
+    fn expires_enveloped_messages_and_unwraps_body() {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            remote_domain: REMOTE_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+            committed_root: H256::zero(),
+            optimistic_seconds: 100,
+            root_verifier: None,
+        };
+        let info = mock_info("owner", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let now = mock_env().block.time.seconds();
+        let recipient = common::addr_to_h256(&deps.api, &Addr::unchecked("recipient")).unwrap();
+        let messages: Vec<NomadMessage> = [now, now - 1]
+            .iter()
+            .enumerate()
+            .map(|(nonce, expires_at)| NomadMessage {
+                origin: REMOTE_DOMAIN,
+                sender: H256::zero(),
+                nonce: nonce as u32,
+                destination: LOCAL_DOMAIN,
+                recipient,
+                body: Envelope::new(*expires_at, "hello".as_bytes().to_vec()).to_vec(),
+            })
+            .collect();
+        for message in &messages {
+            _set_message_proven(deps.as_mut(), message.to_leaf()).unwrap();
+        }
+
+        // Unexpired message delivered without envelope
+        let msg = ExecuteMsg::Process {
+            message: messages[0].to_vec(),
+        };
+        let info = mock_info("processor", &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                let HandleExecuteMsg::HandleMsg { message, .. } = from_binary(msg).unwrap();
+                assert_eq!("hello".as_bytes().to_vec(), message);
+            }
+            _ => panic!("expected wasm execute"),
+        }
+
+        // Expired message never delivered
+        let msg = ExecuteMsg::Process {
+            message: messages[1].to_vec(),
+        };
+        let info = mock_info("processor", &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert!(res.messages.is_empty());
+        assert!(!from_binary::<bool>(&res.data.unwrap()).unwrap());
+
+        let event = &res.events[0];
+        assert_eq!("MessageExpired".to_owned(), event.ty);
+        assert_eq!(
+            (now - 1).to_string(),
+            event_attr_value_by_key(&event, "expires_at").unwrap()
+        );
+
+        let leaf = messages[1].to_leaf();
+        let status = query_message_status(deps.as_ref(), leaf).unwrap().status;
+        assert_eq!(MessageStatus::Expired, status);
+
+        // Failed message expires on retry after expiry
+        let reply_msg = Reply {
+            id: PROCESS_ID,
+            result: ContractResult::Err("out of gas".to_owned()),
+        };
+        reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(1);
+        let msg = ExecuteMsg::RetryProcess {
+            message: messages[0].to_vec(),
+        };
+        let info = mock_info("processor", &[]);
+        let res = execute(deps.as_mut(), env, info, msg).unwrap();
+        assert!(res.messages.is_empty());
+
+        let leaf = messages[0].to_leaf();
+        let status = query_message_status(deps.as_ref(), leaf).unwrap().status;
+        assert_eq!(MessageStatus::Expired, status);
+    }
+
+    #[test]
+    fn batch_proves_and_processes_items_independently() {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            remote_domain: REMOTE_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+            committed_root: H256::zero(),
+            optimistic_seconds: 100,
+            root_verifier: None,
+        };
+        let info = mock_info("owner", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let recipient = common::addr_to_h256(&deps.api, &Addr::unchecked("recipient")).unwrap();
+        let messages: Vec<NomadMessage> = (0..4)
+            .map(|nonce| NomadMessage {
+                origin: REMOTE_DOMAIN,
+                sender: H256::zero(),
+                nonce,
+                destination: LOCAL_DOMAIN,
+                recipient,
+                body: "0x".as_bytes().to_vec(),
+            })
+            .collect();
+        let leaves: Vec<H256> = messages.iter().map(|message| message.to_leaf()).collect();
+        let tree = merkle_tree::FullMerkle::from_leaves(&leaves).unwrap();
+
+        let msg = ExecuteMsg::SetConfirmation {
+            root: tree.root(),
+            confirm_at: 1,
+        };
+        let info = mock_info("owner", &[]);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let item = |index: usize, proof: Vec<H256>| BatchItem {
+            message: messages[index].to_vec(),
+            proof,
+            index: index as u64,
+        };
+        let items = vec![
+            item(0, tree.prove(0).unwrap().path.to_vec()),
+            // Shares path with first item, needs no siblings
+            item(1, vec![]),
+            BatchItem {
+                message: vec![1, 2, 3],
+                proof: vec![],
+                index: 2,
+            },
+            // Sibling leaf 2 not yet known
+            item(3, vec![]),
+            item(2, tree.prove(2).unwrap().path[..2].to_vec()),
+        ];
+
+        let msg = ExecuteMsg::BatchProveAndProcess { items };
+        let info = mock_info("processor", &coins(10, "token"));
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let results = from_binary::<BatchProveAndProcessResponse>(&res.data.unwrap())
+            .unwrap()
+            .results;
+        let dispatched: Vec<bool> = results.iter().map(|result| result.dispatched).collect();
+        assert_eq!(vec![true, true, false, false, true], dispatched);
+        assert_eq!(leaves[3], results[3].leaf);
+        assert!(results[3]
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("missing siblings"));
+
+        // Failed items do not revert others, funds are not forwarded
+        assert_eq!(3, res.messages.len());
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { funds, .. }) => assert!(funds.is_empty()),
+            _ => panic!("expected wasm execute"),
+        }
+        let status = query_message_status(deps.as_ref(), leaves[3])
+            .unwrap()
+            .status;
+        assert_eq!(MessageStatus::None, status);
+
+        assert!(res
+            .messages
+            .iter()
+            .all(|sub_msg| sub_msg.id == BATCH_PROCESS_ID));
+
+        // First delivery replies first, batch replies report outcome as
+        // events and keep batch response data. Dispatched item still fails
+        let reply_msg = Reply {
+            id: BATCH_PROCESS_ID,
+            result: ContractResult::Err("out of gas".to_owned()),
+        };
+        let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+        assert_eq!(None, res.data);
+        assert_eq!("ProcessFailed", res.events[0].ty);
+
+        let status = query_message_status(deps.as_ref(), leaves[0])
+            .unwrap()
+            .status;
+        assert_eq!(MessageStatus::Failed("out of gas".to_owned()), status);
+        let status = query_message_status(deps.as_ref(), leaves[1])
+            .unwrap()
+            .status;
+        assert_eq!(MessageStatus::Processed, status);
+    }
+
+    #[test]
+    fn batch_item_failing_before_dispatch_keeps_nonce() {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+
+        let mut deps = mock_padded_dependencies(&coins(2, "token"));
+
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            remote_domain: REMOTE_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+            committed_root: H256::zero(),
+            optimistic_seconds: 100,
+            root_verifier: None,
+        };
+        let info = mock_info("owner", &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::SetOrderedDelivery {
+            next_nonce: Some(0),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // Same nonce, first to a recipient that is not an address
+        let recipient = common::addr_to_h256(&deps.api, &Addr::unchecked("recipient")).unwrap();
+        let messages: Vec<NomadMessage> = [H256::repeat_byte(0xff), recipient]
+            .iter()
+            .map(|recipient| NomadMessage {
+                origin: REMOTE_DOMAIN,
+                sender: H256::zero(),
+                nonce: 0,
+                destination: LOCAL_DOMAIN,
+                recipient: *recipient,
+                body: "0x".as_bytes().to_vec(),
+            })
+            .collect();
+        for message in &messages {
+            _set_message_proven(deps.as_mut(), message.to_leaf()).unwrap();
+        }
+
+        let items = messages
+            .iter()
+            .map(|message| BatchItem {
+                message: message.to_vec(),
+                proof: vec![],
+                index: 0,
+            })
+            .collect();
+        let msg = ExecuteMsg::BatchProveAndProcess { items };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("processor", &[]), msg).unwrap();
+
+        let results = from_binary::<BatchProveAndProcessResponse>(&res.data.unwrap())
+            .unwrap()
+            .results;
+        let dispatched: Vec<bool> = results.iter().map(|result| result.dispatched).collect();
+        assert_eq!(vec![false, true], dispatched);
+
+        // Undeliverable message stays proven
+        let status = query_message_status(deps.as_ref(), messages[0].to_leaf())
+            .unwrap()
+            .status;
+        assert_eq!(MessageStatus::Pending, status);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::NextExpectedNonce {}).unwrap();
+        let value: NextExpectedNonceResponse = from_binary(&res).unwrap();
+        assert_eq!(Some(1), value.next_nonce);
+    }
+
+    #[test]
+    fn migrates_legacy_updater_and_sets_updaters() {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            remote_domain: REMOTE_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+            committed_root: H256::zero(),
+            optimistic_seconds: 100,
+            root_verifier: None,
+        };
+        let info = mock_info("owner", &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        // Simulate v0.1.0 storage with single updater
+        UPDATERS.remove(&mut deps.storage);
+        THRESHOLD.remove(&mut deps.storage);
+        LEGACY_UPDATER
+            .save(&mut deps.storage, &updater.address())
+            .unwrap();
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Updaters {}).unwrap();
+        let value: UpdatersResponse = from_binary(&res).unwrap();
+        assert_eq!(vec![updater.signer_id()], value.updaters);
+        assert_eq!(1, value.threshold);
+
+        // Only owner sets updaters
+        let updaters = vec![
+            updater.signer_id(),
+            SignerId::Ed25519PubKey(vec![2u8; 32].into()),
+        ];
+        let msg = ExecuteMsg::SetUpdaters {
+            updaters: updaters.clone(),
+            threshold: 2,
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("not_owner", &[]),
+            msg.clone(),
+        );
+        assert!(res.is_err());
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Updaters {}).unwrap();
+        let value: UpdatersResponse = from_binary(&res).unwrap();
+        assert_eq!(updaters, value.updaters);
+        assert_eq!(2, value.threshold);
+    }
+
+    #[tokio::test]
+    async fn attested_root_acceptable_immediately() {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+        let validators = vec![
+            NativeUpdater::new(NativeKey::secp256k1(&"2".repeat(64)), LOCAL_DOMAIN),
+            NativeUpdater::new(NativeKey::ed25519(&"3".repeat(64)), LOCAL_DOMAIN),
+        ];
+        let eth_validator: Updater = Updater::from_privkey(&"4".repeat(64), LOCAL_DOMAIN);
+
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let root_verifier = RootVerifier::Attestation {
+            validators: vec![
+                validators[0].signer_id(),
+                validators[1].signer_id(),
+                eth_validator.signer_id(),
+            ],
+            threshold: 2,
+        };
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            remote_domain: REMOTE_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+            committed_root: H256::zero(),
+            optimistic_seconds: 100,
+            root_verifier: Some(root_verifier.clone()),
+        };
+        let info = mock_info("owner", &coins(100, "earth"));
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::RootVerifier {}).unwrap();
+        let value: RootVerifierResponse = from_binary(&res).unwrap();
+        assert_eq!(root_verifier, value.root_verifier);
+
+        let committed_root = H256::zero();
+        let new_root = H256::repeat_byte(1);
+        let info = mock_info("submitter", &[]);
+
+        // Updater signature alone is not an attestation
+        let update = updater.sign_update(committed_root, new_root).await.unwrap();
+        let msg = ExecuteMsg::Update {
+            committed_root,
+            new_root,
+            signatures: vec![update.signature.to_vec()],
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::NotValidatorAttestation {}
+        ));
+
+        // Below threshold
+        let signature = validators[0].sign_update(committed_root, new_root);
+        let msg = ExecuteMsg::Update {
+            committed_root,
+            new_root,
+            signatures: vec![signature.clone(), signature.clone()],
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::NotValidatorAttestation {}
+        ));
+
+        let eth_signature = eth_validator
+            .sign_update(committed_root, new_root)
+            .await
+            .unwrap()
+            .signature
+            .to_vec();
+        let msg = ExecuteMsg::Update {
+            committed_root,
+            new_root,
+            signatures: vec![eth_signature, signature],
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(
+            mock_env().block.time.seconds().to_string(),
+            event_attr_value_by_key(&res.events[0], "confirm_at").unwrap()
+        );
+
+        // No optimistic window
+        let msg = QueryMsg::AcceptableRoot { root: new_root };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value: AcceptableRootResponse = from_binary(&res).unwrap();
+        assert!(value.acceptable);
+    }
+
+    #[tokio::test]
+    async fn owner_sets_root_verifier() {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+        let validator = NativeUpdater::new(NativeKey::ed25519(&"2".repeat(64)), LOCAL_DOMAIN);
+
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let optimistic_seconds = 100u64;
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            remote_domain: REMOTE_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+            committed_root: H256::zero(),
+            optimistic_seconds,
+            root_verifier: None,
+        };
+        let owner_info = mock_info("owner", &[]);
+        instantiate(deps.as_mut(), mock_env(), owner_info.clone(), msg).unwrap();
+
+        // Optimistic by default
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::RootVerifier {}).unwrap();
+        let value: RootVerifierResponse = from_binary(&res).unwrap();
+        assert_eq!(RootVerifier::Optimistic {}, value.root_verifier);
+
+        // Validator threshold must be reachable
+        let msg = ExecuteMsg::SetRootVerifier {
+            root_verifier: RootVerifier::Attestation {
+                validators: vec![validator.signer_id()],
+                threshold: 2,
+            },
+        };
+        let res = execute(deps.as_mut(), mock_env(), owner_info.clone(), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::UpdaterSetError(_)
+        ));
+
+        // Only owner
+        let attestation = RootVerifier::Attestation {
+            validators: vec![validator.signer_id()],
+            threshold: 1,
+        };
+        let msg = ExecuteMsg::SetRootVerifier {
+            root_verifier: attestation.clone(),
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("not_owner", &[]),
+            msg.clone(),
+        );
+        assert!(res.is_err());
+        execute(deps.as_mut(), mock_env(), owner_info.clone(), msg).unwrap();
+
+        let committed_root = H256::zero();
+        let first_root = H256::repeat_byte(1);
+        let msg = ExecuteMsg::Update {
+            committed_root,
+            new_root: first_root,
+            signatures: vec![validator.sign_update(committed_root, first_root)],
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("submitter", &[]), msg).unwrap();
+
+        // Switching back to optimistic reinstates the updater and timeout
+        let msg = ExecuteMsg::SetRootVerifier {
+            root_verifier: RootVerifier::Optimistic {},
+        };
+        execute(deps.as_mut(), mock_env(), owner_info, msg).unwrap();
+
+        let second_root = H256::repeat_byte(2);
+        let msg = ExecuteMsg::Update {
+            committed_root: first_root,
+            new_root: second_root,
+            signatures: vec![validator.sign_update(first_root, second_root)],
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("submitter", &[]), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::NotUpdaterSignature {}
+        ));
+
+        let update = updater.sign_update(first_root, second_root).await.unwrap();
+        let msg = ExecuteMsg::Update {
+            committed_root: first_root,
+            new_root: second_root,
+            signatures: vec![update.signature.to_vec()],
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("submitter", &[]), msg).unwrap();
+
+        let msg = QueryMsg::ConfirmAt { root: second_root };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value: ConfirmAtResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            mock_env().block.time.seconds() + optimistic_seconds,
+            value.confirm_at
+        );
+    }
+
+    /// Origin chain headers and root proof generated by
+    /// testdata/generate_light_client_fixture.py
+    #[derive(serde::Deserialize)]
+    struct LightClientFixture {
+        chain_id: String,
+        home: Binary,
+        trusted_height: u64,
+        trusted_state: ConsensusState,
+        trusted_validators: Vec<Validator>,
+        adjacent: HeaderFixture,
+        skipping: HeaderFixture,
+        proof: RootProofFixture,
+        leaves: Vec<H256>,
+        root: H256,
+    }
+
+    #[derive(Clone, serde::Deserialize)]
+    struct HeaderFixture {
+        signed_header: SignedHeader,
+        validators: Vec<Validator>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct RootProofFixture {
+        height: u64,
+        incremental_merkle: Binary,
+        proofs: Vec<Binary>,
+    }
+
+    const TRUSTING_PERIOD: u64 = 14 * 24 * 60 * 60;
+
+    fn light_client_fixture() -> LightClientFixture {
+        from_slice(include_bytes!("../testdata/light_client.json")).unwrap()
+    }
+
+    fn instantiate_light_client_replica(mut deps: DepsMut, fixture: &LightClientFixture) {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            remote_domain: REMOTE_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+            committed_root: H256::zero(),
+            optimistic_seconds: 100,
+            root_verifier: Some(RootVerifier::LightClient {
+                chain_id: fixture.chain_id.clone(),
+                home: fixture.home.clone(),
+                trusting_period: TRUSTING_PERIOD,
+            }),
+        };
+        instantiate(deps.branch(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::SetTrustedConsensusState {
+            height: fixture.trusted_height,
+            consensus_state: fixture.trusted_state.clone(),
+        };
+        execute(deps, mock_env(), mock_info("owner", &[]), msg).unwrap();
+    }
+
+    fn update_light_client_msg(
+        update: &HeaderFixture,
+        trusted_height: u64,
+        trusted_validators: &[Validator],
+    ) -> ExecuteMsg {
+        ExecuteMsg::UpdateLightClient {
+            signed_header: update.signed_header.clone(),
+            validators: update.validators.clone(),
+            trusted_height,
+            trusted_validators: trusted_validators.to_vec(),
+        }
+    }
+
+    #[test]
+    fn light_client_proves_committed_root() {
+        let fixture = light_client_fixture();
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        instantiate_light_client_replica(deps.as_mut(), &fixture);
+        let info = mock_info("submitter", &[]);
+
+        // Only owner sets trusted state
+        let msg = ExecuteMsg::SetTrustedConsensusState {
+            height: 100,
+            consensus_state: fixture.trusted_state.clone(),
+        };
+        assert!(execute(deps.as_mut(), mock_env(), info.clone(), msg).is_err());
+
+        // Trusting period required
+        let msg = ExecuteMsg::SetRootVerifier {
+            root_verifier: RootVerifier::LightClient {
+                chain_id: fixture.chain_id.clone(),
+                home: fixture.home.clone(),
+                trusting_period: 0,
+            },
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::InvalidLightClient {}
+        ));
+
+        // Roots are not accepted by signature
+        let msg = ExecuteMsg::Update {
+            committed_root: H256::zero(),
+            new_root: fixture.root,
+            signatures: vec![],
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::UpdateInLightClientMode {}
+        ));
+
+        let prove_msg = ExecuteMsg::ProveCommittedRoot {
+            height: fixture.proof.height,
+            incremental_merkle: fixture.proof.incremental_merkle.clone(),
+            proofs: fixture.proof.proofs.clone(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), prove_msg.clone());
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::ConsensusStateNotFound { height: 5 }
+        ));
+
+        // Adjacent header signed by trusted next validators
+        let msg = update_light_client_msg(
+            &fixture.adjacent,
+            fixture.trusted_height,
+            &fixture.trusted_validators,
+        );
+        execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::ConsensusStateExists { height: 2 }
+        ));
+
+        // Skip to header with new validator set
+        let adjacent_height = fixture.adjacent.signed_header.header.height;
+        let msg = update_light_client_msg(
+            &fixture.skipping,
+            adjacent_height,
+            &fixture.adjacent.validators,
+        );
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::LatestConsensusHeight {},
+        )
+        .unwrap();
+        let value: LatestConsensusHeightResponse = from_binary(&res).unwrap();
+        assert_eq!(fixture.proof.height, value.height);
+
+        let msg = QueryMsg::ConsensusState {
+            height: fixture.proof.height,
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value: ConsensusStateResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            Some(ConsensusState::from(&fixture.skipping.signed_header.header)),
+            value.consensus_state
+        );
+
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), prove_msg.clone()).unwrap();
+        assert_eq!(
+            mock_env().block.time.seconds().to_string(),
+            event_attr_value_by_key(&res.events[0], "confirm_at").unwrap()
+        );
+
+        // Committed root is root of home's tree
+        let mut tree = IncrementalMerkle::default();
+        fixture.leaves.iter().for_each(|leaf| tree.ingest(*leaf));
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::CommittedRoot {}).unwrap();
+        let value: CommittedRootResponse = from_binary(&res).unwrap();
+        assert_eq!(fixture.root, value.committed_root);
+        assert_eq!(tree.root(), value.committed_root);
+
+        let msg = QueryMsg::AcceptableRoot { root: fixture.root };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value: AcceptableRootResponse = from_binary(&res).unwrap();
+        assert!(value.acceptable);
+
+        // Cannot reprove at same height
+        let res = execute(deps.as_mut(), mock_env(), info, prove_msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::StaleRootProof {
+                height: 5,
+                proven_height: 5
+            }
+        ));
+    }
+
+    #[test]
+    fn light_client_rejects_invalid_headers_and_proofs() {
+        let fixture = light_client_fixture();
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        instantiate_light_client_replica(deps.as_mut(), &fixture);
+        let info = mock_info("submitter", &[]);
+        let trusted_height = fixture.trusted_height;
+
+        // Header altered after signing
+        let mut update = fixture.adjacent.clone();
+        update.signed_header.header.app_hash = Binary::from(vec![1u8; 32]);
+        let msg = update_light_client_msg(&update, trusted_height, &fixture.trusted_validators);
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::LightClientError(LightClientError::CommitMismatch {})
+        ));
+
+        // Only 20 of 100 voting power signed
+        let mut update = fixture.adjacent.clone();
+        update.signed_header.commit.signatures[0].block_id_flag = BLOCK_ID_FLAG_ABSENT;
+        update.signed_header.commit.signatures[1].block_id_flag = BLOCK_ID_FLAG_ABSENT;
+        let msg = update_light_client_msg(&update, trusted_height, &fixture.trusted_validators);
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::LightClientError(LightClientError::InsufficientVotingPower {
+                signed: 20,
+                needed: 66
+            })
+        ));
+
+        // Trusted validators must match trusted state
+        let msg = update_light_client_msg(
+            &fixture.skipping,
+            trusted_height,
+            &fixture.skipping.validators,
+        );
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::LightClientError(LightClientError::TrustedValidatorsHashMismatch {})
+        ));
+
+        // Trusted state expired
+        let mut env = mock_env();
+        env.block.time = cosmwasm_std::Timestamp::from_seconds(
+            fixture.trusted_state.time.seconds as u64 + TRUSTING_PERIOD,
+        );
+        let msg = update_light_client_msg(
+            &fixture.adjacent,
+            trusted_height,
+            &fixture.trusted_validators,
+        );
+        let res = execute(deps.as_mut(), env, info.clone(), msg.clone());
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::LightClientError(LightClientError::TrustExpired { height: 1 })
+        ));
+
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let msg = update_light_client_msg(
+            &fixture.skipping,
+            trusted_height,
+            &fixture.trusted_validators,
+        );
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        // Proof against wrong app hash
+        let msg = ExecuteMsg::ProveCommittedRoot {
+            height: fixture.adjacent.signed_header.header.height,
+            incremental_merkle: fixture.proof.incremental_merkle.clone(),
+            proofs: fixture.proof.proofs.clone(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::Ics23Error(Ics23Error::RootMismatch {})
+        ));
+
+        // Tree other than the proven one
+        let mut tree: IncrementalMerkle = from_slice(&fixture.proof.incremental_merkle).unwrap();
+        tree.ingest(H256::repeat_byte(1));
+        let msg = ExecuteMsg::ProveCommittedRoot {
+            height: fixture.proof.height,
+            incremental_merkle: to_binary(&tree).unwrap(),
+            proofs: fixture.proof.proofs.clone(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::Ics23Error(Ics23Error::ValueMismatch {})
+        ));
+
+        // Store proof without multistore proof
+        let msg = ExecuteMsg::ProveCommittedRoot {
+            height: fixture.proof.height,
+            incremental_merkle: fixture.proof.incremental_merkle.clone(),
+            proofs: fixture.proof.proofs[..1].to_vec(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::Ics23Error(Ics23Error::WrongProofCount {
+                expected: 2,
+                count: 1
+            })
+        ));
+    }
 }
@@ -7,12 +7,27 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("Cannot migrate from contract {found}, expected {expected}")]
+    WrongContract { expected: String, found: String },
+
+    #[error("Cannot downgrade from version {stored} to {version}")]
+    CannotDowngrade { stored: String, version: String },
+
+    #[error("{0}")]
+    SemVer(#[from] semver::Error),
+
     #[error("Message with leaf {leaf} already proven")]
     MessageAlreadyProven { leaf: H256 },
 
     #[error("Message with leaf {leaf} not yet proven")]
     MessageNotYetProven { leaf: H256 },
 
+    #[error("Message with leaf {leaf} has not failed")]
+    MessageNotFailed { leaf: H256 },
+
+    #[error("No message processing")]
+    NotProcessing {},
+
     #[error("Not a current committed root: {old_root}")]
     NotCurrentCommittedRoot { old_root: H256 },
 
@@ -22,18 +37,69 @@ pub enum ContractError {
     #[error("Not updater signature")]
     NotUpdaterSignature {},
 
+    #[error("Update not attested by validator threshold")]
+    NotValidatorAttestation {},
+
+    #[error("Message nonce {nonce} is ahead of next expected nonce {next_nonce}")]
+    FutureNonce { nonce: u32, next_nonce: u32 },
+
+    #[error("Message nonce {nonce} is behind next expected nonce {next_nonce}")]
+    StaleNonce { nonce: u32, next_nonce: u32 },
+
+    #[error("Malformed message")]
+    MalformedMessage {},
+
+    #[error("Replica is not in light client mode")]
+    NotLightClient {},
+
+    #[error("Light client replica only accepts proven roots")]
+    UpdateInLightClientMode {},
+
+    #[error("Invalid light client: chain id, home and trusting period must be set")]
+    InvalidLightClient {},
+
+    #[error("No consensus state at height {height}")]
+    ConsensusStateNotFound { height: u64 },
+
+    #[error("Consensus state at height {height} already exists")]
+    ConsensusStateExists { height: u64 },
+
+    #[error("Root proof at height {height} not after proven height {proven_height}")]
+    StaleRootProof { height: u64, proven_height: u64 },
+
     #[error("Failed to prove message. Leaf: {leaf}. Index: {index}")]
     FailedProveCall { leaf: H256, index: u64 },
 
+    #[error("Batch proof of leaf {leaf} at index {index} is missing siblings")]
+    IncompleteBatchProof { leaf: H256, index: u64 },
+
     #[error("Failed to process message with error: {0}")]
     FailedProcessCall(String),
 
     #[error("Unknown reply message id received: {id}")]
     UnknownReplyMessage { id: u64 },
 
+    #[error("{0}")]
+    LightClientError(#[from] common::light_client::LightClientError),
+
+    #[error("{0}")]
+    Ics23Error(#[from] common::light_client::ics23::Ics23Error),
+
+    #[error("{0}")]
+    IbcChannelError(#[from] common::IbcChannelError),
+
+    #[error("{0}")]
+    UpdaterSetError(#[from] common::UpdaterSetError),
+
     #[error("{0}")]
     NomadBaseError(#[from] nomad_base::ContractError),
 
     #[error("{0}")]
     OwnableError(#[from] ownable::ContractError),
+
+    #[error("{0}")]
+    AddressError(#[from] common::AddressError),
+
+    #[error("{0}")]
+    NomadError(#[from] common::NomadError),
 }
@@ -0,0 +1,283 @@
+use common::{Decode, NomadAck, NomadMessage, NomadPacket};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, Binary, DepsMut, Env, Event, IbcBasicResponse, IbcChannelCloseMsg,
+    IbcChannelConnectMsg, IbcChannelOpenMsg, IbcPacketAckMsg, IbcPacketReceiveMsg,
+    IbcPacketTimeoutMsg, IbcReceiveResponse, Response,
+};
+
+use crate::contract::{execute_prove, execute_update};
+use crate::error::ContractError;
+
+/// Packets are checked exactly like direct submissions (updater signatures,
+/// message proofs), so channels are accepted from any counterparty
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_open(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<(), ContractError> {
+    common::check_nomad_channel(msg.channel(), msg.counterparty_version())?;
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_connect(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    common::check_nomad_channel(msg.channel(), msg.counterparty_version())?;
+
+    Ok(IbcBasicResponse::new().add_event(
+        Event::new("IbcChannelConnect")
+            .add_attribute("channel_id", msg.channel().endpoint.channel_id.clone()),
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_close(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Ok(IbcBasicResponse::new().add_event(
+        Event::new("IbcChannelClose")
+            .add_attribute("channel_id", msg.channel().endpoint.channel_id.clone()),
+    ))
+}
+
+/// Submit update or prove message carried by packet. Rejected packets are
+/// acknowledged with an error rather than failing the relayer's transaction
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, ContractError> {
+    match _receive_packet(deps, env, &msg.packet.data) {
+        Ok(res) => Ok(IbcReceiveResponse::new()
+            .set_ack(NomadAck::success()?)
+            .add_attributes(res.attributes)
+            .add_events(res.events)),
+        Err(error) => Ok(IbcReceiveResponse::new()
+            .set_ack(NomadAck::error(&error)?)
+            .add_event(
+                Event::new("IbcPacketRejected")
+                    .add_attribute("channel_id", msg.packet.dest.channel_id)
+                    .add_attribute("sequence", msg.packet.sequence.to_string())
+                    .add_attribute("error", error.to_string()),
+            )),
+    }
+}
+
+/// Proven messages are left pending for `Process`, so recipient handle
+/// replies cannot overwrite the acknowledgement
+pub fn _receive_packet(deps: DepsMut, env: Env, data: &Binary) -> Result<Response, ContractError> {
+    match from_binary(data)? {
+        NomadPacket::Update {
+            committed_root,
+            new_root,
+            signatures,
+        } => execute_update(deps, env, committed_root, new_root, signatures),
+        NomadPacket::Message {
+            message,
+            proof,
+            index,
+        } => {
+            let nomad_message = NomadMessage::read_from(&mut message.as_slice())
+                .map_err(|_| ContractError::MalformedMessage {})?;
+
+            let local_domain = nomad_base::query_local_domain(deps.as_ref())?.local_domain;
+            if nomad_message.destination != local_domain {
+                return Err(ContractError::WrongDestination {
+                    destination: nomad_message.destination,
+                });
+            }
+
+            let leaf = nomad_message.to_leaf();
+            let res = execute_prove(deps, env, leaf, proof, index)?;
+            let proven: bool = from_binary(&res.data.unwrap())?;
+            if !proven {
+                return Err(ContractError::FailedProveCall { leaf, index });
+            }
+
+            Ok(Response::new().add_event(
+                Event::new("IbcMessageProven")
+                    .add_attribute("leaf", format!("{:?}", leaf))
+                    .add_attribute("index", index.to_string()),
+            ))
+        }
+    }
+}
+
+/// Replica sends no packets
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_ack(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Ok(IbcBasicResponse::new())
+}
+
+/// Replica sends no packets
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_timeout(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    Ok(IbcBasicResponse::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract::{instantiate, query};
+    use common::merkle_tree::FullMerkle;
+    use common::nomad_base::CommittedRootResponse;
+    use common::replica::{InstantiateMsg, MessageStatusResponse, QueryMsg};
+    use common::{Encode, MessageStatus, NOMAD_IBC_VERSION};
+    use cosmwasm_std::testing::{
+        mock_env, mock_ibc_channel_connect_ack, mock_ibc_packet_recv, mock_info,
+    };
+    use cosmwasm_std::IbcOrder;
+    use ethers_core::types::H256;
+    use test_utils::{mock_padded_dependencies, Updater};
+
+    const LOCAL_DOMAIN: u32 = 2000;
+    const REMOTE_DOMAIN: u32 = 1000;
+    const UPDATER_PRIVKEY: &str =
+        "1111111111111111111111111111111111111111111111111111111111111111";
+    const CHANNEL_ID: &str = "channel-0";
+    const OPTIMISTIC_SECONDS: u64 = 100;
+
+    fn receive(deps: DepsMut, env: Env, packet: &NomadPacket) -> NomadAck {
+        let msg = mock_ibc_packet_recv(CHANNEL_ID, packet).unwrap();
+        let res = ibc_packet_receive(deps, env, msg).unwrap();
+        from_binary(&res.acknowledgement).unwrap()
+    }
+
+    #[tokio::test]
+    async fn receives_updates_and_messages() {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+        let mut deps = mock_padded_dependencies(&[]);
+
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            remote_domain: REMOTE_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+            committed_root: H256::zero(),
+            optimistic_seconds: OPTIMISTIC_SECONDS,
+            root_verifier: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        let msg = mock_ibc_channel_connect_ack(CHANNEL_ID, IbcOrder::Ordered, NOMAD_IBC_VERSION);
+        ibc_channel_connect(deps.as_mut(), mock_env(), msg).unwrap();
+
+        let nomad_message = NomadMessage {
+            origin: REMOTE_DOMAIN,
+            sender: H256::repeat_byte(1),
+            nonce: 0,
+            destination: LOCAL_DOMAIN,
+            recipient: H256::repeat_byte(2),
+            body: vec![1, 2, 3],
+        };
+        let leaf = nomad_message.to_leaf();
+        let tree = FullMerkle::from_leaves(&[leaf]).unwrap();
+        let new_root = tree.root();
+
+        // Updates pass through replica update checks
+        let update = updater.sign_update(H256::zero(), new_root).await.unwrap();
+        let packet = NomadPacket::Update {
+            committed_root: H256::zero(),
+            new_root,
+            signatures: vec![update.signature.to_vec()],
+        };
+        assert_eq!(
+            NomadAck::Result(Binary::from(vec![1])),
+            receive(deps.as_mut(), mock_env(), &packet)
+        );
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::CommittedRoot {}).unwrap();
+        let value: CommittedRootResponse = from_binary(&res).unwrap();
+        assert_eq!(new_root, value.committed_root);
+
+        // Replayed update no longer builds off committed root
+        assert!(matches!(
+            receive(deps.as_mut(), mock_env(), &packet),
+            NomadAck::Error(_)
+        ));
+
+        let packet = NomadPacket::Message {
+            message: nomad_message.to_vec(),
+            proof: tree.prove(0).unwrap().path,
+            index: 0,
+        };
+
+        // Root still in optimistic window
+        assert_eq!(
+            NomadAck::Error(ContractError::FailedProveCall { leaf, index: 0 }.to_string()),
+            receive(deps.as_mut(), mock_env(), &packet)
+        );
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(OPTIMISTIC_SECONDS);
+        assert_eq!(
+            NomadAck::Result(Binary::from(vec![1])),
+            receive(deps.as_mut(), env, &packet)
+        );
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::MessageStatus { leaf }).unwrap();
+        let value: MessageStatusResponse = from_binary(&res).unwrap();
+        assert_eq!(MessageStatus::Pending, value.status);
+    }
+
+    #[test]
+    fn rejects_malformed_and_misdirected_messages() {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+        let mut deps = mock_padded_dependencies(&[]);
+
+        let msg = InstantiateMsg {
+            local_domain: LOCAL_DOMAIN,
+            remote_domain: REMOTE_DOMAIN,
+            updaters: vec![updater.signer_id()],
+            threshold: 1,
+            committed_root: H256::zero(),
+            optimistic_seconds: OPTIMISTIC_SECONDS,
+            root_verifier: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        let packet = NomadPacket::Message {
+            message: vec![1, 2, 3],
+            proof: [H256::zero(); 32],
+            index: 0,
+        };
+        assert_eq!(
+            NomadAck::Error(ContractError::MalformedMessage {}.to_string()),
+            receive(deps.as_mut(), mock_env(), &packet)
+        );
+
+        let nomad_message = NomadMessage {
+            origin: REMOTE_DOMAIN,
+            sender: H256::repeat_byte(1),
+            nonce: 0,
+            destination: 3000,
+            recipient: H256::repeat_byte(2),
+            body: vec![],
+        };
+        let packet = NomadPacket::Message {
+            message: nomad_message.to_vec(),
+            proof: [H256::zero(); 32],
+            index: 0,
+        };
+        assert_eq!(
+            NomadAck::Error(ContractError::WrongDestination { destination: 3000 }.to_string()),
+            receive(deps.as_mut(), mock_env(), &packet)
+        );
+    }
+}
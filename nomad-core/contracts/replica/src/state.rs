@@ -1,14 +1,31 @@
+use common::light_client::ConsensusState;
+use common::replica::RootVerifier;
 use common::MessageStatus;
 use cw_storage_plus::{Item, Map};
+use ethers_core::types::H256;
 
-pub const CHAIN_ADDR_LENGTH_BYTES: Item<usize> = Item::new("replica_chain_addr_length");
+/// Process gas used when owner has not set one
+pub const DEFAULT_PROCESS_GAS: u64 = 850_000;
 
 pub const REMOTE_DOMAIN: Item<u32> = Item::new("replica_remote_domain");
 pub const OPTIMISTIC_SECONDS: Item<u64> = Item::new("replica_optimistic_seconds");
+/// Optimistic if unset (replicas instantiated before root verifiers)
+pub const ROOT_VERIFIER: Item<RootVerifier> = Item::new("replica_root_verifier");
 
 // Kludge: can't use H256 for primary key, can't use u256 for timestamps
 pub const CONFIRM_AT: Map<&[u8], u64> = Map::new("replica_confirm_at");
 pub const MESSAGES: Map<&[u8], MessageStatus> = Map::new("replica_messages");
 
+/// Gas limit of recipient handle submessage
 pub const PROCESS_GAS: Item<u64> = Item::new("replica_process_gas");
-pub const RESERVE_GAS: Item<u64> = Item::new("replica_reserve_gas");
+/// Next nonce `Process` accepts. Unset if messages may be processed in any
+/// order
+pub const NEXT_NONCE: Item<u32> = Item::new("replica_next_nonce");
+/// Leaves of in-flight handle submessages, innermost last
+pub const PROCESSING: Item<Vec<H256>> = Item::new("replica_processing");
+
+/// Verified origin chain consensus states, keyed by height
+pub const CONSENSUS_STATES: Map<u64, ConsensusState> = Map::new("replica_consensus_states");
+pub const LATEST_CONSENSUS_HEIGHT: Item<u64> = Item::new("replica_latest_consensus_height");
+/// Consensus state height the committed root was last proven at
+pub const PROVEN_ROOT_HEIGHT: Item<u64> = Item::new("replica_proven_root_height");
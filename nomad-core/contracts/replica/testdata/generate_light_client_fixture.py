@@ -0,0 +1,449 @@
+#!/usr/bin/env python3
+"""Generate light_client.json, the origin chain fixture for replica light
+client tests.
+
+Builds a 6-validator Tendermint chain (trusted height 1, adjacent header 2,
+header 5 reached by skipping with a changed validator set), and the ICS-23
+proofs of home's `merkle_incremental_merkle` item in the wasm store at
+height 5. Everything is deterministic, so rerunning reproduces the checked
+in fixture. Requires the `cryptography` package for ed25519.
+"""
+
+import base64
+import hashlib
+import json
+import os
+
+from cryptography.hazmat.primitives.asymmetric.ed25519 import Ed25519PrivateKey
+from cryptography.hazmat.primitives.serialization import Encoding, PublicFormat
+
+CHAIN_ID = "nomad-origin-1"
+# Canonical address of home on the origin chain
+HOME = bytes(range(1, 21))
+MERKLE_KEY = b"merkle_incremental_merkle"
+WASM_CONTRACT_STORE_PREFIX = b"\x03"
+BASE_TIME = 1_571_797_000
+
+
+# ---------------------------------------------------------------- keccak256
+
+KECCAK_ROUND_CONSTANTS = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808A,
+    0x8000000080008000, 0x000000000000808B, 0x0000000080000001,
+    0x8000000080008081, 0x8000000000008009, 0x000000000000008A,
+    0x0000000000000088, 0x0000000080008009, 0x000000008000000A,
+    0x000000008000808B, 0x800000000000008B, 0x8000000000008089,
+    0x8000000000008003, 0x8000000000008002, 0x8000000000000080,
+    0x000000000000800A, 0x800000008000000A, 0x8000000080008081,
+    0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+]
+KECCAK_ROTATIONS = [
+    [0, 36, 3, 41, 18], [1, 44, 10, 45, 2], [62, 6, 43, 15, 61],
+    [28, 55, 25, 21, 56], [27, 20, 39, 8, 14],
+]
+MASK64 = (1 << 64) - 1
+
+
+def rotl64(x, n):
+    return ((x << n) | (x >> (64 - n))) & MASK64 if n else x
+
+
+def keccak_f(state):
+    for rc in KECCAK_ROUND_CONSTANTS:
+        c = [state[x][0] ^ state[x][1] ^ state[x][2] ^ state[x][3] ^ state[x][4]
+             for x in range(5)]
+        d = [c[(x - 1) % 5] ^ rotl64(c[(x + 1) % 5], 1) for x in range(5)]
+        state = [[state[x][y] ^ d[x] for y in range(5)] for x in range(5)]
+        b = [[0] * 5 for _ in range(5)]
+        for x in range(5):
+            for y in range(5):
+                b[y][(2 * x + 3 * y) % 5] = rotl64(state[x][y], KECCAK_ROTATIONS[x][y])
+        state = [[b[x][y] ^ (~b[(x + 1) % 5][y] & b[(x + 2) % 5][y])
+                  for y in range(5)] for x in range(5)]
+        state[0][0] ^= rc
+    return state
+
+
+def keccak256(data):
+    rate = 136
+    padded = bytearray(data) + b"\x01"
+    padded += b"\x00" * (-len(padded) % rate)
+    padded[-1] |= 0x80
+    state = [[0] * 5 for _ in range(5)]
+    for offset in range(0, len(padded), rate):
+        block = padded[offset:offset + rate]
+        for i in range(rate // 8):
+            x, y = i % 5, i // 5
+            state[x][y] ^= int.from_bytes(block[8 * i:8 * i + 8], "little")
+        state = keccak_f(state)
+    out = b""
+    for i in range(4):
+        out += state[i % 5][i // 5].to_bytes(8, "little")
+    return out
+
+
+assert keccak256(b"").hex() == \
+    "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+
+
+# ------------------------------------------------------ incremental merkle
+
+TREE_DEPTH = 32
+ZERO_HASHES = [bytes(32)]
+for _ in range(TREE_DEPTH):
+    ZERO_HASHES.append(keccak256(ZERO_HASHES[-1] * 2))
+
+
+def incremental_merkle(leaves):
+    branch = ZERO_HASHES[:TREE_DEPTH]
+    for count, leaf in enumerate(leaves, start=1):
+        node, size = leaf, count
+        for i in range(TREE_DEPTH):
+            if size & 1:
+                branch[i] = node
+                break
+            node = keccak256(branch[i] + node)
+            size //= 2
+    return branch
+
+
+def incremental_merkle_root(branch, count):
+    node, size = bytes(32), count
+    for i in range(TREE_DEPTH):
+        node = keccak256(branch[i] + node) if size & 1 else keccak256(node + ZERO_HASHES[i])
+        size //= 2
+    return node
+
+
+# ---------------------------------------------------------------- protobuf
+
+def varint(n):
+    out = b""
+    n &= MASK64
+    while n >= 0x80:
+        out += bytes([(n & 0x7F) | 0x80])
+        n >>= 7
+    return out + bytes([n])
+
+
+def zigzag(n):
+    return varint((n << 1) ^ (n >> 63))
+
+
+def field_varint(field, n):
+    return varint(field << 3) + varint(n) if n else b""
+
+
+def field_sfixed64(field, n):
+    return varint((field << 3) | 1) + (n & MASK64).to_bytes(8, "little") if n else b""
+
+
+def field_message(field, data):
+    return varint((field << 3) | 2) + varint(len(data)) + data
+
+
+def field_bytes(field, data):
+    return field_message(field, data) if data else b""
+
+
+def length_prefixed(data):
+    return varint(len(data)) + data
+
+
+def sha256(data):
+    return hashlib.sha256(data).digest()
+
+
+# ------------------------------------------------------------- tendermint
+
+def simple_merkle_root(items):
+    if not items:
+        return sha256(b"")
+    if len(items) == 1:
+        return sha256(b"\x00" + items[0])
+    split = 1
+    while split * 2 < len(items):
+        split *= 2
+    return sha256(b"\x01" + simple_merkle_root(items[:split]) + simple_merkle_root(items[split:]))
+
+
+def timestamp(t):
+    return field_varint(1, t["seconds"]) + field_varint(2, t["nanos"])
+
+
+def block_id(b):
+    psh = field_varint(1, b["part_set_header"]["total"]) + field_bytes(2, b["part_set_header"]["hash"])
+    return field_bytes(1, b["hash"]) + field_message(2, psh)
+
+
+def header_hash(h):
+    version = field_varint(1, h["version"]["block"]) + field_varint(2, h["version"]["app"])
+    return simple_merkle_root([
+        version,
+        field_bytes(1, h["chain_id"].encode()),
+        field_varint(1, h["height"]),
+        timestamp(h["time"]),
+        block_id(h["last_block_id"]),
+    ] + [field_bytes(1, h[name]) for name in [
+        "last_commit_hash", "data_hash", "validators_hash", "next_validators_hash",
+        "consensus_hash", "app_hash", "last_results_hash", "evidence_hash",
+        "proposer_address",
+    ]])
+
+
+def validator_set_hash(validators):
+    return simple_merkle_root([
+        field_message(1, field_bytes(1, v["pub_key"])) + field_varint(2, v["voting_power"])
+        for v in validators
+    ])
+
+
+def vote_sign_bytes(commit, sig):
+    vote = (field_varint(1, 2)
+            + field_sfixed64(2, commit["height"])
+            + field_sfixed64(3, commit["round"])
+            + field_message(4, block_id(commit["block_id"]))
+            + field_message(5, timestamp(sig["timestamp"]))
+            + field_bytes(6, CHAIN_ID.encode()))
+    return length_prefixed(vote)
+
+
+class Key:
+    def __init__(self, name):
+        self.private = Ed25519PrivateKey.from_private_bytes(sha256(name.encode()))
+        self.pub_key = self.private.public_key().public_bytes(Encoding.Raw, PublicFormat.Raw)
+        self.address = sha256(self.pub_key)[:20]
+
+
+KEYS = {name: Key(name) for name in "ABCDE"}
+
+
+def validator_set(powers):
+    return [{"pub_key": KEYS[name].pub_key, "voting_power": power} for name, power in powers]
+
+
+def label_hash(label):
+    return sha256(label.encode())
+
+
+def make_header(height, seconds, validators, next_validators, app_hash):
+    return {
+        "version": {"block": 11, "app": 0},
+        "chain_id": CHAIN_ID,
+        "height": height,
+        "time": {"seconds": seconds, "nanos": 500_000_000},
+        "last_block_id": {
+            "hash": label_hash("block %d" % (height - 1)),
+            "part_set_header": {"total": 1, "hash": label_hash("parts %d" % (height - 1))},
+        },
+        "last_commit_hash": label_hash("last commit %d" % height),
+        "data_hash": label_hash("data %d" % height),
+        "validators_hash": validator_set_hash(validators),
+        "next_validators_hash": validator_set_hash(next_validators),
+        "consensus_hash": label_hash("consensus params"),
+        "app_hash": app_hash,
+        "last_results_hash": label_hash("results %d" % height),
+        "evidence_hash": sha256(b""),
+        "proposer_address": KEYS["A"].address,
+    }
+
+
+def sign_header(header, signer_names, validators):
+    """Commit for header, signed by signer_names, in validator set order"""
+    commit = {
+        "height": header["height"],
+        "round": 1,
+        "block_id": {
+            "hash": header_hash(header),
+            "part_set_header": {"total": 1, "hash": label_hash("parts %d" % header["height"])},
+        },
+        "signatures": [],
+    }
+    for i, validator in enumerate(validators):
+        name = next(n for n, k in KEYS.items() if k.pub_key == validator["pub_key"])
+        if name not in signer_names:
+            commit["signatures"].append({
+                "block_id_flag": 1,
+                "validator_address": b"",
+                "timestamp": {"seconds": 0, "nanos": 0},
+                "signature": b"",
+            })
+            continue
+        sig = {
+            "block_id_flag": 2,
+            "validator_address": KEYS[name].address,
+            "timestamp": {"seconds": header["time"]["seconds"] + 1, "nanos": 1000 * (i + 1)},
+        }
+        sig["signature"] = KEYS[name].private.sign(vote_sign_bytes(commit, sig))
+        commit["signatures"].append(sig)
+    return {"header": header, "commit": commit}
+
+
+# -------------------------------------------------------------- ics23 proofs
+
+IAVL_VERSION = 5
+
+
+def iavl_tree(items):
+    """IAVL node over sorted (key, value) items, split evenly"""
+    if len(items) == 1:
+        key, value = items[0]
+        prefix = zigzag(0) + zigzag(1) + zigzag(IAVL_VERSION)
+        node_hash = sha256(prefix + length_prefixed(key) + length_prefixed(sha256(value)))
+        return {"hash": node_hash, "height": 0, "size": 1, "prefix": prefix, "key": key}
+    mid = len(items) // 2
+    left, right = iavl_tree(items[:mid]), iavl_tree(items[mid:])
+    height = max(left["height"], right["height"]) + 1
+    size = left["size"] + right["size"]
+    varints = zigzag(height) + zigzag(size) + zigzag(IAVL_VERSION)
+    node_hash = sha256(varints + length_prefixed(left["hash"]) + length_prefixed(right["hash"]))
+    return {"hash": node_hash, "height": height, "size": size, "varints": varints,
+            "left": left, "right": right}
+
+
+def iavl_path(node, key):
+    """Leaf prefix and inner ops (leaf to root) for key"""
+    if "key" in node:
+        assert node["key"] == key
+        return node["prefix"], []
+    if key < min_key(node["right"]):
+        leaf, path = iavl_path(node["left"], key)
+        op = (node["varints"] + b"\x20", b"\x20" + node["right"]["hash"])
+    else:
+        leaf, path = iavl_path(node["right"], key)
+        op = (node["varints"] + b"\x20" + node["left"]["hash"] + b"\x20", b"")
+    return leaf, path + [op]
+
+
+def min_key(node):
+    return node["key"] if "key" in node else min_key(node["left"])
+
+
+def simple_merkle_path(items, index):
+    """Inner ops (leaf to root) proving items[index]"""
+    if len(items) == 1:
+        return []
+    split = 1
+    while split * 2 < len(items):
+        split *= 2
+    if index < split:
+        return simple_merkle_path(items[:split], index) + \
+            [(b"\x01", simple_merkle_root(items[split:]))]
+    return simple_merkle_path(items[split:], index - split) + \
+        [(b"\x01" + simple_merkle_root(items[:split]), b"")]
+
+
+def commitment_proof(key, value, leaf_prefix, path):
+    leaf = field_varint(1, 1) + field_varint(3, 1) + field_varint(4, 1) + field_bytes(5, leaf_prefix)
+    exist = field_bytes(1, key) + field_bytes(2, value) + field_message(3, leaf)
+    for prefix, suffix in path:
+        exist += field_message(4, field_varint(1, 1) + field_bytes(2, prefix) + field_bytes(3, suffix))
+    return field_message(1, exist)
+
+
+def wasm_store(merkle_value):
+    """Wasm store holding home's merkle alongside other contract state"""
+    def contract_key(address, key):
+        return WASM_CONTRACT_STORE_PREFIX + address + key
+
+    items = {
+        contract_key(HOME, MERKLE_KEY): merkle_value,
+        contract_key(HOME, b"home_queue"): b'{"front":0}',
+        contract_key(HOME, b"contract_info"): b'{"contract":"crates.io:home"}',
+        contract_key(bytes(range(30, 50)), b"balance"): b'"100"',
+        contract_key(bytes(range(60, 80)), MERKLE_KEY): b"{}",
+        b"\x01" + bytes(8): b"code",
+        b"\x04" + bytes(8): b"sequence",
+    }
+    return sorted(items.items())
+
+
+def multistore(wasm_root):
+    stores = {name: label_hash("store " + name) for name in
+              ["acc", "bank", "distribution", "gov", "ibc", "params", "staking"]}
+    stores["wasm"] = wasm_root
+    return sorted(stores.items())
+
+
+def store_leaf(name, root):
+    return length_prefixed(name.encode()) + length_prefixed(sha256(root))
+
+
+# ------------------------------------------------------------------ fixture
+
+def b64(value):
+    if isinstance(value, bytes):
+        return base64.b64encode(value).decode()
+    if isinstance(value, dict):
+        return {k: b64(v) for k, v in value.items()}
+    if isinstance(value, list):
+        return [b64(v) for v in value]
+    return value
+
+
+def main():
+    leaves = [keccak256(b"message %d" % i) for i in range(3)]
+    branch = incremental_merkle(leaves)
+    merkle_value = json.dumps(
+        {"branch": ["0x" + node.hex() for node in branch], "count": len(leaves)},
+        separators=(",", ":"),
+    ).encode()
+    root = incremental_merkle_root(branch, len(leaves))
+
+    items = wasm_store(merkle_value)
+    tree = iavl_tree(items)
+    key = WASM_CONTRACT_STORE_PREFIX + HOME + MERKLE_KEY
+    leaf_prefix, path = iavl_path(tree, key)
+    wasm_proof = commitment_proof(key, merkle_value, leaf_prefix, path)
+
+    stores = multistore(tree["hash"])
+    store_leaves = [store_leaf(name, store_root) for name, store_root in stores]
+    app_hash = simple_merkle_root(store_leaves)
+    wasm_index = [name for name, _ in stores].index("wasm")
+    multistore_proof = commitment_proof(
+        b"wasm", tree["hash"], b"\x00", simple_merkle_path(store_leaves, wasm_index))
+
+    validators_1 = validator_set([("A", 40), ("B", 30), ("C", 20), ("D", 10)])
+    validators_2 = validator_set([("B", 30), ("C", 20), ("D", 10), ("E", 10)])
+
+    header_1 = make_header(1, BASE_TIME, validators_1, validators_1, label_hash("app 1"))
+    header_2 = make_header(2, BASE_TIME + 6, validators_1, validators_1, label_hash("app 2"))
+    header_5 = make_header(5, BASE_TIME + 24, validators_2, validators_2, app_hash)
+
+    fixture = {
+        "chain_id": CHAIN_ID,
+        "home": HOME,
+        "trusted_height": 1,
+        "trusted_state": {
+            "time": header_1["time"],
+            "app_hash": header_1["app_hash"],
+            "next_validators_hash": header_1["next_validators_hash"],
+        },
+        "trusted_validators": validators_1,
+        "adjacent": {
+            # D absent: 90 of 100
+            "signed_header": sign_header(header_2, "ABC", validators_1),
+            "validators": validators_1,
+        },
+        "skipping": {
+            # B, C, D carry 60 of 100 trusted power
+            "signed_header": sign_header(header_5, "BCDE", validators_2),
+            "validators": validators_2,
+        },
+        "proof": {
+            "height": 5,
+            "incremental_merkle": merkle_value,
+            "proofs": [wasm_proof, multistore_proof],
+        },
+        "leaves": ["0x" + leaf.hex() for leaf in leaves],
+        "root": "0x" + root.hex(),
+    }
+
+    out = os.path.join(os.path.dirname(os.path.abspath(__file__)), "light_client.json")
+    with open(out, "w") as f:
+        json.dump(b64(fixture), f, indent=2)
+        f.write("\n")
+
+
+if __name__ == "__main__":
+    main()
@@ -4,43 +4,227 @@ use cosmwasm_std::{
     to_binary, Binary, Deps, DepsMut, Env, Event, MessageInfo, Response, StdResult,
 };
 use cw2::set_contract_version;
+use ethers_core::types::H256;
+use xapp::{NomadRecipient, RemoteMessage, Router};
 
 use crate::error::ContractError;
-use common::test::test_recipient::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::{MESSAGE_COUNT, ROUTER};
+use common::test::test_recipient::{
+    ExecuteMsg, InstantiateMsg, MessageCountResponse, QueryMsg, RemoteRouterResponse,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:test-recipient";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Reference xApp. Accepts raw message bodies from enrolled remote routers
+/// and counts them
+pub struct TestRecipient;
+
+impl NomadRecipient for TestRecipient {
+    type Message = Vec<u8>;
+    type Error = ContractError;
+
+    fn router(&self) -> &Router {
+        &ROUTER
+    }
+
+    fn decode(&self, message: &[u8]) -> Result<Vec<u8>, ContractError> {
+        Ok(message.to_vec())
+    }
+
+    fn handle(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        msg: RemoteMessage<Vec<u8>>,
+    ) -> Result<Response, ContractError> {
+        MESSAGE_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+
+        Ok(Response::new().add_event(
+            Event::new("Handle")
+                .add_attribute("origin", msg.origin.to_string())
+                .add_attribute("nonce", msg.nonce.to_string())
+                .add_attribute("sender", format!("{:?}", msg.sender)),
+        ))
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
+    mut deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
-    _msg: InstantiateMsg,
+    info: MessageInfo,
+    msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    ROUTER.instantiate(deps.branch(), &info, &msg.home, &msg.connection_manager)?;
+    MESSAGE_COUNT.save(deps.storage, &0)?;
+
     Ok(Response::new())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    _deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::HandleMsg {
-            origin: _,
-            nonce: _,
-            sender: _,
-            message: _,
-        } => Ok(Response::new().add_event(Event::new("Handle"))),
+            origin,
+            nonce,
+            sender,
+            message,
+        } => TestRecipient.handle_msg(
+            deps,
+            env,
+            info,
+            RemoteMessage {
+                origin,
+                nonce,
+                sender,
+                message,
+            },
+        ),
+        ExecuteMsg::EnrollRemoteRouter { domain, router } => {
+            Ok(ROUTER.execute_enroll_remote_router(deps, info, domain, router)?)
+        }
+        ExecuteMsg::SendMessage {
+            destination,
+            message,
+        } => execute_send_message(deps, destination, message),
     }
 }
 
+pub fn execute_send_message(
+    deps: DepsMut,
+    destination: u32,
+    message: Vec<u8>,
+) -> Result<Response, ContractError> {
+    let dispatch_msg = ROUTER.dispatch_to_remote(deps.as_ref(), destination, message)?;
+
+    Ok(Response::new()
+        .add_submessage(dispatch_msg)
+        .add_event(Event::new("SendMessage").add_attribute("destination", destination.to_string())))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(_deps: Deps, _env: Env, _msg: QueryMsg) -> StdResult<Binary> {
-    Ok(to_binary(&1)?)
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::RemoteRouter { domain } => to_binary(&query_remote_router(deps, domain)?),
+        QueryMsg::MessageCount {} => to_binary(&query_message_count(deps)?),
+    }
+}
+
+pub fn query_remote_router(deps: Deps, domain: u32) -> StdResult<RemoteRouterResponse> {
+    let router = ROUTER
+        .remote_router(deps, domain)?
+        .unwrap_or_else(H256::zero);
+    Ok(RemoteRouterResponse { router })
+}
+
+pub fn query_message_count(deps: Deps) -> StdResult<MessageCountResponse> {
+    let count = MESSAGE_COUNT.load(deps.storage)?;
+    Ok(MessageCountResponse { count })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::connection_manager::{
+        self, DomainToReplicaResponse, HomeResponse, ReplicaToDomainResponse,
+    };
+    use cosmwasm_std::testing::{mock_env, mock_info};
+    use cosmwasm_std::{from_binary, ContractResult, SystemError, SystemResult, WasmQuery};
+    use test_utils::mock_padded_dependencies;
+    use xapp::XAppError;
+
+    const REMOTE_DOMAIN: u32 = 2000;
+
+    #[test]
+    fn handles_messages_from_enrolled_routers() {
+        let mut deps = mock_padded_dependencies(&[]);
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { msg, .. } => {
+                let res = match from_binary::<connection_manager::QueryMsg>(msg).unwrap() {
+                    connection_manager::QueryMsg::DomainToReplica { .. } => {
+                        to_binary(&DomainToReplicaResponse {
+                            replica: "replica".to_owned(),
+                        })
+                    }
+                    connection_manager::QueryMsg::ReplicaToDomain { .. } => {
+                        to_binary(&ReplicaToDomainResponse { domain: 0 })
+                    }
+                    connection_manager::QueryMsg::Home {} => to_binary(&HomeResponse {
+                        home: "0x0".to_owned(),
+                    }),
+                    _ => panic!("unexpected query"),
+                };
+                SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+            }
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "wasm".to_owned(),
+            }),
+        });
+
+        let msg = InstantiateMsg {
+            home: "home".to_owned(),
+            connection_manager: "connection_manager".to_owned(),
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        let router = H256::repeat_byte(1);
+        let handle_msg = ExecuteMsg::HandleMsg {
+            origin: REMOTE_DOMAIN,
+            nonce: 0,
+            sender: router,
+            message: vec![1, 2, 3],
+        };
+
+        // Remote router not enrolled
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("replica", &[]),
+            handle_msg.clone(),
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::XAppError(XAppError::NoRouter { .. })
+        ));
+
+        let msg = ExecuteMsg::EnrollRemoteRouter {
+            domain: REMOTE_DOMAIN,
+            router,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        // Caller not enrolled replica
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("not_replica", &[]),
+            handle_msg.clone(),
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::XAppError(XAppError::RecipientError(_))
+        ));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("replica", &[]),
+            handle_msg,
+        )
+        .unwrap();
+        assert_eq!("Handle", res.events[0].ty);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::MessageCount {}).unwrap();
+        let value: MessageCountResponse = from_binary(&res).unwrap();
+        assert_eq!(1, value.count);
+    }
 }
@@ -1,4 +1,10 @@
-use cosmwasm_std::Addr;
 use cw_storage_plus::Item;
+use xapp::Router;
 
-pub const OWNER: Item<Addr> = Item::new("ownable_owner");
+pub const ROUTER: Router<'static> = Router::new(
+    "test_recipient_home",
+    "test_recipient_connection_manager",
+    "test_recipient_remote_routers",
+);
+
+pub const MESSAGE_COUNT: Item<u64> = Item::new("test_recipient_message_count");
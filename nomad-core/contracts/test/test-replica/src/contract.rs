@@ -7,7 +7,7 @@ use ethers_core::types::H256;
 
 use crate::error::ContractError;
 use common::{
-    replica::{InstantiateMsg, QueryMsg},
+    replica::{InstantiateMsg, MigrateMsg, QueryMsg},
     test::test_replica::ExecuteMsg,
 };
 use replica::state::{CONFIRM_AT, MESSAGES};
@@ -64,6 +64,11 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
     Ok(replica::reply(deps, env, msg)?)
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    Ok(replica::migrate(deps, env, msg)?)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     replica::query(deps, env, msg)
@@ -1,21 +1,33 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, ContractResult, CosmosMsg, Deps, DepsMut, Env, Event, MessageInfo, Reply,
-    ReplyOn, Response, StdResult, SubMsg, WasmMsg,
+    coins, to_binary, Addr, BankMsg, Binary, ContractResult, CosmosMsg, Deps, DepsMut, Env, Event,
+    MessageInfo, Order, Reply, ReplyOn, Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
 };
-use cw2::set_contract_version;
-use ethers_core::types::H160;
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Bound;
+use semver::Version;
 
 use crate::error::ContractError;
-use crate::state::{HOME, UPDATER};
-use common::updater_manager::{ExecuteMsg, InstantiateMsg, QueryMsg, UpdaterResponse};
+use crate::state::{
+    Bond, SlashingConfig, BONDS, HOME, LEGACY_BOND, LEGACY_UPDATER, SLASHES, SLASHING_CONFIG,
+    SLASH_COUNT, THRESHOLD, UPDATERS,
+};
+use common::updater_manager::{
+    BondResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SlashRecord,
+    SlashingConfigResponse, SlashingHistoryResponse, UpdatersResponse,
+};
+use common::SignerId;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:updater-manager";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-pub const SET_UPDATER_ID: u64 = 1;
+pub const SET_UPDATERS_ID: u64 = 1;
+
+const BPS_DENOMINATOR: u64 = 10_000;
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -31,8 +43,46 @@ pub fn instantiate(
         common::ownable::InstantiateMsg {},
     )?;
 
+    if msg.reporter_share_bps > BPS_DENOMINATOR {
+        return Err(ContractError::InvalidReporterShare {
+            bps: msg.reporter_share_bps,
+        });
+    }
+    common::validate_updater_set(&msg.updaters, msg.threshold)?;
+
+    let treasury = msg
+        .treasury
+        .map(|treasury| deps.api.addr_validate(&treasury))
+        .transpose()?;
+    let config = SlashingConfig {
+        bond_denom: msg.bond_denom,
+        bond_amount: msg.bond_amount,
+        reporter_share_bps: msg.reporter_share_bps,
+        treasury,
+    };
+
+    let paid = must_pay_bond(&info, &config, msg.updaters.len())?;
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    UPDATER.save(deps.storage, &msg.updater)?;
+    SLASHING_CONFIG.save(deps.storage, &config)?;
+    UPDATERS.save(deps.storage, &msg.updaters)?;
+    THRESHOLD.save(deps.storage, &msg.threshold)?;
+
+    // Each updater is bonded the configured amount. Any overpayment is added
+    // to the first updater's bond so it is refunded on removal
+    let mut excess = paid - Uint128::new(config.bond_amount.u128() * msg.updaters.len() as u128);
+    for updater in msg.updaters.iter() {
+        BONDS.save(
+            deps.storage,
+            updater.as_bytes(),
+            &Bond {
+                depositor: info.sender.clone(),
+                amount: config.bond_amount + excess,
+            },
+        )?;
+        excess = Uint128::zero();
+    }
+    SLASH_COUNT.save(deps.storage, &0)?;
 
     Ok(Response::new())
 }
@@ -46,17 +96,52 @@ pub fn only_home(deps: Deps, info: MessageInfo) -> Result<Response, ContractErro
     Ok(Response::new())
 }
 
+/// Return amount of bond denom sent with message, erroring if below the
+/// configured bond amount for `count` updaters
+fn must_pay_bond(
+    info: &MessageInfo,
+    config: &SlashingConfig,
+    count: usize,
+) -> Result<Uint128, ContractError> {
+    let amount = info
+        .funds
+        .iter()
+        .filter(|coin| coin.denom == config.bond_denom)
+        .fold(Uint128::zero(), |acc, coin| acc + coin.amount);
+
+    let required = config
+        .bond_amount
+        .checked_mul(Uint128::from(count as u128))
+        .map_err(StdError::from)?;
+    if amount < required {
+        return Err(ContractError::InsufficientBond {
+            required,
+            denom: config.bond_denom.clone(),
+        });
+    }
+
+    Ok(amount)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::SetHome { home } => execute_set_home(deps, info, home),
-        ExecuteMsg::SetUpdater { updater } => execute_set_updater(deps, info, updater),
-        ExecuteMsg::SlashUpdater { reporter } => execute_slash_updater(deps, info, reporter),
+        ExecuteMsg::AddUpdater { updater } => execute_add_updater(deps, info, updater),
+        ExecuteMsg::RemoveUpdater { updater } => execute_remove_updater(deps, info, updater),
+        ExecuteMsg::SetThreshold { threshold } => execute_set_threshold(deps, info, threshold),
+        ExecuteMsg::SetSlashingConfig {
+            reporter_share_bps,
+            treasury,
+        } => execute_set_slashing_config(deps, info, reporter_share_bps, treasury),
+        ExecuteMsg::SlashUpdaters { reporter, updaters } => {
+            execute_slash_updaters(deps, env, info, reporter, updaters)
+        }
         ExecuteMsg::RenounceOwnership {} => Ok(ownable::execute_renounce_ownership(deps, info)?),
         ExecuteMsg::TransferOwnership { new_owner } => {
             Ok(ownable::execute_transfer_ownership(deps, info, new_owner)?)
@@ -77,100 +162,488 @@ pub fn execute_set_home(
     Ok(Response::new().add_event(Event::new("SetHome").add_attribute("home", home)))
 }
 
-pub fn execute_set_updater(
+pub fn execute_add_updater(
     deps: DepsMut,
     info: MessageInfo,
-    updater: H160,
+    updater: SignerId,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info.clone())?;
+
+    let config = SLASHING_CONFIG.load(deps.storage)?;
+    let bond_amount = must_pay_bond(&info, &config, 1)?;
+
+    let mut updaters = UPDATERS.load(deps.storage)?;
+    let threshold = THRESHOLD.load(deps.storage)?;
+    updaters.push(updater.clone());
+    common::validate_updater_set(&updaters, threshold)?;
+
+    UPDATERS.save(deps.storage, &updaters)?;
+    BONDS.save(
+        deps.storage,
+        updater.as_bytes(),
+        &Bond {
+            depositor: info.sender,
+            amount: bond_amount,
+        },
+    )?;
+
+    let sub_msg = set_updaters_sub_msg(deps.as_ref(), updaters, threshold)?;
+
+    Ok(Response::new()
+        .add_event(
+            Event::new("AddUpdater")
+                .add_attribute("updater", format!("{:?}", updater))
+                .add_attribute("bond", bond_amount.to_string()),
+        )
+        .add_submessage(sub_msg))
+}
+
+pub fn execute_remove_updater(
+    deps: DepsMut,
+    info: MessageInfo,
+    updater: SignerId,
 ) -> Result<Response, ContractError> {
     ownable::only_owner(deps.as_ref(), info)?;
 
-    UPDATER.save(deps.storage, &updater)?;
+    let mut updaters = UPDATERS.load(deps.storage)?;
+    let index =
+        updaters
+            .iter()
+            .position(|u| *u == updater)
+            .ok_or_else(|| ContractError::NotUpdater {
+                updater: updater.clone(),
+            })?;
+    updaters.remove(index);
+
+    let threshold = THRESHOLD.load(deps.storage)?;
+    common::validate_updater_set(&updaters, threshold)?;
+    UPDATERS.save(deps.storage, &updaters)?;
 
+    // Return whatever remains of the outgoing updater's bond to its depositor
+    let config = SLASHING_CONFIG.load(deps.storage)?;
+    let bond = BONDS.load(deps.storage, updater.as_bytes())?;
+    BONDS.remove(deps.storage, updater.as_bytes());
+    let refund_msgs = bank_send_msgs(&bond.depositor, bond.amount, &config.bond_denom);
+
+    let sub_msg = set_updaters_sub_msg(deps.as_ref(), updaters, threshold)?;
+
+    Ok(Response::new()
+        .add_event(
+            Event::new("RemoveUpdater")
+                .add_attribute("updater", format!("{:?}", updater))
+                .add_attribute("refunded_bond", bond.amount.to_string()),
+        )
+        .add_messages(refund_msgs)
+        .add_submessage(sub_msg))
+}
+
+pub fn execute_set_threshold(
+    deps: DepsMut,
+    info: MessageInfo,
+    threshold: u32,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+
+    let updaters = UPDATERS.load(deps.storage)?;
+    common::validate_updater_set(&updaters, threshold)?;
+    THRESHOLD.save(deps.storage, &threshold)?;
+
+    let sub_msg = set_updaters_sub_msg(deps.as_ref(), updaters, threshold)?;
+
+    Ok(Response::new()
+        .add_event(Event::new("SetThreshold").add_attribute("threshold", threshold.to_string()))
+        .add_submessage(sub_msg))
+}
+
+/// Build call pushing updater set and threshold to home
+fn set_updaters_sub_msg(
+    deps: Deps,
+    updaters: Vec<SignerId>,
+    threshold: u32,
+) -> Result<SubMsg, ContractError> {
     let home_addr = HOME.load(deps.storage)?;
 
-    let set_updater_msg = common::home::ExecuteMsg::SetUpdater {
-        updater: updater.clone(),
+    let set_updaters_msg = common::home::ExecuteMsg::SetUpdaters {
+        updaters,
+        threshold,
     };
     let wasm_msg = WasmMsg::Execute {
         contract_addr: home_addr.to_string(),
-        msg: to_binary(&set_updater_msg)?,
+        msg: to_binary(&set_updaters_msg)?,
         funds: vec![],
     };
     let cosmos_msg = CosmosMsg::Wasm(wasm_msg);
 
-    let sub_msg = SubMsg {
-        id: SET_UPDATER_ID,
+    Ok(SubMsg {
+        id: SET_UPDATERS_ID,
         msg: cosmos_msg,
         gas_limit: None,
         reply_on: ReplyOn::Always,
-    };
+    })
+}
 
-    Ok(Response::new()
-        .add_event(Event::new("SetUpdater").add_attribute("new_updater", format!("{:?}", updater)))
-        .add_submessage(sub_msg))
+pub fn execute_set_slashing_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    reporter_share_bps: u64,
+    treasury: Option<String>,
+) -> Result<Response, ContractError> {
+    ownable::only_owner(deps.as_ref(), info)?;
+
+    if reporter_share_bps > BPS_DENOMINATOR {
+        return Err(ContractError::InvalidReporterShare {
+            bps: reporter_share_bps,
+        });
+    }
+
+    let treasury = treasury
+        .map(|treasury| deps.api.addr_validate(&treasury))
+        .transpose()?;
+
+    let mut config = SLASHING_CONFIG.load(deps.storage)?;
+    config.reporter_share_bps = reporter_share_bps;
+    config.treasury = treasury.clone();
+    SLASHING_CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_event(
+        Event::new("SetSlashingConfig")
+            .add_attribute("reporter_share_bps", reporter_share_bps.to_string())
+            .add_attribute(
+                "treasury",
+                treasury.map_or("burn".to_owned(), |addr| addr.to_string()),
+            ),
+    ))
 }
 
-pub fn execute_slash_updater(
+pub fn execute_slash_updaters(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     reporter: String,
+    updaters: Vec<SignerId>,
 ) -> Result<Response, ContractError> {
     only_home(deps.as_ref(), info)?;
 
-    // TODO: implement updater slashing
-    Ok(Response::new().add_event(Event::new("SlashUpdater").add_attribute("reporter", reporter)))
+    let reporter_addr = deps.api.addr_validate(&reporter)?;
+    let config = SLASHING_CONFIG.load(deps.storage)?;
+
+    let mut response = Response::new();
+    for updater in updaters {
+        // Updaters without a bond (e.g. already removed) have nothing to slash
+        let mut bond = match BONDS.may_load(deps.storage, updater.as_bytes())? {
+            Some(bond) => bond,
+            None => continue,
+        };
+
+        // Empty out bond so it cannot be refunded on updater removal
+        let slashed = bond.amount;
+        bond.amount = Uint128::zero();
+        BONDS.save(deps.storage, updater.as_bytes(), &bond)?;
+
+        let reporter_amount = slashed.multiply_ratio(config.reporter_share_bps, BPS_DENOMINATOR);
+        let remainder_amount = slashed - reporter_amount;
+
+        let mut msgs = bank_send_msgs(&reporter_addr, reporter_amount, &config.bond_denom);
+        match &config.treasury {
+            Some(treasury) => msgs.extend(bank_send_msgs(
+                treasury,
+                remainder_amount,
+                &config.bond_denom,
+            )),
+            None if !remainder_amount.is_zero() => msgs.push(BankMsg::Burn {
+                amount: coins(remainder_amount.u128(), &config.bond_denom),
+            }),
+            None => {}
+        }
+
+        let id = SLASH_COUNT.load(deps.storage)?;
+        let record = SlashRecord {
+            id,
+            updater: updater.clone(),
+            reporter: reporter_addr.to_string(),
+            reporter_amount,
+            remainder_amount,
+            treasury: config
+                .treasury
+                .as_ref()
+                .map(|treasury| treasury.to_string()),
+            height: env.block.height,
+        };
+        SLASHES.save(deps.storage, id, &record)?;
+        SLASH_COUNT.save(deps.storage, &(id + 1))?;
+
+        response = response
+            .add_event(
+                Event::new("SlashUpdater")
+                    .add_attribute("reporter", reporter.clone())
+                    .add_attribute("updater", format!("{:?}", updater))
+                    .add_attribute("reporter_amount", reporter_amount.to_string())
+                    .add_attribute("remainder_amount", remainder_amount.to_string()),
+            )
+            .add_messages(msgs);
+    }
+
+    Ok(response)
+}
+
+fn bank_send_msgs(to: &Addr, amount: Uint128, denom: &str) -> Vec<BankMsg> {
+    if amount.is_zero() {
+        return vec![];
+    }
+
+    vec![BankMsg::Send {
+        to_address: to.to_string(),
+        amount: coins(amount.u128(), denom),
+    }]
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
-        SET_UPDATER_ID => reply_set_updater(deps.as_ref(), msg),
+        SET_UPDATERS_ID => reply_set_updaters(deps.as_ref(), msg),
         _ => Err(ContractError::UnknownReplyMessage { id: msg.id }),
     }
 }
 
-pub fn reply_set_updater(_deps: Deps, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply_set_updaters(_deps: Deps, msg: Reply) -> Result<Response, ContractError> {
     match msg.result {
         ContractResult::Ok(_) => Ok(Response::new()),
-        ContractResult::Err(e) => Err(ContractError::FailedSetUpdaterCall(e)),
+        ContractResult::Err(e) => Err(ContractError::FailedSetUpdatersCall(e)),
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored_version = ensure_upgrade(deps.as_ref())?;
+
+    // Per-version storage migrations, oldest first
+    if stored_version < Version::new(0, 2, 0) {
+        migrate_v0_1_to_v0_2(deps.branch(), msg)?;
+    }
+    if stored_version < Version::new(0, 3, 0) {
+        migrate_v0_2_to_v0_3(deps.branch())?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_event(
+        Event::new("Migrate")
+            .add_attribute("from_version", stored_version.to_string())
+            .add_attribute("to_version", CONTRACT_VERSION),
+    ))
+}
+
+/// v0.2.0 introduced updater bonds and slashing config. The existing updater
+/// starts with an empty bond held on behalf of the owner.
+fn migrate_v0_1_to_v0_2(deps: DepsMut, msg: MigrateMsg) -> Result<(), ContractError> {
+    let params = msg
+        .slashing_params
+        .ok_or(ContractError::MissingSlashingParams {})?;
+
+    if params.reporter_share_bps > BPS_DENOMINATOR {
+        return Err(ContractError::InvalidReporterShare {
+            bps: params.reporter_share_bps,
+        });
+    }
+
+    let treasury = params
+        .treasury
+        .map(|treasury| deps.api.addr_validate(&treasury))
+        .transpose()?;
+    SLASHING_CONFIG.save(
+        deps.storage,
+        &SlashingConfig {
+            bond_denom: params.bond_denom,
+            bond_amount: params.bond_amount,
+            reporter_share_bps: params.reporter_share_bps,
+            treasury,
+        },
+    )?;
+
+    let owner = ownable::query_owner(deps.as_ref())?.owner;
+    LEGACY_BOND.save(
+        deps.storage,
+        &Bond {
+            depositor: Addr::unchecked(owner),
+            amount: Uint128::zero(),
+        },
+    )?;
+    SLASH_COUNT.save(deps.storage, &0)?;
+
+    Ok(())
+}
+
+/// v0.3.0 replaced the single updater with a threshold updater set. The
+/// existing updater becomes a 1-of-1 set and keeps its bond.
+fn migrate_v0_2_to_v0_3(deps: DepsMut) -> Result<(), ContractError> {
+    let updater = LEGACY_UPDATER.load(deps.storage)?;
+    let bond = LEGACY_BOND.load(deps.storage)?;
+
+    let updater = SignerId::from(updater);
+    UPDATERS.save(deps.storage, &vec![updater.clone()])?;
+    THRESHOLD.save(deps.storage, &1)?;
+    BONDS.save(deps.storage, updater.as_bytes(), &bond)?;
+
+    LEGACY_UPDATER.remove(deps.storage);
+    LEGACY_BOND.remove(deps.storage);
+
+    Ok(())
+}
+
+/// Checks that stored cw2 info belongs to this contract and that stored
+/// version is not newer than this code (no downgrades). Returns stored version.
+pub fn ensure_upgrade(deps: Deps) -> Result<Version, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::WrongContract {
+            expected: CONTRACT_NAME.to_owned(),
+            found: stored.contract,
+        });
+    }
+
+    let stored_version: Version = stored.version.parse()?;
+    let version: Version = CONTRACT_VERSION.parse()?;
+    if stored_version > version {
+        return Err(ContractError::CannotDowngrade {
+            stored: stored.version,
+            version: CONTRACT_VERSION.to_owned(),
+        });
+    }
+
+    Ok(stored_version)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Updater {} => to_binary(&query_updater(deps)?),
+        QueryMsg::Updaters {} => to_binary(&query_updaters(deps)?),
+        QueryMsg::Bond { updater } => to_binary(&query_bond(deps, updater)?),
+        QueryMsg::SlashingConfig {} => to_binary(&query_slashing_config(deps)?),
+        QueryMsg::SlashingHistory { start_after, limit } => {
+            to_binary(&query_slashing_history(deps, start_after, limit)?)
+        }
         QueryMsg::Owner {} => to_binary(&ownable::query_owner(deps)?),
     }
 }
 
-pub fn query_updater(deps: Deps) -> StdResult<UpdaterResponse> {
-    let updater = UPDATER.load(deps.storage)?;
-    Ok(UpdaterResponse { updater })
+pub fn query_updaters(deps: Deps) -> StdResult<UpdatersResponse> {
+    let updaters = UPDATERS.load(deps.storage)?;
+    let threshold = THRESHOLD.load(deps.storage)?;
+    Ok(UpdatersResponse {
+        updaters,
+        threshold,
+    })
+}
+
+pub fn query_bond(deps: Deps, updater: SignerId) -> StdResult<BondResponse> {
+    let bond = BONDS.load(deps.storage, updater.as_bytes())?;
+    let config = SLASHING_CONFIG.load(deps.storage)?;
+    Ok(BondResponse {
+        updater,
+        depositor: bond.depositor.to_string(),
+        amount: bond.amount,
+        denom: config.bond_denom,
+    })
+}
+
+pub fn query_slashing_config(deps: Deps) -> StdResult<SlashingConfigResponse> {
+    let config = SLASHING_CONFIG.load(deps.storage)?;
+    Ok(SlashingConfigResponse {
+        bond_denom: config.bond_denom,
+        bond_amount: config.bond_amount,
+        reporter_share_bps: config.reporter_share_bps,
+        treasury: config.treasury.map(|treasury| treasury.to_string()),
+    })
+}
+
+pub fn query_slashing_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<SlashingHistoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(id.to_be_bytes().to_vec()));
+
+    let slashes = SLASHES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, slash)| slash))
+        .collect::<StdResult<Vec<SlashRecord>>>()?;
+
+    Ok(SlashingHistoryResponse { slashes })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
-    use cosmwasm_std::{coins, from_binary};
+    use common::updater_manager::SlashingParams;
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{from_binary, OwnedDeps};
+    use ethers_core::types::H160;
 
     const UPDATER: H160 = H160::repeat_byte(3);
+    const BOND_DENOM: &str = "earth";
+    const BOND_AMOUNT: u128 = 100;
+
+    fn init_msg(treasury: Option<String>) -> InstantiateMsg {
+        InstantiateMsg {
+            updaters: vec![UPDATER.into()],
+            threshold: 1,
+            bond_denom: BOND_DENOM.to_owned(),
+            bond_amount: Uint128::new(BOND_AMOUNT),
+            reporter_share_bps: 2_500,
+            treasury,
+        }
+    }
+
+    fn setup(treasury: Option<String>) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let info = mock_info("owner", &coins(BOND_AMOUNT, BOND_DENOM));
+        instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg(treasury)).unwrap();
+
+        let msg = ExecuteMsg::SetHome {
+            home: "home".to_owned(),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        deps
+    }
+
+    fn query_bond_amount(deps: Deps, updater: SignerId) -> Uint128 {
+        let res = query(deps, mock_env(), QueryMsg::Bond { updater }).unwrap();
+        let value: BondResponse = from_binary(&res).unwrap();
+        value.amount
+    }
 
     #[test]
     fn proper_initialization() {
         let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
 
-        let msg = InstantiateMsg { updater: UPDATER };
-        let info = mock_info("owner", &coins(100, "earth"));
+        let msg = init_msg(None);
+        let info = mock_info("owner", &coins(BOND_AMOUNT, BOND_DENOM));
 
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(0, res.messages.len());
 
-        // Updater
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::Updater {}).unwrap();
-        let value: UpdaterResponse = from_binary(&res).unwrap();
-        assert_eq!(UPDATER, value.updater);
+        // Updaters
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Updaters {}).unwrap();
+        let value: UpdatersResponse = from_binary(&res).unwrap();
+        assert_eq!(vec![SignerId::from(UPDATER)], value.updaters);
+        assert_eq!(1, value.threshold);
+
+        // Bond
+        let msg = QueryMsg::Bond {
+            updater: UPDATER.into(),
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value: BondResponse = from_binary(&res).unwrap();
+        assert_eq!(SignerId::from(UPDATER), value.updater);
+        assert_eq!("owner", value.depositor);
+        assert_eq!(Uint128::new(BOND_AMOUNT), value.amount);
+        assert_eq!(BOND_DENOM, value.denom);
 
         // Owner
         let res = query(deps.as_ref(), mock_env(), QueryMsg::Owner {}).unwrap();
@@ -184,4 +657,459 @@ mod tests {
         };
         let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     }
+
+    #[test]
+    fn bonds_each_initial_updater() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let second = SignerId::Ed25519PubKey(vec![4u8; 32].into());
+        let mut msg = init_msg(None);
+        msg.updaters = vec![UPDATER.into(), second.clone()];
+        msg.threshold = 2;
+
+        // One bond is not enough for two updaters
+        let info = mock_info("owner", &coins(BOND_AMOUNT, BOND_DENOM));
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg.clone());
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::InsufficientBond { .. }
+        ));
+
+        // Overpayment is credited to first updater
+        let info = mock_info("owner", &coins(BOND_AMOUNT * 2 + 10, BOND_DENOM));
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(
+            Uint128::new(BOND_AMOUNT + 10),
+            query_bond_amount(deps.as_ref(), UPDATER.into())
+        );
+        assert_eq!(
+            Uint128::new(BOND_AMOUNT),
+            query_bond_amount(deps.as_ref(), second.clone())
+        );
+    }
+
+    #[test]
+    fn rejects_insufficient_bond() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        // Underpaid bond on instantiation
+        let info = mock_info("owner", &coins(BOND_AMOUNT - 1, BOND_DENOM));
+        let res = instantiate(deps.as_mut(), mock_env(), info, init_msg(None));
+        assert!(res.is_err());
+
+        // Bond in wrong denom
+        let info = mock_info("owner", &coins(BOND_AMOUNT, "token"));
+        let res = instantiate(deps.as_mut(), mock_env(), info, init_msg(None));
+        assert!(res.is_err());
+
+        // Unbonded updater addition
+        let mut deps = setup(None);
+        let info = mock_info("owner", &[]);
+        let msg = ExecuteMsg::AddUpdater {
+            updater: H160::repeat_byte(4).into(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_updater_set() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+
+        let mut msg = init_msg(None);
+        msg.threshold = 2;
+        let info = mock_info("owner", &coins(BOND_AMOUNT, BOND_DENOM));
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::UpdaterSetError(_)
+        ));
+
+        let mut deps = setup(None);
+
+        // Duplicate updater
+        let info = mock_info("owner", &coins(BOND_AMOUNT, BOND_DENOM));
+        let msg = ExecuteMsg::AddUpdater {
+            updater: UPDATER.into(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::UpdaterSetError(_)
+        ));
+
+        // Removing last updater would leave threshold unreachable
+        let info = mock_info("owner", &[]);
+        let msg = ExecuteMsg::RemoveUpdater {
+            updater: UPDATER.into(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::UpdaterSetError(_)
+        ));
+
+        // Unknown updater
+        let msg = ExecuteMsg::RemoveUpdater {
+            updater: H160::repeat_byte(9).into(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg);
+        assert!(matches!(res.unwrap_err(), ContractError::NotUpdater { .. }));
+    }
+
+    #[test]
+    fn manages_updater_set_and_refunds_removed_bond() {
+        let mut deps = setup(None);
+
+        let new_updater = SignerId::Secp256k1PubKey(vec![4u8; 33].into());
+        let info = mock_info("new_depositor", &coins(BOND_AMOUNT * 2, BOND_DENOM));
+        let msg = ExecuteMsg::AddUpdater {
+            updater: new_updater.clone(),
+        };
+
+        // Only owner can add updater
+        let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+        assert!(res.is_err());
+
+        // Added updater pushed to home
+        let info = mock_info("owner", &coins(BOND_AMOUNT * 2, BOND_DENOM));
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(1, res.messages.len());
+        assert_eq!(SET_UPDATERS_ID, res.messages[0].id);
+        assert_eq!(
+            Uint128::new(BOND_AMOUNT * 2),
+            query_bond_amount(deps.as_ref(), new_updater.clone())
+        );
+
+        // Raise threshold
+        let info = mock_info("owner", &[]);
+        let msg = ExecuteMsg::SetThreshold { threshold: 2 };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert_eq!(SET_UPDATERS_ID, res.messages[0].id);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Updaters {}).unwrap();
+        let value: UpdatersResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            vec![SignerId::from(UPDATER), new_updater.clone()],
+            value.updaters
+        );
+        assert_eq!(2, value.threshold);
+
+        // Threshold must be lowered before updater can be removed
+        let msg = ExecuteMsg::RemoveUpdater {
+            updater: UPDATER.into(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
+        assert!(res.is_err());
+
+        let threshold_msg = ExecuteMsg::SetThreshold { threshold: 1 };
+        execute(deps.as_mut(), mock_env(), info.clone(), threshold_msg).unwrap();
+
+        // Refund to depositor, then set updaters call on home
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(2, res.messages.len());
+        assert_eq!(
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "owner".to_owned(),
+                amount: coins(BOND_AMOUNT, BOND_DENOM),
+            }),
+            res.messages[0].msg
+        );
+        assert_eq!(SET_UPDATERS_ID, res.messages[1].id);
+
+        // Removed updater's bond is gone
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Bond {
+                updater: UPDATER.into(),
+            },
+        );
+        assert!(res.is_err());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Updaters {}).unwrap();
+        let value: UpdatersResponse = from_binary(&res).unwrap();
+        assert_eq!(vec![new_updater], value.updaters);
+        assert_eq!(1, value.threshold);
+    }
+
+    #[test]
+    fn only_home_slashes_updater() {
+        let mut deps = setup(None);
+
+        let info = mock_info("not_home", &[]);
+        let msg = ExecuteMsg::SlashUpdaters {
+            reporter: "reporter".to_owned(),
+            updaters: vec![UPDATER.into()],
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg);
+        assert!(res.is_err());
+
+        assert_eq!(
+            Uint128::new(BOND_AMOUNT),
+            query_bond_amount(deps.as_ref(), UPDATER.into())
+        );
+    }
+
+    #[test]
+    fn slashes_bond_to_reporter_and_burns_remainder() {
+        let mut deps = setup(None);
+
+        let info = mock_info("home", &[]);
+        let msg = ExecuteMsg::SlashUpdaters {
+            reporter: "reporter".to_owned(),
+            updaters: vec![UPDATER.into()],
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        assert_eq!(2, res.messages.len());
+        assert_eq!(
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "reporter".to_owned(),
+                amount: coins(25, BOND_DENOM),
+            }),
+            res.messages[0].msg
+        );
+        assert_eq!(
+            CosmosMsg::Bank(BankMsg::Burn {
+                amount: coins(75, BOND_DENOM),
+            }),
+            res.messages[1].msg
+        );
+
+        // Bond emptied
+        assert!(query_bond_amount(deps.as_ref(), UPDATER.into()).is_zero());
+
+        // Slash recorded
+        let msg = QueryMsg::SlashingHistory {
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value: SlashingHistoryResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            vec![SlashRecord {
+                id: 0,
+                updater: UPDATER.into(),
+                reporter: "reporter".to_owned(),
+                reporter_amount: Uint128::new(25),
+                remainder_amount: Uint128::new(75),
+                treasury: None,
+                height: mock_env().block.height,
+            }],
+            value.slashes
+        );
+    }
+
+    #[test]
+    fn slashes_each_bonded_updater() {
+        let mut deps = setup(None);
+
+        let second = SignerId::Ed25519PubKey(vec![4u8; 32].into());
+        let info = mock_info("owner", &coins(BOND_AMOUNT, BOND_DENOM));
+        let msg = ExecuteMsg::AddUpdater {
+            updater: second.clone(),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // Unbonded updaters are skipped
+        let info = mock_info("home", &[]);
+        let msg = ExecuteMsg::SlashUpdaters {
+            reporter: "reporter".to_owned(),
+            updaters: vec![UPDATER.into(), H160::repeat_byte(9).into(), second.clone()],
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(4, res.messages.len());
+        assert_eq!(2, res.events.len());
+
+        assert!(query_bond_amount(deps.as_ref(), UPDATER.into()).is_zero());
+        assert!(query_bond_amount(deps.as_ref(), second.clone()).is_zero());
+
+        let msg = QueryMsg::SlashingHistory {
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value: SlashingHistoryResponse = from_binary(&res).unwrap();
+        let slashed: Vec<SignerId> = value
+            .slashes
+            .into_iter()
+            .map(|slash| slash.updater)
+            .collect();
+        assert_eq!(vec![SignerId::from(UPDATER), second], slashed);
+    }
+
+    #[test]
+    fn slashes_remainder_to_treasury() {
+        let mut deps = setup(Some("treasury".to_owned()));
+
+        let info = mock_info("home", &[]);
+        let msg = ExecuteMsg::SlashUpdaters {
+            reporter: "reporter".to_owned(),
+            updaters: vec![UPDATER.into()],
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+
+        assert_eq!(
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "treasury".to_owned(),
+                amount: coins(75, BOND_DENOM),
+            }),
+            res.messages[1].msg
+        );
+
+        // Slashing an emptied bond sends nothing but is still recorded
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        let msg = QueryMsg::SlashingHistory {
+            start_after: Some(0),
+            limit: Some(1),
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value: SlashingHistoryResponse = from_binary(&res).unwrap();
+        assert_eq!(1, value.slashes.len());
+        assert_eq!(1, value.slashes[0].id);
+        assert!(value.slashes[0].reporter_amount.is_zero());
+    }
+
+    #[test]
+    fn owner_sets_slashing_config() {
+        let mut deps = setup(None);
+
+        let msg = ExecuteMsg::SetSlashingConfig {
+            reporter_share_bps: 10_001,
+            treasury: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg);
+        assert!(res.is_err());
+
+        let msg = ExecuteMsg::SetSlashingConfig {
+            reporter_share_bps: 5_000,
+            treasury: Some("treasury".to_owned()),
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("not_owner", &[]),
+            msg.clone(),
+        );
+        assert!(res.is_err());
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::SlashingConfig {}).unwrap();
+        let value: SlashingConfigResponse = from_binary(&res).unwrap();
+        assert_eq!(5_000, value.reporter_share_bps);
+        assert_eq!(Some("treasury".to_owned()), value.treasury);
+    }
+
+    /// Rewrite storage into v0.2.0 layout (single bonded updater)
+    fn into_v0_2_layout(deps: DepsMut) {
+        let bond = BONDS.load(deps.storage, UPDATER.as_bytes()).unwrap();
+        UPDATERS.remove(deps.storage);
+        THRESHOLD.remove(deps.storage);
+        BONDS.remove(deps.storage, UPDATER.as_bytes());
+        LEGACY_UPDATER.save(deps.storage, &UPDATER).unwrap();
+        LEGACY_BOND.save(deps.storage, &bond).unwrap();
+        set_contract_version(deps.storage, CONTRACT_NAME, "0.2.0").unwrap();
+    }
+
+    #[test]
+    fn migrates_from_pre_bonding_version() {
+        let mut deps = setup(None);
+
+        // Simulate v0.1.0 storage layout
+        into_v0_2_layout(deps.as_mut());
+        SLASHING_CONFIG.remove(deps.as_mut().storage);
+        LEGACY_BOND.remove(deps.as_mut().storage);
+        SLASH_COUNT.remove(deps.as_mut().storage);
+        set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.1.0").unwrap();
+
+        // Slashing params required
+        let res = migrate(
+            deps.as_mut(),
+            mock_env(),
+            MigrateMsg {
+                slashing_params: None,
+            },
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::MissingSlashingParams {}
+        ));
+
+        let msg = MigrateMsg {
+            slashing_params: Some(SlashingParams {
+                bond_denom: BOND_DENOM.to_owned(),
+                bond_amount: Uint128::new(BOND_AMOUNT),
+                reporter_share_bps: 5_000,
+                treasury: None,
+            }),
+        };
+        migrate(deps.as_mut(), mock_env(), msg).unwrap();
+
+        let version = get_contract_version(deps.as_ref().storage).unwrap();
+        assert_eq!(CONTRACT_VERSION, version.version);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::SlashingConfig {}).unwrap();
+        let value: SlashingConfigResponse = from_binary(&res).unwrap();
+        assert_eq!(5_000, value.reporter_share_bps);
+
+        // Existing updater has empty bond held for owner
+        let msg = QueryMsg::Bond {
+            updater: UPDATER.into(),
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let value: BondResponse = from_binary(&res).unwrap();
+        assert_eq!("owner", value.depositor);
+        assert_eq!(Uint128::zero(), value.amount);
+    }
+
+    #[test]
+    fn migrates_single_updater_to_updater_set() {
+        let mut deps = setup(None);
+        into_v0_2_layout(deps.as_mut());
+
+        let msg = MigrateMsg {
+            slashing_params: None,
+        };
+        migrate(deps.as_mut(), mock_env(), msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Updaters {}).unwrap();
+        let value: UpdatersResponse = from_binary(&res).unwrap();
+        assert_eq!(vec![SignerId::from(UPDATER)], value.updaters);
+        assert_eq!(1, value.threshold);
+
+        // Bond carried over
+        assert_eq!(
+            Uint128::new(BOND_AMOUNT),
+            query_bond_amount(deps.as_ref(), UPDATER.into())
+        );
+        assert!(LEGACY_UPDATER
+            .may_load(deps.as_ref().storage)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn refuses_downgrade_and_foreign_contract() {
+        let mut deps = setup(None);
+
+        set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "99.0.0").unwrap();
+        let msg = MigrateMsg {
+            slashing_params: None,
+        };
+        let res = migrate(deps.as_mut(), mock_env(), msg.clone());
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::CannotDowngrade { .. }
+        ));
+
+        set_contract_version(deps.as_mut().storage, "crates.io:home", CONTRACT_VERSION).unwrap();
+        let res = migrate(deps.as_mut(), mock_env(), msg);
+        assert!(matches!(
+            res.unwrap_err(),
+            ContractError::WrongContract { .. }
+        ));
+    }
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,14 +6,38 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("Cannot migrate from contract {found}, expected {expected}")]
+    WrongContract { expected: String, found: String },
+
+    #[error("Cannot downgrade from version {stored} to {version}")]
+    CannotDowngrade { stored: String, version: String },
+
+    #[error("{0}")]
+    SemVer(#[from] semver::Error),
+
     #[error("Only the home can call slash")]
     SlashNotHome,
 
+    #[error("Insufficient updater bond. Required: {required}{denom}")]
+    InsufficientBond { required: Uint128, denom: String },
+
+    #[error("Slashing params required to migrate from pre-bonding version")]
+    MissingSlashingParams {},
+
+    #[error("Reporter share {bps} bps exceeds 10000 bps")]
+    InvalidReporterShare { bps: u64 },
+
     #[error("Unknown reply message id received: {id}")]
     UnknownReplyMessage { id: u64 },
 
-    #[error("Failed to call set updater on updater manager: {0}")]
-    FailedSetUpdaterCall(String),
+    #[error("Failed to call set updaters on home: {0}")]
+    FailedSetUpdatersCall(String),
+
+    #[error("{updater:?} is not in the updater set")]
+    NotUpdater { updater: common::SignerId },
+
+    #[error("{0}")]
+    UpdaterSetError(#[from] common::UpdaterSetError),
 
     #[error("{0}")]
     OwnableError(#[from] ownable::ContractError),
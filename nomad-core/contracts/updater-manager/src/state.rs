@@ -1,6 +1,36 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
 use ethers_core::types::H160;
+use serde::{Deserialize, Serialize};
 
-pub const UPDATER: Item<H160> = Item::new("updater_manager_updater");
+use common::updater_manager::SlashRecord;
+use common::SignerId;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SlashingConfig {
+    pub bond_denom: String,
+    pub bond_amount: Uint128,
+    pub reporter_share_bps: u64,
+    pub treasury: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Bond {
+    pub depositor: Addr,
+    pub amount: Uint128,
+}
+
+pub const UPDATERS: Item<Vec<SignerId>> = Item::new("updater_manager_updaters");
+pub const THRESHOLD: Item<u32> = Item::new("updater_manager_threshold");
 pub const HOME: Item<Addr> = Item::new("updater_manager_home");
+
+pub const SLASHING_CONFIG: Item<SlashingConfig> = Item::new("updater_manager_slashing_config");
+// Bond posted for each updater in the set
+pub const BONDS: Map<&[u8], Bond> = Map::new("updater_manager_bonds");
+
+pub const SLASH_COUNT: Item<u64> = Item::new("updater_manager_slash_count");
+pub const SLASHES: Map<u64, SlashRecord> = Map::new("updater_manager_slashes");
+
+// Single updater and its bond, as stored before v0.3.0. Read only by migration
+pub const LEGACY_UPDATER: Item<H160> = Item::new("updater_manager_updater");
+pub const LEGACY_BOND: Item<Bond> = Item::new("updater_manager_bond");
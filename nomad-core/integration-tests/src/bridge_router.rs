@@ -0,0 +1,310 @@
+#[cfg(test)]
+mod test {
+    use ::bridge_router::native_token_id;
+    use common::{
+        bridge_router, connection_manager, h256_to_string, home, replica, test::test_replica,
+        Encode, NomadMessage, TokenId, TransferMessage,
+    };
+    use cosmwasm_std::{coins, from_binary, Addr, Uint128};
+    use cw20::BalanceResponse;
+    use cw_multi_test::Executor;
+    use ethers_core::types::{H160, H256};
+    use test_utils::{event_attr_value_by_key, Updater};
+
+    use crate::utils::helpers::{
+        addr_to_h256, app_event_by_ty, instantiate_bridge_router, instantiate_connection_manager,
+        instantiate_home, instantiate_test_replica, mock_app, App, BOND_DENOM,
+    };
+
+    const REMOTE_DOMAIN: u32 = 1000;
+    const LOCAL_DOMAIN: u32 = 2000;
+    const RECIPIENT: &str = "recipient01";
+    const UPDATER_PRIVKEY: &str =
+        "1111111111111111111111111111111111111111111111111111111111111111";
+
+    /// Remote router is a 20 byte EVM address, left-padded
+    fn remote_router() -> H256 {
+        H256::from(H160::repeat_byte(0xee))
+    }
+
+    struct Deployment {
+        home: Addr,
+        bridge_router: Addr,
+        replica: Addr,
+    }
+
+    fn deploy(app: &mut App) -> Deployment {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+        let owner = Addr::unchecked("owner");
+
+        let home = instantiate_home(app, owner.clone(), LOCAL_DOMAIN, updater.address());
+        let connection_manager = instantiate_connection_manager(app, owner.clone());
+
+        let bridge_router = instantiate_bridge_router(
+            app,
+            owner.clone(),
+            LOCAL_DOMAIN,
+            home.clone(),
+            connection_manager.clone(),
+        );
+
+        let replica = instantiate_test_replica(
+            app,
+            owner.clone(),
+            LOCAL_DOMAIN,
+            REMOTE_DOMAIN,
+            updater.address(),
+            H256::zero(),
+            100,
+        );
+
+        let enroll_msg = connection_manager::ExecuteMsg::OwnerEnrollReplica {
+            domain: REMOTE_DOMAIN,
+            replica: replica.to_string(),
+        };
+        app.execute_contract(owner.clone(), connection_manager, &enroll_msg, &[])
+            .unwrap();
+
+        let set_router_msg = bridge_router::ExecuteMsg::SetRouter {
+            domain: REMOTE_DOMAIN,
+            router: remote_router(),
+        };
+        app.execute_contract(owner, bridge_router.clone(), &set_router_msg, &[])
+            .unwrap();
+
+        Deployment {
+            home,
+            bridge_router,
+            replica,
+        }
+    }
+
+    /// Prove and process transfer message from remote router on replica
+    fn deliver_transfer(
+        app: &mut App,
+        deployment: &Deployment,
+        nonce: u32,
+        transfer: TransferMessage,
+    ) -> bool {
+        let sender = Addr::unchecked(h256_to_string(H256::zero()));
+
+        let nomad_message = NomadMessage {
+            origin: REMOTE_DOMAIN,
+            sender: remote_router(),
+            nonce,
+            destination: LOCAL_DOMAIN,
+            recipient: addr_to_h256(deployment.bridge_router.clone()),
+            body: transfer.to_vec(),
+        };
+
+        let prove_msg = test_replica::ExecuteMsg::SetProven {
+            leaf: nomad_message.to_leaf(),
+        };
+        app.execute_contract(sender.clone(), deployment.replica.clone(), &prove_msg, &[])
+            .unwrap();
+
+        let msg = test_replica::ExecuteMsg::ReplicaExecuteMsg(replica::ExecuteMsg::Process {
+            message: nomad_message.to_vec(),
+        });
+        let res = app
+            .execute_contract(sender, deployment.replica.clone(), &msg, &[])
+            .unwrap();
+        println!("\nProcess transfer: {:?}", res);
+
+        from_binary::<bool>(&res.data.as_ref().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn escrows_native_tokens_and_dispatches_transfer() {
+        let mut app = mock_app();
+        let deployment = deploy(&mut app);
+        let owner = Addr::unchecked("owner");
+
+        let msg = bridge_router::ExecuteMsg::SendNative {
+            destination: REMOTE_DOMAIN,
+            recipient: H256::repeat_byte(1),
+        };
+        let res = app
+            .execute_contract(
+                owner,
+                deployment.bridge_router.clone(),
+                &msg,
+                &coins(100, BOND_DENOM),
+            )
+            .unwrap();
+        println!("\nSend native: {:?}", res);
+
+        assert!(app_event_by_ty(&res, "wasm-Send").is_some());
+
+        // Transfer dispatched to remote router
+        let transfer = TransferMessage {
+            token: TokenId {
+                domain: LOCAL_DOMAIN,
+                id: native_token_id(BOND_DENOM),
+            },
+            recipient: H256::repeat_byte(1),
+            amount: Uint128::new(100),
+        };
+        let nomad_message = NomadMessage {
+            origin: LOCAL_DOMAIN,
+            sender: addr_to_h256(deployment.bridge_router.clone()),
+            nonce: 0,
+            destination: REMOTE_DOMAIN,
+            recipient: remote_router(),
+            body: transfer.to_vec(),
+        };
+        let dispatch_event = app_event_by_ty(&res, "wasm-Dispatch").unwrap();
+        assert_eq!(
+            format!("{:?}", nomad_message.to_leaf()),
+            event_attr_value_by_key(&dispatch_event, "message_hash").unwrap()
+        );
+
+        // Funds escrowed in bridge router
+        let balance = app
+            .wrap()
+            .query_balance(deployment.bridge_router.clone(), BOND_DENOM)
+            .unwrap();
+        assert_eq!(Uint128::new(100), balance.amount);
+
+        let escrowed_res: bridge_router::EscrowedResponse = app
+            .wrap()
+            .query_wasm_smart(
+                deployment.bridge_router,
+                &bridge_router::QueryMsg::Escrowed {
+                    id: native_token_id(BOND_DENOM),
+                },
+            )
+            .unwrap();
+        assert_eq!(Uint128::new(100), escrowed_res.amount);
+
+        // Message dispatched through home
+        let nonces_res: home::NoncesResponse = app
+            .wrap()
+            .query_wasm_smart(
+                deployment.home,
+                &home::QueryMsg::Nonces {
+                    domain: REMOTE_DOMAIN,
+                },
+            )
+            .unwrap();
+        assert_eq!(1, nonces_res.next_nonce);
+    }
+
+    #[test]
+    fn deploys_and_mints_representation_for_remote_token() {
+        let mut app = mock_app();
+        let deployment = deploy(&mut app);
+
+        let token = TokenId {
+            domain: REMOTE_DOMAIN,
+            id: H256::repeat_byte(7),
+        };
+        let transfer = TransferMessage {
+            token,
+            recipient: addr_to_h256(Addr::unchecked(RECIPIENT)),
+            amount: Uint128::new(50),
+        };
+
+        // First transfer deploys representation
+        assert!(deliver_transfer(&mut app, &deployment, 0, transfer.clone()));
+
+        let representation_res: bridge_router::RepresentationResponse = app
+            .wrap()
+            .query_wasm_smart(
+                deployment.bridge_router.clone(),
+                &bridge_router::QueryMsg::Representation { token },
+            )
+            .unwrap();
+        let representation = representation_res.representation;
+        assert_ne!("0x0", representation);
+
+        let canonical_res: bridge_router::CanonicalTokenResponse = app
+            .wrap()
+            .query_wasm_smart(
+                deployment.bridge_router.clone(),
+                &bridge_router::QueryMsg::CanonicalToken {
+                    representation: representation.clone(),
+                },
+            )
+            .unwrap();
+        assert_eq!(Some(token), canonical_res.token);
+
+        // Second transfer mints on existing representation
+        assert!(deliver_transfer(&mut app, &deployment, 1, transfer));
+
+        let balance_res: BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                representation,
+                &cw20::Cw20QueryMsg::Balance {
+                    address: RECIPIENT.to_owned(),
+                },
+            )
+            .unwrap();
+        assert_eq!(Uint128::new(100), balance_res.balance);
+    }
+
+    #[test]
+    fn releases_escrowed_native_tokens() {
+        let mut app = mock_app();
+        let deployment = deploy(&mut app);
+        let owner = Addr::unchecked("owner");
+
+        let msg = bridge_router::ExecuteMsg::SendNative {
+            destination: REMOTE_DOMAIN,
+            recipient: H256::repeat_byte(1),
+        };
+        app.execute_contract(
+            owner,
+            deployment.bridge_router.clone(),
+            &msg,
+            &coins(100, BOND_DENOM),
+        )
+        .unwrap();
+
+        // Remote sends part of the native tokens back
+        let transfer = TransferMessage {
+            token: TokenId {
+                domain: LOCAL_DOMAIN,
+                id: native_token_id(BOND_DENOM),
+            },
+            recipient: addr_to_h256(Addr::unchecked(RECIPIENT)),
+            amount: Uint128::new(40),
+        };
+        assert!(deliver_transfer(&mut app, &deployment, 0, transfer));
+
+        let balance = app.wrap().query_balance(RECIPIENT, BOND_DENOM).unwrap();
+        assert_eq!(Uint128::new(40), balance.amount);
+
+        let escrowed_res: bridge_router::EscrowedResponse = app
+            .wrap()
+            .query_wasm_smart(
+                deployment.bridge_router,
+                &bridge_router::QueryMsg::Escrowed {
+                    id: native_token_id(BOND_DENOM),
+                },
+            )
+            .unwrap();
+        assert_eq!(Uint128::new(60), escrowed_res.amount);
+    }
+
+    #[test]
+    fn rejects_release_exceeding_escrow() {
+        let mut app = mock_app();
+        let deployment = deploy(&mut app);
+
+        // Nothing escrowed, handle errors and replica reports failure
+        let transfer = TransferMessage {
+            token: TokenId {
+                domain: LOCAL_DOMAIN,
+                id: native_token_id(BOND_DENOM),
+            },
+            recipient: addr_to_h256(Addr::unchecked(RECIPIENT)),
+            amount: Uint128::new(40),
+        };
+        assert!(!deliver_transfer(&mut app, &deployment, 0, transfer));
+
+        let balance = app.wrap().query_balance(RECIPIENT, BOND_DENOM).unwrap();
+        assert_eq!(Uint128::zero(), balance.amount);
+    }
+}
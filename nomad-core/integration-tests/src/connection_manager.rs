@@ -4,14 +4,13 @@ mod test {
     use cosmwasm_std::Addr;
     use cw_multi_test::Executor;
     use ethers_core::types::{H160, H256};
-    use test_utils::Watcher;
+    use test_utils::{NativeKey, NativeWatcher, Watcher};
 
     use crate::utils::helpers::{
         app_event_by_ty, instantiate_connection_manager, instantiate_home,
         instantiate_test_replica, mock_app,
     };
 
-    const CHAIN_ADDR_LENGTH_BYTES: usize = 11; // e.g. "Contract #0".len()
     const LOCAL_DOMAIN: u32 = 1000;
     const REMOTE_DOMAIN: u32 = 2000;
     const WATCHER_PRIVKEY: &str =
@@ -25,8 +24,7 @@ mod test {
         let owner = Addr::unchecked("owner");
 
         // Instantiate connection manager
-        let connection_manager_addr =
-            instantiate_connection_manager(&mut app, owner.clone(), CHAIN_ADDR_LENGTH_BYTES);
+        let connection_manager_addr = instantiate_connection_manager(&mut app, owner.clone());
 
         // Instantiate home
         let home_addr = instantiate_home(&mut app, owner.clone(), LOCAL_DOMAIN, updater);
@@ -62,14 +60,12 @@ mod test {
         let owner = Addr::unchecked("owner");
 
         // Instantiate connection manager
-        let connection_manager_addr =
-            instantiate_connection_manager(&mut app, owner.clone(), CHAIN_ADDR_LENGTH_BYTES);
+        let connection_manager_addr = instantiate_connection_manager(&mut app, owner.clone());
 
         // Instantiate replica to enroll
         let replica_addr = instantiate_test_replica(
             &mut app,
             owner.clone(),
-            CHAIN_ADDR_LENGTH_BYTES,
             LOCAL_DOMAIN,
             REMOTE_DOMAIN,
             updater,
@@ -105,7 +101,7 @@ mod test {
 
         // Set watcher permissions for replica
         let set_permission_msg = common::connection_manager::ExecuteMsg::SetWatcherPermission {
-            watcher: watcher.address(),
+            watcher: watcher.signer_id(),
             domain: REMOTE_DOMAIN,
             access: true,
         };
@@ -128,6 +124,7 @@ mod test {
             domain: REMOTE_DOMAIN,
             updater: H256::from(updater),
             signature: signed_failure.signature.to_vec(),
+            watcher: None,
         };
         let res = app
             .execute_contract(
@@ -156,6 +153,104 @@ mod test {
         assert!(!is_replica);
     }
 
+    #[test]
+    fn unenrolls_replica_on_native_watcher_signed_failure() {
+        let mut app = mock_app();
+
+        let watcher = NativeWatcher::new(NativeKey::ed25519(WATCHER_PRIVKEY), REMOTE_DOMAIN);
+        let other_watcher =
+            NativeWatcher::new(NativeKey::secp256k1(WATCHER_PRIVKEY), REMOTE_DOMAIN);
+
+        let updater = H160::repeat_byte(1);
+        let owner = Addr::unchecked("owner");
+
+        let connection_manager_addr = instantiate_connection_manager(&mut app, owner.clone());
+        let replica_addr = instantiate_test_replica(
+            &mut app,
+            owner.clone(),
+            LOCAL_DOMAIN,
+            REMOTE_DOMAIN,
+            updater,
+            H256::zero(),
+            100,
+        );
+
+        let enroll_replica_msg = common::connection_manager::ExecuteMsg::OwnerEnrollReplica {
+            domain: REMOTE_DOMAIN,
+            replica: replica_addr.to_string(),
+        };
+        app.execute_contract(
+            owner.clone(),
+            connection_manager_addr.clone(),
+            &enroll_replica_msg,
+            &[],
+        )
+        .unwrap();
+
+        // Both watchers permissioned
+        for signer_id in [watcher.signer_id(), other_watcher.signer_id()] {
+            let set_permission_msg = common::connection_manager::ExecuteMsg::SetWatcherPermission {
+                watcher: signer_id,
+                domain: REMOTE_DOMAIN,
+                access: true,
+            };
+            app.execute_contract(
+                owner.clone(),
+                connection_manager_addr.clone(),
+                &set_permission_msg,
+                &[],
+            )
+            .unwrap();
+        }
+
+        let signature = watcher.sign_failure_notification(H256::from(updater));
+
+        // Signature must verify for named watcher
+        let unenroll_replica_msg = common::connection_manager::ExecuteMsg::UnenrollReplica {
+            domain: REMOTE_DOMAIN,
+            updater: H256::from(updater),
+            signature: signature.clone(),
+            watcher: Some(other_watcher.signer_id()),
+        };
+        let res = app.execute_contract(
+            owner.clone(),
+            connection_manager_addr.clone(),
+            &unenroll_replica_msg,
+            &[],
+        );
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("Signature does not verify for watcher"));
+
+        let unenroll_replica_msg = common::connection_manager::ExecuteMsg::UnenrollReplica {
+            domain: REMOTE_DOMAIN,
+            updater: H256::from(updater),
+            signature,
+            watcher: Some(watcher.signer_id()),
+        };
+        let res = app
+            .execute_contract(
+                owner,
+                connection_manager_addr.clone(),
+                &unenroll_replica_msg,
+                &[],
+            )
+            .unwrap();
+        assert!(app_event_by_ty(&res, "wasm-ReplicaUnenrolled").is_some());
+
+        let is_replica_res: connection_manager::IsReplicaResponse = app
+            .wrap()
+            .query_wasm_smart(
+                connection_manager_addr,
+                &connection_manager::QueryMsg::IsReplica {
+                    replica: replica_addr.to_string(),
+                },
+            )
+            .unwrap();
+        assert!(!is_replica_res.is_replica);
+    }
+
     #[tokio::test]
     async fn unenroll_replica_reverts_if_no_such_replica() {
         let mut app = mock_app();
@@ -166,14 +261,12 @@ mod test {
         let owner = Addr::unchecked("owner");
 
         // Instantiate connection manager
-        let connection_manager_addr =
-            instantiate_connection_manager(&mut app, owner.clone(), CHAIN_ADDR_LENGTH_BYTES);
+        let connection_manager_addr = instantiate_connection_manager(&mut app, owner.clone());
 
         // Instantiate replica to enroll
         let replica_addr = instantiate_test_replica(
             &mut app,
             owner.clone(),
-            CHAIN_ADDR_LENGTH_BYTES,
             LOCAL_DOMAIN,
             REMOTE_DOMAIN,
             updater,
@@ -209,7 +302,7 @@ mod test {
 
         // Set watcher permissions for replica
         let set_permission_msg = common::connection_manager::ExecuteMsg::SetWatcherPermission {
-            watcher: watcher.address(),
+            watcher: watcher.signer_id(),
             domain: REMOTE_DOMAIN,
             access: true,
         };
@@ -236,6 +329,7 @@ mod test {
             domain: 9999,
             updater: H256::from(updater),
             signature: signed_failure.signature.to_vec(),
+            watcher: None,
         };
         let res = app.execute_contract(
             owner.clone(),
@@ -264,14 +358,12 @@ mod test {
         let owner = Addr::unchecked("owner");
 
         // Instantiate connection manager
-        let connection_manager_addr =
-            instantiate_connection_manager(&mut app, owner.clone(), CHAIN_ADDR_LENGTH_BYTES);
+        let connection_manager_addr = instantiate_connection_manager(&mut app, owner.clone());
 
         // Instantiate replica to enroll
         let replica_addr = instantiate_test_replica(
             &mut app,
             owner.clone(),
-            CHAIN_ADDR_LENGTH_BYTES,
             LOCAL_DOMAIN,
             REMOTE_DOMAIN,
             updater,
@@ -307,7 +399,7 @@ mod test {
 
         // Set watcher permissions for replica
         let set_permission_msg = common::connection_manager::ExecuteMsg::SetWatcherPermission {
-            watcher: watcher.address(),
+            watcher: watcher.signer_id(),
             domain: REMOTE_DOMAIN,
             access: true,
         };
@@ -330,6 +422,7 @@ mod test {
             domain: REMOTE_DOMAIN,
             updater: H256::from(updater),
             signature: signed_failure.signature.to_vec(),
+            watcher: None,
         };
         let res = app.execute_contract(
             owner.clone(),
@@ -359,14 +452,12 @@ mod test {
         let owner = Addr::unchecked("owner");
 
         // Instantiate connection manager
-        let connection_manager_addr =
-            instantiate_connection_manager(&mut app, owner.clone(), CHAIN_ADDR_LENGTH_BYTES);
+        let connection_manager_addr = instantiate_connection_manager(&mut app, owner.clone());
 
         // Instantiate replica to enroll
         let replica_addr = instantiate_test_replica(
             &mut app,
             owner.clone(),
-            CHAIN_ADDR_LENGTH_BYTES,
             LOCAL_DOMAIN,
             REMOTE_DOMAIN,
             updater,
@@ -402,7 +493,7 @@ mod test {
 
         // Set watcher permissions for replica
         let set_permission_msg = common::connection_manager::ExecuteMsg::SetWatcherPermission {
-            watcher: watcher.address(),
+            watcher: watcher.signer_id(),
             domain: REMOTE_DOMAIN,
             access: true,
         };
@@ -425,6 +516,7 @@ mod test {
             domain: REMOTE_DOMAIN,
             updater: H256::from(fake_updater),
             signature: signed_failure.signature.to_vec(),
+            watcher: None,
         };
         let res = app.execute_contract(
             owner.clone(),
@@ -0,0 +1,340 @@
+#[cfg(test)]
+mod test {
+    use common::{
+        connection_manager, governance_router, h256_to_string, home, replica, test::test_replica,
+        Encode, NomadMessage,
+    };
+    use cosmwasm_std::{from_binary, to_binary, to_vec, Addr, WasmMsg};
+    use cw_multi_test::Executor;
+    use ethers_core::types::H256;
+    use test_utils::Updater;
+
+    use crate::utils::helpers::{
+        addr_to_h256, app_event_by_ty, instantiate_connection_manager,
+        instantiate_governance_router, instantiate_home, instantiate_test_replica, mock_app,
+    };
+
+    const REMOTE_DOMAIN: u32 = 1000;
+    const LOCAL_DOMAIN: u32 = 2000;
+    const REMOTE_ROUTER: &str = "remote_router";
+    const UPDATER_PRIVKEY: &str =
+        "1111111111111111111111111111111111111111111111111111111111111111";
+
+    #[test]
+    fn governor_dispatches_calls_to_remote_router() {
+        let mut app = mock_app();
+
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+        let owner = Addr::unchecked("owner");
+
+        let home_addr = instantiate_home(&mut app, owner.clone(), LOCAL_DOMAIN, updater.address());
+        let connection_manager_addr = instantiate_connection_manager(&mut app, owner.clone());
+
+        // Owner is local governor
+        let router_addr = instantiate_governance_router(
+            &mut app,
+            owner.clone(),
+            LOCAL_DOMAIN,
+            home_addr.clone(),
+            connection_manager_addr,
+            LOCAL_DOMAIN,
+            Some(owner.clone()),
+        );
+
+        let set_router_msg = governance_router::ExecuteMsg::SetRouter {
+            domain: REMOTE_DOMAIN,
+            router: addr_to_h256(Addr::unchecked(REMOTE_ROUTER)),
+        };
+        app.execute_contract(owner.clone(), router_addr.clone(), &set_router_msg, &[])
+            .unwrap();
+
+        let call = WasmMsg::Execute {
+            contract_addr: "remote_contract".to_owned(),
+            msg: to_binary(&"call").unwrap(),
+            funds: vec![],
+        };
+        let msg = governance_router::ExecuteMsg::DispatchCalls {
+            domain: REMOTE_DOMAIN,
+            calls: vec![call],
+        };
+        let res = app
+            .execute_contract(owner.clone(), router_addr, &msg, &[])
+            .unwrap();
+        println!("\nDispatch calls: {:?}", res);
+
+        assert!(app_event_by_ty(&res, "wasm-Dispatch").is_some());
+
+        // Home nonce for remote domain incremented
+        let nonces_res: home::NoncesResponse = app
+            .wrap()
+            .query_wasm_smart(
+                home_addr,
+                &home::QueryMsg::Nonces {
+                    domain: REMOTE_DOMAIN,
+                },
+            )
+            .unwrap();
+        assert_eq!(1, nonces_res.next_nonce);
+    }
+
+    #[test]
+    fn executes_calls_dispatched_to_local_router() {
+        let mut app = mock_app();
+
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+        let owner = Addr::unchecked("owner");
+
+        let home_addr = instantiate_home(&mut app, owner.clone(), LOCAL_DOMAIN, updater.address());
+        let connection_manager_addr = instantiate_connection_manager(&mut app, owner.clone());
+
+        // Connection manager accepts home as deliverer of local messages
+        let set_home_msg = connection_manager::ExecuteMsg::SetHome {
+            home: home_addr.to_string(),
+        };
+        app.execute_contract(
+            owner.clone(),
+            connection_manager_addr.clone(),
+            &set_home_msg,
+            &[],
+        )
+        .unwrap();
+
+        let router_addr = instantiate_governance_router(
+            &mut app,
+            owner.clone(),
+            LOCAL_DOMAIN,
+            home_addr,
+            connection_manager_addr,
+            LOCAL_DOMAIN,
+            Some(owner.clone()),
+        );
+
+        // Router is its own counterpart on local domain
+        let set_router_msg = governance_router::ExecuteMsg::SetRouter {
+            domain: LOCAL_DOMAIN,
+            router: addr_to_h256(router_addr.clone()),
+        };
+        app.execute_contract(owner.clone(), router_addr.clone(), &set_router_msg, &[])
+            .unwrap();
+
+        // Local governor enrolls remote router through a loopback message
+        let call = WasmMsg::Execute {
+            contract_addr: router_addr.to_string(),
+            msg: to_binary(&governance_router::ExecuteMsg::SetRouter {
+                domain: REMOTE_DOMAIN,
+                router: addr_to_h256(Addr::unchecked(REMOTE_ROUTER)),
+            })
+            .unwrap(),
+            funds: vec![],
+        };
+        let msg = governance_router::ExecuteMsg::DispatchCalls {
+            domain: LOCAL_DOMAIN,
+            calls: vec![call],
+        };
+        let res = app
+            .execute_contract(owner.clone(), router_addr.clone(), &msg, &[])
+            .unwrap();
+        println!("\nDispatch calls to local router: {:?}", res);
+
+        assert!(app_event_by_ty(&res, "wasm-LoopbackDispatch").is_some());
+        assert!(app_event_by_ty(&res, "wasm-HandleGovernanceMessage").is_some());
+        assert!(app_event_by_ty(&res, "wasm-LoopbackFailed").is_none());
+
+        let router_res: governance_router::RouterResponse = app
+            .wrap()
+            .query_wasm_smart(
+                router_addr,
+                &governance_router::QueryMsg::Router {
+                    domain: REMOTE_DOMAIN,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            addr_to_h256(Addr::unchecked(REMOTE_ROUTER)),
+            router_res.router
+        );
+    }
+
+    #[test]
+    fn executes_calls_from_remote_governor() {
+        let mut app = mock_app();
+
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+        let sender = Addr::unchecked(h256_to_string(H256::zero()));
+        let owner = Addr::unchecked("owner");
+
+        let home_addr = instantiate_home(&mut app, owner.clone(), LOCAL_DOMAIN, updater.address());
+        let connection_manager_addr = instantiate_connection_manager(&mut app, owner.clone());
+
+        let router_addr = instantiate_governance_router(
+            &mut app,
+            owner.clone(),
+            LOCAL_DOMAIN,
+            home_addr,
+            connection_manager_addr.clone(),
+            LOCAL_DOMAIN,
+            Some(owner.clone()),
+        );
+
+        // Replica owned by governance router
+        let replica_addr = instantiate_test_replica(
+            &mut app,
+            router_addr.clone(),
+            LOCAL_DOMAIN,
+            REMOTE_DOMAIN,
+            updater.address(),
+            H256::zero(),
+            100,
+        );
+
+        let enroll_msg = connection_manager::ExecuteMsg::OwnerEnrollReplica {
+            domain: REMOTE_DOMAIN,
+            replica: replica_addr.to_string(),
+        };
+        app.execute_contract(owner.clone(), connection_manager_addr, &enroll_msg, &[])
+            .unwrap();
+
+        // Enroll remote router then hand governorship to remote domain
+        let set_router_msg = governance_router::ExecuteMsg::SetRouter {
+            domain: REMOTE_DOMAIN,
+            router: addr_to_h256(Addr::unchecked(REMOTE_ROUTER)),
+        };
+        app.execute_contract(owner.clone(), router_addr.clone(), &set_router_msg, &[])
+            .unwrap();
+
+        let transfer_msg = governance_router::ExecuteMsg::TransferGovernor {
+            domain: REMOTE_DOMAIN,
+            governor: None,
+        };
+        app.execute_contract(owner.clone(), router_addr.clone(), &transfer_msg, &[])
+            .unwrap();
+
+        let governor_res: governance_router::GovernorResponse = app
+            .wrap()
+            .query_wasm_smart(
+                router_addr.clone(),
+                &governance_router::QueryMsg::Governor {},
+            )
+            .unwrap();
+        assert_eq!(REMOTE_DOMAIN, governor_res.domain);
+        assert_eq!("0x0", governor_res.governor);
+
+        // Remote governor updates replica optimistic timeout
+        let new_optimistic_seconds = 500;
+        let call = WasmMsg::Execute {
+            contract_addr: replica_addr.to_string(),
+            msg: to_binary(&test_replica::ExecuteMsg::ReplicaExecuteMsg(
+                replica::ExecuteMsg::SetOptimisticTimeout {
+                    optimistic_seconds: new_optimistic_seconds,
+                },
+            ))
+            .unwrap(),
+            funds: vec![],
+        };
+        let gov_msg = governance_router::GovernanceMessage::Batch { calls: vec![call] };
+
+        let nomad_message = NomadMessage {
+            origin: REMOTE_DOMAIN,
+            sender: addr_to_h256(Addr::unchecked(REMOTE_ROUTER)),
+            nonce: 0,
+            destination: LOCAL_DOMAIN,
+            recipient: addr_to_h256(router_addr.clone()),
+            body: to_vec(&gov_msg).unwrap(),
+        };
+
+        let prove_msg = test_replica::ExecuteMsg::SetProven {
+            leaf: nomad_message.to_leaf(),
+        };
+        app.execute_contract(sender.clone(), replica_addr.clone(), &prove_msg, &[])
+            .unwrap();
+
+        let msg = test_replica::ExecuteMsg::ReplicaExecuteMsg(replica::ExecuteMsg::Process {
+            message: nomad_message.to_vec(),
+        });
+        let res = app
+            .execute_contract(sender.clone(), replica_addr.clone(), &msg, &[])
+            .unwrap();
+        println!("\nProcess governance message: {:?}", res);
+
+        let success = from_binary::<bool>(&res.data.as_ref().unwrap()).unwrap();
+        assert!(success);
+        assert!(app_event_by_ty(&res, "wasm-HandleGovernanceMessage").is_some());
+
+        let optimistic_seconds_res: replica::OptimisticSecondsResponse = app
+            .wrap()
+            .query_wasm_smart(replica_addr, &replica::QueryMsg::OptimisticSeconds {})
+            .unwrap();
+        assert_eq!(
+            new_optimistic_seconds,
+            optimistic_seconds_res.optimistic_seconds
+        );
+    }
+
+    #[test]
+    fn rejects_message_from_non_governor_router() {
+        let mut app = mock_app();
+
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+        let sender = Addr::unchecked(h256_to_string(H256::zero()));
+        let owner = Addr::unchecked("owner");
+
+        let home_addr = instantiate_home(&mut app, owner.clone(), LOCAL_DOMAIN, updater.address());
+        let connection_manager_addr = instantiate_connection_manager(&mut app, owner.clone());
+
+        let router_addr = instantiate_governance_router(
+            &mut app,
+            owner.clone(),
+            LOCAL_DOMAIN,
+            home_addr,
+            connection_manager_addr.clone(),
+            LOCAL_DOMAIN,
+            Some(owner.clone()),
+        );
+
+        let replica_addr = instantiate_test_replica(
+            &mut app,
+            router_addr.clone(),
+            LOCAL_DOMAIN,
+            REMOTE_DOMAIN,
+            updater.address(),
+            H256::zero(),
+            100,
+        );
+
+        let enroll_msg = connection_manager::ExecuteMsg::OwnerEnrollReplica {
+            domain: REMOTE_DOMAIN,
+            replica: replica_addr.to_string(),
+        };
+        app.execute_contract(owner.clone(), connection_manager_addr, &enroll_msg, &[])
+            .unwrap();
+
+        // Governor is local, so no remote message should be accepted
+        let gov_msg = governance_router::GovernanceMessage::Batch { calls: vec![] };
+        let nomad_message = NomadMessage {
+            origin: REMOTE_DOMAIN,
+            sender: addr_to_h256(Addr::unchecked(REMOTE_ROUTER)),
+            nonce: 0,
+            destination: LOCAL_DOMAIN,
+            recipient: addr_to_h256(router_addr),
+            body: to_vec(&gov_msg).unwrap(),
+        };
+
+        let prove_msg = test_replica::ExecuteMsg::SetProven {
+            leaf: nomad_message.to_leaf(),
+        };
+        app.execute_contract(sender.clone(), replica_addr.clone(), &prove_msg, &[])
+            .unwrap();
+
+        let msg = test_replica::ExecuteMsg::ReplicaExecuteMsg(replica::ExecuteMsg::Process {
+            message: nomad_message.to_vec(),
+        });
+        let res = app
+            .execute_contract(sender.clone(), replica_addr, &msg, &[])
+            .unwrap();
+        println!("\nProcess (non-governor origin): {:?}", res);
+
+        // Replica swallows handle error and returns false
+        let success = from_binary::<bool>(&res.data.as_ref().unwrap()).unwrap();
+        assert!(!success);
+    }
+}
@@ -7,7 +7,8 @@ mod test {
     use test_utils::Updater;
 
     use crate::utils::helpers::{
-        app_event_by_ty, instantiate_home, instantiate_updater_manager, mock_app,
+        app_event_by_ty, instantiate_home, instantiate_updater_manager, mock_app, BOND_AMOUNT,
+        BOND_DENOM, REPORTER_SHARE_BPS,
     };
 
     const LOCAL_DOMAIN: u32 = 1000;
@@ -69,13 +70,41 @@ mod test {
         let update_msg = home::ExecuteMsg::ImproperUpdate {
             old_root: suggested.committed_root,
             new_root: improper_root,
-            signature: update.signature.to_vec(),
+            signatures: vec![update.signature.to_vec()],
         };
+        let balance_before = app.wrap().query_balance(&owner, BOND_DENOM).unwrap();
         let res = app
-            .execute_contract(owner, home_addr.clone(), &update_msg, &[])
+            .execute_contract(owner.clone(), home_addr.clone(), &update_msg, &[])
             .unwrap();
         println!("Improper Update: {:?}", res);
 
-        assert!(app_event_by_ty(&res, "wasm-SlashUpdater").is_some())
+        assert!(app_event_by_ty(&res, "wasm-SlashUpdater").is_some());
+
+        // Reporter receives its share of the bond and the rest is burned
+        let reporter_share = BOND_AMOUNT * REPORTER_SHARE_BPS as u128 / 10_000;
+        let balance_after = app.wrap().query_balance(&owner, BOND_DENOM).unwrap();
+        assert_eq!(
+            balance_before.amount.u128() + reporter_share,
+            balance_after.amount.u128()
+        );
+
+        let manager_balance = app
+            .wrap()
+            .query_balance(&updater_manager_addr, BOND_DENOM)
+            .unwrap();
+        assert!(manager_balance.amount.is_zero());
+
+        let history: updater_manager::SlashingHistoryResponse = app
+            .wrap()
+            .query_wasm_smart(
+                updater_manager_addr,
+                &updater_manager::QueryMsg::SlashingHistory {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(1, history.slashes.len());
+        assert_eq!(owner.to_string(), history.slashes[0].reporter);
     }
 }
@@ -1,6 +1,11 @@
+mod bridge_router;
 mod connection_manager;
+mod governance_router;
 mod home;
+mod migrate;
+mod reference_recipient;
 mod replica;
+mod replica_hub;
 mod updater_manager;
 
 pub mod utils;
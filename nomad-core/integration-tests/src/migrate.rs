@@ -0,0 +1,284 @@
+#[cfg(test)]
+mod test {
+    use ::updater_manager::state::{
+        BONDS, LEGACY_UPDATER, SLASHING_CONFIG, SLASH_COUNT, THRESHOLD, UPDATERS,
+    };
+    use common::{connection_manager, home, nomad_base, replica, updater_manager, SignerId};
+    use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response, Uint128};
+    use cw_multi_test::{AppResponse, ContractWrapper, Executor};
+    use ethers_core::types::H256;
+    use test_utils::Updater;
+
+    use crate::utils::helpers::{
+        app_event_by_ty, instantiate_connection_manager, instantiate_home,
+        instantiate_test_replica, instantiate_updater_manager, mock_app,
+        store_connection_manager_code, store_home_code, store_test_replica_code,
+        store_updater_manager_code, BOND_DENOM, REPORTER_SHARE_BPS,
+    };
+
+    const LOCAL_DOMAIN: u32 = 1000;
+    const REMOTE_DOMAIN: u32 = 2000;
+    const UPDATER_PRIVKEY: &str =
+        "1111111111111111111111111111111111111111111111111111111111111111";
+
+    /// Home instantiated with a stored version newer than the current code
+    fn instantiate_home_future_version(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: home::InstantiateMsg,
+    ) -> Result<Response, ::home::ContractError> {
+        let res = ::home::contract::instantiate(deps.branch(), env, info, msg)?;
+        cw2::set_contract_version(deps.storage, "crates.io:home", "99.0.0")?;
+        Ok(res)
+    }
+
+    /// Updater manager instantiated with v0.1.0 storage layout (single
+    /// updater, no bonding)
+    fn instantiate_updater_manager_v0_1(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: updater_manager::InstantiateMsg,
+    ) -> Result<Response, ::updater_manager::ContractError> {
+        let updater = match msg.updaters[0].clone() {
+            SignerId::EthAddress(address) => address,
+            _ => panic!("v0.1.0 updater must be an ethereum address"),
+        };
+        let res = ::updater_manager::contract::instantiate(deps.branch(), env, info, msg)?;
+        UPDATERS.remove(deps.storage);
+        THRESHOLD.remove(deps.storage);
+        BONDS.remove(deps.storage, updater.as_bytes());
+        LEGACY_UPDATER.save(deps.storage, &updater)?;
+        SLASHING_CONFIG.remove(deps.storage);
+        SLASH_COUNT.remove(deps.storage);
+        cw2::set_contract_version(deps.storage, "crates.io:updater-manager", "0.1.0")?;
+        Ok(res)
+    }
+
+    /// Version migrated to, read from migrate event
+    fn migrated_to_version(res: &AppResponse) -> String {
+        println!("\nMigrate: {:?}", res);
+
+        let event = app_event_by_ty(res, "wasm-Migrate").unwrap();
+        event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == "to_version")
+            .unwrap()
+            .value
+            .clone()
+    }
+
+    #[test]
+    fn migrates_core_contracts_to_new_code() {
+        let mut app = mock_app();
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+        let owner = Addr::unchecked("owner");
+
+        let home_addr = instantiate_home(&mut app, owner.clone(), LOCAL_DOMAIN, updater.address());
+        let replica_addr = instantiate_test_replica(
+            &mut app,
+            owner.clone(),
+            LOCAL_DOMAIN,
+            REMOTE_DOMAIN,
+            updater.address(),
+            H256::zero(),
+            100,
+        );
+        let connection_manager_addr = instantiate_connection_manager(&mut app, owner.clone());
+        let updater_manager_addr =
+            instantiate_updater_manager(&mut app, owner.clone(), updater.address());
+
+        let home_code_id = store_home_code(&mut app);
+        let version = migrated_to_version(
+            &app.migrate_contract(
+                owner.clone(),
+                home_addr.clone(),
+                &home::MigrateMsg {},
+                home_code_id,
+            )
+            .unwrap(),
+        );
+        assert_eq!("0.3.0", version);
+
+        let replica_code_id = store_test_replica_code(&mut app);
+        migrated_to_version(
+            &app.migrate_contract(
+                owner.clone(),
+                replica_addr,
+                &replica::MigrateMsg {},
+                replica_code_id,
+            )
+            .unwrap(),
+        );
+
+        let connection_manager_code_id = store_connection_manager_code(&mut app);
+        migrated_to_version(
+            &app.migrate_contract(
+                owner.clone(),
+                connection_manager_addr,
+                &connection_manager::MigrateMsg {},
+                connection_manager_code_id,
+            )
+            .unwrap(),
+        );
+
+        let updater_manager_code_id = store_updater_manager_code(&mut app);
+        let version = migrated_to_version(
+            &app.migrate_contract(
+                owner.clone(),
+                updater_manager_addr,
+                &updater_manager::MigrateMsg {
+                    slashing_params: None,
+                },
+                updater_manager_code_id,
+            )
+            .unwrap(),
+        );
+        assert_eq!("0.3.0", version);
+
+        // State survives migration
+        let updaters_res: nomad_base::UpdatersResponse = app
+            .wrap()
+            .query_wasm_smart(home_addr, &home::QueryMsg::Updaters {})
+            .unwrap();
+        assert_eq!(vec![updater.signer_id()], updaters_res.updaters);
+        assert_eq!(1, updaters_res.threshold);
+    }
+
+    #[test]
+    fn refuses_downgrade() {
+        let mut app = mock_app();
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+        let owner = Addr::unchecked("owner");
+
+        let future_home_code_id = app.store_code(Box::new(
+            ContractWrapper::new_with_empty(
+                ::home::contract::execute,
+                instantiate_home_future_version,
+                ::home::contract::query,
+            )
+            .with_reply(::home::contract::reply)
+            .with_migrate(::home::contract::migrate),
+        ));
+        let home_addr = app
+            .instantiate_contract(
+                future_home_code_id,
+                owner.clone(),
+                &home::InstantiateMsg {
+                    local_domain: LOCAL_DOMAIN,
+                    updaters: vec![updater.signer_id()],
+                    threshold: 1,
+                },
+                &[],
+                String::from("HOME"),
+                Some(owner.to_string()),
+            )
+            .unwrap();
+
+        let home_code_id = store_home_code(&mut app);
+        let err = app
+            .migrate_contract(owner, home_addr, &home::MigrateMsg {}, home_code_id)
+            .unwrap_err();
+        assert!(err.to_string().contains("Cannot downgrade"));
+    }
+
+    #[test]
+    fn migrates_updater_manager_from_pre_bonding_layout() {
+        let mut app = mock_app();
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+        let owner = Addr::unchecked("owner");
+
+        let v0_1_code_id = app.store_code(Box::new(
+            ContractWrapper::new_with_empty(
+                ::updater_manager::contract::execute,
+                instantiate_updater_manager_v0_1,
+                ::updater_manager::contract::query,
+            )
+            .with_reply(::updater_manager::contract::reply)
+            .with_migrate(::updater_manager::contract::migrate),
+        ));
+        let updater_manager_addr = app
+            .instantiate_contract(
+                v0_1_code_id,
+                owner.clone(),
+                &updater_manager::InstantiateMsg {
+                    updaters: vec![updater.signer_id()],
+                    threshold: 1,
+                    bond_denom: BOND_DENOM.to_owned(),
+                    bond_amount: Uint128::zero(),
+                    reporter_share_bps: REPORTER_SHARE_BPS,
+                    treasury: None,
+                },
+                &[],
+                String::from("UPDATER_MANAGER"),
+                Some(owner.to_string()),
+            )
+            .unwrap();
+
+        let code_id = store_updater_manager_code(&mut app);
+
+        // Bonding params required to migrate from v0.1.0
+        let err = app
+            .migrate_contract(
+                owner.clone(),
+                updater_manager_addr.clone(),
+                &updater_manager::MigrateMsg {
+                    slashing_params: None,
+                },
+                code_id,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("Slashing params required"));
+
+        let version = migrated_to_version(
+            &app.migrate_contract(
+                owner.clone(),
+                updater_manager_addr.clone(),
+                &updater_manager::MigrateMsg {
+                    slashing_params: Some(updater_manager::SlashingParams {
+                        bond_denom: BOND_DENOM.to_owned(),
+                        bond_amount: Uint128::new(100),
+                        reporter_share_bps: REPORTER_SHARE_BPS,
+                        treasury: None,
+                    }),
+                },
+                code_id,
+            )
+            .unwrap(),
+        );
+        assert_eq!("0.3.0", version);
+
+        let config_res: updater_manager::SlashingConfigResponse = app
+            .wrap()
+            .query_wasm_smart(
+                updater_manager_addr.clone(),
+                &updater_manager::QueryMsg::SlashingConfig {},
+            )
+            .unwrap();
+        assert_eq!(Uint128::new(100), config_res.bond_amount);
+
+        // Existing updater becomes 1-of-1 set with empty bond
+        let updaters_res: updater_manager::UpdatersResponse = app
+            .wrap()
+            .query_wasm_smart(
+                updater_manager_addr.clone(),
+                &updater_manager::QueryMsg::Updaters {},
+            )
+            .unwrap();
+        assert_eq!(vec![updater.signer_id()], updaters_res.updaters);
+        assert_eq!(1, updaters_res.threshold);
+
+        let bond_res: updater_manager::BondResponse = app
+            .wrap()
+            .query_wasm_smart(
+                updater_manager_addr,
+                &updater_manager::QueryMsg::Bond {
+                    updater: updater.signer_id(),
+                },
+            )
+            .unwrap();
+        assert_eq!(Uint128::zero(), bond_res.amount);
+    }
+}
@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod test {
+    use common::{
+        connection_manager, h256_to_string, reference_recipient, replica, test::test_replica,
+        Encode, NomadMessage,
+    };
+    use cosmwasm_std::{from_binary, Addr};
+    use cw_multi_test::Executor;
+    use ethers_core::types::H256;
+    use test_utils::Updater;
+
+    use crate::utils::helpers::{
+        addr_to_h256, instantiate_connection_manager, instantiate_reference_recipient,
+        instantiate_test_replica, mock_app, App,
+    };
+
+    const REMOTE_DOMAIN: u32 = 1000;
+    const LOCAL_DOMAIN: u32 = 2000;
+    const UPDATER_PRIVKEY: &str =
+        "1111111111111111111111111111111111111111111111111111111111111111";
+
+    /// Instantiate connection manager, replica and recipient. Replica is
+    /// enrolled for remote domain iff `enroll`
+    fn deploy(app: &mut App, enroll: bool) -> (Addr, Addr) {
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+        let owner = Addr::unchecked("owner");
+
+        let connection_manager_addr = instantiate_connection_manager(app, owner.clone());
+
+        let replica_addr = instantiate_test_replica(
+            app,
+            owner.clone(),
+            LOCAL_DOMAIN,
+            REMOTE_DOMAIN,
+            updater.address(),
+            H256::zero(),
+            100,
+        );
+
+        let recipient_addr =
+            instantiate_reference_recipient(app, owner.clone(), connection_manager_addr.clone());
+
+        if enroll {
+            let enroll_msg = connection_manager::ExecuteMsg::OwnerEnrollReplica {
+                domain: REMOTE_DOMAIN,
+                replica: replica_addr.to_string(),
+            };
+            app.execute_contract(owner, connection_manager_addr, &enroll_msg, &[])
+                .unwrap();
+        }
+
+        (replica_addr, recipient_addr)
+    }
+
+    /// Prove and process message on replica, returning success flag
+    fn process(app: &mut App, replica_addr: Addr, nomad_message: NomadMessage) -> bool {
+        let sender = Addr::unchecked(h256_to_string(H256::zero()));
+
+        let prove_msg = test_replica::ExecuteMsg::SetProven {
+            leaf: nomad_message.to_leaf(),
+        };
+        app.execute_contract(sender.clone(), replica_addr.clone(), &prove_msg, &[])
+            .unwrap();
+
+        let msg = test_replica::ExecuteMsg::ReplicaExecuteMsg(replica::ExecuteMsg::Process {
+            message: nomad_message.to_vec(),
+        });
+        let res = app
+            .execute_contract(sender, replica_addr, &msg, &[])
+            .unwrap();
+        println!("\nProcess: {:?}", res);
+
+        from_binary::<bool>(&res.data.as_ref().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn handles_message_from_enrolled_replica() {
+        let mut app = mock_app();
+        let (replica_addr, recipient_addr) = deploy(&mut app, true);
+
+        let nomad_message = NomadMessage {
+            origin: REMOTE_DOMAIN,
+            sender: H256::repeat_byte(1),
+            nonce: 0,
+            destination: LOCAL_DOMAIN,
+            recipient: addr_to_h256(recipient_addr.clone()),
+            body: "hello".as_bytes().to_vec(),
+        };
+        assert!(process(&mut app, replica_addr, nomad_message.clone()));
+
+        let count_res: reference_recipient::MessageCountResponse = app
+            .wrap()
+            .query_wasm_smart(
+                recipient_addr.clone(),
+                &reference_recipient::QueryMsg::MessageCount {},
+            )
+            .unwrap();
+        assert_eq!(1, count_res.count);
+
+        let last_res: reference_recipient::LastMessageResponse = app
+            .wrap()
+            .query_wasm_smart(
+                recipient_addr,
+                &reference_recipient::QueryMsg::LastMessage {},
+            )
+            .unwrap();
+        assert_eq!(
+            Some(reference_recipient::ReceivedMessage {
+                origin: REMOTE_DOMAIN,
+                nonce: 0,
+                sender: nomad_message.sender,
+                message: nomad_message.body,
+            }),
+            last_res.message
+        );
+    }
+
+    #[test]
+    fn rejects_message_from_unenrolled_replica() {
+        let mut app = mock_app();
+        let (replica_addr, recipient_addr) = deploy(&mut app, false);
+
+        let nomad_message = NomadMessage {
+            origin: REMOTE_DOMAIN,
+            sender: H256::repeat_byte(1),
+            nonce: 0,
+            destination: LOCAL_DOMAIN,
+            recipient: addr_to_h256(recipient_addr.clone()),
+            body: "hello".as_bytes().to_vec(),
+        };
+        assert!(!process(&mut app, replica_addr, nomad_message));
+
+        let count_res: reference_recipient::MessageCountResponse = app
+            .wrap()
+            .query_wasm_smart(
+                recipient_addr,
+                &reference_recipient::QueryMsg::MessageCount {},
+            )
+            .unwrap();
+        assert_eq!(0, count_res.count);
+    }
+
+    #[test]
+    fn rejects_message_with_spoofed_origin() {
+        let mut app = mock_app();
+        let (replica_addr, recipient_addr) = deploy(&mut app, true);
+
+        // Replica is enrolled for REMOTE_DOMAIN but message claims other origin
+        let nomad_message = NomadMessage {
+            origin: 3000,
+            sender: H256::repeat_byte(1),
+            nonce: 0,
+            destination: LOCAL_DOMAIN,
+            recipient: addr_to_h256(recipient_addr.clone()),
+            body: "hello".as_bytes().to_vec(),
+        };
+        assert!(!process(&mut app, replica_addr, nomad_message));
+
+        let count_res: reference_recipient::MessageCountResponse = app
+            .wrap()
+            .query_wasm_smart(
+                recipient_addr,
+                &reference_recipient::QueryMsg::MessageCount {},
+            )
+            .unwrap();
+        assert_eq!(0, count_res.count);
+    }
+}
@@ -2,24 +2,82 @@
 mod test {
     use common::merkle_tree::{merkle_root_from_branch, Proof};
     use common::{
-        addr_to_h256, h256_to_string, replica, test::test_replica, Encode, MessageStatus,
+        h256_to_string, home, merkle, replica, test::test_replica, Encode, MessageStatus,
         NomadMessage,
     };
     use cosmwasm_std::{from_binary, Addr};
-    use cw_multi_test::Executor;
+    use cw_multi_test::{ContractWrapper, Executor};
     use ethers_core::types::H256;
     use test_utils::Updater;
 
     use crate::utils::helpers::{
-        app_event_by_ty, instantiate_bad_recipient, instantiate_test_recipient,
-        instantiate_test_replica, mock_app,
+        addr_to_h256, app_event_by_ty, instantiate_bad_recipient, instantiate_home,
+        instantiate_test_recipient, instantiate_test_replica, mock_app, App,
     };
 
-    const CHAIN_ADDR_LENGTH_BYTES: usize = 11; // e.g. "Contract #0".len()
     const REMOTE_DOMAIN: u32 = 1000;
     const LOCAL_DOMAIN: u32 = 2000;
     const UPDATER_PRIVKEY: &str =
         "1111111111111111111111111111111111111111111111111111111111111111";
+    /// Gas consumed by `undergassed_recipient` handle
+    const HANDLE_GAS: u64 = 600_000;
+
+    /// Recipient whose handle runs out of gas when replica's process gas is
+    /// below `HANDLE_GAS`. Multi-test does not meter gas so recipient checks
+    /// process gas itself.
+    mod undergassed_recipient {
+        use common::replica;
+        use common::test::bad_recipient::{ExecuteMsg, InstantiateMsg, QueryMsg};
+        use cosmwasm_std::{
+            Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+        };
+
+        pub fn instantiate(
+            _deps: DepsMut,
+            _env: Env,
+            _info: MessageInfo,
+            _msg: InstantiateMsg,
+        ) -> StdResult<Response> {
+            Ok(Response::new())
+        }
+
+        pub fn execute(
+            deps: DepsMut,
+            _env: Env,
+            info: MessageInfo,
+            _msg: ExecuteMsg,
+        ) -> StdResult<Response> {
+            let res: replica::ProcessGasResponse = deps
+                .querier
+                .query_wasm_smart(info.sender, &replica::QueryMsg::ProcessGas {})?;
+            if res.process_gas < super::HANDLE_GAS {
+                return Err(StdError::generic_err("out of gas"));
+            }
+            Ok(Response::new())
+        }
+
+        pub fn query(_deps: Deps, _env: Env, _msg: QueryMsg) -> StdResult<Binary> {
+            Err(StdError::generic_err("no queries"))
+        }
+    }
+
+    fn instantiate_undergassed_recipient(app: &mut App, deployer: Addr) -> Addr {
+        let code_id = app.store_code(Box::new(ContractWrapper::new_with_empty(
+            undergassed_recipient::execute,
+            undergassed_recipient::instantiate,
+            undergassed_recipient::query,
+        )));
+
+        app.instantiate_contract(
+            code_id,
+            deployer,
+            &common::test::bad_recipient::InstantiateMsg {},
+            &[],
+            String::from("UNDERGASSED_RECIPIENT"),
+            None,
+        )
+        .unwrap()
+    }
 
     #[test]
     fn proves_message() {
@@ -38,7 +96,6 @@ mod test {
         let replica_addr = instantiate_test_replica(
             &mut app,
             owner.clone(),
-            CHAIN_ADDR_LENGTH_BYTES,
             LOCAL_DOMAIN,
             REMOTE_DOMAIN,
             updater.address(),
@@ -79,6 +136,79 @@ mod test {
         assert!(success);
     }
 
+    #[test]
+    fn proves_message_with_proof_from_home() {
+        let mut app = mock_app();
+
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, REMOTE_DOMAIN);
+
+        let owner = Addr::unchecked("owner");
+        let sender = Addr::unchecked(h256_to_string(H256::zero()));
+
+        // Home on remote domain storing full tree
+        let home_addr = instantiate_home(&mut app, owner.clone(), REMOTE_DOMAIN, updater.address());
+        app.execute_contract(
+            owner.clone(),
+            home_addr.clone(),
+            &home::ExecuteMsg::SetFullTreeStorage { enabled: true },
+            &[],
+        )
+        .unwrap();
+
+        for i in 0..3u8 {
+            let dispatch_msg = home::ExecuteMsg::Dispatch {
+                destination: LOCAL_DOMAIN,
+                recipient: H256::repeat_byte(1),
+                message_body: vec![i],
+            };
+            app.execute_contract(sender.clone(), home_addr.clone(), &dispatch_msg, &[])
+                .unwrap();
+        }
+
+        let root_res: merkle::RootResponse = app
+            .wrap()
+            .query_wasm_smart(home_addr.clone(), &home::QueryMsg::Root {})
+            .unwrap();
+        let proof: Proof = app
+            .wrap()
+            .query_wasm_smart(home_addr, &home::QueryMsg::Proof { leaf_index: 1 })
+            .unwrap();
+
+        let replica_addr = instantiate_test_replica(
+            &mut app,
+            owner,
+            LOCAL_DOMAIN,
+            REMOTE_DOMAIN,
+            updater.address(),
+            H256::zero(),
+            100,
+        );
+
+        let set_committed_msg = test_replica::ExecuteMsg::SetCommittedRoot {
+            root: root_res.root,
+        };
+        app.execute_contract(
+            sender.clone(),
+            replica_addr.clone(),
+            &set_committed_msg,
+            &[],
+        )
+        .unwrap();
+
+        // Proof from home accepted by replica as is
+        let msg = test_replica::ExecuteMsg::ReplicaExecuteMsg(replica::ExecuteMsg::Prove {
+            leaf: proof.leaf,
+            proof: proof.path,
+            index: proof.index as u64,
+        });
+        let res = app
+            .execute_contract(sender, replica_addr, &msg, &[])
+            .unwrap();
+        println!("\nProve with home proof: {:?}", res);
+
+        assert!(from_binary::<bool>(&res.data.unwrap()).unwrap());
+    }
+
     #[test]
     fn rejects_invalid_message_proof() {
         let mut app = mock_app();
@@ -96,7 +226,6 @@ mod test {
         let replica_addr = instantiate_test_replica(
             &mut app,
             owner.clone(),
-            CHAIN_ADDR_LENGTH_BYTES,
             LOCAL_DOMAIN,
             REMOTE_DOMAIN,
             updater.address(),
@@ -153,7 +282,6 @@ mod test {
         let replica_addr = instantiate_test_replica(
             &mut app,
             owner.clone(),
-            CHAIN_ADDR_LENGTH_BYTES,
             LOCAL_DOMAIN,
             REMOTE_DOMAIN,
             updater.address(),
@@ -162,7 +290,13 @@ mod test {
         );
 
         // Instantiate recipient
-        let recipient_addr = instantiate_test_recipient(&mut app, owner.clone());
+        let recipient_addr = instantiate_test_recipient(
+            &mut app,
+            owner.clone(),
+            replica_addr.clone(),
+            REMOTE_DOMAIN,
+            H256::zero(),
+        );
         let recipient_addr_h256 = addr_to_h256(recipient_addr.clone());
 
         let nomad_message = NomadMessage {
@@ -212,7 +346,6 @@ mod test {
         let replica_addr = instantiate_test_replica(
             &mut app,
             owner.clone(),
-            CHAIN_ADDR_LENGTH_BYTES,
             LOCAL_DOMAIN,
             REMOTE_DOMAIN,
             updater.address(),
@@ -221,7 +354,13 @@ mod test {
         );
 
         // Instantiate recipient
-        let recipient_addr = instantiate_test_recipient(&mut app, owner.clone());
+        let recipient_addr = instantiate_test_recipient(
+            &mut app,
+            owner.clone(),
+            replica_addr.clone(),
+            REMOTE_DOMAIN,
+            H256::zero(),
+        );
         let recipient_addr_h256 = addr_to_h256(recipient_addr.clone());
 
         let nomad_message = NomadMessage {
@@ -262,7 +401,6 @@ mod test {
         let replica_addr = instantiate_test_replica(
             &mut app,
             owner.clone(),
-            CHAIN_ADDR_LENGTH_BYTES,
             LOCAL_DOMAIN,
             REMOTE_DOMAIN,
             updater.address(),
@@ -271,7 +409,13 @@ mod test {
         );
 
         // Instantiate recipient
-        let recipient_addr = instantiate_test_recipient(&mut app, owner.clone());
+        let recipient_addr = instantiate_test_recipient(
+            &mut app,
+            owner.clone(),
+            replica_addr.clone(),
+            REMOTE_DOMAIN,
+            H256::zero(),
+        );
         let recipient_addr_h256 = addr_to_h256(recipient_addr.clone());
 
         let nomad_message = NomadMessage {
@@ -312,7 +456,6 @@ mod test {
         let replica_addr = instantiate_test_replica(
             &mut app,
             owner.clone(),
-            CHAIN_ADDR_LENGTH_BYTES,
             LOCAL_DOMAIN,
             REMOTE_DOMAIN,
             updater.address(),
@@ -367,7 +510,6 @@ mod test {
         let replica_addr = instantiate_test_replica(
             &mut app,
             owner.clone(),
-            CHAIN_ADDR_LENGTH_BYTES,
             LOCAL_DOMAIN,
             REMOTE_DOMAIN,
             updater.address(),
@@ -426,7 +568,6 @@ mod test {
         let replica_addr = instantiate_test_replica(
             &mut app,
             owner.clone(),
-            CHAIN_ADDR_LENGTH_BYTES,
             LOCAL_DOMAIN,
             REMOTE_DOMAIN,
             updater.address(),
@@ -435,7 +576,13 @@ mod test {
         );
 
         // Instantiate recipient
-        let recipient_addr = instantiate_test_recipient(&mut app, owner.clone());
+        let recipient_addr = instantiate_test_recipient(
+            &mut app,
+            owner.clone(),
+            replica_addr.clone(),
+            REMOTE_DOMAIN,
+            H256::zero(),
+        );
         let recipient_addr_h256 = addr_to_h256(recipient_addr.clone());
 
         // Create message
@@ -506,7 +653,6 @@ mod test {
         let replica_addr = instantiate_test_replica(
             &mut app,
             owner.clone(),
-            CHAIN_ADDR_LENGTH_BYTES,
             LOCAL_DOMAIN,
             REMOTE_DOMAIN,
             updater.address(),
@@ -515,7 +661,13 @@ mod test {
         );
 
         // Instantiate recipient
-        let recipient_addr = instantiate_test_recipient(&mut app, owner.clone());
+        let recipient_addr = instantiate_test_recipient(
+            &mut app,
+            owner.clone(),
+            replica_addr.clone(),
+            REMOTE_DOMAIN,
+            H256::zero(),
+        );
         let recipient_addr_h256 = addr_to_h256(recipient_addr.clone());
 
         // Create message
@@ -557,5 +709,214 @@ mod test {
             .contains("Failed to prove message"));
     }
 
-    // TODO: undergased process test case
+    #[test]
+    fn processes_undergassed_message_and_retries() {
+        let mut app = mock_app();
+
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+
+        let sender_string = h256_to_string(H256::zero());
+
+        let owner = Addr::unchecked("owner");
+        let sender = Addr::unchecked(&sender_string);
+        let committed_root = H256::zero();
+        let optimistic_seconds = 100;
+
+        // Instantiate replica
+        let replica_addr = instantiate_test_replica(
+            &mut app,
+            owner.clone(),
+            LOCAL_DOMAIN,
+            REMOTE_DOMAIN,
+            updater.address(),
+            committed_root,
+            optimistic_seconds,
+        );
+
+        // Instantiate recipient needing more gas than replica gives it
+        let recipient_addr = instantiate_undergassed_recipient(&mut app, owner.clone());
+        let recipient_addr_h256 = addr_to_h256(recipient_addr);
+
+        let set_gas_msg =
+            test_replica::ExecuteMsg::ReplicaExecuteMsg(replica::ExecuteMsg::SetProcessGas {
+                process_gas: HANDLE_GAS - 1,
+            });
+        app.execute_contract(owner.clone(), replica_addr.clone(), &set_gas_msg, &[])
+            .unwrap();
+
+        let nomad_message = NomadMessage {
+            origin: REMOTE_DOMAIN,
+            sender: H256::zero(),
+            nonce: 0,
+            destination: LOCAL_DOMAIN,
+            recipient: recipient_addr_h256,
+            body: "0x".as_bytes().to_vec(),
+        };
+        let leaf = nomad_message.to_leaf();
+
+        // Prove message
+        let prove_msg = test_replica::ExecuteMsg::SetProven { leaf };
+        app.execute_contract(sender.clone(), replica_addr.clone(), &prove_msg, &[])
+            .unwrap();
+
+        // Execute process message
+        let msg = test_replica::ExecuteMsg::ReplicaExecuteMsg(replica::ExecuteMsg::Process {
+            message: nomad_message.to_vec(),
+        });
+        let res = app
+            .execute_contract(sender.clone(), replica_addr.clone(), &msg, &[])
+            .unwrap();
+        println!("\nProcess (undergassed): {:?}", res);
+
+        // Assert call completed but delivery failed
+        let success = from_binary::<bool>(&res.data.clone().unwrap()).unwrap();
+        assert!(!success);
+        assert!(app_event_by_ty(&res, "wasm-ProcessFailed").is_some());
+
+        // Message marked failed with error recorded
+        let query_msg = replica::QueryMsg::MessageStatus { leaf };
+        let message_status_res: replica::MessageStatusResponse = app
+            .wrap()
+            .query_wasm_smart(replica_addr.clone(), &query_msg)
+            .unwrap();
+        match message_status_res.status {
+            MessageStatus::Failed(error) => assert!(error.contains("out of gas")),
+            status => panic!("Expected failed status, got {:?}", status),
+        }
+
+        // Failed message cannot be processed again
+        let res = app.execute_contract(sender.clone(), replica_addr.clone(), &msg, &[]);
+        assert!(res.is_err());
+
+        // Owner raises process gas and message is retried
+        let set_gas_msg =
+            test_replica::ExecuteMsg::ReplicaExecuteMsg(replica::ExecuteMsg::SetProcessGas {
+                process_gas: HANDLE_GAS,
+            });
+        app.execute_contract(owner, replica_addr.clone(), &set_gas_msg, &[])
+            .unwrap();
+
+        let retry_msg =
+            test_replica::ExecuteMsg::ReplicaExecuteMsg(replica::ExecuteMsg::RetryProcess {
+                message: nomad_message.to_vec(),
+            });
+        let res = app
+            .execute_contract(sender, replica_addr.clone(), &retry_msg, &[])
+            .unwrap();
+        println!("\nRetry process: {:?}", res);
+
+        let success = from_binary::<bool>(&res.data.clone().unwrap()).unwrap();
+        assert!(success);
+
+        let message_status_res: replica::MessageStatusResponse = app
+            .wrap()
+            .query_wasm_smart(replica_addr, &query_msg)
+            .unwrap();
+        assert_eq!(MessageStatus::Processed, message_status_res.status);
+    }
+
+    #[test]
+    fn batch_keeps_item_results_after_replies() {
+        let mut app = mock_app();
+
+        let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
+
+        let sender_string = h256_to_string(H256::zero());
+
+        let owner = Addr::unchecked("owner");
+        let sender = Addr::unchecked(&sender_string);
+
+        // Instantiate replica
+        let replica_addr = instantiate_test_replica(
+            &mut app,
+            owner.clone(),
+            LOCAL_DOMAIN,
+            REMOTE_DOMAIN,
+            updater.address(),
+            H256::zero(),
+            100,
+        );
+
+        // One recipient handles messages, the other always errors
+        let recipient_addr = instantiate_test_recipient(
+            &mut app,
+            owner.clone(),
+            replica_addr.clone(),
+            REMOTE_DOMAIN,
+            H256::zero(),
+        );
+        let bad_recipient_addr = instantiate_bad_recipient(&mut app, owner);
+
+        let messages: Vec<NomadMessage> = [recipient_addr, bad_recipient_addr]
+            .into_iter()
+            .enumerate()
+            .map(|(nonce, recipient)| NomadMessage {
+                origin: REMOTE_DOMAIN,
+                sender: H256::zero(),
+                nonce: nonce as u32,
+                destination: LOCAL_DOMAIN,
+                recipient: addr_to_h256(recipient),
+                body: "0x".as_bytes().to_vec(),
+            })
+            .collect();
+
+        // Prove messages
+        for message in &messages {
+            let prove_msg = test_replica::ExecuteMsg::SetProven {
+                leaf: message.to_leaf(),
+            };
+            app.execute_contract(sender.clone(), replica_addr.clone(), &prove_msg, &[])
+                .unwrap();
+        }
+
+        let mut items: Vec<replica::BatchItem> = messages
+            .iter()
+            .map(|message| replica::BatchItem {
+                message: message.to_vec(),
+                proof: vec![],
+                index: 0,
+            })
+            .collect();
+        items.push(replica::BatchItem {
+            message: vec![1, 2, 3],
+            proof: vec![],
+            index: 0,
+        });
+
+        let msg = test_replica::ExecuteMsg::ReplicaExecuteMsg(
+            replica::ExecuteMsg::BatchProveAndProcess { items },
+        );
+        let res = app
+            .execute_contract(sender.clone(), replica_addr.clone(), &msg, &[])
+            .unwrap();
+        println!("\nBatch prove and process: {:?}", res);
+
+        // Replies do not replace batch results
+        let results =
+            from_binary::<replica::BatchProveAndProcessResponse>(&res.data.clone().unwrap())
+                .unwrap()
+                .results;
+        let dispatched: Vec<bool> = results.iter().map(|result| result.dispatched).collect();
+        assert_eq!(vec![true, true, false], dispatched);
+
+        // Delivery outcomes reported as events
+        assert!(app_event_by_ty(&res, "wasm-ProcessSucceeded").is_some());
+        assert!(app_event_by_ty(&res, "wasm-ProcessFailed").is_some());
+
+        let statuses: Vec<MessageStatus> = messages
+            .iter()
+            .map(|message| {
+                let query_msg = replica::QueryMsg::MessageStatus {
+                    leaf: message.to_leaf(),
+                };
+                let message_status_res: replica::MessageStatusResponse = app
+                    .wrap()
+                    .query_wasm_smart(replica_addr.clone(), &query_msg)
+                    .unwrap();
+                message_status_res.status
+            })
+            .collect();
+        assert_eq!(MessageStatus::Processed, statuses[0]);
+        assert!(matches!(statuses[1], MessageStatus::Failed(_)));
+    }
 }
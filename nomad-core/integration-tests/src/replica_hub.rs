@@ -0,0 +1,202 @@
+#[cfg(test)]
+mod test {
+    use common::{
+        connection_manager, merkle_tree, reference_recipient, replica_hub, Encode, NomadMessage,
+    };
+    use cosmwasm_std::{from_binary, Addr};
+    use cw_multi_test::Executor;
+    use ethers_core::types::H256;
+    use test_utils::{Updater, Watcher};
+
+    use crate::utils::helpers::{
+        addr_to_h256, instantiate_connection_manager, instantiate_reference_recipient,
+        instantiate_replica_hub, mock_app, App,
+    };
+
+    const LOCAL_DOMAIN: u32 = 3000;
+    const DOMAIN_A: u32 = 1000;
+    const DOMAIN_B: u32 = 2000;
+    const UPDATER_A_PRIVKEY: &str =
+        "1111111111111111111111111111111111111111111111111111111111111111";
+    const UPDATER_B_PRIVKEY: &str =
+        "2111111111111111111111111111111111111111111111111111111111111111";
+    const WATCHER_PRIVKEY: &str =
+        "3111111111111111111111111111111111111111111111111111111111111111";
+
+    /// Instantiate connection manager, hub serving both domains and
+    /// recipient. Hub is enrolled for `enrolled` domains.
+    fn deploy(app: &mut App, enrolled: &[u32]) -> (Addr, Addr, Addr) {
+        let owner = Addr::unchecked("owner");
+
+        let remotes = [(DOMAIN_A, UPDATER_A_PRIVKEY), (DOMAIN_B, UPDATER_B_PRIVKEY)]
+            .iter()
+            .map(|(domain, privkey)| replica_hub::RemoteHome {
+                domain: *domain,
+                updaters: vec![Updater::from_privkey(privkey, *domain).signer_id()],
+                threshold: 1,
+                committed_root: H256::zero(),
+                optimistic_seconds: 100,
+            })
+            .collect();
+
+        let connection_manager_addr = instantiate_connection_manager(app, owner.clone());
+        let hub_addr = instantiate_replica_hub(app, owner.clone(), LOCAL_DOMAIN, remotes);
+        let recipient_addr =
+            instantiate_reference_recipient(app, owner.clone(), connection_manager_addr.clone());
+
+        for domain in enrolled {
+            let enroll_msg = connection_manager::ExecuteMsg::OwnerEnrollHub {
+                domain: *domain,
+                hub: hub_addr.to_string(),
+            };
+            app.execute_contract(
+                owner.clone(),
+                connection_manager_addr.clone(),
+                &enroll_msg,
+                &[],
+            )
+            .unwrap();
+        }
+
+        (connection_manager_addr, hub_addr, recipient_addr)
+    }
+
+    /// Confirm root containing message as only leaf on message's origin, then
+    /// prove and process. Returns success flag.
+    fn deliver(app: &mut App, hub_addr: Addr, nomad_message: NomadMessage) -> bool {
+        let owner = Addr::unchecked("owner");
+        let proof = [H256::zero(); 32];
+        let root = merkle_tree::merkle_root_from_branch(
+            nomad_message.to_leaf(),
+            &proof[..],
+            merkle_tree::TREE_DEPTH,
+            0,
+        );
+
+        let confirm_msg = replica_hub::ExecuteMsg::SetConfirmation {
+            domain: nomad_message.origin,
+            root,
+            confirm_at: 1,
+        };
+        app.execute_contract(owner.clone(), hub_addr.clone(), &confirm_msg, &[])
+            .unwrap();
+
+        let msg = replica_hub::ExecuteMsg::ProveAndProcess {
+            message: nomad_message.to_vec(),
+            proof,
+            index: 0,
+        };
+        let res = app.execute_contract(owner, hub_addr, &msg, &[]).unwrap();
+        println!("\nProve and process: {:?}", res);
+
+        from_binary::<bool>(&res.data.as_ref().unwrap()).unwrap()
+    }
+
+    fn message_count(app: &App, recipient_addr: Addr) -> u64 {
+        let res: reference_recipient::MessageCountResponse = app
+            .wrap()
+            .query_wasm_smart(
+                recipient_addr,
+                &reference_recipient::QueryMsg::MessageCount {},
+            )
+            .unwrap();
+        res.count
+    }
+
+    #[test]
+    fn delivers_messages_from_many_origins() {
+        let mut app = mock_app();
+        let (_, hub_addr, recipient_addr) = deploy(&mut app, &[DOMAIN_A, DOMAIN_B]);
+
+        for (count, origin) in [DOMAIN_A, DOMAIN_B].iter().enumerate() {
+            let nomad_message = NomadMessage {
+                origin: *origin,
+                sender: H256::repeat_byte(1),
+                nonce: 0,
+                destination: LOCAL_DOMAIN,
+                recipient: addr_to_h256(recipient_addr.clone()),
+                body: "hello".as_bytes().to_vec(),
+            };
+            assert!(deliver(&mut app, hub_addr.clone(), nomad_message));
+            assert_eq!(
+                count as u64 + 1,
+                message_count(&app, recipient_addr.clone())
+            );
+
+            let last_res: reference_recipient::LastMessageResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    recipient_addr.clone(),
+                    &reference_recipient::QueryMsg::LastMessage {},
+                )
+                .unwrap();
+            assert_eq!(*origin, last_res.message.unwrap().origin);
+        }
+    }
+
+    #[test]
+    fn rejects_origin_hub_not_enrolled_for() {
+        let mut app = mock_app();
+        let (_, hub_addr, recipient_addr) = deploy(&mut app, &[DOMAIN_A]);
+
+        // Hub proves message but recipient rejects unenrolled origin
+        let nomad_message = NomadMessage {
+            origin: DOMAIN_B,
+            sender: H256::repeat_byte(1),
+            nonce: 0,
+            destination: LOCAL_DOMAIN,
+            recipient: addr_to_h256(recipient_addr.clone()),
+            body: "hello".as_bytes().to_vec(),
+        };
+        assert!(!deliver(&mut app, hub_addr, nomad_message));
+        assert_eq!(0, message_count(&app, recipient_addr));
+    }
+
+    #[tokio::test]
+    async fn unenrolls_hub_domain_on_watcher_signed_failure() {
+        let mut app = mock_app();
+        let owner = Addr::unchecked("owner");
+        let (connection_manager_addr, hub_addr, _) = deploy(&mut app, &[DOMAIN_A, DOMAIN_B]);
+
+        let watcher = Watcher::from_privkey(WATCHER_PRIVKEY, DOMAIN_A);
+        let set_permission_msg = connection_manager::ExecuteMsg::SetWatcherPermission {
+            watcher: watcher.signer_id(),
+            domain: DOMAIN_A,
+            access: true,
+        };
+        app.execute_contract(
+            owner.clone(),
+            connection_manager_addr.clone(),
+            &set_permission_msg,
+            &[],
+        )
+        .unwrap();
+
+        // Watcher reports domain A's updater
+        let updater = H256::from(Updater::from_privkey(UPDATER_A_PRIVKEY, DOMAIN_A).address());
+        let signed_failure = watcher.sign_failure_notification(updater).await.unwrap();
+        let unenroll_msg = connection_manager::ExecuteMsg::UnenrollReplica {
+            domain: DOMAIN_A,
+            updater,
+            signature: signed_failure.signature.to_vec(),
+            watcher: None,
+        };
+        app.execute_contract(owner, connection_manager_addr.clone(), &unenroll_msg, &[])
+            .unwrap();
+
+        // Only domain A is cut off from hub
+        for (domain, replica) in [
+            (DOMAIN_A, "0x0".to_owned()),
+            (DOMAIN_B, hub_addr.to_string()),
+        ] {
+            let res: connection_manager::DomainToReplicaResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    connection_manager_addr.clone(),
+                    &connection_manager::QueryMsg::DomainToReplica { domain },
+                )
+                .unwrap();
+            assert_eq!(replica, res.replica);
+        }
+    }
+}
@@ -1,19 +1,20 @@
 #[cfg(test)]
 mod test {
-    use common::{home, nomad_base, updater_manager};
-    use cosmwasm_std::Addr;
+    use common::{home, nomad_base, updater_manager, SignerId};
+    use cosmwasm_std::{coins, Addr};
     use cw_multi_test::Executor;
-    use ethers_core::types::H160;
     use test_utils::Updater;
 
-    use crate::utils::helpers::{instantiate_home, instantiate_updater_manager, mock_app};
+    use crate::utils::helpers::{
+        instantiate_home, instantiate_updater_manager, mock_app, BOND_AMOUNT, BOND_DENOM,
+    };
 
     const LOCAL_DOMAIN: u32 = 1000;
     const UPDATER_PRIVKEY: &str =
         "1111111111111111111111111111111111111111111111111111111111111111";
 
     #[test]
-    fn updater_manager_calls_home_set_updater() {
+    fn updater_manager_calls_home_set_updaters() {
         let mut app = mock_app();
 
         let updater: Updater = Updater::from_privkey(UPDATER_PRIVKEY, LOCAL_DOMAIN);
@@ -52,30 +53,91 @@ mod test {
         )
         .unwrap();
 
-        // Execute updater_manager::set_updater
-        let new_updater = H160::repeat_byte(5);
-        let set_updater_msg = updater_manager::ExecuteMsg::SetUpdater {
-            updater: new_updater,
+        // Add new updater to set
+        let new_updater = SignerId::Ed25519PubKey(vec![5u8; 32].into());
+        let add_updater_msg = updater_manager::ExecuteMsg::AddUpdater {
+            updater: new_updater.clone(),
         };
         let res = app
-            .execute_contract(owner, updater_manager_addr.clone(), &set_updater_msg, &[])
+            .execute_contract(
+                owner.clone(),
+                updater_manager_addr.clone(),
+                &add_updater_msg,
+                &coins(BOND_AMOUNT, BOND_DENOM),
+            )
             .unwrap();
-        println!("Set Updater: {:?}", res);
+        println!("Add Updater: {:?}", res);
 
-        // Check updater manager updater is new_updater
-        let updater_manager_updater_res: updater_manager::UpdaterResponse = app
+        // Check home updater set includes new_updater
+        let home_updaters_res: nomad_base::UpdatersResponse = app
             .wrap()
-            .query_wasm_smart(updater_manager_addr, &updater_manager::QueryMsg::Updater {})
+            .query_wasm_smart(home_addr.clone(), &home::QueryMsg::Updaters {})
             .unwrap();
-        let updater_manager_updater = updater_manager_updater_res.updater;
-        assert_eq!(new_updater, updater_manager_updater);
+        assert_eq!(
+            vec![updater.signer_id(), new_updater.clone()],
+            home_updaters_res.updaters
+        );
 
-        // Check home updater is now new_updater
-        let home_updater_res: nomad_base::UpdaterResponse = app
+        // Remove original updater, refunding its bond to owner
+        let balance_before = app.wrap().query_balance(&owner, BOND_DENOM).unwrap();
+        let remove_updater_msg = updater_manager::ExecuteMsg::RemoveUpdater {
+            updater: updater.signer_id(),
+        };
+        let res = app
+            .execute_contract(
+                owner.clone(),
+                updater_manager_addr.clone(),
+                &remove_updater_msg,
+                &[],
+            )
+            .unwrap();
+        println!("Remove Updater: {:?}", res);
+
+        let balance_after = app.wrap().query_balance(&owner, BOND_DENOM).unwrap();
+        assert_eq!(
+            balance_before.amount.u128() + BOND_AMOUNT,
+            balance_after.amount.u128()
+        );
+
+        // Check new bond posted and previous bond refunded
+        let bond_res: updater_manager::BondResponse = app
+            .wrap()
+            .query_wasm_smart(
+                updater_manager_addr.clone(),
+                &updater_manager::QueryMsg::Bond {
+                    updater: new_updater.clone(),
+                },
+            )
+            .unwrap();
+        assert_eq!(new_updater, bond_res.updater);
+        assert_eq!(BOND_AMOUNT, bond_res.amount.u128());
+
+        let manager_balance = app
+            .wrap()
+            .query_balance(&updater_manager_addr, BOND_DENOM)
+            .unwrap();
+        assert_eq!(BOND_AMOUNT, manager_balance.amount.u128());
+
+        // Check updater manager updaters is [new_updater]
+        let updater_manager_updaters_res: updater_manager::UpdatersResponse = app
+            .wrap()
+            .query_wasm_smart(
+                updater_manager_addr,
+                &updater_manager::QueryMsg::Updaters {},
+            )
+            .unwrap();
+        assert_eq!(
+            vec![new_updater.clone()],
+            updater_manager_updaters_res.updaters
+        );
+        assert_eq!(1, updater_manager_updaters_res.threshold);
+
+        // Check home updaters is now [new_updater]
+        let home_updaters_res: nomad_base::UpdatersResponse = app
             .wrap()
-            .query_wasm_smart(home_addr, &home::QueryMsg::Updater {})
+            .query_wasm_smart(home_addr, &home::QueryMsg::Updaters {})
             .unwrap();
-        let home_updater = home_updater_res.updater;
-        assert_eq!(new_updater, home_updater);
+        assert_eq!(vec![new_updater], home_updaters_res.updaters);
+        assert_eq!(1, home_updaters_res.threshold);
     }
 }
@@ -1,13 +1,22 @@
 #[cfg(test)]
 pub mod helpers {
-    use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
-    use cosmwasm_std::{Addr, Event};
-    use cw_multi_test::{App, AppBuilder, AppResponse, BankKeeper, ContractWrapper, Executor};
+    use cosmwasm_std::testing::{mock_env, MockStorage};
+    use cosmwasm_std::{coins, Addr, Event, Uint128};
+    use cw_multi_test::{AppBuilder, AppResponse, BankKeeper, ContractWrapper, Executor};
     use ethers_core::types::{H160, H256};
+    use test_utils::PaddedMockApi;
+
+    pub(crate) const BOND_DENOM: &str = "earth";
+    pub(crate) const BOND_AMOUNT: u128 = 100;
+    pub(crate) const REPORTER_SHARE_BPS: u64 = 5_000;
+    const OWNER_BALANCE: u128 = 10_000;
+
+    /// App whose api fits canonical addresses in an H256
+    pub(crate) type App = cw_multi_test::App<BankKeeper, PaddedMockApi>;
 
     pub(crate) fn mock_app() -> App {
         let env = mock_env();
-        let api = MockApi::default();
+        let api = PaddedMockApi::default();
         let bank = BankKeeper::new();
         let storage = MockStorage::new();
 
@@ -16,7 +25,16 @@ pub mod helpers {
             .with_block(env.block)
             .with_bank(bank)
             .with_storage(storage)
-            .build(|_, _, _| {})
+            .build(|router, _, storage| {
+                router
+                    .bank
+                    .init_balance(
+                        storage,
+                        &Addr::unchecked("owner"),
+                        coins(OWNER_BALANCE, BOND_DENOM),
+                    )
+                    .unwrap();
+            })
     }
 
     pub(crate) fn instantiate_home(
@@ -29,17 +47,24 @@ pub mod helpers {
 
         let init_msg = common::home::InstantiateMsg {
             local_domain,
-            updater,
+            updaters: vec![updater.into()],
+            threshold: 1,
         };
 
-        app.instantiate_contract(code_id, owner, &init_msg, &[], String::from("HOME"), None)
-            .unwrap()
+        app.instantiate_contract(
+            code_id,
+            owner.clone(),
+            &init_msg,
+            &[],
+            String::from("HOME"),
+            Some(owner.to_string()),
+        )
+        .unwrap()
     }
 
     pub(crate) fn instantiate_test_replica(
         app: &mut App,
         owner: Addr,
-        chain_addr_length_bytes: usize,
         local_domain: u32,
         remote_domain: u32,
         updater: H160,
@@ -48,71 +73,151 @@ pub mod helpers {
     ) -> Addr {
         let code_id = store_test_replica_code(app);
         let init_msg = common::replica::InstantiateMsg {
-            chain_addr_length_bytes,
             local_domain,
             remote_domain,
-            updater,
+            updaters: vec![updater.into()],
+            threshold: 1,
             committed_root,
             optimistic_seconds,
+            root_verifier: None,
         };
 
         app.instantiate_contract(
             code_id,
-            owner,
+            owner.clone(),
             &init_msg,
             &[],
             String::from("test_replica"),
-            None,
+            Some(owner.to_string()),
+        )
+        .unwrap()
+    }
+
+    pub(crate) fn instantiate_replica_hub(
+        app: &mut App,
+        owner: Addr,
+        local_domain: u32,
+        remotes: Vec<common::replica_hub::RemoteHome>,
+    ) -> Addr {
+        let code_id = store_replica_hub_code(app);
+        let init_msg = common::replica_hub::InstantiateMsg {
+            local_domain,
+            remotes,
+        };
+
+        app.instantiate_contract(
+            code_id,
+            owner.clone(),
+            &init_msg,
+            &[],
+            String::from("REPLICA_HUB"),
+            Some(owner.to_string()),
         )
         .unwrap()
     }
 
     pub(crate) fn instantiate_updater_manager(app: &mut App, owner: Addr, updater: H160) -> Addr {
         let code_id = store_updater_manager_code(app);
-        let init_msg = common::updater_manager::InstantiateMsg { updater };
+        let init_msg = common::updater_manager::InstantiateMsg {
+            updaters: vec![updater.into()],
+            threshold: 1,
+            bond_denom: BOND_DENOM.to_owned(),
+            bond_amount: Uint128::new(BOND_AMOUNT),
+            reporter_share_bps: REPORTER_SHARE_BPS,
+            treasury: None,
+        };
 
         app.instantiate_contract(
             code_id,
-            owner,
+            owner.clone(),
             &init_msg,
-            &[],
+            &coins(BOND_AMOUNT, BOND_DENOM),
             String::from("UPDATER_MANAGER"),
-            None,
+            Some(owner.to_string()),
         )
         .unwrap()
     }
 
-    pub(crate) fn instantiate_connection_manager(
-        app: &mut App,
-        owner: Addr,
-        chain_addr_length_bytes: usize,
-    ) -> Addr {
+    pub(crate) fn instantiate_connection_manager(app: &mut App, owner: Addr) -> Addr {
         let code_id = store_connection_manager_code(app);
-        let init_msg = common::connection_manager::InstantiateMsg {
-            chain_addr_length_bytes,
-        };
+        let init_msg = common::connection_manager::InstantiateMsg {};
 
         app.instantiate_contract(
             code_id,
-            owner,
+            owner.clone(),
             &init_msg,
             &[],
             String::from("CONNECTION_MANAGER"),
-            None,
+            Some(owner.to_string()),
         )
         .unwrap()
     }
 
-    pub(crate) fn instantiate_test_recipient(app: &mut App, deployer: Addr) -> Addr {
+    /// Instantiate test recipient accepting messages from `remote_router`
+    /// delivered by `replica`
+    pub(crate) fn instantiate_test_recipient(
+        app: &mut App,
+        deployer: Addr,
+        replica: Addr,
+        remote_domain: u32,
+        remote_router: H256,
+    ) -> Addr {
+        let connection_manager = instantiate_connection_manager(app, deployer.clone());
+        let enroll_msg = common::connection_manager::ExecuteMsg::OwnerEnrollReplica {
+            domain: remote_domain,
+            replica: replica.to_string(),
+        };
+        app.execute_contract(
+            deployer.clone(),
+            connection_manager.clone(),
+            &enroll_msg,
+            &[],
+        )
+        .unwrap();
+
         let code_id = store_test_recipient_code(app);
-        let init_msg = common::test::test_recipient::InstantiateMsg {};
+        let init_msg = common::test::test_recipient::InstantiateMsg {
+            home: "home".to_owned(),
+            connection_manager: connection_manager.to_string(),
+        };
+
+        let recipient = app
+            .instantiate_contract(
+                code_id,
+                deployer.clone(),
+                &init_msg,
+                &[],
+                String::from("RECIPIENT"),
+                None,
+            )
+            .unwrap();
+
+        let enroll_msg = common::test::test_recipient::ExecuteMsg::EnrollRemoteRouter {
+            domain: remote_domain,
+            router: remote_router,
+        };
+        app.execute_contract(deployer, recipient.clone(), &enroll_msg, &[])
+            .unwrap();
+
+        recipient
+    }
+
+    pub(crate) fn instantiate_reference_recipient(
+        app: &mut App,
+        deployer: Addr,
+        connection_manager: Addr,
+    ) -> Addr {
+        let code_id = store_reference_recipient_code(app);
+        let init_msg = common::reference_recipient::InstantiateMsg {
+            connection_manager: connection_manager.to_string(),
+        };
 
         app.instantiate_contract(
             code_id,
             deployer,
             &init_msg,
             &[],
-            String::from("RECIPIENT"),
+            String::from("REFERENCE_RECIPIENT"),
             None,
         )
         .unwrap()
@@ -120,7 +225,7 @@ pub mod helpers {
 
     pub(crate) fn instantiate_bad_recipient(app: &mut App, deployer: Addr) -> Addr {
         let code_id = store_bad_recipient_code(app);
-        let init_msg = common::test::test_recipient::InstantiateMsg {};
+        let init_msg = common::test::bad_recipient::InstantiateMsg {};
 
         app.instantiate_contract(
             code_id,
@@ -133,6 +238,62 @@ pub mod helpers {
         .unwrap()
     }
 
+    pub(crate) fn instantiate_governance_router(
+        app: &mut App,
+        owner: Addr,
+        local_domain: u32,
+        home: Addr,
+        connection_manager: Addr,
+        governor_domain: u32,
+        governor: Option<Addr>,
+    ) -> Addr {
+        let code_id = store_governance_router_code(app);
+        let init_msg = common::governance_router::InstantiateMsg {
+            local_domain,
+            home: home.to_string(),
+            connection_manager: connection_manager.to_string(),
+            governor_domain,
+            governor: governor.map(|governor| governor.to_string()),
+        };
+
+        app.instantiate_contract(
+            code_id,
+            owner,
+            &init_msg,
+            &[],
+            String::from("GOVERNANCE_ROUTER"),
+            None,
+        )
+        .unwrap()
+    }
+
+    pub(crate) fn instantiate_bridge_router(
+        app: &mut App,
+        owner: Addr,
+        local_domain: u32,
+        home: Addr,
+        connection_manager: Addr,
+    ) -> Addr {
+        let code_id = store_bridge_router_code(app);
+        let token_code_id = store_cw20_code(app);
+        let init_msg = common::bridge_router::InstantiateMsg {
+            local_domain,
+            home: home.to_string(),
+            connection_manager: connection_manager.to_string(),
+            token_code_id,
+        };
+
+        app.instantiate_contract(
+            code_id,
+            owner,
+            &init_msg,
+            &[],
+            String::from("BRIDGE_ROUTER"),
+            None,
+        )
+        .unwrap()
+    }
+
     pub(crate) fn store_home_code(app: &mut App) -> u64 {
         let home_contract = Box::new(
             ContractWrapper::new_with_empty(
@@ -140,7 +301,8 @@ pub mod helpers {
                 home::contract::instantiate,
                 home::contract::query,
             )
-            .with_reply(home::contract::reply),
+            .with_reply(home::contract::reply)
+            .with_migrate(home::contract::migrate),
         );
 
         app.store_code(home_contract)
@@ -153,12 +315,27 @@ pub mod helpers {
                 test_replica::contract::instantiate,
                 test_replica::contract::query,
             )
-            .with_reply(test_replica::contract::reply),
+            .with_reply(test_replica::contract::reply)
+            .with_migrate(test_replica::contract::migrate),
         );
 
         app.store_code(test_replica_contract)
     }
 
+    pub(crate) fn store_replica_hub_code(app: &mut App) -> u64 {
+        let replica_hub_contract = Box::new(
+            ContractWrapper::new_with_empty(
+                replica_hub::contract::execute,
+                replica_hub::contract::instantiate,
+                replica_hub::contract::query,
+            )
+            .with_reply(replica_hub::contract::reply)
+            .with_migrate(replica_hub::contract::migrate),
+        );
+
+        app.store_code(replica_hub_contract)
+    }
+
     pub(crate) fn store_updater_manager_code(app: &mut App) -> u64 {
         let updater_manager_contract = Box::new(
             ContractWrapper::new_with_empty(
@@ -166,18 +343,22 @@ pub mod helpers {
                 updater_manager::contract::instantiate,
                 updater_manager::contract::query,
             )
-            .with_reply(updater_manager::contract::reply),
+            .with_reply(updater_manager::contract::reply)
+            .with_migrate(updater_manager::contract::migrate),
         );
 
         app.store_code(updater_manager_contract)
     }
 
     pub(crate) fn store_connection_manager_code(app: &mut App) -> u64 {
-        let connection_manager_contract = Box::new(ContractWrapper::new_with_empty(
-            connection_manager::contract::execute,
-            connection_manager::contract::instantiate,
-            connection_manager::contract::query,
-        ));
+        let connection_manager_contract = Box::new(
+            ContractWrapper::new_with_empty(
+                connection_manager::contract::execute,
+                connection_manager::contract::instantiate,
+                connection_manager::contract::query,
+            )
+            .with_migrate(connection_manager::contract::migrate),
+        );
 
         app.store_code(connection_manager_contract)
     }
@@ -192,6 +373,16 @@ pub mod helpers {
         app.store_code(test_recipient_contract)
     }
 
+    pub(crate) fn store_reference_recipient_code(app: &mut App) -> u64 {
+        let reference_recipient_contract = Box::new(ContractWrapper::new_with_empty(
+            reference_recipient::contract::execute,
+            reference_recipient::contract::instantiate,
+            reference_recipient::contract::query,
+        ));
+
+        app.store_code(reference_recipient_contract)
+    }
+
     pub(crate) fn store_bad_recipient_code(app: &mut App) -> u64 {
         let bad_recipient_contract = Box::new(ContractWrapper::new_with_empty(
             bad_recipient::contract::execute,
@@ -202,6 +393,44 @@ pub mod helpers {
         app.store_code(bad_recipient_contract)
     }
 
+    pub(crate) fn store_governance_router_code(app: &mut App) -> u64 {
+        let governance_router_contract = Box::new(ContractWrapper::new_with_empty(
+            governance_router::contract::execute,
+            governance_router::contract::instantiate,
+            governance_router::contract::query,
+        ));
+
+        app.store_code(governance_router_contract)
+    }
+
+    pub(crate) fn store_bridge_router_code(app: &mut App) -> u64 {
+        let bridge_router_contract = Box::new(
+            ContractWrapper::new_with_empty(
+                bridge_router::contract::execute,
+                bridge_router::contract::instantiate,
+                bridge_router::contract::query,
+            )
+            .with_reply(bridge_router::contract::reply),
+        );
+
+        app.store_code(bridge_router_contract)
+    }
+
+    pub(crate) fn store_cw20_code(app: &mut App) -> u64 {
+        let cw20_contract = Box::new(ContractWrapper::new_with_empty(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        ));
+
+        app.store_code(cw20_contract)
+    }
+
+    /// Encode address as home does under `PaddedMockApi`
+    pub(crate) fn addr_to_h256(address: Addr) -> H256 {
+        common::addr_to_h256(&PaddedMockApi::default(), &address).unwrap()
+    }
+
     pub fn app_event_by_ty(res: &AppResponse, ty: &str) -> Option<Event> {
         res.events
             .iter()
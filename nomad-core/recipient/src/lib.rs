@@ -0,0 +1,176 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, MessageInfo, StdError, StdResult};
+use cw_storage_plus::Item;
+use thiserror::Error;
+
+use common::connection_manager::{
+    self, DomainToReplicaResponse, HomeResponse, LocalDomainResponse, ReplicaToDomainResponse,
+};
+
+/// Connection manager used to authenticate replicas
+pub const CONNECTION_MANAGER: Item<Addr> = Item::new("nomad_recipient_connection_manager");
+
+/// Error types for message recipients
+#[derive(Error, Debug)]
+pub enum RecipientError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Caller {address} is not an enrolled replica")]
+    NotReplica { address: String },
+
+    #[error("Message origin {origin} does not match replica domain {domain}")]
+    ReplicaOriginMismatch { origin: u32, domain: u32 },
+}
+
+pub fn set_connection_manager(deps: DepsMut, connection_manager: &str) -> StdResult<Addr> {
+    let connection_manager_addr = deps.api.addr_validate(connection_manager)?;
+    CONNECTION_MANAGER.save(deps.storage, &connection_manager_addr)?;
+    Ok(connection_manager_addr)
+}
+
+/// Checks that caller is a replica enrolled in the stored connection manager
+/// and that the claimed `origin` is that replica's remote domain
+pub fn only_replica(deps: Deps, info: &MessageInfo, origin: u32) -> Result<(), RecipientError> {
+    let connection_manager = CONNECTION_MANAGER.load(deps.storage)?;
+    only_enrolled_replica(deps, &connection_manager, &info.sender, origin)
+}
+
+/// Checks that `replica` is enrolled in `connection_manager` for `origin`.
+/// Replica hubs may be enrolled for several origins. Home delivers messages
+/// dispatched to its own domain, so it is accepted when `origin` is the local
+/// domain.
+pub fn only_enrolled_replica(
+    deps: Deps,
+    connection_manager: &Addr,
+    replica: &Addr,
+    origin: u32,
+) -> Result<(), RecipientError> {
+    let domain_to_replica_resp: DomainToReplicaResponse = deps.querier.query_wasm_smart(
+        connection_manager,
+        &connection_manager::QueryMsg::DomainToReplica { domain: origin },
+    )?;
+    if domain_to_replica_resp.replica == replica.as_str() {
+        return Ok(());
+    }
+
+    let home_resp: HomeResponse = deps
+        .querier
+        .query_wasm_smart(connection_manager, &connection_manager::QueryMsg::Home {})?;
+    if home_resp.home == replica.as_str() {
+        let local_domain_resp: LocalDomainResponse = deps.querier.query_wasm_smart(
+            connection_manager,
+            &connection_manager::QueryMsg::LocalDomain {},
+        )?;
+        if origin == local_domain_resp.local_domain {
+            return Ok(());
+        }
+    }
+
+    // Not enrolled for origin, find out why
+    let replica_to_domain_resp: ReplicaToDomainResponse = deps.querier.query_wasm_smart(
+        connection_manager,
+        &connection_manager::QueryMsg::ReplicaToDomain {
+            replica: replica.to_string(),
+        },
+    )?;
+    let domain = replica_to_domain_resp.domain;
+
+    if domain == 0 {
+        return Err(RecipientError::NotReplica {
+            address: replica.to_string(),
+        });
+    }
+
+    Err(RecipientError::ReplicaOriginMismatch { origin, domain })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_info};
+    use cosmwasm_std::{
+        coins, from_binary, to_binary, ContractResult, SystemError, SystemResult, WasmQuery,
+    };
+
+    const LOCAL_DOMAIN: u32 = 5000;
+    const REPLICA_DOMAIN: u32 = 1000;
+    const HUB_DOMAIN: u32 = 3000;
+
+    #[test]
+    fn checks_replica_enrollment_and_origin() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { msg, .. } => {
+                let res = match from_binary::<connection_manager::QueryMsg>(msg).unwrap() {
+                    connection_manager::QueryMsg::DomainToReplica { domain } => {
+                        let replica = match domain {
+                            REPLICA_DOMAIN => "replica",
+                            HUB_DOMAIN => "hub",
+                            _ => "0x0",
+                        };
+                        to_binary(&DomainToReplicaResponse {
+                            replica: replica.to_owned(),
+                        })
+                    }
+                    connection_manager::QueryMsg::ReplicaToDomain { replica } => {
+                        let domain = if replica == "replica" {
+                            REPLICA_DOMAIN
+                        } else {
+                            0
+                        };
+                        to_binary(&ReplicaToDomainResponse { domain })
+                    }
+                    connection_manager::QueryMsg::Home {} => to_binary(&HomeResponse {
+                        home: "home".to_owned(),
+                    }),
+                    connection_manager::QueryMsg::LocalDomain {} => {
+                        to_binary(&LocalDomainResponse {
+                            local_domain: LOCAL_DOMAIN,
+                        })
+                    }
+                    _ => panic!("unexpected query"),
+                };
+                SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+            }
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "wasm".to_owned(),
+            }),
+        });
+
+        set_connection_manager(deps.as_mut(), "connection_manager").unwrap();
+
+        only_replica(deps.as_ref(), &mock_info("replica", &[]), REPLICA_DOMAIN).unwrap();
+
+        let res = only_replica(
+            deps.as_ref(),
+            &mock_info("not_replica", &[]),
+            REPLICA_DOMAIN,
+        );
+        assert!(matches!(
+            res.unwrap_err(),
+            RecipientError::NotReplica { .. }
+        ));
+
+        let res = only_replica(deps.as_ref(), &mock_info("replica", &[]), 2000);
+        assert!(matches!(
+            res.unwrap_err(),
+            RecipientError::ReplicaOriginMismatch { .. }
+        ));
+
+        // Hub only enrolled through domain to replica mapping
+        only_replica(deps.as_ref(), &mock_info("hub", &[]), HUB_DOMAIN).unwrap();
+        let res = only_replica(deps.as_ref(), &mock_info("hub", &[]), REPLICA_DOMAIN);
+        assert!(matches!(
+            res.unwrap_err(),
+            RecipientError::NotReplica { .. }
+        ));
+
+        // Home only delivers messages from local domain
+        only_replica(deps.as_ref(), &mock_info("home", &[]), LOCAL_DOMAIN).unwrap();
+        let res = only_replica(deps.as_ref(), &mock_info("home", &[]), REPLICA_DOMAIN);
+        assert!(matches!(
+            res.unwrap_err(),
+            RecipientError::NotReplica { .. }
+        ));
+    }
+}
@@ -0,0 +1,104 @@
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    Addr, Api, CanonicalAddr, Coin, OwnedDeps, RecoverPubkeyError, StdError, StdResult,
+    VerificationError,
+};
+
+/// Api whose canonical addresses are the human address bytes left-padded to
+/// 32 bytes. `MockApi` canonical addresses do not fit in an H256
+#[derive(Copy, Clone, Default)]
+pub struct PaddedMockApi(MockApi);
+
+impl Api for PaddedMockApi {
+    fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+        let canonical = self.addr_canonicalize(human)?;
+        let normalized = self.addr_humanize(&canonical)?;
+        if human != normalized.as_str() {
+            return Err(StdError::generic_err(
+                "Invalid input: address not normalized",
+            ));
+        }
+        Ok(Addr::unchecked(human))
+    }
+
+    fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+        let bytes = human.as_bytes();
+        if bytes.is_empty() || bytes.len() > 32 {
+            return Err(StdError::generic_err(
+                "Invalid input: human address must be 1 to 32 bytes",
+            ));
+        }
+
+        let mut canonical = vec![0u8; 32 - bytes.len()];
+        canonical.extend_from_slice(bytes);
+        Ok(canonical.into())
+    }
+
+    fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+        let trimmed: Vec<u8> = canonical
+            .as_slice()
+            .iter()
+            .skip_while(|byte| **byte == 0)
+            .copied()
+            .collect();
+        let human = String::from_utf8(trimmed)
+            .map_err(|_| StdError::generic_err("Invalid input: canonical address not utf8"))?;
+        if human.is_empty() {
+            return Err(StdError::generic_err("Invalid input: empty address"));
+        }
+        Ok(Addr::unchecked(human))
+    }
+
+    fn secp256k1_verify(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.0.secp256k1_verify(message_hash, signature, public_key)
+    }
+
+    fn secp256k1_recover_pubkey(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        recovery_param: u8,
+    ) -> Result<Vec<u8>, RecoverPubkeyError> {
+        self.0
+            .secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+    }
+
+    fn ed25519_verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.0.ed25519_verify(message, signature, public_key)
+    }
+
+    fn ed25519_batch_verify(
+        &self,
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        public_keys: &[&[u8]],
+    ) -> Result<bool, VerificationError> {
+        self.0
+            .ed25519_batch_verify(messages, signatures, public_keys)
+    }
+
+    fn debug(&self, message: &str) {
+        self.0.debug(message)
+    }
+}
+
+/// `mock_dependencies_with_balance` using `PaddedMockApi`
+pub fn mock_padded_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, PaddedMockApi, MockQuerier> {
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: PaddedMockApi::default(),
+        querier: MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]),
+    }
+}
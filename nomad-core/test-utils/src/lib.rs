@@ -1,11 +1,17 @@
 use cosmwasm_std::{Event, Response};
 
+mod api_utils;
+pub use api_utils::*;
+
 mod updater_utils;
 pub use updater_utils::*;
 
 mod watcher_utils;
 pub use watcher_utils::*;
 
+mod native_signer_utils;
+pub use native_signer_utils::*;
+
 mod merkle_utils;
 pub use merkle_utils::*;
 
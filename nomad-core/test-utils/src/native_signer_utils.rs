@@ -0,0 +1,101 @@
+use common::SignerId;
+use cosmwasm_std::Binary;
+use ethers_core::types::H256;
+use k256::ecdsa::signature::Signer as _;
+
+use crate::{failure_notification_digest, update_digest};
+
+/// Non-Ethereum signing key. Signs the same digests as `Updater` and `Watcher`
+/// but without the Ethereum signed message prefix
+#[derive(Clone)]
+pub enum NativeKey {
+    /// Signs SHA-256 of the digest, verified with `secp256k1_verify`
+    Secp256k1(k256::ecdsa::SigningKey),
+    /// Signs the digest, verified with `ed25519_verify`
+    Ed25519(ed25519_zebra::SigningKey),
+}
+
+impl NativeKey {
+    pub fn secp256k1(privkey: &str) -> Self {
+        let bytes = hex::decode(privkey).unwrap();
+        Self::Secp256k1(k256::ecdsa::SigningKey::from_bytes(&bytes).unwrap())
+    }
+
+    pub fn ed25519(privkey: &str) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&hex::decode(privkey).unwrap());
+        Self::Ed25519(ed25519_zebra::SigningKey::from(bytes))
+    }
+
+    /// Compressed secp256k1 or ed25519 public key identity
+    pub fn signer_id(&self) -> SignerId {
+        match self {
+            Self::Secp256k1(key) => {
+                SignerId::Secp256k1PubKey(Binary::from(key.verifying_key().to_bytes().as_slice()))
+            }
+            Self::Ed25519(key) => {
+                let pubkey: [u8; 32] = ed25519_zebra::VerificationKey::from(key).into();
+                SignerId::Ed25519PubKey(Binary::from(pubkey.to_vec()))
+            }
+        }
+    }
+
+    /// 64 byte signature over `digest`
+    pub fn sign_digest(&self, digest: H256) -> Vec<u8> {
+        match self {
+            Self::Secp256k1(key) => {
+                let signature: k256::ecdsa::Signature = key.sign(digest.as_bytes());
+                signature.as_ref().to_vec()
+            }
+            Self::Ed25519(key) => {
+                let signature: [u8; 64] = key.sign(digest.as_bytes()).into();
+                signature.to_vec()
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct NativeUpdater {
+    pub local_domain: u32,
+    pub key: NativeKey,
+}
+
+impl NativeUpdater {
+    pub fn new(key: NativeKey, domain: u32) -> Self {
+        Self {
+            local_domain: domain,
+            key,
+        }
+    }
+
+    pub fn signer_id(&self) -> SignerId {
+        self.key.signer_id()
+    }
+
+    pub fn sign_update(&self, old_root: H256, new_root: H256) -> Vec<u8> {
+        self.key
+            .sign_digest(update_digest(self.local_domain, old_root, new_root))
+    }
+}
+
+#[derive(Clone)]
+pub struct NativeWatcher {
+    pub home_domain: u32,
+    pub key: NativeKey,
+}
+
+impl NativeWatcher {
+    pub fn new(key: NativeKey, home_domain: u32) -> Self {
+        Self { home_domain, key }
+    }
+
+    pub fn signer_id(&self) -> SignerId {
+        self.key.signer_id()
+    }
+
+    pub fn sign_failure_notification(&self, updater: H256) -> Vec<u8> {
+        self.key
+            .sign_digest(failure_notification_digest(self.home_domain, updater))
+    }
+}
@@ -1,7 +1,32 @@
+use common::SignerId;
 use ethers_core::types::{Signature, H160, H256};
 use ethers_signers::{LocalWallet, Signer};
 use sha3::{digest::Update as DigestUpdate, Digest, Keccak256};
 
+/// Hash of `domain` concatenated with "NOMAD"
+pub fn domain_hash(domain: u32) -> H256 {
+    H256::from_slice(
+        Keccak256::new()
+            .chain(domain.to_be_bytes())
+            .chain("NOMAD".as_bytes())
+            .finalize()
+            .as_slice(),
+    )
+}
+
+/// Digest updaters sign for update from `old_root` to `new_root` on home
+/// with `domain`
+pub fn update_digest(domain: u32, old_root: H256, new_root: H256) -> H256 {
+    H256::from_slice(
+        Keccak256::new()
+            .chain(domain_hash(domain))
+            .chain(old_root)
+            .chain(new_root)
+            .finalize()
+            .as_slice(),
+    )
+}
+
 #[derive(Debug, Clone)]
 pub struct Update {
     pub origin: u32,
@@ -33,25 +58,8 @@ impl Updater {
         self.signer.address()
     }
 
-    fn domain_hash(&self) -> H256 {
-        H256::from_slice(
-            Keccak256::new()
-                .chain(self.local_domain.to_be_bytes())
-                .chain("NOMAD".as_bytes())
-                .finalize()
-                .as_slice(),
-        )
-    }
-
-    fn message_hash(&self, old_root: H256, new_root: H256) -> H256 {
-        H256::from_slice(
-            Keccak256::new()
-                .chain(self.domain_hash())
-                .chain(old_root)
-                .chain(new_root)
-                .finalize()
-                .as_slice(),
-        )
+    pub fn signer_id(&self) -> SignerId {
+        SignerId::EthAddress(self.address())
     }
 
     pub async fn sign_update(
@@ -59,7 +67,7 @@ impl Updater {
         old_root: H256,
         new_root: H256,
     ) -> Result<Update, <LocalWallet as Signer>::Error> {
-        let message_hash = self.message_hash(old_root, new_root);
+        let message_hash = update_digest(self.local_domain, old_root, new_root);
         Ok(Update {
             origin: self.local_domain,
             old_root: H256::from(old_root),
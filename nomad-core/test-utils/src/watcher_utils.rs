@@ -1,7 +1,22 @@
+use crate::domain_hash;
+use common::SignerId;
 use ethers_core::types::{Signature, H160, H256};
 use ethers_signers::{LocalWallet, Signer};
 use sha3::{digest::Update as DigestUpdate, Digest, Keccak256};
 
+/// Digest watchers sign to notify that `updater` of home with `home_domain`
+/// failed
+pub fn failure_notification_digest(home_domain: u32, updater: H256) -> H256 {
+    H256::from_slice(
+        Keccak256::new()
+            .chain(domain_hash(home_domain))
+            .chain(home_domain.to_be_bytes())
+            .chain(updater.as_ref())
+            .finalize()
+            .as_slice(),
+    )
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct FailureNotification {
     /// Domain of failed home
@@ -41,32 +56,15 @@ impl Watcher {
         self.signer.address()
     }
 
-    fn domain_hash(&self) -> H256 {
-        H256::from_slice(
-            Keccak256::new()
-                .chain(self.home_domain.to_be_bytes())
-                .chain("NOMAD".as_bytes())
-                .finalize()
-                .as_slice(),
-        )
-    }
-
-    fn message_hash(&self, updater: H256) -> H256 {
-        H256::from_slice(
-            Keccak256::new()
-                .chain(self.domain_hash())
-                .chain(self.home_domain.to_be_bytes())
-                .chain(updater.as_ref())
-                .finalize()
-                .as_slice(),
-        )
+    pub fn signer_id(&self) -> SignerId {
+        SignerId::EthAddress(self.address())
     }
 
     pub async fn sign_failure_notification(
         &self,
         updater: H256,
     ) -> Result<SignedFailureNotification, <LocalWallet as Signer>::Error> {
-        let message_hash = self.message_hash(updater);
+        let message_hash = failure_notification_digest(self.home_domain, updater);
         Ok(SignedFailureNotification {
             notification: FailureNotification {
                 home_domain: self.home_domain,
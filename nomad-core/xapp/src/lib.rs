@@ -0,0 +1,35 @@
+//! Building blocks for Nomad xApps: a `Router` holding the local home,
+//! connection manager and enrolled remote routers, and a `NomadRecipient`
+//! trait that authenticates and decodes incoming messages.
+
+use cosmwasm_std::StdError;
+use ethers_core::types::H256;
+use thiserror::Error;
+
+mod recipient;
+pub use recipient::*;
+
+mod router;
+pub use router::*;
+
+/// Error types for xApps
+#[derive(Error, Debug)]
+pub enum XAppError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    RecipientError(#[from] nomad_recipient::RecipientError),
+
+    #[error("{0}")]
+    OwnableError(#[from] ownable::ContractError),
+
+    #[error("{0}")]
+    AddressError(#[from] common::AddressError),
+
+    #[error("No router enrolled for domain {domain}")]
+    NoRouter { domain: u32 },
+
+    #[error("Message sender {sender:?} is not the router for domain {domain}")]
+    NotRemoteRouter { sender: H256, domain: u32 },
+}
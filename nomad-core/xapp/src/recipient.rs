@@ -0,0 +1,77 @@
+use common::HandleExecuteMsg;
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use ethers_core::types::H256;
+
+use crate::{Router, XAppError};
+
+/// Message delivered by a replica with body of type `M`
+#[derive(Clone, Debug, PartialEq)]
+pub struct RemoteMessage<M> {
+    pub origin: u32,
+    pub nonce: u32,
+    pub sender: H256,
+    pub message: M,
+}
+
+impl From<HandleExecuteMsg> for RemoteMessage<Vec<u8>> {
+    fn from(msg: HandleExecuteMsg) -> Self {
+        let HandleExecuteMsg::HandleMsg {
+            origin,
+            nonce,
+            sender,
+            message,
+        } = msg;
+
+        Self {
+            origin,
+            nonce,
+            sender,
+            message,
+        }
+    }
+}
+
+/// Typed handler for messages sent by an xApp's remote routers
+pub trait NomadRecipient {
+    /// Decoded message body
+    type Message;
+    type Error: From<XAppError>;
+
+    fn router(&self) -> &Router;
+
+    fn decode(&self, message: &[u8]) -> Result<Self::Message, Self::Error>;
+
+    fn handle(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        msg: RemoteMessage<Self::Message>,
+    ) -> Result<Response, Self::Error>;
+
+    /// Entry point for `HandleMsg`. Checks that caller is the replica
+    /// enrolled for origin and sender is the origin's enrolled router, then
+    /// decodes and handles message.
+    fn handle_msg(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: RemoteMessage<Vec<u8>>,
+    ) -> Result<Response, Self::Error> {
+        let router = self.router();
+        router.only_replica(deps.as_ref(), &info, msg.origin)?;
+        router.only_remote_router(deps.as_ref(), msg.origin, msg.sender)?;
+
+        let message = self.decode(&msg.message)?;
+        self.handle(
+            deps,
+            env,
+            RemoteMessage {
+                origin: msg.origin,
+                nonce: msg.nonce,
+                sender: msg.sender,
+                message,
+            },
+        )
+    }
+}
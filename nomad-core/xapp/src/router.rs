@@ -0,0 +1,340 @@
+use common::home;
+use common::nomad_base::LocalDomainResponse;
+use cosmwasm_std::{
+    to_binary, Addr, Deps, DepsMut, Event, MessageInfo, Response, StdResult, SubMsg, WasmMsg,
+};
+use cw_storage_plus::{Item, Map};
+use ethers_core::types::H256;
+use ownable::state::OWNER;
+
+use crate::XAppError;
+
+/// Storage shared by xApp routers: the home used to dispatch, the connection
+/// manager used to authenticate replicas and the router enrolled on each
+/// remote domain. Ownership is kept in `ownable` storage so contracts can
+/// reuse `ownable` handlers.
+pub struct Router<'a> {
+    home: Item<'a, Addr>,
+    connection_manager: Item<'a, Addr>,
+    remote_routers: Map<'a, u32, H256>,
+}
+
+impl<'a> Router<'a> {
+    pub const fn new(
+        home_key: &'a str,
+        connection_manager_key: &'a str,
+        remote_routers_key: &'a str,
+    ) -> Self {
+        Self {
+            home: Item::new(home_key),
+            connection_manager: Item::new(connection_manager_key),
+            remote_routers: Map::new(remote_routers_key),
+        }
+    }
+
+    /// Store home and connection manager and make caller the owner
+    pub fn instantiate(
+        &self,
+        deps: DepsMut,
+        info: &MessageInfo,
+        home: &str,
+        connection_manager: &str,
+    ) -> Result<(), XAppError> {
+        OWNER.save(deps.storage, &info.sender)?;
+        self.save_contracts(deps, home, connection_manager)
+    }
+
+    /// Store home and connection manager without setting an owner, for
+    /// routers administered by something other than `ownable`
+    pub fn save_contracts(
+        &self,
+        deps: DepsMut,
+        home: &str,
+        connection_manager: &str,
+    ) -> Result<(), XAppError> {
+        self.home
+            .save(deps.storage, &deps.api.addr_validate(home)?)?;
+        self.connection_manager
+            .save(deps.storage, &deps.api.addr_validate(connection_manager)?)?;
+        Ok(())
+    }
+
+    /// Enroll `router` as the xApp router on `domain` (owner only)
+    pub fn execute_enroll_remote_router(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        domain: u32,
+        router: H256,
+    ) -> Result<Response, XAppError> {
+        ownable::only_owner(deps.as_ref(), info)?;
+        self.enroll_remote_router(deps, domain, router)
+    }
+
+    /// Enroll `router` on `domain`. Caller is responsible for authorization
+    pub fn enroll_remote_router(
+        &self,
+        deps: DepsMut,
+        domain: u32,
+        router: H256,
+    ) -> Result<Response, XAppError> {
+        // Router lives on remote chain, cannot validate locally
+        self.remote_routers.save(deps.storage, domain, &router)?;
+
+        Ok(Response::new().add_event(
+            Event::new("EnrollRemoteRouter")
+                .add_attribute("domain", domain.to_string())
+                .add_attribute("router", format!("{:?}", router)),
+        ))
+    }
+
+    pub fn home(&self, deps: Deps) -> StdResult<Addr> {
+        self.home.load(deps.storage)
+    }
+
+    pub fn connection_manager(&self, deps: Deps) -> StdResult<Addr> {
+        self.connection_manager.load(deps.storage)
+    }
+
+    pub fn remote_router(&self, deps: Deps, domain: u32) -> StdResult<Option<H256>> {
+        self.remote_routers.may_load(deps.storage, domain)
+    }
+
+    /// Checks that caller is the replica enrolled in connection manager for
+    /// `origin`. Home delivers messages dispatched to its own domain, so it
+    /// stands in for the replica when `origin` is the local domain.
+    pub fn only_replica(
+        &self,
+        deps: Deps,
+        info: &MessageInfo,
+        origin: u32,
+    ) -> Result<(), XAppError> {
+        let home = self.home(deps)?;
+        if info.sender == home {
+            let local_domain: LocalDomainResponse = deps
+                .querier
+                .query_wasm_smart(&home, &home::QueryMsg::LocalDomain {})?;
+            if origin == local_domain.local_domain {
+                return Ok(());
+            }
+        }
+
+        let connection_manager = self.connection_manager(deps)?;
+        nomad_recipient::only_enrolled_replica(deps, &connection_manager, &info.sender, origin)?;
+        Ok(())
+    }
+
+    /// Checks that `sender` is the router enrolled for `origin`
+    pub fn only_remote_router(
+        &self,
+        deps: Deps,
+        origin: u32,
+        sender: H256,
+    ) -> Result<(), XAppError> {
+        let router = self
+            .remote_router(deps, origin)?
+            .ok_or(XAppError::NoRouter { domain: origin })?;
+        if router != sender {
+            return Err(XAppError::NotRemoteRouter {
+                sender,
+                domain: origin,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Dispatch `message_body` to the router enrolled on `destination`
+    pub fn dispatch_to_remote(
+        &self,
+        deps: Deps,
+        destination: u32,
+        message_body: Vec<u8>,
+    ) -> Result<SubMsg, XAppError> {
+        let router = self
+            .remote_router(deps, destination)?
+            .ok_or(XAppError::NoRouter {
+                domain: destination,
+            })?;
+        let home = self.home(deps)?;
+        dispatch_to_remote(&home, destination, router, message_body)
+    }
+}
+
+/// Build `home` submessage dispatching `message_body` to `recipient` on
+/// `destination`
+pub fn dispatch_to_remote(
+    home: &Addr,
+    destination: u32,
+    recipient: H256,
+    message_body: Vec<u8>,
+) -> Result<SubMsg, XAppError> {
+    let dispatch_msg = home::ExecuteMsg::Dispatch {
+        destination,
+        recipient,
+        message_body,
+    };
+
+    Ok(SubMsg::new(WasmMsg::Execute {
+        contract_addr: home.to_string(),
+        msg: to_binary(&dispatch_msg)?,
+        funds: vec![],
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::connection_manager::{
+        self, DomainToReplicaResponse, HomeResponse, ReplicaToDomainResponse,
+    };
+    use cosmwasm_std::testing::mock_info;
+    use cosmwasm_std::{
+        from_binary, ContractResult, CosmosMsg, SystemError, SystemResult, WasmQuery,
+    };
+    use test_utils::mock_padded_dependencies;
+
+    const ROUTER: Router<'static> = Router::new("home", "connection_manager", "remote_routers");
+    const LOCAL_DOMAIN: u32 = 1000;
+    const REMOTE_DOMAIN: u32 = 2000;
+
+    #[test]
+    fn owner_enrolls_remote_routers() {
+        let mut deps = mock_padded_dependencies(&[]);
+        ROUTER
+            .instantiate(
+                deps.as_mut(),
+                &mock_info("owner", &[]),
+                "home",
+                "connection_manager",
+            )
+            .unwrap();
+
+        let router = H256::repeat_byte(1);
+        let res = ROUTER.execute_enroll_remote_router(
+            deps.as_mut(),
+            mock_info("not_owner", &[]),
+            REMOTE_DOMAIN,
+            router,
+        );
+        assert!(matches!(res.unwrap_err(), XAppError::OwnableError(_)));
+
+        let res = ROUTER.only_remote_router(deps.as_ref(), REMOTE_DOMAIN, router);
+        assert!(matches!(res.unwrap_err(), XAppError::NoRouter { .. }));
+
+        ROUTER
+            .execute_enroll_remote_router(
+                deps.as_mut(),
+                mock_info("owner", &[]),
+                REMOTE_DOMAIN,
+                router,
+            )
+            .unwrap();
+        assert_eq!(
+            Some(router),
+            ROUTER.remote_router(deps.as_ref(), REMOTE_DOMAIN).unwrap()
+        );
+
+        ROUTER
+            .only_remote_router(deps.as_ref(), REMOTE_DOMAIN, router)
+            .unwrap();
+        let res = ROUTER.only_remote_router(deps.as_ref(), REMOTE_DOMAIN, H256::zero());
+        assert!(matches!(
+            res.unwrap_err(),
+            XAppError::NotRemoteRouter { .. }
+        ));
+    }
+
+    #[test]
+    fn accepts_home_for_local_origin() {
+        let mut deps = mock_padded_dependencies(&[]);
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, .. } if contract_addr == "home" => {
+                let res = to_binary(&LocalDomainResponse {
+                    local_domain: LOCAL_DOMAIN,
+                });
+                SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+            }
+            WasmQuery::Smart { msg, .. } => {
+                let res = match from_binary::<connection_manager::QueryMsg>(msg).unwrap() {
+                    connection_manager::QueryMsg::DomainToReplica { .. } => {
+                        to_binary(&DomainToReplicaResponse {
+                            replica: "replica".to_owned(),
+                        })
+                    }
+                    connection_manager::QueryMsg::ReplicaToDomain { .. } => {
+                        to_binary(&ReplicaToDomainResponse { domain: 0 })
+                    }
+                    connection_manager::QueryMsg::Home {} => to_binary(&HomeResponse {
+                        home: "0x0".to_owned(),
+                    }),
+                    _ => panic!("unexpected query"),
+                };
+                SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+            }
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "wasm".to_owned(),
+            }),
+        });
+        ROUTER
+            .instantiate(
+                deps.as_mut(),
+                &mock_info("owner", &[]),
+                "home",
+                "connection_manager",
+            )
+            .unwrap();
+
+        ROUTER
+            .only_replica(deps.as_ref(), &mock_info("home", &[]), LOCAL_DOMAIN)
+            .unwrap();
+        let res = ROUTER.only_replica(deps.as_ref(), &mock_info("home", &[]), REMOTE_DOMAIN);
+        assert!(matches!(res.unwrap_err(), XAppError::RecipientError(_)));
+    }
+
+    #[test]
+    fn dispatches_to_enrolled_router() {
+        let mut deps = mock_padded_dependencies(&[]);
+        ROUTER
+            .instantiate(
+                deps.as_mut(),
+                &mock_info("owner", &[]),
+                "home",
+                "connection_manager",
+            )
+            .unwrap();
+
+        let res = ROUTER.dispatch_to_remote(deps.as_ref(), REMOTE_DOMAIN, vec![1]);
+        assert!(matches!(res.unwrap_err(), XAppError::NoRouter { .. }));
+
+        let router = common::addr_to_h256(&deps.api, &Addr::unchecked("remote_router")).unwrap();
+        ROUTER
+            .execute_enroll_remote_router(
+                deps.as_mut(),
+                mock_info("owner", &[]),
+                REMOTE_DOMAIN,
+                router,
+            )
+            .unwrap();
+
+        let sub_msg = ROUTER
+            .dispatch_to_remote(deps.as_ref(), REMOTE_DOMAIN, vec![1])
+            .unwrap();
+        match sub_msg.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => {
+                assert_eq!("home", contract_addr);
+                assert_eq!(
+                    home::ExecuteMsg::Dispatch {
+                        destination: REMOTE_DOMAIN,
+                        recipient: router,
+                        message_body: vec![1],
+                    },
+                    from_binary(&msg).unwrap()
+                );
+            }
+            _ => panic!("Expected wasm execute message"),
+        }
+    }
+}